@@ -4,7 +4,7 @@ use core::fmt::{self, Display, Formatter};
 use codec::{
     Compact, CompactAs, Decode, DecodeWithMemTracking, Encode, Error as CodecError, MaxEncodedLen,
 };
-use frame_support::pallet_prelude::*;
+use frame_support::{pallet_prelude::*, traits::tokens::Preservation};
 use runtime_common::prod_or_fast;
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
@@ -158,7 +158,11 @@ pub enum ProxyType {
     ChildKeys,
     SudoUncheckedSetCode,
     SwapHotkey,
-    SubnetLeaseBeneficiary, // Used to operate the leased subnet
+    SubnetLeaseBeneficiary,     // Used to operate the leased subnet
+    SubnetStaking(NetUid),      // Staking calls on a single subnet
+    SubnetChildKeys(NetUid),    // ChildKeys calls on a single subnet
+    SubnetOwner(NetUid),        // Subnet owner calls on a single subnet
+    SubnetRegistration(NetUid), // Registration calls on a single subnet
 }
 
 impl Default for ProxyType {
@@ -168,6 +172,51 @@ impl Default for ProxyType {
     }
 }
 
+/// The funds a proxy allowance limits.
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Debug,
+    MaxEncodedLen,
+    TypeInfo,
+)]
+pub enum ProxyAllowanceKind {
+    /// TAO leaving the free balance of the account.
+    Tao,
+    /// Alpha leaving the stake of the account on a subnet.
+    Alpha(NetUid),
+}
+
+/// A condition guarding the dispatch of a scheduled batch of calls.
+#[derive(
+    Clone, Eq, PartialEq, Encode, Decode, DecodeWithMemTracking, Debug, MaxEncodedLen, TypeInfo,
+)]
+pub enum BatchCondition<AccountId> {
+    /// The time-weighted average price of one alpha of the subnet is at most `price`.
+    AlphaPriceAtMost { netuid: NetUid, price: TaoCurrency },
+    /// The time-weighted average price of one alpha of the subnet is at least `price`.
+    AlphaPriceAtLeast { netuid: NetUid, price: TaoCurrency },
+    /// The owner of the batch stakes at least `amount` to the hotkey on the subnet.
+    StakeAtLeast {
+        hotkey: AccountId,
+        netuid: NetUid,
+        amount: AlphaCurrency,
+    },
+    /// The owner of the batch stakes at most `amount` to the hotkey on the subnet.
+    StakeAtMost {
+        hotkey: AccountId,
+        netuid: NetUid,
+        amount: AlphaCurrency,
+    },
+}
+
 pub trait SubnetInfo<AccountId> {
     fn tao_reserve(netuid: NetUid) -> TaoCurrency;
     fn alpha_reserve(netuid: NetUid) -> AlphaCurrency;
@@ -188,6 +237,12 @@ pub trait BalanceOps<AccountId> {
         coldkey: &AccountId,
         tao: TaoCurrency,
     ) -> Result<TaoCurrency, DispatchError>;
+    fn transfer_balance(
+        from: &AccountId,
+        to: &AccountId,
+        tao: TaoCurrency,
+        preservation: Preservation,
+    ) -> DispatchResult;
     fn increase_stake(
         coldkey: &AccountId,
         hotkey: &AccountId,
@@ -204,6 +259,26 @@ pub trait BalanceOps<AccountId> {
     fn decrease_provided_tao_reserve(netuid: NetUid, tao: TaoCurrency);
     fn increase_provided_alpha_reserve(netuid: NetUid, alpha: AlphaCurrency);
     fn decrease_provided_alpha_reserve(netuid: NetUid, alpha: AlphaCurrency);
+    /// Accounts for a swap of `tao` plus `tao_fee` taken from `escrow` into the pool of the
+    /// subnet, and stakes the `alpha` paid out to `hotkey` on behalf of `coldkey`.
+    fn settle_buy_order_fill(
+        escrow: &AccountId,
+        coldkey: &AccountId,
+        hotkey: &AccountId,
+        netuid: NetUid,
+        tao: TaoCurrency,
+        tao_fee: TaoCurrency,
+        alpha: AlphaCurrency,
+    ) -> DispatchResult;
+    /// Accounts for a swap of escrowed `alpha` plus `alpha_fee` into the pool of the subnet, and
+    /// pays the `tao` paid out to `coldkey`.
+    fn settle_sell_order_fill(
+        coldkey: &AccountId,
+        netuid: NetUid,
+        alpha: AlphaCurrency,
+        alpha_fee: AlphaCurrency,
+        tao: TaoCurrency,
+    ) -> DispatchResult;
 }
 
 pub mod time {
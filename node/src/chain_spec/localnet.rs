@@ -2,6 +2,8 @@
 #![allow(clippy::unwrap_used)]
 
 use super::*;
+use crate::local_drand::LOCAL_DRAND_BEACON_SEED_PROPERTY;
+use pallet_drand::local_beacon::LocalBeacon;
 
 pub fn localnet_config(single_authority: bool) -> Result<ChainSpec, String> {
     let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
@@ -12,6 +14,29 @@ pub fn localnet_config(single_authority: bool) -> Result<ChainSpec, String> {
     properties.insert("tokenDecimals".into(), 9.into());
     properties.insert("ss58Format".into(), 42.into());
 
+    let mut genesis = localnet_genesis(
+        // Initial PoA authorities (Validators)
+        // aura | grandpa
+        if single_authority {
+            // single authority allows you to run the network using a single node
+            vec![authority_keys_from_seed("Alice")]
+        } else {
+            vec![
+                authority_keys_from_seed("Alice"),
+                authority_keys_from_seed("Bob"),
+            ]
+        },
+        // Pre-funded accounts
+        true,
+    );
+
+    // Stand in for drand with a local beacon derived from the seed, published by the node
+    if let Ok(seed) = env::var("LOCAL_DRAND_BEACON_SEED") {
+        let beacon = LocalBeacon::from_seed(seed.as_bytes());
+        genesis["drand"] = serde_json::json!({ "beaconConfig": beacon.config() });
+        properties.insert(LOCAL_DRAND_BEACON_SEED_PROPERTY.into(), seed.into());
+    }
+
     Ok(ChainSpec::builder(
         wasm_binary,
         Extensions {
@@ -29,21 +54,7 @@ pub fn localnet_config(single_authority: bool) -> Result<ChainSpec, String> {
     .with_protocol_id("bittensor")
     .with_id("bittensor")
     .with_chain_type(ChainType::Local)
-    .with_genesis_config_patch(localnet_genesis(
-        // Initial PoA authorities (Validators)
-        // aura | grandpa
-        if single_authority {
-            // single authority allows you to run the network using a single node
-            vec![authority_keys_from_seed("Alice")]
-        } else {
-            vec![
-                authority_keys_from_seed("Alice"),
-                authority_keys_from_seed("Bob"),
-            ]
-        },
-        // Pre-funded accounts
-        true,
-    ))
+    .with_genesis_config_patch(genesis)
     .with_properties(properties)
     .build())
 }
@@ -0,0 +1,81 @@
+//! Publishing of the pulses of a local drand beacon, standing in for drand on dev and test
+//! networks.
+//!
+//! The pulses are written to the offchain storage, where the drand offchain worker reads them in
+//! place of the drand api. The beacon is enabled by the `localDrandBeaconSeed` chain spec property,
+//! the runtime must be configured with the beacon derived from the same seed (see
+//! `LOCAL_DRAND_BEACON_SEED` in the localnet chain spec).
+
+use jsonrpsee::tokio;
+use pallet_drand::local_beacon::{LOCAL_BEACON_GENESIS_TIME, LOCAL_BEACON_PERIOD, LocalBeacon};
+use pallet_drand::local_beacon_storage_key;
+use pallet_drand::types::RoundNumber;
+use sc_service::Properties;
+use sp_core::offchain::OffchainStorage;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The chain spec property holding the seed of the local beacon
+pub const LOCAL_DRAND_BEACON_SEED_PROPERTY: &str = "localDrandBeaconSeed";
+
+/// The number of past rounds kept available to the offchain worker (1 hour)
+const KEPT_ROUNDS: RoundNumber = 1_200;
+
+/// The seed of the local beacon, if the chain spec enables one
+pub fn local_beacon_seed(properties: &Properties) -> Option<String> {
+    properties
+        .get(LOCAL_DRAND_BEACON_SEED_PROPERTY)
+        .and_then(|seed| seed.as_str())
+        .map(ToString::to_string)
+}
+
+/// Publish the pulses of the beacon in the offchain storage, every beacon period
+pub async fn run<S: OffchainStorage>(beacon: LocalBeacon, mut storage: S) {
+    let chain_hash = beacon.chain_hash_hex();
+    let path = |round: &str| local_beacon_storage_key(&format!("/{chain_hash}/public/{round}"));
+    let mut last_published: RoundNumber = 0;
+
+    log::info!("Local drand beacon {chain_hash} started");
+
+    loop {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let current_round = beacon.round_at(now);
+        let oldest_round = current_round.saturating_sub(KEPT_ROUNDS);
+
+        for round in last_published.saturating_add(1).max(oldest_round)..=current_round {
+            match serde_json::to_vec(&beacon.pulse(round)) {
+                Ok(body) => storage.set(
+                    sp_offchain::STORAGE_PREFIX,
+                    &path(&round.to_string()),
+                    &body,
+                ),
+                Err(e) => log::error!("Failed to encode local drand pulse {round}: {e:?}"),
+            }
+        }
+
+        if current_round > last_published {
+            if let Some(latest) = storage.get(
+                sp_offchain::STORAGE_PREFIX,
+                &path(&current_round.to_string()),
+            ) {
+                storage.set(sp_offchain::STORAGE_PREFIX, &path("latest"), &latest);
+            }
+
+            // Remove the published rounds that fell out of the kept window
+            let published_from = last_published.saturating_sub(KEPT_ROUNDS).max(1);
+            let published_to = oldest_round.min(last_published.saturating_add(1));
+            for round in published_from..published_to {
+                storage.remove(sp_offchain::STORAGE_PREFIX, &path(&round.to_string()));
+            }
+            last_published = current_round;
+        }
+
+        // Wake up when the next round is emitted
+        let next_round_time = u64::from(LOCAL_BEACON_GENESIS_TIME)
+            .saturating_add(current_round.saturating_mul(u64::from(LOCAL_BEACON_PERIOD)));
+        let wait = next_round_time.saturating_sub(now).max(1);
+        tokio::time::sleep(Duration::from_secs(wait)).await;
+    }
+}
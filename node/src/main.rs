@@ -10,6 +10,7 @@ mod command;
 mod conditional_evm_block_import;
 mod consensus;
 mod ethereum;
+mod local_drand;
 mod rpc;
 mod service;
 
@@ -3,6 +3,7 @@
 use crate::consensus::ConsensusMechanism;
 use futures::{FutureExt, channel::mpsc, future};
 use node_subtensor_runtime::{RuntimeApi, TransactionConverter, opaque::Block};
+use pallet_drand::local_beacon::LocalBeacon;
 use sc_chain_spec::ChainType;
 use sc_client_api::{Backend as BackendT, BlockBackend};
 use sc_consensus::{BasicQueue, BoxBlockImport};
@@ -34,6 +35,7 @@ use crate::ethereum::{
     BackendType, EthConfiguration, FrontierBackend, FrontierPartialComponents, StorageOverride,
     StorageOverrideHandler, db_config_dir, new_frontier_partial, spawn_frontier_tasks,
 };
+use crate::local_drand;
 
 const LOG_TARGET: &str = "node-service";
 
@@ -397,6 +399,17 @@ where
             .run(client.clone(), task_manager.spawn_handle())
             .boxed(),
         );
+
+        if let (Some(seed), Some(offchain_storage)) = (
+            local_drand::local_beacon_seed(&config.chain_spec.properties()),
+            backend.offchain_storage(),
+        ) {
+            task_manager.spawn_handle().spawn(
+                "local-drand-beacon",
+                None,
+                local_drand::run(LocalBeacon::from_seed(seed.as_bytes()), offchain_storage),
+            );
+        }
     }
 
     let role = config.role;
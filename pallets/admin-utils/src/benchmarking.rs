@@ -11,8 +11,8 @@ use frame_benchmarking::v1::account;
 use frame_benchmarking::v2::*;
 use frame_support::BoundedVec;
 use frame_system::RawOrigin;
-use pallet_subtensor::SubnetworkN;
-use subtensor_runtime_common::NetUid;
+use pallet_subtensor::{MechanismCountCurrent, SubnetworkN};
+use subtensor_runtime_common::{MechId, NetUid};
 
 use super::*;
 
@@ -253,6 +253,19 @@ mod benchmarks {
 		_(RawOrigin::Root, netuid, 16u16/*min_allowed_uids*/)/*sudo_set_min_allowed_uids*/;
     }
 
+    #[benchmark]
+    fn sudo_set_epoch_debug_enabled() {
+        let netuid = NetUid::from(1);
+        pallet_subtensor::Pallet::<T>::init_new_network(netuid, 1u16 /*tempo*/);
+        pallet_subtensor::Pallet::<T>::set_epoch_debug_enabled(netuid, true);
+
+        // Worst case: disabling removes the snapshots of every mechanism
+        MechanismCountCurrent::<T>::insert(netuid, MechId::from(2));
+
+        #[extrinsic_call]
+		_(RawOrigin::Root, netuid, false/*enabled*/)/*sudo_set_epoch_debug_enabled*/;
+    }
+
     #[benchmark]
     fn sudo_set_max_allowed_uids() {
         // disable admin freeze window
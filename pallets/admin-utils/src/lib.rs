@@ -24,7 +24,9 @@ pub mod pallet {
     use frame_support::traits::tokens::Balance;
     use frame_support::{dispatch::DispatchResult, pallet_prelude::StorageMap};
     use frame_system::pallet_prelude::*;
+    use pallet_commitments::CommitmentSchema;
     use pallet_evm_chain_id::{self, ChainId};
+    use pallet_subtensor::CommitmentsInterface;
     use pallet_subtensor::utils::rate_limiting::{Hyperparameter, TransactionType};
     use sp_runtime::BoundedVec;
     use substrate_fixed::types::I96F32;
@@ -89,6 +91,13 @@ pub mod pallet {
             /// Indicates if the Bonds Reset was enabled or disabled.
             enabled: bool,
         },
+        /// Event emitted when the epoch debug snapshot is toggled.
+        EpochDebugToggled {
+            /// The network identifier.
+            netuid: NetUid,
+            /// Indicates if the epoch debug snapshot was enabled or disabled.
+            enabled: bool,
+        },
     }
 
     // Errors inform users that something went wrong.
@@ -2019,6 +2028,234 @@ pub mod pallet {
             );
             Ok(())
         }
+
+        /// Enables or disables the epoch debug snapshot for a given subnet.
+        ///
+        /// When enabled, every epoch of the subnet keeps its intermediate Yuma terms (weights,
+        /// consensus, clipped weights, EMA bonds and liquid alphas) in storage, readable through
+        /// the `get_epoch_debug` runtime API. Disabling it removes the kept snapshots.
+        ///
+        /// The snapshots are written on every epoch without being charged to anyone, so only the
+        /// root account can enable them.
+        ///
+        /// # Parameters
+        /// - `origin`: The origin of the call, which must be the root account.
+        /// - `netuid`: The unique identifier for the subnet.
+        /// - `enabled`: A boolean flag to enable or disable the epoch debug snapshot.
+        #[pallet::call_index(84)]
+        #[pallet::weight(Weight::from_parts(14_000_000, 0)
+        .saturating_add(<T as frame_system::Config>::DbWeight::get().reads(2_u64))
+        .saturating_add(<T as frame_system::Config>::DbWeight::get().writes(3_u64)))]
+        pub fn sudo_set_epoch_debug_enabled(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            enabled: bool,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            ensure!(
+                pallet_subtensor::Pallet::<T>::if_subnet_exist(netuid),
+                Error::<T>::SubnetDoesNotExist
+            );
+            pallet_subtensor::Pallet::<T>::set_epoch_debug_enabled(netuid, enabled);
+
+            Self::deposit_event(Event::EpochDebugToggled { netuid, enabled });
+            log::debug!("EpochDebugToggled( netuid: {netuid:?}, enabled: {enabled:?} ) ");
+            Ok(())
+        }
+
+        /// Sets the drand beacon the reveal rounds of timelocked weight commits refer to.
+        ///
+        /// The beacon must be tracked by the drand pallet, and can only change while the subnet
+        /// has no unrevealed timelocked weight commits.
+        ///
+        /// # Parameters
+        /// - `origin`: The origin of the call, which must be the root account or subnet owner.
+        /// - `netuid`: The unique identifier for the subnet.
+        /// - `beacon`: The chain hash of the beacon, `None` for the default beacon.
+        #[pallet::call_index(81)]
+        #[pallet::weight((1_000_000, DispatchClass::Normal, Pays::Yes))] // TODO: add proper weights
+        pub fn sudo_set_timelocked_weights_beacon(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            beacon: Option<[u8; 32]>,
+        ) -> DispatchResult {
+            let maybe_owner = pallet_subtensor::Pallet::<T>::ensure_sn_owner_or_root_with_limits(
+                origin,
+                netuid,
+                &[Hyperparameter::TimelockedWeightsBeacon.into()],
+            )?;
+
+            ensure!(
+                pallet_subtensor::Pallet::<T>::if_subnet_exist(netuid),
+                Error::<T>::SubnetDoesNotExist
+            );
+
+            pallet_subtensor::Pallet::<T>::set_timelocked_weights_beacon(netuid, beacon)?;
+            log::debug!("TimelockedWeightsBeaconSet( netuid: {netuid:?}, beacon: {beacon:?} ) ");
+            pallet_subtensor::Pallet::<T>::record_owner_rl(
+                maybe_owner,
+                netuid,
+                &[Hyperparameter::TimelockedWeightsBeacon.into()],
+            );
+            Ok(())
+        }
+
+        /// Sets the number of past commitments kept per account on a subnet.
+        ///
+        /// # Parameters
+        /// - `origin`: The origin of the call, which must be the root account or subnet owner.
+        /// - `netuid`: The unique identifier for the subnet.
+        /// - `depth`: The number of past commitments kept per account.
+        #[pallet::call_index(82)]
+        #[pallet::weight((1_000_000, DispatchClass::Normal, Pays::Yes))] // TODO: add proper weights
+        pub fn sudo_set_commitment_history_depth(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            depth: u32,
+        ) -> DispatchResult {
+            let maybe_owner = pallet_subtensor::Pallet::<T>::ensure_sn_owner_or_root_with_limits(
+                origin,
+                netuid,
+                &[Hyperparameter::CommitmentHistoryDepth.into()],
+            )?;
+
+            ensure!(
+                pallet_subtensor::Pallet::<T>::if_subnet_exist(netuid),
+                Error::<T>::SubnetDoesNotExist
+            );
+
+            <T as pallet_subtensor::Config>::CommitmentsInterface::set_commitment_history_depth(
+                netuid, depth,
+            )?;
+            log::debug!("CommitmentHistoryDepthSet( netuid: {netuid:?}, depth: {depth:?} ) ");
+            pallet_subtensor::Pallet::<T>::record_owner_rl(
+                maybe_owner,
+                netuid,
+                &[Hyperparameter::CommitmentHistoryDepth.into()],
+            );
+            Ok(())
+        }
+
+        /// Sets the schema the commitments on a subnet must match.
+        ///
+        /// Commitments set before are not checked against the new schema.
+        ///
+        /// # Parameters
+        /// - `origin`: The origin of the call, which must be the root account or subnet owner.
+        /// - `netuid`: The unique identifier for the subnet.
+        /// - `schema`: The schema of the commitments, `None` for free-form commitments.
+        #[pallet::call_index(83)]
+        #[pallet::weight((1_000_000, DispatchClass::Normal, Pays::Yes))] // TODO: add proper weights
+        pub fn sudo_set_commitment_schema(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            schema: Option<CommitmentSchema>,
+        ) -> DispatchResult {
+            let maybe_owner = pallet_subtensor::Pallet::<T>::ensure_sn_owner_or_root_with_limits(
+                origin,
+                netuid,
+                &[Hyperparameter::CommitmentSchema.into()],
+            )?;
+
+            ensure!(
+                pallet_subtensor::Pallet::<T>::if_subnet_exist(netuid),
+                Error::<T>::SubnetDoesNotExist
+            );
+
+            log::debug!("CommitmentSchemaSet( netuid: {netuid:?}, schema: {schema:?} ) ");
+            <T as pallet_subtensor::Config>::CommitmentsInterface::set_commitment_schema(
+                netuid, schema,
+            )?;
+            pallet_subtensor::Pallet::<T>::record_owner_rl(
+                maybe_owner,
+                netuid,
+                &[Hyperparameter::CommitmentSchema.into()],
+            );
+            Ok(())
+        }
+
+        /// Sets how the burned registrations of a subnet are resolved.
+        ///
+        /// The registrations queued before switching back to immediate registrations are still
+        /// resolved at the end of the adjustment interval.
+        ///
+        /// # Parameters
+        /// - `origin`: The origin of the call, which must be the root account or subnet owner.
+        /// - `netuid`: The unique identifier for the subnet.
+        /// - `mode`: Immediate, highest bid or random burned registrations.
+        #[pallet::call_index(85)]
+        #[pallet::weight((1_000_000, DispatchClass::Normal, Pays::Yes))] // TODO: add proper weights
+        pub fn sudo_set_burned_registration_mode(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            mode: pallet_subtensor::BurnedRegistrationMode,
+        ) -> DispatchResult {
+            let maybe_owner = pallet_subtensor::Pallet::<T>::ensure_sn_owner_or_root_with_limits(
+                origin,
+                netuid,
+                &[Hyperparameter::BurnedRegistrationMode.into()],
+            )?;
+
+            ensure!(
+                pallet_subtensor::Pallet::<T>::if_subnet_exist(netuid),
+                Error::<T>::SubnetDoesNotExist
+            );
+
+            pallet_subtensor::Pallet::<T>::set_burned_registration_mode(netuid, mode);
+            log::debug!("BurnedRegistrationModeSet( netuid: {netuid:?}, mode: {mode:?} ) ");
+            pallet_subtensor::Pallet::<T>::record_owner_rl(
+                maybe_owner,
+                netuid,
+                &[Hyperparameter::BurnedRegistrationMode.into()],
+            );
+            Ok(())
+        }
+
+        /// Sets the calls whose transaction fees the paymaster of a subnet pays and its budgets.
+        ///
+        /// # Parameters
+        /// - `origin`: The origin of the call, which must be the root account or subnet owner.
+        /// - `netuid`: The unique identifier for the subnet.
+        /// - `calls`: The sponsored calls, an empty list disables the paymaster.
+        /// - `account_budget`: The maximum fees paid for a single account.
+        /// - `block_budget`: The maximum fees paid in a single block.
+        #[pallet::call_index(86)]
+        #[pallet::weight((1_000_000, DispatchClass::Normal, Pays::Yes))] // TODO: add proper weights
+        pub fn sudo_set_subnet_paymaster(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            calls: Vec<pallet_subtensor::SponsoredCall>,
+            account_budget: TaoCurrency,
+            block_budget: TaoCurrency,
+        ) -> DispatchResult {
+            let maybe_owner = pallet_subtensor::Pallet::<T>::ensure_sn_owner_or_root_with_limits(
+                origin,
+                netuid,
+                &[Hyperparameter::SubnetPaymaster.into()],
+            )?;
+
+            ensure!(
+                pallet_subtensor::Pallet::<T>::if_subnet_exist(netuid),
+                Error::<T>::SubnetDoesNotExist
+            );
+
+            log::debug!(
+                "SubnetPaymasterSet( netuid: {netuid:?}, calls: {calls:?}, account_budget: {account_budget:?}, block_budget: {block_budget:?} ) "
+            );
+            pallet_subtensor::Pallet::<T>::set_subnet_paymaster(
+                netuid,
+                calls,
+                account_budget,
+                block_budget,
+            )?;
+            pallet_subtensor::Pallet::<T>::record_owner_rl(
+                maybe_owner,
+                netuid,
+                &[Hyperparameter::SubnetPaymaster.into()],
+            );
+            Ok(())
+        }
     }
 }
 
@@ -1935,6 +1935,219 @@ fn test_sudo_set_yuma3_enabled() {
     });
 }
 
+#[test]
+fn test_sudo_set_epoch_debug_enabled() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let sn_owner = U256::from(1);
+        add_network(netuid, 10);
+        let netuid_index = SubtensorModule::get_mechanism_storage_index(netuid, MechId::MAIN);
+        assert!(!SubtensorModule::get_epoch_debug_enabled(netuid));
+
+        assert_eq!(
+            AdminUtils::sudo_set_epoch_debug_enabled(
+                <<Test as Config>::RuntimeOrigin>::signed(sn_owner),
+                netuid,
+                true
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+
+        assert_ok!(AdminUtils::sudo_set_epoch_debug_enabled(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            netuid,
+            true
+        ));
+        assert!(SubtensorModule::get_epoch_debug_enabled(netuid));
+
+        // The subnet owner can not toggle it either.
+        pallet_subtensor::SubnetOwner::<Test>::insert(netuid, sn_owner);
+        assert_eq!(
+            AdminUtils::sudo_set_epoch_debug_enabled(
+                <<Test as Config>::RuntimeOrigin>::signed(sn_owner),
+                netuid,
+                false
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+
+        // Disabling removes the kept snapshot.
+        pallet_subtensor::EpochDebug::<Test>::insert(
+            netuid_index,
+            pallet_subtensor::EpochDebugSnapshot::default(),
+        );
+        assert_ok!(AdminUtils::sudo_set_epoch_debug_enabled(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            netuid,
+            false
+        ));
+        assert!(!SubtensorModule::get_epoch_debug_enabled(netuid));
+        assert!(!pallet_subtensor::EpochDebug::<Test>::contains_key(
+            netuid_index
+        ));
+    });
+}
+
+#[test]
+fn test_sudo_set_burned_registration_mode() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let sn_owner = U256::from(1);
+        add_network(netuid, 10);
+        assert_eq!(
+            SubtensorModule::get_burned_registration_mode(netuid),
+            pallet_subtensor::BurnedRegistrationMode::Immediate
+        );
+
+        assert_eq!(
+            AdminUtils::sudo_set_burned_registration_mode(
+                <<Test as Config>::RuntimeOrigin>::signed(sn_owner),
+                netuid,
+                pallet_subtensor::BurnedRegistrationMode::Random
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+
+        pallet_subtensor::SubnetOwner::<Test>::insert(netuid, sn_owner);
+        assert_ok!(AdminUtils::sudo_set_burned_registration_mode(
+            <<Test as Config>::RuntimeOrigin>::signed(sn_owner),
+            netuid,
+            pallet_subtensor::BurnedRegistrationMode::Random
+        ));
+        assert_eq!(
+            SubtensorModule::get_burned_registration_mode(netuid),
+            pallet_subtensor::BurnedRegistrationMode::Random
+        );
+
+        assert_ok!(AdminUtils::sudo_set_burned_registration_mode(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            netuid,
+            pallet_subtensor::BurnedRegistrationMode::HighestBid
+        ));
+        assert_eq!(
+            SubtensorModule::get_burned_registration_mode(netuid),
+            pallet_subtensor::BurnedRegistrationMode::HighestBid
+        );
+    });
+}
+
+#[test]
+fn test_sudo_set_subnet_paymaster() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let sn_owner = U256::from(1);
+        add_network(netuid, 10);
+        let calls = vec![
+            pallet_subtensor::SponsoredCall::BurnedRegister,
+            pallet_subtensor::SponsoredCall::BurnedRegister,
+            pallet_subtensor::SponsoredCall::ServeAxon,
+        ];
+
+        assert_eq!(
+            AdminUtils::sudo_set_subnet_paymaster(
+                <<Test as Config>::RuntimeOrigin>::signed(sn_owner),
+                netuid,
+                calls.clone(),
+                1_000.into(),
+                10_000.into()
+            ),
+            Err(DispatchError::BadOrigin)
+        );
+
+        pallet_subtensor::SubnetOwner::<Test>::insert(netuid, sn_owner);
+        assert_ok!(AdminUtils::sudo_set_subnet_paymaster(
+            <<Test as Config>::RuntimeOrigin>::signed(sn_owner),
+            netuid,
+            calls,
+            1_000.into(),
+            10_000.into()
+        ));
+        let paymaster =
+            pallet_subtensor::SubnetPaymasters::<Test>::get(netuid).expect("paymaster is set");
+        assert_eq!(
+            paymaster.calls.into_inner(),
+            vec![
+                pallet_subtensor::SponsoredCall::BurnedRegister,
+                pallet_subtensor::SponsoredCall::ServeAxon
+            ]
+        );
+        assert_eq!(paymaster.account_budget, 1_000.into());
+        assert_eq!(paymaster.block_budget, 10_000.into());
+
+        assert_ok!(AdminUtils::sudo_set_subnet_paymaster(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            netuid,
+            vec![],
+            0.into(),
+            0.into()
+        ));
+        assert!(!pallet_subtensor::SubnetPaymasters::<Test>::contains_key(
+            netuid
+        ));
+    });
+}
+
+#[test]
+fn test_sudo_set_timelocked_weights_beacon() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let sn_owner = U256::from(1);
+        let chain_hash = [1u8; 32];
+        add_network(netuid, 10);
+        SubnetOwner::<Test>::insert(netuid, sn_owner);
+
+        assert_noop!(
+            AdminUtils::sudo_set_timelocked_weights_beacon(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(2)),
+                netuid,
+                Some(chain_hash)
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            AdminUtils::sudo_set_timelocked_weights_beacon(
+                <<Test as Config>::RuntimeOrigin>::signed(sn_owner),
+                netuid,
+                Some(chain_hash)
+            ),
+            SubtensorError::<Test>::UnknownDrandBeacon
+        );
+
+        pallet_drand::BeaconConfigs::<Test>::insert(chain_hash, Default::default());
+        assert_ok!(AdminUtils::sudo_set_timelocked_weights_beacon(
+            <<Test as Config>::RuntimeOrigin>::signed(sn_owner),
+            netuid,
+            Some(chain_hash)
+        ));
+        assert_eq!(
+            SubtensorModule::get_timelocked_weights_beacon(netuid),
+            Some(chain_hash)
+        );
+
+        // The beacon cannot change while commits are waiting to be revealed.
+        let netuid_index = SubtensorModule::get_mechanism_storage_index(netuid, MechId::MAIN);
+        TimelockedWeightCommits::<Test>::mutate(netuid_index, 1, |commits| {
+            commits.push_back((U256::from(2), 1, Default::default(), 1000))
+        });
+        assert_noop!(
+            AdminUtils::sudo_set_timelocked_weights_beacon(
+                <<Test as Config>::RuntimeOrigin>::root(),
+                netuid,
+                None
+            ),
+            SubtensorError::<Test>::TimelockedWeightCommitsPending
+        );
+
+        TimelockedWeightCommits::<Test>::remove(netuid_index, 1);
+        assert_ok!(AdminUtils::sudo_set_timelocked_weights_beacon(
+            <<Test as Config>::RuntimeOrigin>::root(),
+            netuid,
+            None
+        ));
+        assert_eq!(SubtensorModule::get_timelocked_weights_beacon(netuid), None);
+    });
+}
+
 #[test]
 fn test_sudo_set_commit_reveal_version() {
     new_test_ext().execute_with(|| {
@@ -2857,3 +3070,75 @@ fn test_sudo_set_min_allowed_uids() {
         );
     });
 }
+
+#[test]
+fn test_sudo_set_commitment_history_depth() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let sn_owner = U256::from(1);
+        add_network(netuid, 10);
+        SubnetOwner::<Test>::insert(netuid, sn_owner);
+
+        assert_noop!(
+            AdminUtils::sudo_set_commitment_history_depth(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(2)),
+                netuid,
+                16
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            AdminUtils::sudo_set_commitment_history_depth(
+                <<Test as Config>::RuntimeOrigin>::root(),
+                NetUid::from(2),
+                16
+            ),
+            Error::<Test>::SubnetDoesNotExist
+        );
+        assert_ok!(AdminUtils::sudo_set_commitment_history_depth(
+            <<Test as Config>::RuntimeOrigin>::signed(sn_owner),
+            netuid,
+            16
+        ));
+    });
+}
+
+#[test]
+fn test_sudo_set_commitment_schema() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let sn_owner = U256::from(1);
+        add_network(netuid, 10);
+        SubnetOwner::<Test>::insert(netuid, sn_owner);
+        let schema = pallet_commitments::CommitmentSchema {
+            fields: vec![pallet_commitments::SchemaField {
+                name: b"version".to_vec().try_into().expect("name is in bounds"),
+                field_type: pallet_commitments::SchemaFieldType::U64,
+            }]
+            .try_into()
+            .expect("fields are in bounds"),
+        };
+
+        assert_noop!(
+            AdminUtils::sudo_set_commitment_schema(
+                <<Test as Config>::RuntimeOrigin>::signed(U256::from(2)),
+                netuid,
+                Some(schema.clone())
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            AdminUtils::sudo_set_commitment_schema(
+                <<Test as Config>::RuntimeOrigin>::root(),
+                NetUid::from(2),
+                Some(schema.clone())
+            ),
+            Error::<Test>::SubnetDoesNotExist
+        );
+        assert_ok!(AdminUtils::sudo_set_commitment_schema(
+            <<Test as Config>::RuntimeOrigin>::signed(sn_owner),
+            netuid,
+            Some(schema)
+        ));
+    });
+}
@@ -19,9 +19,13 @@ use frame_support::{
 };
 use frame_system::pallet_prelude::BlockNumberFor;
 pub use pallet::*;
+use pallet_drand::types::ChainHash;
 use scale_info::prelude::collections::BTreeSet;
 use sp_runtime::SaturatedConversion;
-use sp_runtime::{Saturating, Weight, traits::Zero};
+use sp_runtime::{
+    Saturating, Weight,
+    traits::{BlakeTwo256, Hash, Zero},
+};
 use sp_std::{boxed::Box, vec::Vec};
 use subtensor_runtime_common::NetUid;
 use tle::{
@@ -78,6 +82,14 @@ pub mod pallet {
 
         /// Used to retrieve the given subnet's tempo
         type TempoInterface: GetTempoInterface;
+
+        /// The amount held on deposit for a blob
+        #[pallet::constant]
+        type BlobBaseDeposit: Get<BalanceOf<Self>>;
+
+        /// The amount held on deposit per byte of a blob
+        #[pallet::constant]
+        type BlobByteDeposit: Get<BalanceOf<Self>>;
     }
 
     /// Used to retrieve the given subnet's tempo
@@ -112,6 +124,55 @@ pub mod pallet {
             /// The account
             who: T::AccountId,
         },
+        /// The drand beacon of timelocked commitments was set
+        TimelockBeaconSet {
+            /// The netuid of the commitments
+            netuid: NetUid,
+            /// The chain hash of the beacon, `None` for the default beacon
+            beacon: Option<ChainHash>,
+        },
+        /// The number of past commitments kept per account on a subnet was set
+        CommitmentHistoryDepthSet {
+            /// The netuid of the commitments
+            netuid: NetUid,
+            /// The number of past commitments kept per account
+            depth: u32,
+        },
+        /// The schema of the commitments on a subnet was set
+        CommitmentSchemaSet {
+            /// The netuid of the commitments
+            netuid: NetUid,
+        },
+        /// The schema of the commitments on a subnet was removed
+        CommitmentSchemaRemoved {
+            /// The netuid of the commitments
+            netuid: NetUid,
+        },
+        /// The upload of a blob was started
+        BlobNoted {
+            /// The blake2-256 hash of the blob
+            hash: [u8; 32],
+            /// The account uploading the blob
+            who: T::AccountId,
+            /// The length of the blob in bytes
+            len: u32,
+        },
+        /// An account holds a stored blob, either after uploading all of its chunks or because
+        /// the blob was already stored
+        BlobStored {
+            /// The blake2-256 hash of the blob
+            hash: [u8; 32],
+            /// The account holding the blob
+            who: T::AccountId,
+        },
+        /// An account released a blob or its upload, and its deposit. The blob is removed once no
+        /// account holds it
+        BlobRemoved {
+            /// The blake2-256 hash of the blob
+            hash: [u8; 32],
+            /// The account releasing the blob
+            who: T::AccountId,
+        },
     }
 
     #[pallet::error]
@@ -124,6 +185,29 @@ pub mod pallet {
         SpaceLimitExceeded,
         /// Indicates that unreserve returned a leftover, which is unexpected.
         UnexpectedUnreserveLeftover,
+        /// The drand beacon is not tracked by the drand pallet
+        UnknownBeacon,
+        /// The subnet has timelocked commitments that are not revealed yet
+        TimelockedCommitmentsPending,
+        /// The commitment history depth exceeds the maximum
+        CommitmentHistoryDepthTooLarge,
+        /// The commitment schema has no fields, more fields than a commitment, unnamed or
+        /// duplicate fields, or invalid field types
+        InvalidCommitmentSchema,
+        /// The commitment fields do not match the schema of the subnet
+        CommitmentDoesNotMatchSchema,
+        /// The blob is empty or larger than the maximum blob size
+        InvalidBlobLength,
+        /// The account already holds the blob or is uploading it
+        BlobAlreadyNoted,
+        /// The account neither holds the blob nor is uploading it
+        UnknownBlob,
+        /// The account does not hold the stored blob
+        NotBlobOwner,
+        /// The chunk index is out of range, or the chunk has the wrong length
+        InvalidBlobChunk,
+        /// The uploaded chunks do not hash to the blob hash
+        BlobHashMismatch,
     }
 
     /// Tracks all CommitmentOf that have at least one timelocked field.
@@ -205,14 +289,96 @@ pub mod pallet {
     #[pallet::getter(fn max_space_per_user_per_rate_limit)]
     pub type MaxSpace<T> = StorageValue<_, u32, ValueQuery, DefaultMaxSpace>;
 
+    /// Maps netuid -> chain hash of the drand beacon the reveal rounds of timelocked
+    /// commitments refer to, the default beacon if unset
+    #[pallet::storage]
+    pub type TimelockBeacon<T: Config> = StorageMap<_, Identity, NetUid, ChainHash, OptionQuery>;
+
+    #[pallet::type_value]
+    /// The default number of past commitments kept per account
+    pub fn DefaultCommitmentHistoryDepth() -> u32 {
+        8
+    }
+
+    /// Maps netuid -> number of past commitments kept per account
+    #[pallet::storage]
+    pub type CommitmentHistoryDepth<T: Config> =
+        StorageMap<_, Identity, NetUid, u32, ValueQuery, DefaultCommitmentHistoryDepth>;
+
+    /// Maps (netuid, who) -> the commitments set by the account with the block they were set
+    /// at, oldest first
+    #[pallet::storage]
+    pub type CommitmentHistory<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        NetUid,
+        Twox64Concat,
+        T::AccountId,
+        BoundedVec<
+            (CommitmentInfo<T::MaxFields>, BlockNumberFor<T>),
+            ConstU32<MAX_COMMITMENT_HISTORY_DEPTH>,
+        >,
+        ValueQuery,
+    >;
+
+    /// Maps netuid -> schema the commitments on the subnet must match, free-form if unset
+    #[pallet::storage]
+    pub type CommitmentSchemas<T: Config> =
+        StorageMap<_, Identity, NetUid, CommitmentSchema, OptionQuery>;
+
+    /// Maps (account, blob hash) -> blob being uploaded by the account
+    #[pallet::storage]
+    pub type PendingBlobs<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Identity,
+        [u8; 32],
+        PendingBlob<BalanceOf<T>>,
+        OptionQuery,
+    >;
+
+    /// Maps (account, blob hash, chunk index) -> chunk of a blob being uploaded by the account
+    #[pallet::storage]
+    pub type BlobChunks<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Twox64Concat, T::AccountId>,
+            NMapKey<Identity, [u8; 32]>,
+            NMapKey<Twox64Concat, u32>,
+        ),
+        BoundedVec<u8, ConstU32<MAX_BLOB_CHUNK_SIZE_BYTES>>,
+        OptionQuery,
+    >;
+
+    /// Maps blob hash -> stored blob
+    #[pallet::storage]
+    pub type BlobOf<T: Config> = StorageMap<_, Identity, [u8; 32], BlobInfo, OptionQuery>;
+
+    /// Maps (blob hash, account) -> deposit held by the account for the stored blob
+    #[pallet::storage]
+    pub type BlobHolders<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        [u8; 32],
+        Twox64Concat,
+        T::AccountId,
+        BalanceOf<T>,
+        OptionQuery,
+    >;
+
+    /// Maps blob hash -> data of the stored blob
+    #[pallet::storage]
+    pub type BlobData<T: Config> = StorageMap<_, Identity, [u8; 32], Vec<u8>, OptionQuery>;
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Set the commitment for a given netuid
         #[pallet::call_index(0)]
         #[pallet::weight((
             Weight::from_parts(33_480_000, 0)
-			.saturating_add(T::DbWeight::get().reads(5_u64))
-			.saturating_add(T::DbWeight::get().writes(4_u64)),
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64)),
             DispatchClass::Normal,
             Pays::No
         ))]
@@ -233,6 +399,13 @@ pub mod pallet {
                 Error::<T>::TooManyFieldsInCommitmentInfo
             );
 
+            if let Some(schema) = CommitmentSchemas::<T>::get(netuid) {
+                ensure!(
+                    schema.decode(&info.fields).is_some(),
+                    Error::<T>::CommitmentDoesNotMatchSchema
+                );
+            }
+
             let cur_block = <frame_system::Pallet<T>>::block_number();
 
             let min_used_space: u64 = 100;
@@ -301,6 +474,7 @@ pub mod pallet {
 
             <CommitmentOf<T>>::insert(netuid, &who, id);
             <LastCommitment<T>>::insert(netuid, &who, cur_block);
+            Self::append_commitment_history(netuid, &who, *info.clone(), cur_block);
 
             if let Some(Data::TimelockEncrypted { reveal_round, .. }) = info
                 .fields
@@ -344,6 +518,199 @@ pub mod pallet {
             MaxSpace::<T>::set(new_limit);
             Ok(())
         }
+
+        /// Sudo-set the drand beacon of timelocked commitments on a subnet, `None` for the
+        /// default beacon. Pending commitments would otherwise be revealed against rounds of
+        /// the new beacon, so the beacon can only change once they are all revealed.
+        #[pallet::call_index(3)]
+        #[pallet::weight((
+            Weight::from_parts(2_856_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64)),
+            DispatchClass::Operational,
+            Pays::No
+        ))]
+        pub fn set_timelock_beacon(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            beacon: Option<ChainHash>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            if let Some(chain_hash) = beacon.as_ref() {
+                ensure!(
+                    pallet_drand::Pallet::<T>::is_tracked_beacon(chain_hash),
+                    Error::<T>::UnknownBeacon
+                );
+            }
+            ensure!(
+                !TimelockedIndex::<T>::get()
+                    .iter()
+                    .any(|(n, _)| *n == netuid),
+                Error::<T>::TimelockedCommitmentsPending
+            );
+
+            TimelockBeacon::<T>::set(netuid, beacon);
+            Self::deposit_event(Event::TimelockBeaconSet { netuid, beacon });
+            Ok(())
+        }
+
+        /// Start the upload of a blob of `len` bytes with the given blake2-256 hash, holding
+        /// a deposit for it. Commitments refer to the blob with a `BlakeTwo256` field, a blob
+        /// being stored once whoever refers to it. If the blob is already stored, the account
+        /// holds it right away without uploading it.
+        #[pallet::call_index(4)]
+        #[pallet::weight((
+            Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64)),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn note_blob(origin: OriginFor<T>, hash: [u8; 32], len: u32) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                len > 0 && len <= MAX_BLOB_SIZE_BYTES,
+                Error::<T>::InvalidBlobLength
+            );
+            ensure!(
+                !BlobHolders::<T>::contains_key(hash, &who)
+                    && !PendingBlobs::<T>::contains_key(&who, hash),
+                Error::<T>::BlobAlreadyNoted
+            );
+            let stored = BlobOf::<T>::get(hash);
+            if let Some(blob) = &stored {
+                ensure!(blob.len == len, Error::<T>::InvalidBlobLength);
+            }
+
+            let deposit = T::BlobBaseDeposit::get()
+                .saturating_add(T::BlobByteDeposit::get().saturating_mul(len.into()));
+            T::Currency::reserve(&who, deposit)?;
+
+            Self::deposit_event(Event::BlobNoted {
+                hash,
+                who: who.clone(),
+                len,
+            });
+            if stored.is_some() {
+                Self::hold_blob(hash, who, len, deposit, None);
+            } else {
+                PendingBlobs::<T>::insert(
+                    &who,
+                    hash,
+                    PendingBlob {
+                        len,
+                        deposit,
+                        uploaded_chunks: 0,
+                    },
+                );
+            }
+            Ok(())
+        }
+
+        /// Upload a chunk of a blob. Chunks are `MAX_BLOB_CHUNK_SIZE_BYTES` long except for the
+        /// last one, and can be uploaded again in any order. Once all chunks are uploaded, the
+        /// blob is checked against its hash and stored, unless another account stored it first.
+        #[pallet::call_index(5)]
+        #[pallet::weight((
+            Weight::from_parts(30_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(MAX_BLOB_CHUNKS.saturating_add(3).into()))
+			.saturating_add(T::DbWeight::get().writes(MAX_BLOB_CHUNKS.saturating_add(4).into())),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn upload_blob_chunk(
+            origin: OriginFor<T>,
+            hash: [u8; 32],
+            index: u32,
+            chunk: BoundedVec<u8, ConstU32<MAX_BLOB_CHUNK_SIZE_BYTES>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let mut pending = PendingBlobs::<T>::get(&who, hash).ok_or(Error::<T>::UnknownBlob)?;
+
+            let chunk_count = blob_chunk_count(pending.len);
+            ensure!(index < chunk_count, Error::<T>::InvalidBlobChunk);
+            let expected_len = if index.saturating_add(1) == chunk_count {
+                pending
+                    .len
+                    .saturating_sub(index.saturating_mul(MAX_BLOB_CHUNK_SIZE_BYTES))
+            } else {
+                MAX_BLOB_CHUNK_SIZE_BYTES
+            };
+            ensure!(
+                chunk.len() == expected_len as usize,
+                Error::<T>::InvalidBlobChunk
+            );
+
+            if !BlobChunks::<T>::contains_key((&who, hash, index)) {
+                pending.uploaded_chunks = pending.uploaded_chunks.saturating_add(1);
+            }
+            BlobChunks::<T>::insert((&who, hash, index), chunk);
+
+            if pending.uploaded_chunks < chunk_count {
+                PendingBlobs::<T>::insert(&who, hash, pending);
+                return Ok(());
+            }
+
+            let data: Vec<u8> = (0..chunk_count)
+                .filter_map(|index| BlobChunks::<T>::get((&who, hash, index)))
+                .flat_map(|chunk| chunk.into_inner())
+                .collect();
+            ensure!(
+                BlakeTwo256::hash(&data).to_fixed_bytes() == hash,
+                Error::<T>::BlobHashMismatch
+            );
+
+            let _ = BlobChunks::<T>::clear_prefix((&who, hash), chunk_count, None);
+            PendingBlobs::<T>::remove(&who, hash);
+            Self::hold_blob(hash, who, pending.len, pending.deposit, Some(data));
+            Ok(())
+        }
+
+        /// Release a blob or its upload, and the deposit held for it. The blob is removed once
+        /// no account holds it.
+        #[pallet::call_index(6)]
+        #[pallet::weight((
+            Weight::from_parts(20_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(MAX_BLOB_CHUNKS.saturating_add(3).into())),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn remove_blob(origin: OriginFor<T>, hash: [u8; 32]) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let deposit = if let Some(deposit) = BlobHolders::<T>::take(hash, &who) {
+                if let Some(mut blob) = BlobOf::<T>::get(hash) {
+                    blob.holders = blob.holders.saturating_sub(1);
+                    if blob.holders == 0 {
+                        BlobOf::<T>::remove(hash);
+                        BlobData::<T>::remove(hash);
+                    } else {
+                        BlobOf::<T>::insert(hash, blob);
+                    }
+                }
+                deposit
+            } else if let Some(pending) = PendingBlobs::<T>::take(&who, hash) {
+                let _ = BlobChunks::<T>::clear_prefix(
+                    (&who, hash),
+                    blob_chunk_count(pending.len),
+                    None,
+                );
+                pending.deposit
+            } else if BlobOf::<T>::contains_key(hash) {
+                return Err(Error::<T>::NotBlobOwner.into());
+            } else {
+                return Err(Error::<T>::UnknownBlob.into());
+            };
+
+            let err_amount = T::Currency::unreserve(&who, deposit);
+            ensure!(
+                err_amount.is_zero(),
+                Error::<T>::UnexpectedUnreserveLeftover
+            );
+            Self::deposit_event(Event::BlobRemoved { hash, who });
+            Ok(())
+        }
     }
 
     #[pallet::hooks]
@@ -389,7 +756,7 @@ pub enum CallType {
     Other,
 }
 
-use frame_support::{dispatch::DispatchResult, pallet_prelude::TypeInfo};
+use frame_support::{dispatch::DispatchResult, ensure, pallet_prelude::TypeInfo};
 
 impl<T: Config> Pallet<T> {
     pub fn reveal_timelocked_commitments() -> Result<Weight, sp_runtime::DispatchError> {
@@ -411,6 +778,10 @@ impl<T: Config> Pallet<T> {
                 continue;
             };
 
+            // The beacon and, when one is named, the default beacon configuration.
+            let beacon = TimelockBeacon::<T>::get(netuid);
+            total_weight = total_weight.saturating_add(T::DbWeight::get().reads(2));
+
             let original_fields = registration.info.fields.clone();
             let mut remain_fields = Vec::new();
             let mut revealed_fields = Vec::new();
@@ -422,7 +793,7 @@ impl<T: Config> Pallet<T> {
                         reveal_round,
                     } => {
                         total_weight = total_weight.saturating_add(T::DbWeight::get().reads(1));
-                        let pulse = match pallet_drand::Pulses::<T>::get(reveal_round) {
+                        let pulse = match pallet_drand::Pallet::<T>::pulse(beacon, reveal_round) {
                             Some(p) => p,
                             None => {
                                 remain_fields.push(Data::TimelockEncrypted {
@@ -567,12 +938,142 @@ impl<T: Config> Pallet<T> {
         commitments
     }
 
+    /// Append a commitment to the history of the account, dropping the oldest ones beyond the
+    /// history depth of the subnet
+    fn append_commitment_history(
+        netuid: NetUid,
+        who: &T::AccountId,
+        info: CommitmentInfo<T::MaxFields>,
+        block: BlockNumberFor<T>,
+    ) {
+        let depth =
+            CommitmentHistoryDepth::<T>::get(netuid).min(MAX_COMMITMENT_HISTORY_DEPTH) as usize;
+        CommitmentHistory::<T>::mutate_exists(netuid, who, |maybe_history| {
+            let mut history = maybe_history
+                .take()
+                .map(BoundedVec::into_inner)
+                .unwrap_or_default();
+            history.push((info, block));
+            let excess = history.len().saturating_sub(depth);
+            history.drain(..excess);
+            *maybe_history = (!history.is_empty()).then(|| BoundedVec::truncate_from(history));
+        });
+    }
+
+    /// Set the number of past commitments kept per account on a subnet. Histories longer than
+    /// a lowered depth are trimmed on the next commitment of the account, and are not returned
+    /// beyond the depth meanwhile.
+    pub fn set_commitment_history_depth(netuid: NetUid, depth: u32) -> DispatchResult {
+        ensure!(
+            depth <= MAX_COMMITMENT_HISTORY_DEPTH,
+            Error::<T>::CommitmentHistoryDepthTooLarge
+        );
+
+        CommitmentHistoryDepth::<T>::insert(netuid, depth);
+        Self::deposit_event(Event::CommitmentHistoryDepthSet { netuid, depth });
+        Ok(())
+    }
+
+    /// The last `limit` commitments of the account on the subnet with the block they were set
+    /// at, oldest first
+    pub fn get_commitment_history(
+        netuid: NetUid,
+        who: &T::AccountId,
+        limit: u32,
+    ) -> Vec<(CommitmentInfo<T::MaxFields>, BlockNumberFor<T>)> {
+        let kept = CommitmentHistoryDepth::<T>::get(netuid).min(limit) as usize;
+        let mut history = CommitmentHistory::<T>::get(netuid, who).into_inner();
+        let skipped = history.len().saturating_sub(kept);
+        history.drain(..skipped);
+        history
+    }
+
+    /// Set the schema the commitments on a subnet must match, `None` for free-form commitments.
+    /// Commitments set before are not checked against the new schema.
+    pub fn set_commitment_schema(
+        netuid: NetUid,
+        schema: Option<CommitmentSchema>,
+    ) -> DispatchResult {
+        let Some(schema) = schema else {
+            CommitmentSchemas::<T>::remove(netuid);
+            Self::deposit_event(Event::CommitmentSchemaRemoved { netuid });
+            return Ok(());
+        };
+
+        let names: BTreeSet<&[u8]> = schema
+            .fields
+            .iter()
+            .map(|field| field.name.as_slice())
+            .collect();
+        ensure!(
+            !schema.fields.is_empty()
+                && schema.fields.len() <= T::MaxFields::get() as usize
+                && names.len() == schema.fields.len()
+                && !names.contains(&[][..])
+                && schema
+                    .fields
+                    .iter()
+                    .all(|field| field.field_type.is_valid()),
+            Error::<T>::InvalidCommitmentSchema
+        );
+
+        CommitmentSchemas::<T>::insert(netuid, schema);
+        Self::deposit_event(Event::CommitmentSchemaSet { netuid });
+        Ok(())
+    }
+
+    /// The fields of the commitment of the account decoded with the schema of the subnet, `None`
+    /// if the subnet has no schema or the commitment does not match it
+    pub fn decode_commitment(
+        netuid: NetUid,
+        who: &T::AccountId,
+    ) -> Option<Vec<DecodedCommitmentField>> {
+        let schema = CommitmentSchemas::<T>::get(netuid)?;
+        let registration = CommitmentOf::<T>::get(netuid, who)?;
+        schema.decode(&registration.info.fields)
+    }
+
+    /// The data of a stored blob
+    pub fn get_blob(hash: [u8; 32]) -> Option<Vec<u8>> {
+        BlobData::<T>::get(hash)
+    }
+
+    /// Makes `who` hold the blob with its deposit, storing `data` if no account stored the blob
+    /// yet
+    fn hold_blob(
+        hash: [u8; 32],
+        who: T::AccountId,
+        len: u32,
+        deposit: BalanceOf<T>,
+        data: Option<Vec<u8>>,
+    ) {
+        let created = BlobOf::<T>::mutate(hash, |maybe_blob| match maybe_blob {
+            Some(blob) => {
+                blob.holders = blob.holders.saturating_add(1);
+                false
+            }
+            None => {
+                *maybe_blob = Some(BlobInfo { len, holders: 1 });
+                true
+            }
+        });
+        if let (true, Some(data)) = (created, data) {
+            BlobData::<T>::insert(hash, data);
+        }
+        BlobHolders::<T>::insert(hash, &who, deposit);
+        Self::deposit_event(Event::BlobStored { hash, who });
+    }
+
     pub fn purge_netuid(netuid: NetUid) {
         let _ = CommitmentOf::<T>::clear_prefix(netuid, u32::MAX, None);
         let _ = LastCommitment::<T>::clear_prefix(netuid, u32::MAX, None);
         let _ = LastBondsReset::<T>::clear_prefix(netuid, u32::MAX, None);
         let _ = RevealedCommitments::<T>::clear_prefix(netuid, u32::MAX, None);
         let _ = UsedSpaceOf::<T>::clear_prefix(netuid, u32::MAX, None);
+        let _ = CommitmentHistory::<T>::clear_prefix(netuid, u32::MAX, None);
+        TimelockBeacon::<T>::remove(netuid);
+        CommitmentHistoryDepth::<T>::remove(netuid);
+        CommitmentSchemas::<T>::remove(netuid);
 
         TimelockedIndex::<T>::mutate(|index| {
             index.retain(|(n, _)| *n != netuid);
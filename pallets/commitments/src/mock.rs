@@ -103,6 +103,8 @@ impl pallet_commitments::Config for Test {
     type InitialDeposit = ConstU64<0>;
     type TempoInterface = MockTempoInterface;
     type OnMetadataCommitment = ();
+    type BlobBaseDeposit = ConstU64<100>;
+    type BlobByteDeposit = ConstU64<1>;
 }
 
 pub struct MockTempoInterface;
@@ -123,6 +125,7 @@ impl pallet_drand::Config for Test {
     type Verifier = pallet_drand::verifier::QuicknetVerifier;
     type UnsignedPriority = ConstU64<{ 1 << 20 }>;
     type HttpFetchTimeout = ConstU64<1_000>;
+    type BeaconUsage = ();
 }
 
 pub mod test_crypto {
@@ -4,8 +4,12 @@ use subtensor_runtime_common::NetUid;
 
 #[cfg(test)]
 use crate::{
-    BalanceOf, CommitmentInfo, CommitmentOf, Config, Data, Error, Event, LastBondsReset,
-    LastCommitment, MaxSpace, Pallet, Registration, RevealedCommitments, TimelockedIndex,
+    BalanceOf, BlobChunks, BlobOf, CommitmentFieldValue, CommitmentHistory, CommitmentHistoryDepth,
+    CommitmentInfo, CommitmentOf, CommitmentSchema, CommitmentSchemas, Config, Data,
+    DecodedCommitmentField, Error, Event, LastBondsReset, LastCommitment,
+    MAX_BIGRAW_COMMITMENT_SIZE_BYTES, MAX_BLOB_CHUNK_SIZE_BYTES, MAX_BLOB_SIZE_BYTES,
+    MAX_COMMITMENT_HISTORY_DEPTH, MaxSpace, Pallet, PendingBlobs, Registration,
+    RevealedCommitments, SchemaField, SchemaFieldType, TimelockBeacon, TimelockedIndex,
     UsageTracker, UsedSpaceOf,
     mock::{
         Balances, DRAND_QUICKNET_SIG_2000_HEX, DRAND_QUICKNET_SIG_HEX, RuntimeEvent, RuntimeOrigin,
@@ -15,7 +19,7 @@ use crate::{
 use frame_support::pallet_prelude::Hooks;
 use frame_support::{
     BoundedVec, assert_noop, assert_ok,
-    traits::{Currency, Get, ReservableCurrency},
+    traits::{ConstU32, Currency, Get, ReservableCurrency},
 };
 use frame_system::{Pallet as System, RawOrigin};
 
@@ -1160,6 +1164,83 @@ fn on_initialize_reveals_matured_timelocks() {
     });
 }
 
+#[test]
+fn timelocked_commitments_reveal_with_subnet_beacon() {
+    new_test_ext().execute_with(|| {
+        let who = 42;
+        let netuid = NetUid::from(7);
+        let reveal_round = 1000;
+        let chain_hash = [1u8; 32];
+
+        assert_noop!(
+            Pallet::<Test>::set_timelock_beacon(RuntimeOrigin::signed(who), netuid, None),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Pallet::<Test>::set_timelock_beacon(RuntimeOrigin::root(), netuid, Some(chain_hash)),
+            Error::<Test>::UnknownBeacon
+        );
+
+        pallet_drand::BeaconConfigs::<Test>::insert(chain_hash, Default::default());
+        assert_ok!(Pallet::<Test>::set_timelock_beacon(
+            RuntimeOrigin::root(),
+            netuid,
+            Some(chain_hash)
+        ));
+        assert_eq!(TimelockBeacon::<Test>::get(netuid), Some(chain_hash));
+
+        let inner_info: CommitmentInfo<<Test as Config>::MaxFields> = CommitmentInfo {
+            fields: BoundedVec::try_from(vec![Data::Raw(
+                b"beacon".to_vec().try_into().expect("<= 128 bytes"),
+            )])
+            .expect("Should not exceed MaxFields"),
+        };
+        let encrypted = produce_ciphertext(&inner_info.encode(), reveal_round);
+        let info_outer = CommitmentInfo {
+            fields: BoundedVec::try_from(vec![Data::TimelockEncrypted {
+                encrypted,
+                reveal_round,
+            }])
+            .expect("One field is well under MaxFields"),
+        };
+
+        System::<Test>::set_block_number(1);
+        assert_ok!(Pallet::<Test>::set_commitment(
+            RuntimeOrigin::signed(who),
+            netuid,
+            Box::new(info_outer)
+        ));
+
+        // The beacon cannot change while the commitment is pending
+        assert_noop!(
+            Pallet::<Test>::set_timelock_beacon(RuntimeOrigin::root(), netuid, None),
+            Error::<Test>::TimelockedCommitmentsPending
+        );
+
+        // A pulse of the default beacon is not used for the reveal
+        let drand_sig = hex::decode(DRAND_QUICKNET_SIG_HEX).expect("decode DRAND sig");
+        insert_drand_pulse(reveal_round, &drand_sig);
+        System::<Test>::set_block_number(2);
+        <Pallet<Test> as Hooks<u64>>::on_initialize(2);
+        assert!(RevealedCommitments::<Test>::get(netuid, who).is_none());
+
+        let pulse =
+            pallet_drand::Pulses::<Test>::take(reveal_round).expect("pulse was just inserted");
+        pallet_drand::BeaconPulses::<Test>::insert(chain_hash, reveal_round, pulse);
+        System::<Test>::set_block_number(3);
+        <Pallet<Test> as Hooks<u64>>::on_initialize(3);
+        assert!(RevealedCommitments::<Test>::get(netuid, who).is_some());
+        assert!(!TimelockedIndex::<Test>::get().contains(&(netuid, who)));
+
+        assert_ok!(Pallet::<Test>::set_timelock_beacon(
+            RuntimeOrigin::root(),
+            netuid,
+            None
+        ));
+        assert_eq!(TimelockBeacon::<Test>::get(netuid), None);
+    });
+}
+
 #[test]
 fn set_commitment_unreserve_leftover_fails() {
     new_test_ext().execute_with(|| {
@@ -2302,3 +2383,420 @@ fn purge_netuid_clears_only_that_netuid() {
         assert!(!TimelockedIndex::<Test>::get().contains(&(net_a, who_a1)));
     });
 }
+
+#[test]
+fn commitment_history_keeps_the_last_commitments() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let raw_info = |byte: u8| CommitmentInfo::<TestMaxFields> {
+            fields: BoundedVec::try_from(vec![Data::Raw(
+                vec![byte].try_into().expect("Expected not to panic"),
+            )])
+            .expect("Expected not to panic"),
+        };
+
+        assert_noop!(
+            Pallet::<Test>::set_commitment_history_depth(netuid, MAX_COMMITMENT_HISTORY_DEPTH + 1),
+            Error::<Test>::CommitmentHistoryDepthTooLarge
+        );
+        assert_ok!(Pallet::<Test>::set_commitment_history_depth(netuid, 3));
+        System::<Test>::assert_last_event(RuntimeEvent::Commitments(
+            Event::CommitmentHistoryDepthSet { netuid, depth: 3 },
+        ));
+
+        for block in 1..=5u8 {
+            System::<Test>::set_block_number(block.into());
+            assert_ok!(Pallet::<Test>::set_commitment(
+                RuntimeOrigin::signed(1),
+                netuid,
+                Box::new(raw_info(block))
+            ));
+        }
+
+        // Only the last three commitments are kept, oldest first
+        assert_eq!(
+            Pallet::<Test>::get_commitment_history(netuid, &1, u32::MAX),
+            vec![(raw_info(3), 3), (raw_info(4), 4), (raw_info(5), 5)]
+        );
+        assert_eq!(
+            Pallet::<Test>::get_commitment_history(netuid, &1, 1),
+            vec![(raw_info(5), 5)]
+        );
+        assert!(Pallet::<Test>::get_commitment_history(NetUid::from(2), &1, u32::MAX).is_empty());
+
+        // A lowered depth applies to the returned history right away
+        assert_ok!(Pallet::<Test>::set_commitment_history_depth(netuid, 2));
+        assert_eq!(
+            Pallet::<Test>::get_commitment_history(netuid, &1, u32::MAX),
+            vec![(raw_info(4), 4), (raw_info(5), 5)]
+        );
+
+        // A depth of zero stops keeping history
+        assert_ok!(Pallet::<Test>::set_commitment_history_depth(netuid, 0));
+        assert_ok!(Pallet::<Test>::set_commitment(
+            RuntimeOrigin::signed(1),
+            netuid,
+            Box::new(raw_info(6))
+        ));
+        assert!(!CommitmentHistory::<Test>::contains_key(netuid, 1));
+
+        assert_ok!(Pallet::<Test>::set_commitment_history_depth(netuid, 3));
+        assert_ok!(Pallet::<Test>::set_commitment(
+            RuntimeOrigin::signed(1),
+            netuid,
+            Box::new(raw_info(7))
+        ));
+        Pallet::<Test>::purge_netuid(netuid);
+        assert!(!CommitmentHistory::<Test>::contains_key(netuid, 1));
+        assert_eq!(CommitmentHistoryDepth::<Test>::get(netuid), 8);
+    });
+}
+
+#[test]
+fn commitments_are_validated_against_the_subnet_schema() {
+    new_test_ext().execute_with(|| {
+        System::<Test>::set_block_number(1);
+        let netuid = NetUid::from(1);
+        let field = |name: &[u8], field_type| SchemaField {
+            name: name.to_vec().try_into().expect("Expected not to panic"),
+            field_type,
+        };
+        let schema = |fields: Vec<SchemaField>| CommitmentSchema {
+            fields: fields.try_into().expect("Expected not to panic"),
+        };
+        let raw =
+            |bytes: &[u8]| Data::Raw(bytes.to_vec().try_into().expect("Expected not to panic"));
+        let info = |fields: Vec<Data>| {
+            Box::new(CommitmentInfo::<TestMaxFields> {
+                fields: fields.try_into().expect("Expected not to panic"),
+            })
+        };
+
+        // Invalid schemas
+        for invalid in [
+            schema(vec![]),
+            schema(vec![field(b"", SchemaFieldType::U64)]),
+            schema(vec![
+                field(b"version", SchemaFieldType::U64),
+                field(b"version", SchemaFieldType::Hash),
+            ]),
+            schema(vec![field(b"blob", SchemaFieldType::Bytes { max_len: 0 })]),
+            schema(vec![field(
+                b"blob",
+                SchemaFieldType::Bytes {
+                    max_len: MAX_BIGRAW_COMMITMENT_SIZE_BYTES + 1,
+                },
+            )]),
+        ] {
+            assert_noop!(
+                Pallet::<Test>::set_commitment_schema(netuid, Some(invalid)),
+                Error::<Test>::InvalidCommitmentSchema
+            );
+        }
+
+        let model_schema = schema(vec![
+            field(b"version", SchemaFieldType::U64),
+            field(b"model", SchemaFieldType::Hash),
+            field(b"endpoint", SchemaFieldType::Url { max_len: 64 }),
+        ]);
+        assert_ok!(Pallet::<Test>::set_commitment_schema(
+            netuid,
+            Some(model_schema.clone())
+        ));
+        System::<Test>::assert_last_event(RuntimeEvent::Commitments(Event::CommitmentSchemaSet {
+            netuid,
+        }));
+        assert_eq!(CommitmentSchemas::<Test>::get(netuid), Some(model_schema));
+
+        // Commitments not matching the schema are rejected
+        for invalid in [
+            vec![raw(&7u64.to_le_bytes()), Data::Sha256([1; 32])],
+            vec![raw(&[7]), Data::Sha256([1; 32]), raw(b"https://model.io")],
+            vec![
+                raw(&7u64.to_le_bytes()),
+                raw(&[1; 32]),
+                raw(b"https://model.io"),
+            ],
+            vec![
+                raw(&7u64.to_le_bytes()),
+                Data::Sha256([1; 32]),
+                raw(b"model.io"),
+            ],
+        ] {
+            assert_noop!(
+                Pallet::<Test>::set_commitment(RuntimeOrigin::signed(1), netuid, info(invalid)),
+                Error::<Test>::CommitmentDoesNotMatchSchema
+            );
+        }
+
+        assert_ok!(Pallet::<Test>::set_commitment(
+            RuntimeOrigin::signed(1),
+            netuid,
+            info(vec![
+                raw(&7u64.to_le_bytes()),
+                Data::Sha256([1; 32]),
+                raw(b"https://model.io"),
+            ])
+        ));
+        assert_eq!(
+            Pallet::<Test>::decode_commitment(netuid, &1),
+            Some(vec![
+                DecodedCommitmentField {
+                    name: b"version".to_vec(),
+                    value: CommitmentFieldValue::U64(7),
+                },
+                DecodedCommitmentField {
+                    name: b"model".to_vec(),
+                    value: CommitmentFieldValue::Hash([1; 32]),
+                },
+                DecodedCommitmentField {
+                    name: b"endpoint".to_vec(),
+                    value: CommitmentFieldValue::Url(b"https://model.io".to_vec()),
+                },
+            ])
+        );
+
+        // Other subnets stay free-form
+        assert_ok!(Pallet::<Test>::set_commitment(
+            RuntimeOrigin::signed(1),
+            NetUid::from(2),
+            info(vec![raw(b"anything")])
+        ));
+        assert_eq!(Pallet::<Test>::decode_commitment(NetUid::from(2), &1), None);
+
+        assert_ok!(Pallet::<Test>::set_commitment_schema(netuid, None));
+        System::<Test>::assert_last_event(RuntimeEvent::Commitments(
+            Event::CommitmentSchemaRemoved { netuid },
+        ));
+        assert_ok!(Pallet::<Test>::set_commitment(
+            RuntimeOrigin::signed(1),
+            netuid,
+            info(vec![raw(b"anything")])
+        ));
+    });
+}
+
+#[allow(clippy::indexing_slicing)]
+#[test]
+fn blobs_are_uploaded_in_chunks_and_stored_once() {
+    new_test_ext().execute_with(|| {
+        let owner = 1;
+        let other = 2;
+        let late = 3;
+        Balances::make_free_balance_be(&owner, 1_000_000);
+        Balances::make_free_balance_be(&other, 1_000_000);
+        Balances::make_free_balance_be(&late, 1_000_000);
+
+        let chunk_size = MAX_BLOB_CHUNK_SIZE_BYTES as usize;
+        let blob: Vec<u8> = (0..chunk_size + 100).map(|i| i as u8).collect();
+        let hash = sp_io::hashing::blake2_256(&blob);
+        let len = blob.len() as u32;
+        let chunk = |bytes: &[u8]| -> BoundedVec<u8, ConstU32<MAX_BLOB_CHUNK_SIZE_BYTES>> {
+            bytes.to_vec().try_into().expect("Expected not to panic")
+        };
+
+        assert_noop!(
+            Pallet::<Test>::note_blob(RuntimeOrigin::signed(owner), hash, 0),
+            Error::<Test>::InvalidBlobLength
+        );
+        assert_noop!(
+            Pallet::<Test>::note_blob(RuntimeOrigin::signed(owner), hash, MAX_BLOB_SIZE_BYTES + 1),
+            Error::<Test>::InvalidBlobLength
+        );
+
+        assert_ok!(Pallet::<Test>::note_blob(
+            RuntimeOrigin::signed(owner),
+            hash,
+            len
+        ));
+        let deposit = 100 + u64::from(len);
+        assert_eq!(Balances::reserved_balance(owner), deposit);
+
+        assert_noop!(
+            Pallet::<Test>::note_blob(RuntimeOrigin::signed(owner), hash, len),
+            Error::<Test>::BlobAlreadyNoted
+        );
+
+        // Noting a hash does not keep other accounts from uploading the same blob
+        assert_ok!(Pallet::<Test>::note_blob(
+            RuntimeOrigin::signed(other),
+            hash,
+            len
+        ));
+        assert_ok!(Pallet::<Test>::upload_blob_chunk(
+            RuntimeOrigin::signed(other),
+            hash,
+            1,
+            chunk(&blob[chunk_size..])
+        ));
+        assert_noop!(
+            Pallet::<Test>::upload_blob_chunk(
+                RuntimeOrigin::signed(late),
+                hash,
+                0,
+                chunk(&blob[..chunk_size])
+            ),
+            Error::<Test>::UnknownBlob
+        );
+        assert_noop!(
+            Pallet::<Test>::upload_blob_chunk(
+                RuntimeOrigin::signed(owner),
+                hash,
+                2,
+                chunk(&blob[..100])
+            ),
+            Error::<Test>::InvalidBlobChunk
+        );
+        assert_noop!(
+            Pallet::<Test>::upload_blob_chunk(
+                RuntimeOrigin::signed(owner),
+                hash,
+                0,
+                chunk(&blob[..100])
+            ),
+            Error::<Test>::InvalidBlobChunk
+        );
+
+        // A wrong chunk is caught once all chunks are uploaded, and can be uploaded again
+        assert_ok!(Pallet::<Test>::upload_blob_chunk(
+            RuntimeOrigin::signed(owner),
+            hash,
+            1,
+            chunk(&[0; 100])
+        ));
+        assert_noop!(
+            Pallet::<Test>::upload_blob_chunk(
+                RuntimeOrigin::signed(owner),
+                hash,
+                0,
+                chunk(&blob[..chunk_size])
+            ),
+            Error::<Test>::BlobHashMismatch
+        );
+        assert_ok!(Pallet::<Test>::upload_blob_chunk(
+            RuntimeOrigin::signed(owner),
+            hash,
+            1,
+            chunk(&blob[chunk_size..])
+        ));
+        assert_eq!(Pallet::<Test>::get_blob(hash), None);
+
+        assert_ok!(Pallet::<Test>::upload_blob_chunk(
+            RuntimeOrigin::signed(owner),
+            hash,
+            0,
+            chunk(&blob[..chunk_size])
+        ));
+        System::<Test>::assert_last_event(RuntimeEvent::Commitments(Event::BlobStored {
+            hash,
+            who: owner,
+        }));
+        assert_eq!(Pallet::<Test>::get_blob(hash), Some(blob.clone()));
+        assert!(!PendingBlobs::<Test>::contains_key(owner, hash));
+        assert_eq!(BlobChunks::<Test>::iter_prefix((owner, hash)).count(), 0);
+        assert_eq!(BlobOf::<Test>::get(hash).map(|blob| blob.holders), Some(1));
+
+        // An upload finishing after the blob is stored only adds a holder
+        assert_ok!(Pallet::<Test>::upload_blob_chunk(
+            RuntimeOrigin::signed(other),
+            hash,
+            0,
+            chunk(&blob[..chunk_size])
+        ));
+        assert_eq!(BlobOf::<Test>::get(hash).map(|blob| blob.holders), Some(2));
+        assert_eq!(BlobChunks::<Test>::iter_prefix((other, hash)).count(), 0);
+        assert_eq!(Balances::reserved_balance(other), deposit);
+
+        // Noting a stored blob holds it right away
+        assert_noop!(
+            Pallet::<Test>::note_blob(RuntimeOrigin::signed(late), hash, len + 1),
+            Error::<Test>::InvalidBlobLength
+        );
+        assert_ok!(Pallet::<Test>::note_blob(
+            RuntimeOrigin::signed(late),
+            hash,
+            len
+        ));
+        System::<Test>::assert_last_event(RuntimeEvent::Commitments(Event::BlobStored {
+            hash,
+            who: late,
+        }));
+        assert!(!PendingBlobs::<Test>::contains_key(late, hash));
+        assert_eq!(BlobOf::<Test>::get(hash).map(|blob| blob.holders), Some(3));
+        assert_eq!(Balances::reserved_balance(late), deposit);
+
+        // Commitments of any account refer to the blob by its hash
+        assert_ok!(Pallet::<Test>::set_commitment(
+            RuntimeOrigin::signed(other),
+            NetUid::from(1),
+            Box::new(CommitmentInfo {
+                fields: BoundedVec::try_from(vec![Data::BlakeTwo256(hash)])
+                    .expect("Expected not to panic"),
+            })
+        ));
+
+        // The blob is removed once the last holder releases it
+        assert_noop!(
+            Pallet::<Test>::remove_blob(RuntimeOrigin::signed(4), hash),
+            Error::<Test>::NotBlobOwner
+        );
+        for who in [owner, other] {
+            assert_ok!(Pallet::<Test>::remove_blob(
+                RuntimeOrigin::signed(who),
+                hash
+            ));
+            assert_eq!(Balances::reserved_balance(who), 0);
+            assert_eq!(Pallet::<Test>::get_blob(hash), Some(blob.clone()));
+        }
+        assert_noop!(
+            Pallet::<Test>::remove_blob(RuntimeOrigin::signed(owner), hash),
+            Error::<Test>::NotBlobOwner
+        );
+        assert_ok!(Pallet::<Test>::remove_blob(
+            RuntimeOrigin::signed(late),
+            hash
+        ));
+        assert_eq!(Balances::reserved_balance(late), 0);
+        assert_eq!(Pallet::<Test>::get_blob(hash), None);
+        assert!(!BlobOf::<Test>::contains_key(hash));
+    });
+}
+
+#[test]
+fn removing_a_pending_blob_releases_its_deposit() {
+    new_test_ext().execute_with(|| {
+        let owner = 1;
+        Balances::make_free_balance_be(&owner, 1_000_000);
+        let hash = [7; 32];
+        let len = MAX_BLOB_CHUNK_SIZE_BYTES + 10;
+
+        assert_ok!(Pallet::<Test>::note_blob(
+            RuntimeOrigin::signed(owner),
+            hash,
+            len
+        ));
+        assert_ok!(Pallet::<Test>::upload_blob_chunk(
+            RuntimeOrigin::signed(owner),
+            hash,
+            1,
+            vec![0; 10].try_into().expect("Expected not to panic")
+        ));
+        assert_eq!(Balances::reserved_balance(owner), 100 + u64::from(len));
+
+        assert_ok!(Pallet::<Test>::remove_blob(
+            RuntimeOrigin::signed(owner),
+            hash
+        ));
+        System::<Test>::assert_last_event(RuntimeEvent::Commitments(Event::BlobRemoved {
+            hash,
+            who: owner,
+        }));
+        assert_eq!(Balances::reserved_balance(owner), 0);
+        assert!(!PendingBlobs::<Test>::contains_key(owner, hash));
+        assert_eq!(BlobChunks::<Test>::iter_prefix((owner, hash)).count(), 0);
+        assert_noop!(
+            Pallet::<Test>::remove_blob(RuntimeOrigin::signed(owner), hash),
+            Error::<Test>::UnknownBlob
+        );
+    });
+}
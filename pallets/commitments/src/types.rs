@@ -384,6 +384,9 @@ pub struct CommitmentInfo<FieldLimit: Get<u32>> {
 /// Maximum size of the serialized timelock commitment in bytes
 pub const MAX_TIMELOCK_COMMITMENT_SIZE_BYTES: u32 = 1024;
 pub const MAX_BIGRAW_COMMITMENT_SIZE_BYTES: u32 = 512;
+/// Maximum number of past commitments a subnet can keep per account. Commitments are free, so
+/// the history is kept short.
+pub const MAX_COMMITMENT_HISTORY_DEPTH: u32 = 16;
 
 /// Contains the decrypted data of a revealed commitment.
 #[freeze_struct("bf575857b57f9bef")]
@@ -454,3 +457,216 @@ impl<
         })
     }
 }
+
+/// Maximum number of fields of a commitment schema
+pub const MAX_SCHEMA_FIELDS: u32 = 16;
+/// Maximum length of the name of a commitment schema field
+pub const MAX_SCHEMA_FIELD_NAME_LEN: u32 = 32;
+
+/// The type of a commitment schema field, and the `Data` variants it accepts
+#[derive(
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    MaxEncodedLen,
+    TypeInfo,
+)]
+pub enum SchemaFieldType {
+    /// A little-endian u64 stored as an 8 byte `Raw`
+    U64,
+    /// Any of the hash variants
+    Hash,
+    /// A utf-8 url with a scheme, stored as `Raw` or `BigRaw`
+    Url {
+        /// The maximum length of the url in bytes
+        max_len: u32,
+    },
+    /// Bytes stored as `Raw` or `BigRaw`
+    Bytes {
+        /// The maximum length of the bytes
+        max_len: u32,
+    },
+    /// A timelock-encrypted blob
+    TimelockEncrypted,
+}
+
+impl SchemaFieldType {
+    /// Whether the type is well formed, its maximum length fitting in a `BigRaw`
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Self::Url { max_len } | Self::Bytes { max_len } => {
+                *max_len > 0 && *max_len <= MAX_BIGRAW_COMMITMENT_SIZE_BYTES
+            }
+            _ => true,
+        }
+    }
+
+    /// The value of the data as this type, `None` if the data does not match the type
+    pub fn decode(&self, data: &Data) -> Option<CommitmentFieldValue> {
+        let bytes = match data {
+            Data::Raw(bytes) => Some(bytes.to_vec()),
+            Data::BigRaw(bytes) => Some(bytes.to_vec()),
+            _ => None,
+        };
+
+        match (self, data) {
+            (Self::U64, Data::Raw(bytes)) => <[u8; 8]>::try_from(bytes.as_slice())
+                .ok()
+                .map(|le| CommitmentFieldValue::U64(u64::from_le_bytes(le))),
+            (
+                Self::Hash,
+                Data::BlakeTwo256(hash)
+                | Data::Sha256(hash)
+                | Data::Keccak256(hash)
+                | Data::ShaThree256(hash),
+            ) => Some(CommitmentFieldValue::Hash(*hash)),
+            (Self::Url { max_len }, _) => bytes
+                .filter(|url| url.len() <= *max_len as usize)
+                .filter(|url| sp_std::str::from_utf8(url).is_ok_and(|url| url.contains("://")))
+                .map(CommitmentFieldValue::Url),
+            (Self::Bytes { max_len }, _) => bytes
+                .filter(|bytes| bytes.len() <= *max_len as usize)
+                .map(CommitmentFieldValue::Bytes),
+            (Self::TimelockEncrypted, Data::TimelockEncrypted { reveal_round, .. }) => {
+                Some(CommitmentFieldValue::TimelockEncrypted {
+                    reveal_round: *reveal_round,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A named field of a commitment schema
+#[freeze_struct("5c913c38785e1674")]
+#[derive(
+    Clone,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    MaxEncodedLen,
+    TypeInfo,
+)]
+pub struct SchemaField {
+    /// The name of the field
+    pub name: BoundedVec<u8, ConstU32<MAX_SCHEMA_FIELD_NAME_LEN>>,
+    /// The type of the field
+    pub field_type: SchemaFieldType,
+}
+
+/// The fields commitments on a subnet must have, in order. `ResetBondsFlag` fields are flags
+/// rather than data, and are not part of the schema.
+#[freeze_struct("3ff019526e9044ea")]
+#[derive(
+    Clone,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    MaxEncodedLen,
+    TypeInfo,
+)]
+pub struct CommitmentSchema {
+    /// The fields of the schema
+    pub fields: BoundedVec<SchemaField, ConstU32<MAX_SCHEMA_FIELDS>>,
+}
+
+impl CommitmentSchema {
+    /// The named values of the commitment fields, `None` if they do not match the schema
+    pub fn decode(&self, fields: &[Data]) -> Option<Vec<DecodedCommitmentField>> {
+        let data_fields: Vec<&Data> = fields
+            .iter()
+            .filter(|data| !matches!(data, Data::ResetBondsFlag))
+            .collect();
+        if data_fields.len() != self.fields.len() {
+            return None;
+        }
+
+        self.fields
+            .iter()
+            .zip(data_fields)
+            .map(|(field, data)| {
+                field
+                    .field_type
+                    .decode(data)
+                    .map(|value| DecodedCommitmentField {
+                        name: field.name.to_vec(),
+                        value,
+                    })
+            })
+            .collect()
+    }
+}
+
+/// The value of a commitment field decoded with the subnet schema
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub enum CommitmentFieldValue {
+    /// A u64
+    U64(u64),
+    /// A hash
+    Hash([u8; 32]),
+    /// A utf-8 url
+    Url(Vec<u8>),
+    /// Bytes
+    Bytes(Vec<u8>),
+    /// A timelock-encrypted blob, revealed at the given drand round
+    TimelockEncrypted {
+        /// The drand round to reveal
+        reveal_round: u64,
+    },
+}
+
+/// A commitment field decoded with the subnet schema
+#[freeze_struct("5c979f44ca1780e4")]
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct DecodedCommitmentField {
+    /// The name of the field in the schema
+    pub name: Vec<u8>,
+    /// The value of the field
+    pub value: CommitmentFieldValue,
+}
+
+/// Maximum size of a blob in bytes
+pub const MAX_BLOB_SIZE_BYTES: u32 = 64 * 1024;
+/// Size of the chunks a blob is uploaded in, except for its last chunk
+pub const MAX_BLOB_CHUNK_SIZE_BYTES: u32 = 4 * 1024;
+/// Maximum number of chunks of a blob
+pub const MAX_BLOB_CHUNKS: u32 = MAX_BLOB_SIZE_BYTES / MAX_BLOB_CHUNK_SIZE_BYTES;
+
+/// The number of chunks a blob of the given length is uploaded in
+pub fn blob_chunk_count(len: u32) -> u32 {
+    len.div_ceil(MAX_BLOB_CHUNK_SIZE_BYTES)
+}
+
+/// A blob being uploaded in chunks by an account
+#[freeze_struct("8458f5bb1e5e6e8a")]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, Debug)]
+pub struct PendingBlob<Balance> {
+    /// The length of the blob in bytes
+    pub len: u32,
+    /// The deposit held for the blob
+    pub deposit: Balance,
+    /// The number of chunks uploaded so far
+    pub uploaded_chunks: u32,
+}
+
+/// A stored blob, its data being stored separately
+#[freeze_struct("5514f3c218a44edd")]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, Debug)]
+pub struct BlobInfo {
+    /// The length of the blob in bytes
+    pub len: u32,
+    /// The number of accounts holding a deposit for the blob, the blob is removed once none
+    /// is left
+    pub holders: u32,
+}
@@ -5,10 +5,14 @@
     clippy::indexing_slicing,
     clippy::unwrap_used
 )]
-use crate::{BalanceOf, CrowdloanId, CrowdloanInfo, CurrencyOf, pallet::*};
+use crate::{BalanceOf, CrowdloanId, CrowdloanInfo, CurrencyOf, MaxMilestones, pallet::*};
 use frame_benchmarking::{account, v2::*};
-use frame_support::traits::{Get, StorePreimage, fungible::*};
+use frame_support::{
+    BoundedVec,
+    traits::{Get, StorePreimage, fungible::*},
+};
 use frame_system::{RawOrigin, pallet_prelude::BlockNumberFor};
+use sp_runtime::Perbill;
 
 extern crate alloc;
 
@@ -24,6 +28,50 @@ fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
     assert_eq!(event, &system_event);
 }
 
+// Create a crowdloan released in two milestones, raise its cap from `contributors`
+// contributors and finalize it.
+fn finalized_milestone_crowdloan<T: Config>(contributors: u32) -> (T::AccountId, CrowdloanId) {
+    let creator: T::AccountId = account::<T::AccountId>("creator", 0, SEED);
+    let deposit = T::MinimumDeposit::get();
+    let min_contribution = T::AbsoluteMinimumContribution::get();
+    let cap = deposit + min_contribution * BalanceOf::<T>::from(contributors);
+    let now = frame_system::Pallet::<T>::block_number();
+    let end = now + T::MaximumBlockDuration::get();
+    let target_address: T::AccountId = account::<T::AccountId>("target_address", 0, SEED);
+    let _ = CurrencyOf::<T>::set_balance(&creator, deposit);
+    let _ = Pallet::<T>::create(
+        RawOrigin::Signed(creator.clone()).into(),
+        deposit,
+        min_contribution,
+        cap,
+        end,
+        None,
+        Some(target_address),
+    );
+
+    let crowdloan_id: CrowdloanId = 0;
+    let _ = Pallet::<T>::set_milestones(
+        RawOrigin::Signed(creator.clone()).into(),
+        crowdloan_id,
+        BoundedVec::truncate_from(vec![Perbill::from_percent(50), Perbill::from_percent(50)]),
+    );
+
+    for i in 0..contributors {
+        let contributor: T::AccountId = account::<T::AccountId>("contributor", i, SEED);
+        let _ = CurrencyOf::<T>::set_balance(&contributor, min_contribution);
+        let _ = Pallet::<T>::contribute(
+            RawOrigin::Signed(contributor).into(),
+            crowdloan_id,
+            min_contribution,
+        );
+    }
+
+    frame_system::Pallet::<T>::set_block_number(end);
+    let _ = Pallet::<T>::finalize(RawOrigin::Signed(creator.clone()).into(), crowdloan_id);
+
+    (creator, crowdloan_id)
+}
+
 #[benchmarks]
 mod benchmarks {
     use super::*;
@@ -69,6 +117,7 @@ mod benchmarks {
                 call: Some(T::Preimages::bound(*call).unwrap()),
                 finalized: false,
                 contributors_count: 1,
+                milestones: None,
             })
         );
         // ensure the creator has been deducted the deposit
@@ -494,5 +543,190 @@ mod benchmarks {
         );
     }
 
+    #[benchmark]
+    fn set_milestones() {
+        // create a crowdloan
+        let creator: T::AccountId = account::<T::AccountId>("creator", 0, SEED);
+        let deposit = T::MinimumDeposit::get();
+        let min_contribution = T::AbsoluteMinimumContribution::get();
+        let cap = deposit + deposit;
+        let end = frame_system::Pallet::<T>::block_number() + T::MaximumBlockDuration::get();
+        let target_address: T::AccountId = account::<T::AccountId>("target_address", 0, SEED);
+        let _ = CurrencyOf::<T>::set_balance(&creator, deposit);
+        let _ = Pallet::<T>::create(
+            RawOrigin::Signed(creator.clone()).into(),
+            deposit,
+            min_contribution,
+            cap,
+            end,
+            None,
+            Some(target_address),
+        );
+
+        let crowdloan_id: CrowdloanId = 0;
+        // the worst case is the maximum number of tranches
+        let max_milestones = <MaxMilestones as Get<u32>>::get();
+        let tranches: BoundedVec<Perbill, MaxMilestones> = BoundedVec::truncate_from(
+            (0..max_milestones)
+                .map(|_| Perbill::from_rational(1, max_milestones))
+                .collect(),
+        );
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(creator.clone()),
+            crowdloan_id,
+            tranches.clone(),
+        );
+
+        // ensure the milestones are set
+        assert!(Crowdloans::<T>::get(crowdloan_id).is_some_and(|c| c.milestones.is_some()));
+        // ensure the event is emitted
+        assert_last_event::<T>(
+            Event::<T>::MilestonesSet {
+                crowdloan_id,
+                tranches,
+            }
+            .into(),
+        );
+    }
+
+    #[benchmark]
+    fn request_milestone() {
+        let (creator, crowdloan_id) = finalized_milestone_crowdloan::<T>(1);
+        let voting_end =
+            frame_system::Pallet::<T>::block_number() + T::MilestoneVotingPeriod::get();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(creator.clone()), crowdloan_id);
+
+        // ensure the vote is opened
+        assert!(Crowdloans::<T>::get(crowdloan_id).is_some_and(|c| {
+            c.milestones
+                .is_some_and(|m| m.voting_end == Some(voting_end))
+        }));
+        // ensure the event is emitted
+        assert_last_event::<T>(
+            Event::<T>::MilestoneRequested {
+                crowdloan_id,
+                milestone: 0,
+                voting_end,
+            }
+            .into(),
+        );
+    }
+
+    #[benchmark]
+    fn vote_milestone() {
+        let (creator, crowdloan_id) = finalized_milestone_crowdloan::<T>(1);
+        let _ = Pallet::<T>::request_milestone(RawOrigin::Signed(creator).into(), crowdloan_id);
+
+        // the worst case is a contributor changing its vote
+        let contributor: T::AccountId = account::<T::AccountId>("contributor", 0, SEED);
+        let _ = Pallet::<T>::vote_milestone(
+            RawOrigin::Signed(contributor.clone()).into(),
+            crowdloan_id,
+            true,
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(contributor.clone()), crowdloan_id, false);
+
+        // ensure the vote is recorded
+        assert_eq!(
+            MilestoneVotes::<T>::get(crowdloan_id, &contributor),
+            Some(false)
+        );
+        assert_eq!(
+            MilestoneTally::<T>::get(crowdloan_id),
+            (0, T::AbsoluteMinimumContribution::get())
+        );
+        // ensure the event is emitted
+        assert_last_event::<T>(
+            Event::<T>::MilestoneVoted {
+                crowdloan_id,
+                voter: contributor,
+                approve: false,
+            }
+            .into(),
+        );
+    }
+
+    #[benchmark]
+    fn conclude_milestone(k: Linear<1, { T::MaxContributors::get() - 1 }>) {
+        let (creator, crowdloan_id) = finalized_milestone_crowdloan::<T>(k);
+        let _ =
+            Pallet::<T>::request_milestone(RawOrigin::Signed(creator.clone()).into(), crowdloan_id);
+
+        // every contributor approves the milestone
+        for i in 0..k {
+            let contributor: T::AccountId = account::<T::AccountId>("contributor", i, SEED);
+            let _ = Pallet::<T>::vote_milestone(
+                RawOrigin::Signed(contributor).into(),
+                crowdloan_id,
+                true,
+            );
+        }
+
+        // run to the end of the voting period
+        let voting_end =
+            frame_system::Pallet::<T>::block_number() + T::MilestoneVotingPeriod::get();
+        frame_system::Pallet::<T>::set_block_number(voting_end);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(creator.clone()), crowdloan_id);
+
+        // ensure the first tranche is released to the target address
+        let raised = Crowdloans::<T>::get(crowdloan_id).unwrap().raised;
+        let amount = Perbill::from_percent(50).mul_floor(raised);
+        let target_address: T::AccountId = account::<T::AccountId>("target_address", 0, SEED);
+        assert_eq!(CurrencyOf::<T>::balance(&target_address), amount);
+        // ensure the votes are cleared
+        assert_eq!(MilestoneVotes::<T>::iter_prefix(crowdloan_id).count(), 0);
+        // ensure the event is emitted
+        assert_last_event::<T>(
+            Event::<T>::MilestoneReleased {
+                crowdloan_id,
+                milestone: 0,
+                amount,
+            }
+            .into(),
+        );
+    }
+
+    #[benchmark]
+    fn transfer_contribution() {
+        let (_, crowdloan_id) = finalized_milestone_crowdloan::<T>(2);
+        let amount = T::AbsoluteMinimumContribution::get();
+        let from: T::AccountId = account::<T::AccountId>("contributor", 0, SEED);
+        // the worst case is a transfer to an existing contributor
+        let to: T::AccountId = account::<T::AccountId>("contributor", 1, SEED);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(from.clone()),
+            crowdloan_id,
+            to.clone(),
+            amount,
+        );
+
+        // ensure the contribution is transferred
+        assert_eq!(Contributions::<T>::get(crowdloan_id, &from), None);
+        assert_eq!(
+            Contributions::<T>::get(crowdloan_id, &to),
+            Some(amount + amount)
+        );
+        // ensure the event is emitted
+        assert_last_event::<T>(
+            Event::<T>::ContributionTransferred {
+                crowdloan_id,
+                from,
+                to,
+                amount,
+            }
+            .into(),
+        );
+    }
+
     impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
 }
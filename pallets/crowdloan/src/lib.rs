@@ -14,7 +14,7 @@ use frame_support::{
     dispatch::GetDispatchInfo,
     pallet_prelude::*,
     sp_runtime::{
-        RuntimeDebug,
+        Perbill, RuntimeDebug,
         traits::{AccountIdConversion, Dispatchable, Zero},
     },
     traits::{
@@ -47,11 +47,14 @@ pub type BalanceOf<T> =
 // Define a maximum length for the migration key
 type MigrationKeyMaxLen = ConstU32<128>;
 
+/// The maximum number of milestones (tranches) of a crowdloan.
+pub type MaxMilestones = ConstU32<10>;
+
 pub type BoundedCallOf<T> =
     Bounded<<T as Config>::RuntimeCall, <T as frame_system::Config>::Hashing>;
 
 /// A struct containing the information about a crowdloan.
-#[freeze_struct("5db9538284491545")]
+#[freeze_struct("cd08d8dd34cb1545")]
 #[derive(Encode, Decode, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct CrowdloanInfo<AccountId, Balance, BlockNumber, Call> {
     /// The creator of the crowdloan.
@@ -78,6 +81,25 @@ pub struct CrowdloanInfo<AccountId, Balance, BlockNumber, Call> {
     pub finalized: bool,
     /// The number of contributors to the crowdloan.
     pub contributors_count: u32,
+    /// The optional milestone schedule, if provided, the raised funds are released
+    /// to the target address in tranches approved by the contributors.
+    pub milestones: Option<MilestoneSchedule<Balance, BlockNumber>>,
+}
+
+/// A struct containing the milestone schedule of a crowdloan.
+#[freeze_struct("e141a2cc939e8d9a")]
+#[derive(Encode, Decode, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct MilestoneSchedule<Balance, BlockNumber> {
+    /// The share of the raised amount released by each milestone, summing up to 100%.
+    pub tranches: BoundedVec<Perbill, MaxMilestones>,
+    /// The number of milestones released so far.
+    pub released: u32,
+    /// The amount released to the target address so far.
+    pub released_amount: Balance,
+    /// The end block of the vote on the requested milestone, if any.
+    pub voting_end: Option<BlockNumber>,
+    /// Whether a milestone has been vetoed, making the unreleased funds refundable.
+    pub failed: bool,
 }
 
 pub type CrowdloanInfoOf<T> = CrowdloanInfo<
@@ -145,6 +167,10 @@ pub mod pallet {
         // The maximum number of contributors that can contribute to a crowdloan.
         #[pallet::constant]
         type MaxContributors: Get<u32>;
+
+        /// The block duration of the contributors vote on a requested milestone.
+        #[pallet::constant]
+        type MilestoneVotingPeriod: Get<BlockNumberFor<Self>>;
     }
 
     /// A map of crowdloan ids to their information.
@@ -173,6 +199,16 @@ pub mod pallet {
     #[pallet::storage]
     pub type CurrentCrowdloanId<T: Config> = StorageValue<_, CrowdloanId, OptionQuery>;
 
+    /// A map of crowdloan ids to the votes of their contributors on the requested milestone.
+    #[pallet::storage]
+    pub type MilestoneVotes<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, CrowdloanId, Identity, T::AccountId, bool, OptionQuery>;
+
+    /// A map of crowdloan ids to the contributions approving and vetoing the requested milestone.
+    #[pallet::storage]
+    pub type MilestoneTally<T: Config> =
+        StorageMap<_, Twox64Concat, CrowdloanId, (BalanceOf<T>, BalanceOf<T>), ValueQuery>;
+
     /// Storage for the migration run status.
     #[pallet::storage]
     pub type HasMigrationRun<T: Config> =
@@ -223,6 +259,41 @@ pub mod pallet {
             crowdloan_id: CrowdloanId,
             new_cap: BalanceOf<T>,
         },
+        /// The milestone schedule was set.
+        MilestonesSet {
+            crowdloan_id: CrowdloanId,
+            tranches: BoundedVec<Perbill, MaxMilestones>,
+        },
+        /// The next milestone was requested by the creator.
+        MilestoneRequested {
+            crowdloan_id: CrowdloanId,
+            milestone: u32,
+            voting_end: BlockNumberFor<T>,
+        },
+        /// A contributor voted on the requested milestone.
+        MilestoneVoted {
+            crowdloan_id: CrowdloanId,
+            voter: T::AccountId,
+            approve: bool,
+        },
+        /// A milestone was approved and its tranche was transferred to the target address.
+        MilestoneReleased {
+            crowdloan_id: CrowdloanId,
+            milestone: u32,
+            amount: BalanceOf<T>,
+        },
+        /// A milestone was vetoed, the unreleased funds can be refunded.
+        MilestoneVetoed {
+            crowdloan_id: CrowdloanId,
+            milestone: u32,
+        },
+        /// A contribution was transferred to another account.
+        ContributionTransferred {
+            crowdloan_id: CrowdloanId,
+            from: T::AccountId,
+            to: T::AccountId,
+            amount: BalanceOf<T>,
+        },
     }
 
     #[pallet::error]
@@ -271,6 +342,32 @@ pub mod pallet {
         DepositCannotBeWithdrawn,
         /// The maximum number of contributors has been reached.
         MaxContributorsReached,
+        /// The milestone tranches must be non zero and sum up to 100%.
+        InvalidMilestones,
+        /// Milestones require a target address and no call to dispatch.
+        MilestonesNotSupported,
+        /// The milestones cannot be changed once contributions have been made.
+        ContributionsAlreadyMade,
+        /// The crowdloan has not been finalized.
+        NotFinalized,
+        /// The crowdloan has no milestone schedule.
+        NoMilestones,
+        /// A milestone of the crowdloan has been vetoed.
+        MilestoneFailed,
+        /// All the milestones of the crowdloan have been released.
+        AllMilestonesReleased,
+        /// A milestone vote is already in progress.
+        MilestoneVoteInProgress,
+        /// There is no milestone vote in progress.
+        NoMilestoneVote,
+        /// The milestone voting period has ended.
+        MilestoneVotingEnded,
+        /// The milestone voting period has not ended yet.
+        MilestoneVotingNotEnded,
+        /// The contribution cannot be transferred to its owner.
+        CannotTransferToSelf,
+        /// The contribution is lower than the amount to transfer.
+        InsufficientContribution,
     }
 
     #[pallet::hooks]
@@ -280,7 +377,9 @@ pub mod pallet {
 
             weight = weight
                 // Add the contributors count for each crowdloan
-                .saturating_add(migrations::migrate_add_contributors_count::<T>());
+                .saturating_add(migrations::migrate_add_contributors_count::<T>())
+                // Add the milestone schedule for each crowdloan
+                .saturating_add(migrations::migrate_add_milestones::<T>());
 
             weight
         }
@@ -374,6 +473,7 @@ pub mod pallet {
                 call,
                 finalized: false,
                 contributors_count: 1,
+                milestones: None,
             };
             Crowdloans::<T>::insert(crowdloan_id, &crowdloan);
 
@@ -557,6 +657,9 @@ pub mod pallet {
         /// crowdloan id being finalized so the dispatched call can access it temporarily by accessing
         /// the `CurrentCrowdloanId` storage item.
         ///
+        /// If the crowdloan has milestones, the raised amount is kept in the crowdloan account and
+        /// released to the target address milestone by milestone.
+        ///
         /// The dispatch origin for this call must be _Signed_ and must be the creator of the crowdloan.
         ///
         /// Parameters:
@@ -579,8 +682,10 @@ pub mod pallet {
             ensure!(crowdloan.raised == crowdloan.cap, Error::<T>::CapNotRaised);
             ensure!(!crowdloan.finalized, Error::<T>::AlreadyFinalized);
 
-            // If the target address is provided, transfer the raised amount to it.
-            if let Some(ref target_address) = crowdloan.target_address {
+            // If the target address is provided, transfer the raised amount to it,
+            // unless it is released by milestones.
+            if let (Some(target_address), None) = (&crowdloan.target_address, &crowdloan.milestones)
+            {
                 CurrencyOf::<T>::transfer(
                     &crowdloan.funds_account,
                     target_address,
@@ -630,6 +735,9 @@ pub mod pallet {
         /// If the limit is reached, the call will stop and the crowdloan will be marked as partially refunded.
         /// It may be needed to dispatch this call multiple times to refund all contributors.
         ///
+        /// A finalized crowdloan with a vetoed milestone can also be refunded, each contributor then
+        /// receives its share of the funds that have not been released.
+        ///
         /// The dispatch origin for this call must be _Signed_ and doesn't need to be the creator of the crowdloan.
         ///
         /// Parameters:
@@ -645,9 +753,13 @@ pub mod pallet {
 
             let mut crowdloan = Self::ensure_crowdloan_exists(crowdloan_id)?;
 
-            // Ensure the crowdloan has ended and is not finalized
+            // Ensure the crowdloan has ended and is not finalized, unless a milestone failed
+            let milestone_failed = Self::milestone_failed(&crowdloan);
             ensure!(now >= crowdloan.end, Error::<T>::ContributionPeriodNotEnded);
-            ensure!(!crowdloan.finalized, Error::<T>::AlreadyFinalized);
+            ensure!(
+                !crowdloan.finalized || milestone_failed,
+                Error::<T>::AlreadyFinalized
+            );
 
             let mut refunded_contributors: Vec<T::AccountId> = vec![];
             let mut refund_count = 0;
@@ -665,15 +777,22 @@ pub mod pallet {
                     break;
                 }
 
-                CurrencyOf::<T>::transfer(
-                    &crowdloan.funds_account,
-                    &contributor,
-                    amount,
-                    Preservation::Expendable,
-                )?;
+                // Only the unreleased share is refunded if a milestone failed, the raised
+                // amount is kept as is so every contributor gets the same share.
+                let refund = Self::refundable_amount(&crowdloan, amount);
+                if !refund.is_zero() {
+                    CurrencyOf::<T>::transfer(
+                        &crowdloan.funds_account,
+                        &contributor,
+                        refund,
+                        Preservation::Expendable,
+                    )?;
+                }
 
                 refunded_contributors.push(contributor);
-                crowdloan.raised = crowdloan.raised.saturating_sub(amount);
+                if !milestone_failed {
+                    crowdloan.raised = crowdloan.raised.saturating_sub(amount);
+                }
                 refund_count = refund_count.checked_add(1).ok_or(Error::<T>::Overflow)?;
             }
 
@@ -703,6 +822,8 @@ pub mod pallet {
         ///
         /// The crowdloan will be removed from the storage.
         /// All contributions must have been refunded before the crowdloan can be dissolved (except the creator's one).
+        /// A finalized crowdloan with a vetoed milestone can be dissolved once refunded, the creator then receives
+        /// the funds left in the crowdloan account.
         ///
         /// The dispatch origin for this call must be _Signed_ and must be the creator of the crowdloan.
         ///
@@ -717,7 +838,11 @@ pub mod pallet {
             let who = ensure_signed(origin)?;
 
             let crowdloan = Self::ensure_crowdloan_exists(crowdloan_id)?;
-            ensure!(!crowdloan.finalized, Error::<T>::AlreadyFinalized);
+            let milestone_failed = Self::milestone_failed(&crowdloan);
+            ensure!(
+                !crowdloan.finalized || milestone_failed,
+                Error::<T>::AlreadyFinalized
+            );
 
             // Only the creator can dissolve the crowdloan
             ensure!(who == crowdloan.creator, Error::<T>::InvalidOrigin);
@@ -726,16 +851,26 @@ pub mod pallet {
             // meaning there is no contributions or every contribution has been refunded
             let creator_contribution = Contributions::<T>::get(crowdloan_id, &crowdloan.creator)
                 .ok_or(Error::<T>::NoContribution)?;
-            ensure!(
-                creator_contribution == crowdloan.raised,
-                Error::<T>::NotReadyToDissolve
-            );
+            let refund = if milestone_failed {
+                // The raised amount is kept on milestone refunds, rely on the contributors count
+                ensure!(
+                    crowdloan.contributors_count == 1,
+                    Error::<T>::NotReadyToDissolve
+                );
+                CurrencyOf::<T>::balance(&crowdloan.funds_account)
+            } else {
+                ensure!(
+                    creator_contribution == crowdloan.raised,
+                    Error::<T>::NotReadyToDissolve
+                );
+                creator_contribution
+            };
 
             // Refund the creator's contribution
             CurrencyOf::<T>::transfer(
                 &crowdloan.funds_account,
                 &crowdloan.creator,
-                creator_contribution,
+                refund,
                 Preservation::Expendable,
             )?;
             Contributions::<T>::remove(crowdloan_id, &crowdloan.creator);
@@ -861,6 +996,356 @@ pub mod pallet {
             });
             Ok(())
         }
+
+        /// Set the milestone schedule of a crowdloan before anyone contributed to it.
+        ///
+        /// Once finalized, the raised amount is kept in the crowdloan account and each tranche is
+        /// released to the target address when the creator requests it and the contributors don't
+        /// veto it. An empty schedule removes the milestones.
+        ///
+        /// The dispatch origin for this call must be _Signed_ and must be the creator of the crowdloan.
+        ///
+        /// Parameters:
+        /// - `crowdloan_id`: The id of the crowdloan to set the milestones of.
+        /// - `tranches`: The share of the raised amount released by each milestone.
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::set_milestones())]
+        pub fn set_milestones(
+            origin: OriginFor<T>,
+            #[pallet::compact] crowdloan_id: CrowdloanId,
+            tranches: BoundedVec<Perbill, MaxMilestones>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut crowdloan = Self::ensure_crowdloan_exists(crowdloan_id)?;
+            ensure!(!crowdloan.finalized, Error::<T>::AlreadyFinalized);
+
+            // Only the creator can set the milestones.
+            ensure!(who == crowdloan.creator, Error::<T>::InvalidOrigin);
+
+            // Contributors must know the milestones before contributing.
+            ensure!(
+                crowdloan.contributors_count == 1,
+                Error::<T>::ContributionsAlreadyMade
+            );
+
+            crowdloan.milestones = if tranches.is_empty() {
+                None
+            } else {
+                // The funds are released by transfers to the target address, a call to dispatch
+                // would expect the raised amount to be available on finalize.
+                ensure!(
+                    crowdloan.target_address.is_some() && crowdloan.call.is_none(),
+                    Error::<T>::MilestonesNotSupported
+                );
+
+                let mut total: u32 = 0;
+                for tranche in tranches.iter() {
+                    ensure!(!tranche.is_zero(), Error::<T>::InvalidMilestones);
+                    total = total
+                        .checked_add(tranche.deconstruct())
+                        .ok_or(Error::<T>::InvalidMilestones)?;
+                }
+                ensure!(
+                    total == Perbill::one().deconstruct(),
+                    Error::<T>::InvalidMilestones
+                );
+
+                Some(MilestoneSchedule {
+                    tranches: tranches.clone(),
+                    released: 0,
+                    released_amount: Zero::zero(),
+                    voting_end: None,
+                    failed: false,
+                })
+            };
+            Crowdloans::<T>::insert(crowdloan_id, &crowdloan);
+
+            Self::deposit_event(Event::<T>::MilestonesSet {
+                crowdloan_id,
+                tranches,
+            });
+            Ok(())
+        }
+
+        /// Request the release of the next milestone of a finalized crowdloan.
+        ///
+        /// This opens a vote of `MilestoneVotingPeriod` blocks during which the contributors can
+        /// approve or veto the milestone.
+        ///
+        /// The dispatch origin for this call must be _Signed_ and must be the creator of the crowdloan.
+        ///
+        /// Parameters:
+        /// - `crowdloan_id`: The id of the crowdloan to request the next milestone of.
+        #[pallet::call_index(10)]
+        #[pallet::weight(T::WeightInfo::request_milestone())]
+        pub fn request_milestone(
+            origin: OriginFor<T>,
+            #[pallet::compact] crowdloan_id: CrowdloanId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let now = frame_system::Pallet::<T>::block_number();
+
+            let mut crowdloan = Self::ensure_crowdloan_exists(crowdloan_id)?;
+            ensure!(crowdloan.finalized, Error::<T>::NotFinalized);
+
+            // Only the creator can request a milestone.
+            ensure!(who == crowdloan.creator, Error::<T>::InvalidOrigin);
+
+            let milestones = crowdloan
+                .milestones
+                .as_mut()
+                .ok_or(Error::<T>::NoMilestones)?;
+            ensure!(!milestones.failed, Error::<T>::MilestoneFailed);
+            ensure!(
+                (milestones.released as usize) < milestones.tranches.len(),
+                Error::<T>::AllMilestonesReleased
+            );
+            ensure!(
+                milestones.voting_end.is_none(),
+                Error::<T>::MilestoneVoteInProgress
+            );
+
+            let voting_end = now.saturating_add(T::MilestoneVotingPeriod::get());
+            milestones.voting_end = Some(voting_end);
+            let milestone = milestones.released;
+            Crowdloans::<T>::insert(crowdloan_id, &crowdloan);
+
+            Self::deposit_event(Event::<T>::MilestoneRequested {
+                crowdloan_id,
+                milestone,
+                voting_end,
+            });
+            Ok(())
+        }
+
+        /// Vote on the requested milestone of a crowdloan, the vote is weighted by the contribution.
+        ///
+        /// A contributor can change its vote until the voting period ends.
+        ///
+        /// The dispatch origin for this call must be _Signed_ and must be a contributor other than
+        /// the creator of the crowdloan.
+        ///
+        /// Parameters:
+        /// - `crowdloan_id`: The id of the crowdloan to vote on.
+        /// - `approve`: Whether to approve or veto the milestone.
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::vote_milestone())]
+        pub fn vote_milestone(
+            origin: OriginFor<T>,
+            #[pallet::compact] crowdloan_id: CrowdloanId,
+            approve: bool,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let now = frame_system::Pallet::<T>::block_number();
+
+            let crowdloan = Self::ensure_crowdloan_exists(crowdloan_id)?;
+            let voting_end = crowdloan
+                .milestones
+                .as_ref()
+                .ok_or(Error::<T>::NoMilestones)?
+                .voting_end
+                .ok_or(Error::<T>::NoMilestoneVote)?;
+            ensure!(now < voting_end, Error::<T>::MilestoneVotingEnded);
+
+            // The creator can't vote on its own milestone.
+            ensure!(who != crowdloan.creator, Error::<T>::InvalidOrigin);
+            let contribution =
+                Contributions::<T>::get(crowdloan_id, &who).ok_or(Error::<T>::NoContribution)?;
+
+            MilestoneTally::<T>::mutate(crowdloan_id, |(approvals, vetoes)| {
+                // Remove the previous vote, if any
+                match MilestoneVotes::<T>::get(crowdloan_id, &who) {
+                    Some(true) => *approvals = approvals.saturating_sub(contribution),
+                    Some(false) => *vetoes = vetoes.saturating_sub(contribution),
+                    None => {}
+                }
+
+                if approve {
+                    *approvals = approvals.saturating_add(contribution);
+                } else {
+                    *vetoes = vetoes.saturating_add(contribution);
+                }
+            });
+            MilestoneVotes::<T>::insert(crowdloan_id, &who, approve);
+
+            Self::deposit_event(Event::<T>::MilestoneVoted {
+                crowdloan_id,
+                voter: who,
+                approve,
+            });
+            Ok(())
+        }
+
+        /// Conclude the vote on the requested milestone of a crowdloan.
+        ///
+        /// The milestone is released to the target address unless the contributions vetoing it
+        /// exceed the ones approving it, in which case the unreleased funds become refundable.
+        ///
+        /// The dispatch origin for this call must be _Signed_ and doesn't need to be the creator of the crowdloan.
+        ///
+        /// Parameters:
+        /// - `crowdloan_id`: The id of the crowdloan to conclude the milestone vote of.
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::WeightInfo::conclude_milestone(T::MaxContributors::get()))]
+        pub fn conclude_milestone(
+            origin: OriginFor<T>,
+            #[pallet::compact] crowdloan_id: CrowdloanId,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+            let now = frame_system::Pallet::<T>::block_number();
+
+            let mut crowdloan = Self::ensure_crowdloan_exists(crowdloan_id)?;
+            let raised = crowdloan.raised;
+            let target_address = crowdloan
+                .target_address
+                .clone()
+                .ok_or(Error::<T>::MilestonesNotSupported)?;
+            let milestones = crowdloan
+                .milestones
+                .as_mut()
+                .ok_or(Error::<T>::NoMilestones)?;
+            let voting_end = milestones.voting_end.ok_or(Error::<T>::NoMilestoneVote)?;
+            ensure!(now >= voting_end, Error::<T>::MilestoneVotingNotEnded);
+
+            let milestone = milestones.released;
+            let (approvals, vetoes) = MilestoneTally::<T>::take(crowdloan_id);
+            let _ =
+                MilestoneVotes::<T>::clear_prefix(crowdloan_id, T::MaxContributors::get(), None);
+            milestones.voting_end = None;
+
+            if vetoes > approvals {
+                milestones.failed = true;
+                Crowdloans::<T>::insert(crowdloan_id, &crowdloan);
+
+                Self::deposit_event(Event::<T>::MilestoneVetoed {
+                    crowdloan_id,
+                    milestone,
+                });
+                return Ok(());
+            }
+
+            let released = milestone.checked_add(1).ok_or(Error::<T>::Overflow)?;
+            // The last milestone releases what is left to avoid rounding dust
+            let amount = if released as usize >= milestones.tranches.len() {
+                raised.saturating_sub(milestones.released_amount)
+            } else {
+                milestones
+                    .tranches
+                    .get(milestone as usize)
+                    .ok_or(Error::<T>::AllMilestonesReleased)?
+                    .mul_floor(raised)
+            };
+
+            CurrencyOf::<T>::transfer(
+                &crowdloan.funds_account,
+                &target_address,
+                amount,
+                Preservation::Expendable,
+            )?;
+
+            milestones.released = released;
+            milestones.released_amount = milestones
+                .released_amount
+                .checked_add(amount)
+                .ok_or(Error::<T>::Overflow)?;
+            Crowdloans::<T>::insert(crowdloan_id, &crowdloan);
+
+            Self::deposit_event(Event::<T>::MilestoneReleased {
+                crowdloan_id,
+                milestone,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Transfer a contribution, or part of it, to another account.
+        ///
+        /// The recipient takes over the transferred amount as its own contribution, including
+        /// the right to withdraw it, to be refunded and to vote on milestones. The creator can
+        /// only transfer its contribution over the deposit.
+        ///
+        /// The dispatch origin for this call must be _Signed_ and must be a contributor.
+        ///
+        /// Parameters:
+        /// - `crowdloan_id`: The id of the crowdloan to transfer the contribution of.
+        /// - `to`: The account receiving the contribution.
+        /// - `amount`: The amount of the contribution to transfer.
+        #[pallet::call_index(13)]
+        #[pallet::weight(T::WeightInfo::transfer_contribution())]
+        pub fn transfer_contribution(
+            origin: OriginFor<T>,
+            #[pallet::compact] crowdloan_id: CrowdloanId,
+            to: T::AccountId,
+            #[pallet::compact] amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(who != to, Error::<T>::CannotTransferToSelf);
+
+            let mut crowdloan = Self::ensure_crowdloan_exists(crowdloan_id)?;
+
+            // Moving contributions during a vote would allow to vote twice with the same funds.
+            ensure!(
+                crowdloan
+                    .milestones
+                    .as_ref()
+                    .is_none_or(|m| m.voting_end.is_none()),
+                Error::<T>::MilestoneVoteInProgress
+            );
+
+            let contribution =
+                Contributions::<T>::get(crowdloan_id, &who).ok_or(Error::<T>::NoContribution)?;
+            ensure!(amount > Zero::zero(), Error::<T>::ContributionTooLow);
+
+            // Ensure the creator keeps the deposit
+            let transferable = if who == crowdloan.creator {
+                contribution.saturating_sub(crowdloan.deposit)
+            } else {
+                contribution
+            };
+            ensure!(amount <= transferable, Error::<T>::InsufficientContribution);
+
+            let remaining = contribution
+                .checked_sub(amount)
+                .ok_or(Error::<T>::Underflow)?;
+            if remaining.is_zero() {
+                Contributions::<T>::remove(crowdloan_id, &who);
+                crowdloan.contributors_count = crowdloan
+                    .contributors_count
+                    .checked_sub(1)
+                    .ok_or(Error::<T>::Underflow)?;
+            } else {
+                Contributions::<T>::insert(crowdloan_id, &who, remaining);
+            }
+
+            // Compute the new contribution of the recipient, we also increment the
+            // contributor count if the contribution is new.
+            let received = if let Some(contribution) = Contributions::<T>::get(crowdloan_id, &to) {
+                contribution
+                    .checked_add(amount)
+                    .ok_or(Error::<T>::Overflow)?
+            } else {
+                ensure!(
+                    crowdloan.contributors_count < T::MaxContributors::get(),
+                    Error::<T>::MaxContributorsReached
+                );
+                crowdloan.contributors_count = crowdloan
+                    .contributors_count
+                    .checked_add(1)
+                    .ok_or(Error::<T>::Overflow)?;
+                amount
+            };
+            Contributions::<T>::insert(crowdloan_id, &to, received);
+            Crowdloans::<T>::insert(crowdloan_id, &crowdloan);
+
+            Self::deposit_event(Event::<T>::ContributionTransferred {
+                crowdloan_id,
+                from: who,
+                to,
+                amount,
+            });
+            Ok(())
+        }
     }
 }
 
@@ -888,4 +1373,23 @@ impl<T: Config> Pallet<T> {
         );
         Ok(())
     }
+
+    fn milestone_failed(crowdloan: &CrowdloanInfoOf<T>) -> bool {
+        crowdloan.milestones.as_ref().is_some_and(|m| m.failed)
+    }
+
+    // The part of a contribution that is refunded, which is only the share of
+    // the unreleased funds if a milestone of the crowdloan failed
+    fn refundable_amount(
+        crowdloan: &CrowdloanInfoOf<T>,
+        contribution: BalanceOf<T>,
+    ) -> BalanceOf<T> {
+        match crowdloan.milestones {
+            Some(ref milestones) if milestones.failed => {
+                let unreleased = crowdloan.raised.saturating_sub(milestones.released_amount);
+                Perbill::from_rational(unreleased, crowdloan.raised).mul_floor(contribution)
+            }
+            _ => contribution,
+        }
+    }
 }
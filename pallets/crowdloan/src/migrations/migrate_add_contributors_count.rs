@@ -75,6 +75,7 @@ pub fn migrate_add_contributors_count<T: Config>() -> Weight {
                 call: crowdloan.call,
                 finalized: crowdloan.finalized,
                 contributors_count: contributions as u32,
+                milestones: None,
             },
         );
         weight = weight.saturating_add(T::DbWeight::get().writes(1));
@@ -0,0 +1,140 @@
+use alloc::string::String;
+use frame_support::{BoundedVec, migration::storage_key_iter, traits::Get, weights::Weight};
+use subtensor_macros::freeze_struct;
+
+use crate::*;
+
+mod old_storage {
+    use super::*;
+
+    #[freeze_struct("7d1bccde407bbb5d")]
+    #[derive(Encode, Decode, Debug)]
+    pub struct OldCrowdloanInfo<AccountId, Balance, BlockNumber, Call> {
+        pub creator: AccountId,
+        pub deposit: Balance,
+        pub min_contribution: Balance,
+        pub end: BlockNumber,
+        pub cap: Balance,
+        pub funds_account: AccountId,
+        pub raised: Balance,
+        pub target_address: Option<AccountId>,
+        pub call: Option<Call>,
+        pub finalized: bool,
+        pub contributors_count: u32,
+    }
+}
+
+pub fn migrate_add_milestones<T: Config>() -> Weight {
+    let migration_name = BoundedVec::truncate_from(b"migrate_add_milestones".to_vec());
+    let mut weight = T::DbWeight::get().reads(1);
+
+    if HasMigrationRun::<T>::get(&migration_name) {
+        log::info!(
+            "Migration '{:?}' has already run. Skipping.",
+            String::from_utf8_lossy(&migration_name)
+        );
+        return weight;
+    }
+
+    log::info!(
+        "Running migration '{}'",
+        String::from_utf8_lossy(&migration_name)
+    );
+
+    let pallet_name = b"Crowdloan";
+    let item_name = b"Crowdloans";
+    let crowdloans = storage_key_iter::<
+        CrowdloanId,
+        old_storage::OldCrowdloanInfo<
+            T::AccountId,
+            BalanceOf<T>,
+            BlockNumberFor<T>,
+            BoundedCallOf<T>,
+        >,
+        Twox64Concat,
+    >(pallet_name, item_name)
+    .collect::<Vec<_>>();
+    weight = weight.saturating_add(T::DbWeight::get().reads(crowdloans.len() as u64));
+
+    for (id, crowdloan) in crowdloans {
+        Crowdloans::<T>::insert(
+            id,
+            CrowdloanInfo {
+                creator: crowdloan.creator,
+                deposit: crowdloan.deposit,
+                min_contribution: crowdloan.min_contribution,
+                end: crowdloan.end,
+                cap: crowdloan.cap,
+                funds_account: crowdloan.funds_account,
+                raised: crowdloan.raised,
+                target_address: crowdloan.target_address,
+                call: crowdloan.call,
+                finalized: crowdloan.finalized,
+                contributors_count: crowdloan.contributors_count,
+                milestones: None,
+            },
+        );
+        weight = weight.saturating_add(T::DbWeight::get().writes(1));
+    }
+
+    HasMigrationRun::<T>::insert(&migration_name, true);
+    weight = weight.saturating_add(T::DbWeight::get().writes(1));
+
+    log::info!(
+        "Migration '{:?}' completed successfully.",
+        String::from_utf8_lossy(&migration_name)
+    );
+
+    weight
+}
+
+#[cfg(test)]
+mod tests {
+    use frame_support::{Hashable, storage::unhashed::put_raw};
+    use sp_core::U256;
+    use sp_io::hashing::twox_128;
+
+    use super::*;
+    use crate::mock::{Test, TestState};
+
+    #[test]
+    fn test_migrate_add_milestones_works() {
+        TestState::default().build_and_execute(|| {
+            let pallet_name = twox_128(b"Crowdloan");
+            let storage_name = twox_128(b"Crowdloans");
+            let prefix = [pallet_name, storage_name].concat();
+
+            for id in 0..3u32 {
+                let crowdloan = old_storage::OldCrowdloanInfo {
+                    creator: U256::from(1),
+                    deposit: 100u64,
+                    min_contribution: 10u64,
+                    end: 100u64,
+                    cap: 1000u64,
+                    funds_account: U256::from(2),
+                    raised: 100u64,
+                    target_address: Some(U256::from(3)),
+                    call: None::<BoundedCallOf<Test>>,
+                    finalized: id == 2,
+                    contributors_count: id + 1,
+                };
+                let key = [prefix.clone(), id.twox_64_concat()].concat();
+                put_raw(&key, &crowdloan.encode());
+            }
+
+            migrate_add_milestones::<Test>();
+
+            for id in 0..3u32 {
+                let crowdloan = Crowdloans::<Test>::get(id).expect("crowdloan is migrated");
+                assert_eq!(crowdloan.contributors_count, id + 1);
+                assert_eq!(crowdloan.finalized, id == 2);
+                assert_eq!(crowdloan.target_address, Some(U256::from(3)));
+                assert!(crowdloan.milestones.is_none());
+            }
+
+            assert!(HasMigrationRun::<Test>::get(BoundedVec::truncate_from(
+                b"migrate_add_milestones".to_vec()
+            )));
+        });
+    }
+}
@@ -1,2 +1,4 @@
 mod migrate_add_contributors_count;
+mod migrate_add_milestones;
 pub use migrate_add_contributors_count::*;
+pub use migrate_add_milestones::*;
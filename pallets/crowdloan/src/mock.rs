@@ -89,6 +89,21 @@ impl WeightInfo for TestWeightInfo {
     fn update_cap() -> Weight {
         Weight::zero()
     }
+    fn set_milestones() -> Weight {
+        Weight::zero()
+    }
+    fn request_milestone() -> Weight {
+        Weight::zero()
+    }
+    fn vote_milestone() -> Weight {
+        Weight::zero()
+    }
+    fn conclude_milestone(_k: u32) -> Weight {
+        Weight::zero()
+    }
+    fn transfer_contribution() -> Weight {
+        Weight::zero()
+    }
 }
 
 parameter_types! {
@@ -113,6 +128,7 @@ parameter_types! {
     pub const MaximumBlockDuration: u64 = 100;
     pub const RefundContributorsLimit: u32 = 5;
     pub const MaxContributors: u32 = 10;
+    pub const MilestoneVotingPeriod: u64 = 10;
 }
 
 impl pallet_crowdloan::Config for Test {
@@ -128,6 +144,7 @@ impl pallet_crowdloan::Config for Test {
     type MaximumBlockDuration = MaximumBlockDuration;
     type RefundContributorsLimit = RefundContributorsLimit;
     type MaxContributors = MaxContributors;
+    type MilestoneVotingPeriod = MilestoneVotingPeriod;
 }
 
 // A test pallet used to test some behavior of the crowdloan pallet
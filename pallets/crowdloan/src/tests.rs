@@ -1,12 +1,14 @@
 #![cfg(test)]
 #![allow(clippy::arithmetic_side_effects, clippy::unwrap_used)]
 
-use frame_support::{StorageDoubleMap, assert_err, assert_ok, traits::StorePreimage};
+use frame_support::{BoundedVec, StorageDoubleMap, assert_err, assert_ok, traits::StorePreimage};
 use frame_system::pallet_prelude::BlockNumberFor;
 use sp_core::U256;
-use sp_runtime::DispatchError;
+use sp_runtime::{DispatchError, Perbill};
 
-use crate::{BalanceOf, CrowdloanId, CrowdloanInfo, mock::*, pallet as pallet_crowdloan};
+use crate::{
+    BalanceOf, CrowdloanId, CrowdloanInfo, MilestoneSchedule, mock::*, pallet as pallet_crowdloan,
+};
 
 #[test]
 fn test_create_succeeds() {
@@ -47,6 +49,7 @@ fn test_create_succeeds() {
                     call: Some(call),
                     finalized: false,
                     contributors_count: 1,
+                    milestones: None,
                 })
             );
             // ensure the crowdloan account has the deposit
@@ -2612,3 +2615,658 @@ fn test_update_cap_fails_if_new_cap_is_too_low() {
             );
         });
 }
+
+// Create a crowdloan released to the target address in two milestones of 40% and 60%,
+// raise its cap of 350 from two contributors and finalize it.
+fn create_finalized_milestone_crowdloan() -> CrowdloanId {
+    let creator: AccountOf<Test> = U256::from(1);
+    let target_address: AccountOf<Test> = U256::from(4);
+    let end: BlockNumberFor<Test> = 50;
+
+    assert_ok!(Crowdloan::create(
+        RuntimeOrigin::signed(creator),
+        50,
+        10,
+        350,
+        end,
+        None,
+        Some(target_address),
+    ));
+
+    let crowdloan_id: CrowdloanId = 0;
+    assert_ok!(Crowdloan::set_milestones(
+        RuntimeOrigin::signed(creator),
+        crowdloan_id,
+        BoundedVec::truncate_from(vec![Perbill::from_percent(40), Perbill::from_percent(60)]),
+    ));
+
+    assert_ok!(Crowdloan::contribute(
+        RuntimeOrigin::signed(U256::from(2)),
+        crowdloan_id,
+        200
+    ));
+    assert_ok!(Crowdloan::contribute(
+        RuntimeOrigin::signed(U256::from(3)),
+        crowdloan_id,
+        100
+    ));
+
+    run_to_block(end);
+    assert_ok!(Crowdloan::finalize(
+        RuntimeOrigin::signed(creator),
+        crowdloan_id
+    ));
+
+    crowdloan_id
+}
+
+#[test]
+fn test_set_milestones_succeeds() {
+    TestState::default()
+        .with_balance(U256::from(1), 100)
+        .build_and_execute(|| {
+            let creator: AccountOf<Test> = U256::from(1);
+            let target_address: AccountOf<Test> = U256::from(4);
+
+            assert_ok!(Crowdloan::create(
+                RuntimeOrigin::signed(creator),
+                50,
+                10,
+                300,
+                50,
+                None,
+                Some(target_address),
+            ));
+
+            let crowdloan_id: CrowdloanId = 0;
+            let tranches = BoundedVec::truncate_from(vec![
+                Perbill::from_percent(30),
+                Perbill::from_percent(70),
+            ]);
+            assert_ok!(Crowdloan::set_milestones(
+                RuntimeOrigin::signed(creator),
+                crowdloan_id,
+                tranches.clone()
+            ));
+
+            // ensure the milestones are stored correctly
+            assert_eq!(
+                pallet_crowdloan::Crowdloans::<Test>::get(crowdloan_id).and_then(|c| c.milestones),
+                Some(MilestoneSchedule {
+                    tranches: tranches.clone(),
+                    released: 0,
+                    released_amount: 0,
+                    voting_end: None,
+                    failed: false,
+                })
+            );
+            // ensure the event is emitted
+            assert_eq!(
+                last_event(),
+                pallet_crowdloan::Event::<Test>::MilestonesSet {
+                    crowdloan_id,
+                    tranches,
+                }
+                .into()
+            );
+
+            // an empty schedule removes the milestones
+            assert_ok!(Crowdloan::set_milestones(
+                RuntimeOrigin::signed(creator),
+                crowdloan_id,
+                BoundedVec::new()
+            ));
+            assert!(
+                pallet_crowdloan::Crowdloans::<Test>::get(crowdloan_id)
+                    .is_some_and(|c| c.milestones.is_none())
+            );
+        });
+}
+
+#[test]
+fn test_set_milestones_fails_if_invalid() {
+    TestState::default()
+        .with_balance(U256::from(1), 200)
+        .with_balance(U256::from(2), 100)
+        .build_and_execute(|| {
+            let creator: AccountOf<Test> = U256::from(1);
+            let target_address: AccountOf<Test> = U256::from(4);
+
+            assert_ok!(Crowdloan::create(
+                RuntimeOrigin::signed(creator),
+                50,
+                10,
+                300,
+                50,
+                None,
+                Some(target_address),
+            ));
+            let crowdloan_id: CrowdloanId = 0;
+
+            // the tranches must sum up to 100%
+            assert_err!(
+                Crowdloan::set_milestones(
+                    RuntimeOrigin::signed(creator),
+                    crowdloan_id,
+                    BoundedVec::truncate_from(vec![
+                        Perbill::from_percent(30),
+                        Perbill::from_percent(60)
+                    ])
+                ),
+                pallet_crowdloan::Error::<Test>::InvalidMilestones
+            );
+
+            // the tranches must be non zero
+            assert_err!(
+                Crowdloan::set_milestones(
+                    RuntimeOrigin::signed(creator),
+                    crowdloan_id,
+                    BoundedVec::truncate_from(vec![Perbill::from_percent(100), Perbill::zero()])
+                ),
+                pallet_crowdloan::Error::<Test>::InvalidMilestones
+            );
+
+            // only the creator can set the milestones
+            assert_err!(
+                Crowdloan::set_milestones(
+                    RuntimeOrigin::signed(U256::from(2)),
+                    crowdloan_id,
+                    BoundedVec::truncate_from(vec![Perbill::one()])
+                ),
+                pallet_crowdloan::Error::<Test>::InvalidOrigin
+            );
+
+            // the milestones cannot be changed once contributions have been made
+            assert_ok!(Crowdloan::contribute(
+                RuntimeOrigin::signed(U256::from(2)),
+                crowdloan_id,
+                50
+            ));
+            assert_err!(
+                Crowdloan::set_milestones(
+                    RuntimeOrigin::signed(creator),
+                    crowdloan_id,
+                    BoundedVec::truncate_from(vec![Perbill::one()])
+                ),
+                pallet_crowdloan::Error::<Test>::ContributionsAlreadyMade
+            );
+
+            // milestones require a target address and no call
+            assert_ok!(Crowdloan::create(
+                RuntimeOrigin::signed(creator),
+                50,
+                10,
+                300,
+                50,
+                Some(noop_call()),
+                Some(target_address),
+            ));
+            assert_err!(
+                Crowdloan::set_milestones(
+                    RuntimeOrigin::signed(creator),
+                    1,
+                    BoundedVec::truncate_from(vec![Perbill::one()])
+                ),
+                pallet_crowdloan::Error::<Test>::MilestonesNotSupported
+            );
+        });
+}
+
+#[test]
+fn test_milestones_are_released_when_approved() {
+    TestState::default()
+        .with_balance(U256::from(1), 100)
+        .with_balance(U256::from(2), 200)
+        .with_balance(U256::from(3), 100)
+        .build_and_execute(|| {
+            let creator: AccountOf<Test> = U256::from(1);
+            let target_address: AccountOf<Test> = U256::from(4);
+            let crowdloan_id = create_finalized_milestone_crowdloan();
+            let funds_account = pallet_crowdloan::Pallet::<Test>::funds_account(crowdloan_id);
+
+            // ensure the raised amount is kept in the crowdloan account on finalize
+            assert_eq!(Balances::free_balance(funds_account), 350);
+            assert_eq!(Balances::free_balance(target_address), 0);
+
+            // a milestone can only be concluded once requested
+            assert_err!(
+                Crowdloan::conclude_milestone(RuntimeOrigin::signed(creator), crowdloan_id),
+                pallet_crowdloan::Error::<Test>::NoMilestoneVote
+            );
+
+            // request the first milestone
+            assert_ok!(Crowdloan::request_milestone(
+                RuntimeOrigin::signed(creator),
+                crowdloan_id
+            ));
+            assert_eq!(
+                last_event(),
+                pallet_crowdloan::Event::<Test>::MilestoneRequested {
+                    crowdloan_id,
+                    milestone: 0,
+                    voting_end: 60,
+                }
+                .into()
+            );
+            assert_err!(
+                Crowdloan::request_milestone(RuntimeOrigin::signed(creator), crowdloan_id),
+                pallet_crowdloan::Error::<Test>::MilestoneVoteInProgress
+            );
+
+            // the approvals outweigh the vetoes
+            assert_ok!(Crowdloan::vote_milestone(
+                RuntimeOrigin::signed(U256::from(2)),
+                crowdloan_id,
+                true
+            ));
+            assert_ok!(Crowdloan::vote_milestone(
+                RuntimeOrigin::signed(U256::from(3)),
+                crowdloan_id,
+                false
+            ));
+            assert_eq!(
+                pallet_crowdloan::MilestoneTally::<Test>::get(crowdloan_id),
+                (200, 100)
+            );
+
+            // the vote can't be concluded before the end of the voting period
+            assert_err!(
+                Crowdloan::conclude_milestone(RuntimeOrigin::signed(creator), crowdloan_id),
+                pallet_crowdloan::Error::<Test>::MilestoneVotingNotEnded
+            );
+
+            run_to_block(60);
+            assert_ok!(Crowdloan::conclude_milestone(
+                RuntimeOrigin::signed(U256::from(2)),
+                crowdloan_id
+            ));
+
+            // ensure the first tranche has been released and the votes cleared
+            assert_eq!(Balances::free_balance(target_address), 140);
+            assert_eq!(Balances::free_balance(funds_account), 210);
+            assert_eq!(
+                pallet_crowdloan::MilestoneVotes::<Test>::iter_prefix(crowdloan_id).count(),
+                0
+            );
+            assert_eq!(
+                pallet_crowdloan::MilestoneTally::<Test>::get(crowdloan_id),
+                (0, 0)
+            );
+            assert_eq!(
+                last_event(),
+                pallet_crowdloan::Event::<Test>::MilestoneReleased {
+                    crowdloan_id,
+                    milestone: 0,
+                    amount: 140,
+                }
+                .into()
+            );
+
+            // the last milestone is released without any vote
+            assert_ok!(Crowdloan::request_milestone(
+                RuntimeOrigin::signed(creator),
+                crowdloan_id
+            ));
+            run_to_block(70);
+            assert_ok!(Crowdloan::conclude_milestone(
+                RuntimeOrigin::signed(creator),
+                crowdloan_id
+            ));
+
+            // ensure the whole raised amount has been released
+            assert_eq!(Balances::free_balance(target_address), 350);
+            assert_eq!(Balances::free_balance(funds_account), 0);
+            assert!(
+                pallet_crowdloan::Crowdloans::<Test>::get(crowdloan_id).is_some_and(|c| c
+                    .milestones
+                    .is_some_and(|m| m.released == 2 && m.released_amount == 350))
+            );
+            assert_err!(
+                Crowdloan::request_milestone(RuntimeOrigin::signed(creator), crowdloan_id),
+                pallet_crowdloan::Error::<Test>::AllMilestonesReleased
+            );
+        });
+}
+
+#[test]
+fn test_vote_milestone_fails_if_not_contributor() {
+    TestState::default()
+        .with_balance(U256::from(1), 100)
+        .with_balance(U256::from(2), 200)
+        .with_balance(U256::from(3), 100)
+        .build_and_execute(|| {
+            let creator: AccountOf<Test> = U256::from(1);
+            let crowdloan_id = create_finalized_milestone_crowdloan();
+
+            // no vote can be cast before a milestone is requested
+            assert_err!(
+                Crowdloan::vote_milestone(RuntimeOrigin::signed(U256::from(2)), crowdloan_id, true),
+                pallet_crowdloan::Error::<Test>::NoMilestoneVote
+            );
+
+            assert_ok!(Crowdloan::request_milestone(
+                RuntimeOrigin::signed(creator),
+                crowdloan_id
+            ));
+
+            // the creator can't vote on its own milestone
+            assert_err!(
+                Crowdloan::vote_milestone(RuntimeOrigin::signed(creator), crowdloan_id, true),
+                pallet_crowdloan::Error::<Test>::InvalidOrigin
+            );
+            // only contributors can vote
+            assert_err!(
+                Crowdloan::vote_milestone(RuntimeOrigin::signed(U256::from(5)), crowdloan_id, true),
+                pallet_crowdloan::Error::<Test>::NoContribution
+            );
+
+            // a contributor can change its vote
+            assert_ok!(Crowdloan::vote_milestone(
+                RuntimeOrigin::signed(U256::from(2)),
+                crowdloan_id,
+                true
+            ));
+            assert_ok!(Crowdloan::vote_milestone(
+                RuntimeOrigin::signed(U256::from(2)),
+                crowdloan_id,
+                false
+            ));
+            assert_eq!(
+                pallet_crowdloan::MilestoneTally::<Test>::get(crowdloan_id),
+                (0, 200)
+            );
+
+            // no vote can be cast after the voting period
+            run_to_block(60);
+            assert_err!(
+                Crowdloan::vote_milestone(RuntimeOrigin::signed(U256::from(3)), crowdloan_id, true),
+                pallet_crowdloan::Error::<Test>::MilestoneVotingEnded
+            );
+        });
+}
+
+#[test]
+fn test_vetoed_milestone_refunds_unreleased_funds() {
+    TestState::default()
+        .with_balance(U256::from(1), 100)
+        .with_balance(U256::from(2), 200)
+        .with_balance(U256::from(3), 100)
+        .build_and_execute(|| {
+            let creator: AccountOf<Test> = U256::from(1);
+            let target_address: AccountOf<Test> = U256::from(4);
+            let crowdloan_id = create_finalized_milestone_crowdloan();
+            let funds_account = pallet_crowdloan::Pallet::<Test>::funds_account(crowdloan_id);
+
+            // the first milestone is released
+            assert_ok!(Crowdloan::request_milestone(
+                RuntimeOrigin::signed(creator),
+                crowdloan_id
+            ));
+            run_to_block(60);
+            assert_ok!(Crowdloan::conclude_milestone(
+                RuntimeOrigin::signed(creator),
+                crowdloan_id
+            ));
+            assert_eq!(Balances::free_balance(target_address), 140);
+
+            // the finalized crowdloan can't be refunded while the milestones succeed
+            assert_err!(
+                Crowdloan::refund(RuntimeOrigin::signed(creator), crowdloan_id),
+                pallet_crowdloan::Error::<Test>::AlreadyFinalized
+            );
+
+            // the second milestone is vetoed
+            assert_ok!(Crowdloan::request_milestone(
+                RuntimeOrigin::signed(creator),
+                crowdloan_id
+            ));
+            assert_ok!(Crowdloan::vote_milestone(
+                RuntimeOrigin::signed(U256::from(3)),
+                crowdloan_id,
+                false
+            ));
+            run_to_block(70);
+            assert_ok!(Crowdloan::conclude_milestone(
+                RuntimeOrigin::signed(creator),
+                crowdloan_id
+            ));
+            assert_eq!(
+                last_event(),
+                pallet_crowdloan::Event::<Test>::MilestoneVetoed {
+                    crowdloan_id,
+                    milestone: 1,
+                }
+                .into()
+            );
+            assert_eq!(Balances::free_balance(target_address), 140);
+            assert_err!(
+                Crowdloan::request_milestone(RuntimeOrigin::signed(creator), crowdloan_id),
+                pallet_crowdloan::Error::<Test>::MilestoneFailed
+            );
+
+            // the contributors are refunded their share of the unreleased 60%
+            assert_ok!(Crowdloan::refund(
+                RuntimeOrigin::signed(creator),
+                crowdloan_id
+            ));
+            assert_eq!(Balances::free_balance(U256::from(2)), 120);
+            assert_eq!(Balances::free_balance(U256::from(3)), 60);
+            assert_eq!(Balances::free_balance(funds_account), 30);
+            assert!(
+                pallet_crowdloan::Crowdloans::<Test>::get(crowdloan_id)
+                    .is_some_and(|c| c.contributors_count == 1)
+            );
+
+            // the creator gets its share back on dissolve
+            assert_ok!(Crowdloan::dissolve(
+                RuntimeOrigin::signed(creator),
+                crowdloan_id
+            ));
+            assert_eq!(Balances::free_balance(creator), 80);
+            assert_eq!(Balances::free_balance(funds_account), 0);
+            assert!(pallet_crowdloan::Crowdloans::<Test>::get(crowdloan_id).is_none());
+        });
+}
+
+#[test]
+fn test_transfer_contribution_succeeds() {
+    TestState::default()
+        .with_balance(U256::from(1), 200)
+        .with_balance(U256::from(2), 200)
+        .build_and_execute(|| {
+            let creator: AccountOf<Test> = U256::from(1);
+            let contributor: AccountOf<Test> = U256::from(2);
+            let buyer: AccountOf<Test> = U256::from(3);
+
+            assert_ok!(Crowdloan::create(
+                RuntimeOrigin::signed(creator),
+                50,
+                10,
+                300,
+                50,
+                Some(noop_call()),
+                None,
+            ));
+            let crowdloan_id: CrowdloanId = 0;
+            assert_ok!(Crowdloan::contribute(
+                RuntimeOrigin::signed(contributor),
+                crowdloan_id,
+                100
+            ));
+
+            // transfer part of the contribution to a new contributor
+            assert_ok!(Crowdloan::transfer_contribution(
+                RuntimeOrigin::signed(contributor),
+                crowdloan_id,
+                buyer,
+                40
+            ));
+            assert_eq!(
+                pallet_crowdloan::Contributions::<Test>::get(crowdloan_id, contributor),
+                Some(60)
+            );
+            assert_eq!(
+                pallet_crowdloan::Contributions::<Test>::get(crowdloan_id, buyer),
+                Some(40)
+            );
+            assert!(
+                pallet_crowdloan::Crowdloans::<Test>::get(crowdloan_id)
+                    .is_some_and(|c| c.contributors_count == 3 && c.raised == 150)
+            );
+            assert_eq!(
+                last_event(),
+                pallet_crowdloan::Event::<Test>::ContributionTransferred {
+                    crowdloan_id,
+                    from: contributor,
+                    to: buyer,
+                    amount: 40,
+                }
+                .into()
+            );
+
+            // transfer the rest of the contribution to an existing contributor
+            assert_ok!(Crowdloan::transfer_contribution(
+                RuntimeOrigin::signed(contributor),
+                crowdloan_id,
+                buyer,
+                60
+            ));
+            assert_eq!(
+                pallet_crowdloan::Contributions::<Test>::get(crowdloan_id, contributor),
+                None
+            );
+            assert_eq!(
+                pallet_crowdloan::Contributions::<Test>::get(crowdloan_id, buyer),
+                Some(100)
+            );
+            assert!(
+                pallet_crowdloan::Crowdloans::<Test>::get(crowdloan_id)
+                    .is_some_and(|c| c.contributors_count == 2)
+            );
+
+            // the recipient can withdraw the transferred contribution
+            assert_ok!(Crowdloan::withdraw(
+                RuntimeOrigin::signed(buyer),
+                crowdloan_id
+            ));
+            assert_eq!(Balances::free_balance(buyer), 100);
+        });
+}
+
+#[test]
+fn test_transfer_contribution_fails_if_invalid() {
+    TestState::default()
+        .with_balance(U256::from(1), 200)
+        .with_balance(U256::from(2), 200)
+        .build_and_execute(|| {
+            let creator: AccountOf<Test> = U256::from(1);
+            let contributor: AccountOf<Test> = U256::from(2);
+            let buyer: AccountOf<Test> = U256::from(3);
+
+            assert_ok!(Crowdloan::create(
+                RuntimeOrigin::signed(creator),
+                50,
+                10,
+                300,
+                50,
+                Some(noop_call()),
+                None,
+            ));
+            let crowdloan_id: CrowdloanId = 0;
+            assert_ok!(Crowdloan::contribute(
+                RuntimeOrigin::signed(contributor),
+                crowdloan_id,
+                100
+            ));
+
+            // the contribution can't be transferred to its owner
+            assert_err!(
+                Crowdloan::transfer_contribution(
+                    RuntimeOrigin::signed(contributor),
+                    crowdloan_id,
+                    contributor,
+                    50
+                ),
+                pallet_crowdloan::Error::<Test>::CannotTransferToSelf
+            );
+
+            // only contributors can transfer
+            assert_err!(
+                Crowdloan::transfer_contribution(
+                    RuntimeOrigin::signed(buyer),
+                    crowdloan_id,
+                    contributor,
+                    50
+                ),
+                pallet_crowdloan::Error::<Test>::NoContribution
+            );
+
+            // the amount can't exceed the contribution
+            assert_err!(
+                Crowdloan::transfer_contribution(
+                    RuntimeOrigin::signed(contributor),
+                    crowdloan_id,
+                    buyer,
+                    101
+                ),
+                pallet_crowdloan::Error::<Test>::InsufficientContribution
+            );
+
+            // the creator can't transfer its deposit
+            assert_err!(
+                Crowdloan::transfer_contribution(
+                    RuntimeOrigin::signed(creator),
+                    crowdloan_id,
+                    buyer,
+                    10
+                ),
+                pallet_crowdloan::Error::<Test>::InsufficientContribution
+            );
+        });
+}
+
+#[test]
+fn test_transfer_contribution_fails_during_milestone_vote() {
+    TestState::default()
+        .with_balance(U256::from(1), 100)
+        .with_balance(U256::from(2), 200)
+        .with_balance(U256::from(3), 100)
+        .build_and_execute(|| {
+            let creator: AccountOf<Test> = U256::from(1);
+            let crowdloan_id = create_finalized_milestone_crowdloan();
+
+            assert_ok!(Crowdloan::request_milestone(
+                RuntimeOrigin::signed(creator),
+                crowdloan_id
+            ));
+            assert_ok!(Crowdloan::vote_milestone(
+                RuntimeOrigin::signed(U256::from(3)),
+                crowdloan_id,
+                false
+            ));
+
+            // the contribution can't be used to vote twice
+            assert_err!(
+                Crowdloan::transfer_contribution(
+                    RuntimeOrigin::signed(U256::from(3)),
+                    crowdloan_id,
+                    U256::from(5),
+                    100
+                ),
+                pallet_crowdloan::Error::<Test>::MilestoneVoteInProgress
+            );
+
+            // the contribution can be transferred once the vote is concluded
+            run_to_block(60);
+            assert_ok!(Crowdloan::conclude_milestone(
+                RuntimeOrigin::signed(creator),
+                crowdloan_id
+            ));
+            assert_ok!(Crowdloan::transfer_contribution(
+                RuntimeOrigin::signed(U256::from(3)),
+                crowdloan_id,
+                U256::from(5),
+                100
+            ));
+        });
+}
@@ -40,6 +40,11 @@ pub trait WeightInfo {
 	fn update_min_contribution() -> Weight;
 	fn update_end() -> Weight;
 	fn update_cap() -> Weight;
+	fn set_milestones() -> Weight;
+	fn request_milestone() -> Weight;
+	fn vote_milestone() -> Weight;
+	fn conclude_milestone(k: u32, ) -> Weight;
+	fn transfer_contribution() -> Weight;
 }
 
 /// Weights for `pallet_crowdloan` using the Substrate node and recommended hardware.
@@ -177,6 +182,80 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Crowdloan::Crowdloans` (r:1 w:1)
+	/// Proof: `Crowdloan::Crowdloans` (`max_values`: None, `max_size`: Some(289), added: 2764, mode: `MaxEncodedLen`)
+	fn set_milestones() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `224`
+		//  Estimated: `3754`
+		// Minimum execution time: 9_958_000 picoseconds.
+		Weight::from_parts(10_289_000, 3754)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Crowdloan::Crowdloans` (r:1 w:1)
+	/// Proof: `Crowdloan::Crowdloans` (`max_values`: None, `max_size`: Some(289), added: 2764, mode: `MaxEncodedLen`)
+	fn request_milestone() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `267`
+		//  Estimated: `3754`
+		// Minimum execution time: 9_327_000 picoseconds.
+		Weight::from_parts(9_648_000, 3754)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Crowdloan::Crowdloans` (r:1 w:0)
+	/// Proof: `Crowdloan::Crowdloans` (`max_values`: None, `max_size`: Some(289), added: 2764, mode: `MaxEncodedLen`)
+	/// Storage: `Crowdloan::Contributions` (r:1 w:0)
+	/// Proof: `Crowdloan::Contributions` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Crowdloan::MilestoneTally` (r:1 w:1)
+	/// Proof: `Crowdloan::MilestoneTally` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	/// Storage: `Crowdloan::MilestoneVotes` (r:1 w:1)
+	/// Proof: `Crowdloan::MilestoneVotes` (`max_values`: None, `max_size`: Some(45), added: 2520, mode: `MaxEncodedLen`)
+	fn vote_milestone() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `3754`
+		// Minimum execution time: 17_493_000 picoseconds.
+		Weight::from_parts(18_055_000, 3754)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Crowdloan::Crowdloans` (r:1 w:1)
+	/// Proof: `Crowdloan::Crowdloans` (`max_values`: None, `max_size`: Some(289), added: 2764, mode: `MaxEncodedLen`)
+	/// Storage: `Crowdloan::MilestoneTally` (r:1 w:1)
+	/// Proof: `Crowdloan::MilestoneTally` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	/// Storage: `Crowdloan::MilestoneVotes` (r:0 w:499)
+	/// Proof: `Crowdloan::MilestoneVotes` (`max_values`: None, `max_size`: Some(45), added: 2520, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(104), added: 2579, mode: `MaxEncodedLen`)
+	/// The range of component `k` is `[1, 499]`.
+	fn conclude_milestone(k: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `478 + k * (49 ±0)`
+		//  Estimated: `6148 + k * (49 ±0)`
+		// Minimum execution time: 52_179_000 picoseconds.
+		Weight::from_parts(49_814_367, 6148)
+			// Standard Error: 4_122
+			.saturating_add(Weight::from_parts(1_412_530, 0).saturating_mul(k.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(k.into())))
+			.saturating_add(Weight::from_parts(0, 49).saturating_mul(k.into()))
+	}
+	/// Storage: `Crowdloan::Crowdloans` (r:1 w:1)
+	/// Proof: `Crowdloan::Crowdloans` (`max_values`: None, `max_size`: Some(289), added: 2764, mode: `MaxEncodedLen`)
+	/// Storage: `Crowdloan::Contributions` (r:2 w:2)
+	/// Proof: `Crowdloan::Contributions` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn transfer_contribution() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `381`
+		//  Estimated: `6044`
+		// Minimum execution time: 15_849_000 picoseconds.
+		Weight::from_parts(16_401_000, 6044)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -313,4 +392,78 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Crowdloan::Crowdloans` (r:1 w:1)
+	/// Proof: `Crowdloan::Crowdloans` (`max_values`: None, `max_size`: Some(289), added: 2764, mode: `MaxEncodedLen`)
+	fn set_milestones() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `224`
+		//  Estimated: `3754`
+		// Minimum execution time: 9_958_000 picoseconds.
+		Weight::from_parts(10_289_000, 3754)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Crowdloan::Crowdloans` (r:1 w:1)
+	/// Proof: `Crowdloan::Crowdloans` (`max_values`: None, `max_size`: Some(289), added: 2764, mode: `MaxEncodedLen`)
+	fn request_milestone() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `267`
+		//  Estimated: `3754`
+		// Minimum execution time: 9_327_000 picoseconds.
+		Weight::from_parts(9_648_000, 3754)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Crowdloan::Crowdloans` (r:1 w:0)
+	/// Proof: `Crowdloan::Crowdloans` (`max_values`: None, `max_size`: Some(289), added: 2764, mode: `MaxEncodedLen`)
+	/// Storage: `Crowdloan::Contributions` (r:1 w:0)
+	/// Proof: `Crowdloan::Contributions` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `Crowdloan::MilestoneTally` (r:1 w:1)
+	/// Proof: `Crowdloan::MilestoneTally` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	/// Storage: `Crowdloan::MilestoneVotes` (r:1 w:1)
+	/// Proof: `Crowdloan::MilestoneVotes` (`max_values`: None, `max_size`: Some(45), added: 2520, mode: `MaxEncodedLen`)
+	fn vote_milestone() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `3754`
+		// Minimum execution time: 17_493_000 picoseconds.
+		Weight::from_parts(18_055_000, 3754)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Crowdloan::Crowdloans` (r:1 w:1)
+	/// Proof: `Crowdloan::Crowdloans` (`max_values`: None, `max_size`: Some(289), added: 2764, mode: `MaxEncodedLen`)
+	/// Storage: `Crowdloan::MilestoneTally` (r:1 w:1)
+	/// Proof: `Crowdloan::MilestoneTally` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	/// Storage: `Crowdloan::MilestoneVotes` (r:0 w:499)
+	/// Proof: `Crowdloan::MilestoneVotes` (`max_values`: None, `max_size`: Some(45), added: 2520, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(104), added: 2579, mode: `MaxEncodedLen`)
+	/// The range of component `k` is `[1, 499]`.
+	fn conclude_milestone(k: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `478 + k * (49 ±0)`
+		//  Estimated: `6148 + k * (49 ±0)`
+		// Minimum execution time: 52_179_000 picoseconds.
+		Weight::from_parts(49_814_367, 6148)
+			// Standard Error: 4_122
+			.saturating_add(Weight::from_parts(1_412_530, 0).saturating_mul(k.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(k.into())))
+			.saturating_add(Weight::from_parts(0, 49).saturating_mul(k.into()))
+	}
+	/// Storage: `Crowdloan::Crowdloans` (r:1 w:1)
+	/// Proof: `Crowdloan::Crowdloans` (`max_values`: None, `max_size`: Some(289), added: 2764, mode: `MaxEncodedLen`)
+	/// Storage: `Crowdloan::Contributions` (r:2 w:2)
+	/// Proof: `Crowdloan::Contributions` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	fn transfer_contribution() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `381`
+		//  Estimated: `6044`
+		// Minimum execution time: 15_849_000 picoseconds.
+		Weight::from_parts(16_401_000, 6044)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
 }
\ No newline at end of file
@@ -69,6 +69,7 @@ mod benchmarks {
         let pulses_payload = PulsesPayload {
             block_number,
             pulses: vec![p.clone()], // Wrap the pulse in a vector
+            beacon_pulses: vec![],
             public: alice.into(),
         };
 
@@ -89,5 +90,32 @@ mod benchmarks {
         assert_eq!(OldestStoredRound::<T>::get(), oldest_stored_round);
     }
 
+    #[benchmark]
+    fn add_beacon() {
+        let info: BeaconInfoResponse = serde_json::from_str(DRAND_INFO_RESPONSE).unwrap();
+        let mut config = info.try_into_beacon_config().unwrap();
+        let chain_hash: ChainHash = [1u8; 32];
+        config.hash = BoundedHash::truncate_from(chain_hash.to_vec());
+
+        #[extrinsic_call]
+        add_beacon(RawOrigin::Root, config.clone());
+
+        assert_eq!(BeaconConfigs::<T>::get(chain_hash), Some(config));
+    }
+
+    #[benchmark]
+    fn remove_beacon() {
+        let info: BeaconInfoResponse = serde_json::from_str(DRAND_INFO_RESPONSE).unwrap();
+        let mut config = info.try_into_beacon_config().unwrap();
+        let chain_hash: ChainHash = [1u8; 32];
+        config.hash = BoundedHash::truncate_from(chain_hash.to_vec());
+        BeaconConfigs::<T>::insert(chain_hash, config);
+
+        #[extrinsic_call]
+        remove_beacon(RawOrigin::Root, chain_hash);
+
+        assert!(!BeaconConfigs::<T>::contains_key(chain_hash));
+    }
+
     impl_benchmark_test_suite!(Drand, crate::mock::new_test_ext(), crate::mock::Test);
 }
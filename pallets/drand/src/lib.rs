@@ -26,6 +26,10 @@
 //! signed transaction to encode them in the runtime. The runtime uses the optimized arkworks host
 //! functions to efficiently verify the pulse.
 //!
+//! Next to the default beacon, root can add a few additional beacons using the same scheme (e.g.
+//! a local test beacon). Their pulses are stored by chain hash, and consumers can name the chain
+//! their rounds refer to through [`Pallet::pulse`] and [`Pallet::last_stored_round`].
+//!
 //! Run `cargo doc --package pallet-drand --open` to view this pallet's documentation.
 
 // We make sure this pallet uses `no_std` for compiling to Wasm.
@@ -53,12 +57,15 @@ use sha2::{Digest, Sha256};
 use sp_core::blake2_256;
 use sp_runtime::{
     KeyTypeId, Saturating,
+    offchain::StorageKind,
     traits::{Hash, One},
     transaction_validity::{InvalidTransaction, TransactionValidity, ValidTransaction},
 };
 
 pub mod bls12_381;
 pub mod drand_priority;
+#[cfg(feature = "std")]
+pub mod local_beacon;
 pub mod migrations;
 pub mod types;
 pub mod utils;
@@ -90,11 +97,32 @@ const ENDPOINTS: [&str; 5] = [
 pub const QUICKNET_CHAIN_HASH: &str =
     "52db9ba70e0cc0f6eaf7803dd07447a1f5477735fd3f661792ba94600c84e971";
 
-const CHAIN_HASH: &str = QUICKNET_CHAIN_HASH;
+/// prefix of the offchain storage keys under which a local beacon publishes its responses, see
+/// [`local_beacon_storage_key`]
+pub const LOCAL_BEACON_STORAGE_PREFIX: &[u8] = b"drand::local-beacon";
 
 pub const MAX_PULSES_TO_FETCH: u64 = 50;
 pub const MAX_KEPT_PULSES: u64 = 216_000; // 1 week
 pub const MAX_REMOVED_PULSES: u64 = 100;
+/// the maximum number of additional beacons tracked next to the default one
+pub const MAX_BEACONS: usize = 4;
+
+/// something that knows whether other pallets still refer to an additional beacon
+pub trait BeaconUsage {
+    /// whether the beacon with the given chain hash is still referred to
+    fn is_beacon_used(chain_hash: &ChainHash) -> bool;
+    /// the weight of [`BeaconUsage::is_beacon_used`]
+    fn is_beacon_used_weight() -> Weight;
+}
+
+impl BeaconUsage for () {
+    fn is_beacon_used(_chain_hash: &ChainHash) -> bool {
+        false
+    }
+    fn is_beacon_used_weight() -> Weight {
+        Weight::zero()
+    }
+}
 
 /// Defines application identifier for crypto keys of this module.
 ///
@@ -178,6 +206,9 @@ pub mod pallet {
         /// complete.
         #[pallet::constant]
         type HttpFetchTimeout: Get<u64>;
+        /// something that knows whether an additional beacon is still in use, which prevents
+        /// its removal
+        type BeaconUsage: BeaconUsage;
     }
 
     /// the drand beacon configuration
@@ -216,6 +247,25 @@ pub mod pallet {
         }
     }
 
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// The configuration of the default beacon, quicknet if unset.
+        pub beacon_config: Option<BeaconConfiguration>,
+        /// The phantom just for type place holder.
+        #[serde(skip)]
+        pub _phantom: PhantomData<T>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            if let Some(config) = &self.beacon_config {
+                BeaconConfig::<T>::put(config.clone());
+            }
+        }
+    }
+
     /// Define a maximum length for the migration key
     type MigrationKeyMaxLen = ConstU32<128>;
 
@@ -235,6 +285,33 @@ pub mod pallet {
     #[pallet::storage]
     pub type OldestStoredRound<T: Config> = StorageValue<_, RoundNumber, ValueQuery>;
 
+    /// the configurations of the additional beacons, by chain hash
+    #[pallet::storage]
+    pub type BeaconConfigs<T: Config> =
+        StorageMap<_, Blake2_128Concat, ChainHash, BeaconConfiguration, OptionQuery>;
+
+    /// map (chain hash, round number) to pulse for the additional beacons
+    #[pallet::storage]
+    pub type BeaconPulses<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        ChainHash,
+        Blake2_128Concat,
+        RoundNumber,
+        Pulse,
+        OptionQuery,
+    >;
+
+    /// last stored round of the additional beacons
+    #[pallet::storage]
+    pub type BeaconLastStoredRound<T: Config> =
+        StorageMap<_, Blake2_128Concat, ChainHash, RoundNumber, ValueQuery>;
+
+    /// oldest stored round of the additional beacons
+    #[pallet::storage]
+    pub type BeaconOldestStoredRound<T: Config> =
+        StorageMap<_, Blake2_128Concat, ChainHash, RoundNumber, ValueQuery>;
+
     /// Defines the block when next unsigned transaction will be accepted.
     ///
     /// To prevent spam of unsigned (and unpaid!) transactions on the network,
@@ -252,6 +329,21 @@ pub mod pallet {
         NewPulse { rounds: Vec<RoundNumber> },
         /// Oldest Stored Round has been set.
         SetOldestStoredRound(u64),
+        /// An additional beacon is now tracked.
+        BeaconAdded { chain_hash: ChainHash },
+        /// An additional beacon is no longer tracked, its pulses are removed when blocks have
+        /// weight left.
+        BeaconRemoved { chain_hash: ChainHash },
+        /// Successfully set new pulse(s) of an additional beacon.
+        NewBeaconPulse {
+            chain_hash: ChainHash,
+            rounds: Vec<RoundNumber>,
+        },
+        /// The pulses of an additional beacon were rejected, the other beacons are still written.
+        BeaconPulseRejected {
+            chain_hash: ChainHash,
+            error: DispatchError,
+        },
     }
 
     #[pallet::error]
@@ -268,6 +360,18 @@ pub mod pallet {
         InvalidRoundNumber,
         /// the pulse could not be verified
         PulseVerificationError,
+        /// the beacon is already tracked, or its pulses are still being removed
+        BeaconAlreadyTracked,
+        /// the beacon is not tracked
+        UnknownBeacon,
+        /// the maximum number of additional beacons is reached
+        TooManyBeacons,
+        /// the beacon does not use the scheme of the default beacon
+        UnsupportedBeaconScheme,
+        /// the chain hash of the beacon configuration is not 32 bytes
+        InvalidChainHash,
+        /// the beacon is still in use
+        BeaconInUse,
     }
 
     #[pallet::hooks]
@@ -278,6 +382,9 @@ pub mod pallet {
                 log::debug!("Drand: Failed to fetch pulse from drand. {e:?}");
             }
         }
+        fn on_idle(_block_number: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            Self::clear_removed_beacons(remaining_weight)
+        }
         fn on_runtime_upgrade() -> frame_support::weights::Weight {
             /*let weight = */
             frame_support::weights::Weight::from_parts(0, 0) /*;*/
@@ -330,11 +437,13 @@ pub mod pallet {
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        /// Verify and write a pulse from the beacon into the runtime
+        /// Verify and write pulses from the default beacon and the additional beacons into the
+        /// runtime
         #[pallet::call_index(0)]
         #[pallet::weight(Weight::from_parts(4_294_000_000, 0)
         .saturating_add(T::DbWeight::get().reads(3_u64))
-        .saturating_add(T::DbWeight::get().writes(4_u64)))]
+        .saturating_add(T::DbWeight::get().writes(4_u64))
+        .saturating_mul((pulses_payload.beacon_pulses.len() as u64).saturating_add(1)))]
         pub fn write_pulse(
             origin: OriginFor<T>,
             pulses_payload: PulsesPayload<T::Public, BlockNumberFor<T>>,
@@ -387,6 +496,23 @@ pub mod pallet {
             // Prune old pulses
             Self::prune_old_pulses(last_stored_round);
 
+            // A bad pulse of an additional beacon must not hold back the other beacons
+            for (chain_hash, pulses) in &pulses_payload.beacon_pulses {
+                let result = frame_support::storage::with_storage_layer(|| {
+                    Self::write_beacon_pulses(*chain_hash, pulses)
+                });
+                if let Err(error) = result {
+                    log::warn!(
+                        "Drand: Rejected pulses of beacon {}. {error:?}",
+                        hex::encode(chain_hash)
+                    );
+                    Self::deposit_event(Event::BeaconPulseRejected {
+                        chain_hash: *chain_hash,
+                        error,
+                    });
+                }
+            }
+
             // Update the next unsigned block number
             let current_block = frame_system::Pallet::<T>::block_number();
             <NextUnsignedAt<T>>::put(current_block);
@@ -435,6 +561,72 @@ pub mod pallet {
             Self::deposit_event(Event::SetOldestStoredRound(oldest_round));
             Ok(())
         }
+
+        /// allows the root user to track an additional beacon next to the default one
+        /// pulses of all beacons are checked by the same verifier, so the beacon must use the
+        /// scheme of the default beacon.
+        ///
+        /// * `origin`: the root user
+        /// * `config`: the beacon configuration
+        #[pallet::call_index(3)]
+        #[pallet::weight((Weight::from_parts(8_766_000, 0)
+        .saturating_add(T::DbWeight::get().reads(3_u64.saturating_add(MAX_BEACONS as u64)))
+        .saturating_add(T::DbWeight::get().writes(1_u64)), DispatchClass::Operational))]
+        pub fn add_beacon(origin: OriginFor<T>, config: BeaconConfiguration) -> DispatchResult {
+            ensure_root(origin)?;
+            let chain_hash: ChainHash = config
+                .hash
+                .clone()
+                .into_inner()
+                .try_into()
+                .map_err(|_| Error::<T>::InvalidChainHash)?;
+
+            let default_config = BeaconConfig::<T>::get();
+            ensure!(
+                config.hash != default_config.hash
+                    && !BeaconConfigs::<T>::contains_key(chain_hash)
+                    && !BeaconLastStoredRound::<T>::contains_key(chain_hash),
+                Error::<T>::BeaconAlreadyTracked
+            );
+            ensure!(
+                config.scheme_id == default_config.scheme_id,
+                Error::<T>::UnsupportedBeaconScheme
+            );
+            ensure!(
+                BeaconConfigs::<T>::iter_keys().count() < MAX_BEACONS,
+                Error::<T>::TooManyBeacons
+            );
+
+            BeaconConfigs::<T>::insert(chain_hash, config);
+            Self::deposit_event(Event::BeaconAdded { chain_hash });
+            Ok(())
+        }
+
+        /// allows the root user to stop tracking an additional beacon
+        /// its stored pulses are removed when blocks have weight left.
+        /// the beacon can not be removed while it is still in use.
+        ///
+        /// * `origin`: the root user
+        /// * `chain_hash`: the chain hash of the beacon
+        #[pallet::call_index(4)]
+        #[pallet::weight((Weight::from_parts(5_370_000, 0)
+        .saturating_add(T::DbWeight::get().reads(1_u64))
+        .saturating_add(T::DbWeight::get().writes(1_u64))
+        .saturating_add(T::BeaconUsage::is_beacon_used_weight()), DispatchClass::Operational))]
+        pub fn remove_beacon(origin: OriginFor<T>, chain_hash: ChainHash) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(
+                BeaconConfigs::<T>::contains_key(chain_hash),
+                Error::<T>::UnknownBeacon
+            );
+            ensure!(
+                !T::BeaconUsage::is_beacon_used(&chain_hash),
+                Error::<T>::BeaconInUse
+            );
+            BeaconConfigs::<T>::remove(chain_hash);
+            Self::deposit_event(Event::BeaconRemoved { chain_hash });
+            Ok(())
+        }
     }
 }
 
@@ -450,9 +642,70 @@ impl<T: Config> Pallet<T> {
             return Err("Drand: Too early to send unsigned transaction");
         }
 
-        let mut last_stored_round = LastStoredRound::<T>::get();
+        let pulses =
+            Self::fetch_new_pulses(&Self::default_chain_hash(), LastStoredRound::<T>::get())?;
+
+        let mut beacon_pulses = Vec::new();
+        for chain_hash in BeaconConfigs::<T>::iter_keys() {
+            let last_stored_round = BeaconLastStoredRound::<T>::get(chain_hash);
+            match Self::fetch_new_pulses(&hex::encode(chain_hash), last_stored_round) {
+                Ok(new_pulses) if !new_pulses.is_empty() => {
+                    beacon_pulses.push((chain_hash, new_pulses))
+                }
+                Ok(_) => {}
+                Err(e) => log::debug!(
+                    "Drand: Failed to fetch pulses of beacon {}. {e:?}",
+                    hex::encode(chain_hash)
+                ),
+            }
+        }
+
+        if pulses.is_empty() && beacon_pulses.is_empty() {
+            return Ok(());
+        }
+
+        let last_round = pulses.last().map(|pulse| pulse.round);
+        let signer = Signer::<T, T::AuthorityId>::all_accounts();
+
+        let results = signer.send_unsigned_transaction(
+            |account| PulsesPayload {
+                block_number,
+                pulses: pulses.clone(),
+                beacon_pulses: beacon_pulses.clone(),
+                public: account.public.clone(),
+            },
+            |pulses_payload, signature| Call::write_pulse {
+                pulses_payload,
+                signature: Some(signature),
+            },
+        );
+
+        for (acc, res) in &results {
+            match res {
+                Ok(()) => log::debug!(
+                    "Drand: [{:?}] Submitted new pulses up to round: {:?}",
+                    acc.id,
+                    last_round
+                ),
+                Err(e) => log::error!(
+                    "Drand: [{:?}] Failed to submit transaction: {:?}",
+                    acc.id,
+                    e
+                ),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// fetch the pulses of the given chain following `last_stored_round`, up to
+    /// `MAX_PULSES_TO_FETCH`
+    fn fetch_new_pulses(
+        chain_hash: &str,
+        mut last_stored_round: RoundNumber,
+    ) -> Result<Vec<Pulse>, &'static str> {
         let latest_unbounded_pulse =
-            Self::fetch_drand_latest().map_err(|_| "Failed to query drand")?;
+            Self::fetch_beacon_latest(chain_hash).map_err(|_| "Failed to query drand")?;
         let latest_pulse = latest_unbounded_pulse
             .try_into_pulse()
             .map_err(|_| "Drand: Received pulse contains invalid data")?;
@@ -461,67 +714,51 @@ impl<T: Config> Pallet<T> {
         // If last_stored_round is zero, set it to current_round - 1
         if last_stored_round == 0 {
             last_stored_round = current_round.saturating_sub(1);
-            LastStoredRound::<T>::put(last_stored_round);
         }
 
-        if current_round > last_stored_round {
-            let rounds_to_fetch = cmp::min(
-                current_round.saturating_sub(last_stored_round),
-                MAX_PULSES_TO_FETCH,
-            );
-            let mut pulses = Vec::new();
-
-            for round in (last_stored_round.saturating_add(1))
-                ..=(last_stored_round.saturating_add(rounds_to_fetch))
-            {
-                let unbounded_pulse = Self::fetch_drand_by_round(round)
-                    .map_err(|_| "Drand: Failed to query drand for round")?;
-                let pulse = unbounded_pulse
-                    .try_into_pulse()
-                    .map_err(|_| "Drand: Received pulse contains invalid data")?;
-                pulses.push(pulse);
-            }
-
-            let signer = Signer::<T, T::AuthorityId>::all_accounts();
-
-            let results = signer.send_unsigned_transaction(
-                |account| PulsesPayload {
-                    block_number,
-                    pulses: pulses.clone(),
-                    public: account.public.clone(),
-                },
-                |pulses_payload, signature| Call::write_pulse {
-                    pulses_payload,
-                    signature: Some(signature),
-                },
-            );
+        let rounds_to_fetch = cmp::min(
+            current_round.saturating_sub(last_stored_round),
+            MAX_PULSES_TO_FETCH,
+        );
+        let mut pulses = Vec::new();
 
-            for (acc, res) in &results {
-                match res {
-                    Ok(()) => log::debug!(
-                        "Drand: [{:?}] Submitted new pulses up to round: {:?}",
-                        acc.id,
-                        last_stored_round.saturating_add(rounds_to_fetch)
-                    ),
-                    Err(e) => log::error!(
-                        "Drand: [{:?}] Failed to submit transaction: {:?}",
-                        acc.id,
-                        e
-                    ),
-                }
-            }
+        for round in (last_stored_round.saturating_add(1))
+            ..=(last_stored_round.saturating_add(rounds_to_fetch))
+        {
+            let unbounded_pulse = Self::fetch_beacon_by_round(chain_hash, round)
+                .map_err(|_| "Drand: Failed to query drand for round")?;
+            let pulse = unbounded_pulse
+                .try_into_pulse()
+                .map_err(|_| "Drand: Received pulse contains invalid data")?;
+            pulses.push(pulse);
         }
 
-        Ok(())
+        Ok(pulses)
+    }
+
+    /// the chain hash of the default beacon as used in the drand api paths
+    fn default_chain_hash() -> String {
+        hex::encode(BeaconConfig::<T>::get().hash)
     }
 
     fn fetch_drand_by_round(round: RoundNumber) -> Result<DrandResponseBody, &'static str> {
-        let relative_path = format!("/{CHAIN_HASH}/public/{round}");
-        Self::fetch_and_decode_from_any_endpoint(&relative_path)
+        Self::fetch_beacon_by_round(&Self::default_chain_hash(), round)
     }
 
     fn fetch_drand_latest() -> Result<DrandResponseBody, &'static str> {
-        let relative_path = format!("/{CHAIN_HASH}/public/latest");
+        Self::fetch_beacon_latest(&Self::default_chain_hash())
+    }
+
+    fn fetch_beacon_by_round(
+        chain_hash: &str,
+        round: RoundNumber,
+    ) -> Result<DrandResponseBody, &'static str> {
+        let relative_path = format!("/{chain_hash}/public/{round}");
+        Self::fetch_and_decode_from_any_endpoint(&relative_path)
+    }
+
+    fn fetch_beacon_latest(chain_hash: &str) -> Result<DrandResponseBody, &'static str> {
+        let relative_path = format!("/{chain_hash}/public/latest");
         Self::fetch_and_decode_from_any_endpoint(&relative_path)
     }
 
@@ -529,6 +766,16 @@ impl<T: Config> Pallet<T> {
     fn fetch_and_decode_from_any_endpoint(
         relative_path: &str,
     ) -> Result<DrandResponseBody, &'static str> {
+        // A local beacon of a dev or test network stands in for the endpoints.
+        let local_response = sp_io::offchain::local_storage_get(
+            StorageKind::PERSISTENT,
+            &local_beacon_storage_key(relative_path),
+        );
+        if let Some(body) = local_response {
+            return serde_json::from_slice::<DrandResponseBody>(&body)
+                .map_err(|_| "Drand: Invalid response from the local beacon");
+        }
+
         let uris: Vec<String> = ENDPOINTS
             .iter()
             .map(|e| format!("{e}{relative_path}"))
@@ -628,6 +875,78 @@ impl<T: Config> Pallet<T> {
         bounded_rand
     }
 
+    /// get the pulse of a round of the beacon with the given chain hash
+    /// `None` and the chain hash of the default beacon both refer to the default beacon
+    pub fn pulse(chain_hash: Option<ChainHash>, round: RoundNumber) -> Option<Pulse> {
+        match Self::additional_beacon(chain_hash) {
+            Some(chain_hash) => BeaconPulses::<T>::get(chain_hash, round),
+            None => Pulses::<T>::get(round),
+        }
+    }
+
+    /// get the last stored round of the beacon with the given chain hash
+    /// `None` and the chain hash of the default beacon both refer to the default beacon
+    pub fn last_stored_round(chain_hash: Option<ChainHash>) -> RoundNumber {
+        match Self::additional_beacon(chain_hash) {
+            Some(chain_hash) => BeaconLastStoredRound::<T>::get(chain_hash),
+            None => LastStoredRound::<T>::get(),
+        }
+    }
+
+    /// whether pulses of the beacon with the given chain hash are written to the runtime
+    pub fn is_tracked_beacon(chain_hash: &ChainHash) -> bool {
+        Self::is_default_beacon(chain_hash) || BeaconConfigs::<T>::contains_key(chain_hash)
+    }
+
+    fn is_default_beacon(chain_hash: &ChainHash) -> bool {
+        BeaconConfig::<T>::get().hash.as_slice() == chain_hash.as_slice()
+    }
+
+    fn additional_beacon(chain_hash: Option<ChainHash>) -> Option<ChainHash> {
+        chain_hash.filter(|chain_hash| !Self::is_default_beacon(chain_hash))
+    }
+
+    /// verify and write pulses of an additional beacon
+    fn write_beacon_pulses(chain_hash: ChainHash, pulses: &[Pulse]) -> DispatchResult {
+        let config = BeaconConfigs::<T>::get(chain_hash).ok_or(Error::<T>::UnknownBeacon)?;
+
+        let mut last_stored_round = BeaconLastStoredRound::<T>::get(chain_hash);
+        let oldest_stored_round = BeaconOldestStoredRound::<T>::get(chain_hash);
+        let is_first_storage = last_stored_round == 0 && oldest_stored_round == 0;
+        let mut new_rounds = Vec::new();
+
+        for pulse in pulses {
+            let is_verified = T::Verifier::verify(config.clone(), pulse.clone())
+                .map_err(|_| Error::<T>::PulseVerificationError)?;
+
+            if is_verified {
+                ensure!(
+                    pulse.round > last_stored_round,
+                    Error::<T>::InvalidRoundNumber
+                );
+                BeaconPulses::<T>::insert(chain_hash, pulse.round, pulse.clone());
+                last_stored_round = pulse.round;
+                new_rounds.push(pulse.round);
+            }
+        }
+
+        BeaconLastStoredRound::<T>::insert(chain_hash, last_stored_round);
+        if let Some(first_round) = new_rounds.first().filter(|_| is_first_storage) {
+            BeaconOldestStoredRound::<T>::insert(chain_hash, first_round);
+        }
+
+        Self::prune_old_beacon_pulses(chain_hash, last_stored_round);
+
+        if !new_rounds.is_empty() {
+            Self::deposit_event(Event::NewBeaconPulse {
+                chain_hash,
+                rounds: new_rounds,
+            });
+        }
+
+        Ok(())
+    }
+
     fn validate_signature_and_parameters(
         payload: &impl SignedPayload<T>,
         signature: &T::Signature,
@@ -700,6 +1019,64 @@ impl<T: Config> Pallet<T> {
 
         OldestStoredRound::<T>::put(oldest);
     }
+
+    fn prune_old_beacon_pulses(chain_hash: ChainHash, last_stored_round: RoundNumber) {
+        let mut oldest = BeaconOldestStoredRound::<T>::get(chain_hash);
+        if oldest == 0 {
+            return;
+        }
+
+        let mut removed: u64 = 0;
+        while last_stored_round.saturating_sub(oldest).saturating_add(1) > MAX_KEPT_PULSES
+            && removed < MAX_REMOVED_PULSES
+        {
+            BeaconPulses::<T>::remove(chain_hash, oldest);
+            oldest = oldest.saturating_add(1);
+            removed = removed.saturating_add(1);
+        }
+
+        BeaconOldestStoredRound::<T>::insert(chain_hash, oldest);
+    }
+
+    /// remove the pulses of beacons that are no longer tracked, within the given weight
+    fn clear_removed_beacons(remaining_weight: Weight) -> Weight {
+        let db_weight = T::DbWeight::get();
+        let mut weight = db_weight.reads(1);
+
+        for chain_hash in BeaconLastStoredRound::<T>::iter_keys().collect::<Vec<_>>() {
+            weight.saturating_accrue(db_weight.reads(1));
+            if BeaconConfigs::<T>::contains_key(chain_hash) {
+                continue;
+            }
+
+            let limit = remaining_weight
+                .saturating_sub(weight)
+                .saturating_sub(db_weight.writes(2))
+                .ref_time()
+                .checked_div(db_weight.write)
+                .unwrap_or(MAX_REMOVED_PULSES)
+                .min(MAX_REMOVED_PULSES);
+            if limit == 0 {
+                break;
+            }
+
+            let result = BeaconPulses::<T>::clear_prefix(chain_hash, limit as u32, None);
+            weight.saturating_accrue(db_weight.writes(u64::from(result.backend)));
+            if result.maybe_cursor.is_none() {
+                BeaconLastStoredRound::<T>::remove(chain_hash);
+                BeaconOldestStoredRound::<T>::remove(chain_hash);
+                weight.saturating_accrue(db_weight.writes(2));
+            }
+        }
+
+        weight
+    }
+}
+
+/// the offchain storage key under which a local beacon publishes the response to the given drand
+/// api path, e.g. `/{chain_hash}/public/latest`
+pub fn local_beacon_storage_key(relative_path: &str) -> Vec<u8> {
+    [LOCAL_BEACON_STORAGE_PREFIX, relative_path.as_bytes()].concat()
 }
 
 /// construct a message (e.g. signed by drand)
@@ -0,0 +1,112 @@
+//! A local stand-in for a drand beacon, for dev and test networks
+//!
+//! The beacon key is derived from a seed, and pulses are signed the way quicknet signs them
+//! (unchained, signatures in G1 and public key in G2), so they are verified by the
+//! [`QuicknetVerifier`](crate::verifier::QuicknetVerifier) against the local public key. Rounds
+//! follow the quicknet genesis time and period, so tools computing quicknet rounds from a time
+//! keep working against a local beacon.
+//!
+//! The node publishes the pulses in the offchain storage under
+//! [`local_beacon_storage_key`](crate::local_beacon_storage_key), where the offchain worker reads
+//! them before querying the drand api.
+
+use crate::{
+    message,
+    types::{BeaconConfiguration, BoundedHash, DrandResponseBody, Metadata, OpaquePublicKey},
+};
+use alloc::{string::String, vec::Vec};
+use ark_bls12_381::{Fr, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup, hashing::HashToCurve};
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalSerialize;
+use sha2::{Digest, Sha256};
+use tle::curves::drand::TinyBLS381;
+use w3f_bls::engine::EngineBLS;
+
+use crate::types::RoundNumber;
+
+/// the scheme of the local beacon, the one of quicknet
+pub const LOCAL_BEACON_SCHEME: &[u8] = b"bls-unchained-g1-rfc9380";
+/// the beacon id of the local beacon
+pub const LOCAL_BEACON_ID: &[u8] = b"local";
+/// the genesis time of the local beacon, the one of quicknet
+pub const LOCAL_BEACON_GENESIS_TIME: u32 = 1_692_803_367;
+/// the period of the local beacon in seconds, the one of quicknet
+pub const LOCAL_BEACON_PERIOD: u32 = 3;
+
+/// A deterministic beacon generating quicknet-format pulses from a seed
+pub struct LocalBeacon {
+    secret: Fr,
+    public_key: Vec<u8>,
+}
+
+impl LocalBeacon {
+    /// derive the beacon key from the given seed
+    pub fn from_seed(seed: &[u8]) -> Self {
+        let secret = Fr::from_be_bytes_mod_order(&Sha256::digest(seed));
+
+        let mut public_key = Vec::new();
+        (G2Affine::generator() * secret)
+            .into_affine()
+            .serialize_compressed(&mut public_key)
+            .expect("serializing into a vec does not fail; qed");
+
+        Self { secret, public_key }
+    }
+
+    /// the chain hash of the beacon, the sha256 hash of its public key
+    pub fn chain_hash(&self) -> [u8; 32] {
+        Sha256::digest(&self.public_key).into()
+    }
+
+    /// the chain hash of the beacon as used in the drand api paths
+    pub fn chain_hash_hex(&self) -> String {
+        hex::encode(self.chain_hash())
+    }
+
+    /// the beacon configuration to put in the runtime
+    pub fn config(&self) -> BeaconConfiguration {
+        let hash = BoundedHash::truncate_from(self.chain_hash().to_vec());
+        BeaconConfiguration {
+            public_key: OpaquePublicKey::truncate_from(self.public_key.clone()),
+            period: LOCAL_BEACON_PERIOD,
+            genesis_time: LOCAL_BEACON_GENESIS_TIME,
+            hash: hash.clone(),
+            group_hash: hash,
+            scheme_id: BoundedHash::truncate_from(LOCAL_BEACON_SCHEME.to_vec()),
+            metadata: Metadata {
+                beacon_id: BoundedHash::truncate_from(LOCAL_BEACON_ID.to_vec()),
+            },
+        }
+    }
+
+    /// the round emitted at the given unix time in seconds, 0 before the genesis time
+    pub fn round_at(&self, unix_time: u64) -> RoundNumber {
+        match unix_time.checked_sub(LOCAL_BEACON_GENESIS_TIME.into()) {
+            Some(elapsed) => elapsed
+                .checked_div(LOCAL_BEACON_PERIOD.into())
+                .unwrap_or_default()
+                .saturating_add(1),
+            None => 0,
+        }
+    }
+
+    /// the pulse of the given round, as returned by the drand api
+    pub fn pulse(&self, round: RoundNumber) -> DrandResponseBody {
+        let message_hash = <TinyBLS381 as EngineBLS>::hash_to_curve_map()
+            .hash(&message(round, &[]))
+            .expect("hashing a sha256 digest to the curve does not fail; qed");
+
+        let mut signature = Vec::new();
+        (message_hash * self.secret)
+            .into_affine()
+            .serialize_compressed(&mut signature)
+            .expect("serializing into a vec does not fail; qed");
+
+        DrandResponseBody {
+            round,
+            randomness: Sha256::digest(&signature).to_vec(),
+            signature,
+        }
+    }
+}
@@ -94,6 +94,17 @@ where
 
 parameter_types! {
     pub const UnsignedPriority: u64 = 1 << 20;
+    pub static UsedBeacon: Option<ChainHash> = None;
+}
+
+pub struct MockBeaconUsage;
+impl BeaconUsage for MockBeaconUsage {
+    fn is_beacon_used(chain_hash: &ChainHash) -> bool {
+        UsedBeacon::get().as_ref() == Some(chain_hash)
+    }
+    fn is_beacon_used_weight() -> Weight {
+        Weight::zero()
+    }
 }
 
 impl pallet_drand_bridge::Config for Test {
@@ -102,6 +113,7 @@ impl pallet_drand_bridge::Config for Test {
     type Verifier = QuicknetVerifier;
     type UnsignedPriority = UnsignedPriority;
     type HttpFetchTimeout = ConstU64<1_000>;
+    type BeaconUsage = MockBeaconUsage;
 }
 
 // Build genesis storage according to the mock runtime.
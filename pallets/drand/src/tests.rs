@@ -15,22 +15,30 @@
  */
 
 use crate::{
-    BeaconConfig, BeaconConfigurationPayload, BeaconInfoResponse, Call, DrandResponseBody,
-    ENDPOINTS, Error, HasMigrationRun, LastStoredRound, MAX_KEPT_PULSES, OldestStoredRound, Pulse,
-    Pulses, PulsesPayload, QUICKNET_CHAIN_HASH, migrations::migrate_prune_old_pulses,
-    migrations::migrate_set_oldest_round, mock::*,
+    BeaconConfig, BeaconConfigs, BeaconConfiguration, BeaconConfigurationPayload,
+    BeaconInfoResponse, BeaconLastStoredRound, BeaconPulses, Call, ChainHash, DrandResponseBody,
+    ENDPOINTS, Error, HasMigrationRun, LastStoredRound, MAX_BEACONS, MAX_KEPT_PULSES,
+    OldestStoredRound, Pulse, Pulses, PulsesPayload, QUICKNET_CHAIN_HASH,
+    local_beacon::LocalBeacon,
+    local_beacon_storage_key,
+    migrations::migrate_prune_old_pulses,
+    migrations::migrate_set_oldest_round,
+    mock::*,
+    verifier::{QuicknetVerifier, Verifier},
 };
 use codec::Encode;
 use frame_support::{
     BoundedVec, assert_noop, assert_ok,
-    pallet_prelude::{InvalidTransaction, TransactionSource},
+    pallet_prelude::{InvalidTransaction, TransactionSource, Weight},
+    traits::Hooks,
     weights::RuntimeDbWeight,
 };
 use frame_system::RawOrigin;
 use sp_core::Get;
 use sp_runtime::{
+    DispatchError,
     offchain::{
-        OffchainWorkerExt,
+        OffchainDbExt, OffchainWorkerExt, StorageKind,
         testing::{PendingRequest, TestOffchainExt},
     },
     traits::ValidateUnsigned,
@@ -73,6 +81,7 @@ fn it_can_submit_valid_pulse_when_beacon_config_exists() {
 
         let pulses_payload = PulsesPayload {
             pulses: vec![p.clone()],
+            beacon_pulses: vec![],
             block_number,
             public: alice.public(),
         };
@@ -121,6 +130,7 @@ fn it_rejects_invalid_pulse_due_to_bad_signature() {
         // Prepare the pulses payload
         let pulses_payload = PulsesPayload {
             pulses: vec![p.clone()],
+            beacon_pulses: vec![],
             block_number,
             public: alice.public(),
         };
@@ -167,6 +177,7 @@ fn it_rejects_pulses_with_non_incremental_round_numbers() {
         let p: Pulse = u_p.try_into_pulse().unwrap();
         let pulses_payload = PulsesPayload {
             pulses: vec![p.clone()],
+            beacon_pulses: vec![],
             block_number,
             public: alice.public(),
         };
@@ -278,6 +289,7 @@ fn test_validate_unsigned_write_pulse() {
         let pulses_payload = PulsesPayload {
             block_number,
             pulses: vec![],
+            beacon_pulses: vec![],
             public: alice.public(),
         };
         let signature = alice.sign(&pulses_payload.encode());
@@ -303,6 +315,7 @@ fn test_not_validate_unsigned_write_pulse_with_bad_proof() {
         let pulses_payload = PulsesPayload {
             block_number,
             pulses: vec![],
+            beacon_pulses: vec![],
             public: alice.public(),
         };
 
@@ -329,6 +342,7 @@ fn test_not_validate_unsigned_write_pulse_with_no_payload_signature() {
         let pulses_payload = PulsesPayload {
             block_number,
             pulses: vec![],
+            beacon_pulses: vec![],
             public: alice.public(),
         };
 
@@ -399,6 +413,7 @@ fn validate_unsigned_rejects_future_block_number() {
         let pulses_payload = PulsesPayload {
             block_number: future_block_number,
             pulses: vec![],
+            beacon_pulses: vec![],
             public: alice.public(),
         };
         let signature = alice.sign(&pulses_payload.encode());
@@ -743,3 +758,305 @@ fn test_migrate_set_oldest_round() {
         assert_eq!(weight, expected);
     });
 }
+
+/// A beacon using the quicknet key and scheme under another chain hash, so quicknet pulses
+/// verify against it.
+fn additional_beacon_config(chain_hash: ChainHash) -> BeaconConfiguration {
+    let info: BeaconInfoResponse = serde_json::from_str(DRAND_INFO_RESPONSE).unwrap();
+    let mut config = info.try_into_beacon_config().unwrap();
+    config.hash = BoundedVec::truncate_from(chain_hash.to_vec());
+    config
+}
+
+#[test]
+fn test_add_and_remove_beacon() {
+    new_test_ext().execute_with(|| {
+        let chain_hash = [1u8; 32];
+        let config = additional_beacon_config(chain_hash);
+
+        assert_noop!(
+            Drand::add_beacon(
+                RuntimeOrigin::signed(sp_keyring::Sr25519Keyring::Alice.public()),
+                config.clone()
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Drand::add_beacon(RuntimeOrigin::root(), BeaconConfig::<Test>::get()),
+            Error::<Test>::BeaconAlreadyTracked
+        );
+
+        let mut other_scheme = config.clone();
+        other_scheme.scheme_id = BoundedVec::truncate_from(b"pedersen-bls-chained".to_vec());
+        assert_noop!(
+            Drand::add_beacon(RuntimeOrigin::root(), other_scheme),
+            Error::<Test>::UnsupportedBeaconScheme
+        );
+
+        let mut short_hash = config.clone();
+        short_hash.hash = BoundedVec::truncate_from(vec![1; 31]);
+        assert_noop!(
+            Drand::add_beacon(RuntimeOrigin::root(), short_hash),
+            Error::<Test>::InvalidChainHash
+        );
+
+        assert_ok!(Drand::add_beacon(RuntimeOrigin::root(), config.clone()));
+        assert_eq!(BeaconConfigs::<Test>::get(chain_hash), Some(config.clone()));
+        assert!(Drand::is_tracked_beacon(&chain_hash));
+        assert_noop!(
+            Drand::add_beacon(RuntimeOrigin::root(), config),
+            Error::<Test>::BeaconAlreadyTracked
+        );
+
+        for i in 2..=MAX_BEACONS {
+            assert_ok!(Drand::add_beacon(
+                RuntimeOrigin::root(),
+                additional_beacon_config([i as u8; 32])
+            ));
+        }
+        assert_noop!(
+            Drand::add_beacon(RuntimeOrigin::root(), additional_beacon_config([0xff; 32])),
+            Error::<Test>::TooManyBeacons
+        );
+
+        assert_noop!(
+            Drand::remove_beacon(
+                RuntimeOrigin::signed(sp_keyring::Sr25519Keyring::Alice.public()),
+                chain_hash
+            ),
+            DispatchError::BadOrigin
+        );
+
+        // Beacons still in use are kept
+        UsedBeacon::set(Some(chain_hash));
+        assert_noop!(
+            Drand::remove_beacon(RuntimeOrigin::root(), chain_hash),
+            Error::<Test>::BeaconInUse
+        );
+        UsedBeacon::set(None);
+
+        assert_ok!(Drand::remove_beacon(RuntimeOrigin::root(), chain_hash));
+        assert!(!Drand::is_tracked_beacon(&chain_hash));
+        assert_noop!(
+            Drand::remove_beacon(RuntimeOrigin::root(), chain_hash),
+            Error::<Test>::UnknownBeacon
+        );
+    });
+}
+
+#[test]
+fn test_write_pulse_of_additional_beacon() {
+    new_test_ext().execute_with(|| {
+        let alice = sp_keyring::Sr25519Keyring::Alice;
+        let block_number = 100_000_000;
+        System::set_block_number(block_number);
+
+        let chain_hash = [1u8; 32];
+        let default_chain_hash: ChainHash = hex::decode(QUICKNET_CHAIN_HASH)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let u_p: DrandResponseBody = serde_json::from_str(DRAND_PULSE).unwrap();
+        let p: Pulse = u_p.try_into_pulse().unwrap();
+
+        let pulses_payload = PulsesPayload {
+            pulses: vec![],
+            beacon_pulses: vec![(chain_hash, vec![p.clone()])],
+            block_number,
+            public: alice.public(),
+        };
+
+        // The beacon is not tracked yet, its pulses are rejected without failing the call
+        assert_ok!(Drand::write_pulse(
+            RuntimeOrigin::none(),
+            pulses_payload.clone(),
+            None
+        ));
+        System::assert_last_event(
+            Event::BeaconPulseRejected {
+                chain_hash,
+                error: Error::<Test>::UnknownBeacon.into(),
+            }
+            .into(),
+        );
+        assert_eq!(Drand::last_stored_round(Some(chain_hash)), 0);
+
+        assert_ok!(Drand::add_beacon(
+            RuntimeOrigin::root(),
+            additional_beacon_config(chain_hash)
+        ));
+        assert_ok!(Drand::write_pulse(
+            RuntimeOrigin::none(),
+            pulses_payload,
+            None
+        ));
+
+        assert_eq!(
+            BeaconPulses::<Test>::get(chain_hash, ROUND_NUMBER),
+            Some(p.clone())
+        );
+        assert_eq!(Drand::pulse(Some(chain_hash), ROUND_NUMBER), Some(p));
+        assert_eq!(Drand::last_stored_round(Some(chain_hash)), ROUND_NUMBER);
+
+        // The default beacon is untouched, whether it is named or not
+        assert_eq!(Drand::pulse(None, ROUND_NUMBER), None);
+        assert_eq!(Drand::pulse(Some(default_chain_hash), ROUND_NUMBER), None);
+        assert_eq!(Drand::last_stored_round(None), 0);
+        assert_eq!(Drand::last_stored_round(Some(default_chain_hash)), 0);
+    });
+}
+
+#[test]
+fn test_rejected_beacon_pulses_do_not_block_other_beacons() {
+    new_test_ext().execute_with(|| {
+        let block_number = 100_000_000;
+        System::set_block_number(block_number);
+
+        let chain_hash = [1u8; 32];
+        let bad_chain_hash = [2u8; 32];
+        assert_ok!(Drand::add_beacon(
+            RuntimeOrigin::root(),
+            additional_beacon_config(chain_hash)
+        ));
+        assert_ok!(Drand::add_beacon(
+            RuntimeOrigin::root(),
+            additional_beacon_config(bad_chain_hash)
+        ));
+
+        let u_p: DrandResponseBody = serde_json::from_str(DRAND_PULSE).unwrap();
+        let p: Pulse = u_p.try_into_pulse().unwrap();
+
+        assert_ok!(Drand::write_pulse(
+            RuntimeOrigin::none(),
+            PulsesPayload {
+                pulses: vec![p.clone()],
+                beacon_pulses: vec![
+                    // The repeated round fails after the first pulse is written
+                    (bad_chain_hash, vec![p.clone(), p.clone()]),
+                    (chain_hash, vec![p.clone()]),
+                ],
+                block_number,
+                public: sp_keyring::Sr25519Keyring::Alice.public(),
+            },
+            None
+        ));
+
+        // The default beacon and the valid beacon are written
+        assert_eq!(Drand::pulse(None, ROUND_NUMBER), Some(p.clone()));
+        assert_eq!(Drand::pulse(Some(chain_hash), ROUND_NUMBER), Some(p));
+
+        // Nothing of the rejected beacon is kept
+        assert_eq!(Drand::last_stored_round(Some(bad_chain_hash)), 0);
+        assert!(!BeaconPulses::<Test>::contains_key(
+            bad_chain_hash,
+            ROUND_NUMBER
+        ));
+        System::assert_has_event(
+            Event::BeaconPulseRejected {
+                chain_hash: bad_chain_hash,
+                error: Error::<Test>::InvalidRoundNumber.into(),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn test_removed_beacon_pulses_are_cleared() {
+    new_test_ext().execute_with(|| {
+        let chain_hash = [1u8; 32];
+        let config = additional_beacon_config(chain_hash);
+        assert_ok!(Drand::add_beacon(RuntimeOrigin::root(), config.clone()));
+
+        let u_p: DrandResponseBody = serde_json::from_str(DRAND_PULSE).unwrap();
+        let p: Pulse = u_p.try_into_pulse().unwrap();
+        assert_ok!(Drand::write_pulse(
+            RuntimeOrigin::none(),
+            PulsesPayload {
+                pulses: vec![],
+                beacon_pulses: vec![(chain_hash, vec![p])],
+                block_number: 1,
+                public: sp_keyring::Sr25519Keyring::Alice.public(),
+            },
+            None
+        ));
+
+        // Tracked beacons are left alone
+        Drand::on_idle(1, Weight::MAX);
+        assert!(BeaconPulses::<Test>::contains_key(chain_hash, ROUND_NUMBER));
+
+        assert_ok!(Drand::remove_beacon(RuntimeOrigin::root(), chain_hash));
+
+        // The beacon cannot be added back until its pulses are removed
+        assert_noop!(
+            Drand::add_beacon(RuntimeOrigin::root(), config.clone()),
+            Error::<Test>::BeaconAlreadyTracked
+        );
+
+        Drand::on_idle(1, Weight::MAX);
+        assert_eq!(BeaconPulses::<Test>::iter_prefix(chain_hash).count(), 0);
+        assert!(!BeaconLastStoredRound::<Test>::contains_key(chain_hash));
+        assert_ok!(Drand::add_beacon(RuntimeOrigin::root(), config));
+    });
+}
+
+#[test]
+fn test_local_beacon_pulses_are_verified() {
+    let beacon = LocalBeacon::from_seed(b"local");
+    let config = beacon.config();
+    let pulse = beacon.pulse(ROUND_NUMBER).try_into_pulse().unwrap();
+    assert_eq!(beacon.chain_hash_hex().len(), 64);
+
+    assert_eq!(
+        QuicknetVerifier::verify(config.clone(), pulse.clone()),
+        Ok(true)
+    );
+
+    // The signature only holds for its round and beacon
+    let mut other_round = pulse.clone();
+    other_round.round = ROUND_NUMBER + 1;
+    assert_eq!(QuicknetVerifier::verify(config, other_round), Ok(false));
+
+    let info: BeaconInfoResponse = serde_json::from_str(DRAND_INFO_RESPONSE).unwrap();
+    let quicknet = info.try_into_beacon_config().unwrap();
+    assert_eq!(QuicknetVerifier::verify(quicknet, pulse), Ok(false));
+
+    // The same seed gives the same beacon
+    assert_eq!(LocalBeacon::from_seed(b"local").config(), beacon.config());
+    assert_eq!(beacon.round_at(1_692_803_367), 1);
+    assert_eq!(beacon.round_at(1_692_803_367 + 3 * 999), ROUND_NUMBER);
+}
+
+#[test]
+fn test_local_beacon_stands_in_for_endpoints() {
+    let beacon = LocalBeacon::from_seed(b"local");
+    let chain_hash = beacon.chain_hash_hex();
+    let expected_pulse = beacon.pulse(ROUND_NUMBER);
+
+    let (offchain, _state) = TestOffchainExt::new();
+    let mut t = sp_io::TestExternalities::default();
+    t.register_extension(OffchainDbExt::new(offchain.clone()));
+    t.register_extension(OffchainWorkerExt::new(offchain));
+
+    // No http request is expected
+    t.execute_with(|| {
+        BeaconConfig::<Test>::put(beacon.config());
+        let body = serde_json::to_vec(&expected_pulse).unwrap();
+        for path in [
+            format!("/{chain_hash}/public/{ROUND_NUMBER}"),
+            format!("/{chain_hash}/public/latest"),
+        ] {
+            sp_io::offchain::local_storage_set(
+                StorageKind::PERSISTENT,
+                &local_beacon_storage_key(&path),
+                &body,
+            );
+        }
+
+        assert_eq!(
+            Drand::fetch_drand_by_round(ROUND_NUMBER).unwrap(),
+            expected_pulse
+        );
+        assert_eq!(Drand::fetch_drand_latest().unwrap(), expected_pulse);
+    });
+}
@@ -27,6 +27,8 @@ pub type OpaquePublicKey = BoundedVec<u8, ConstU32<96>>;
 pub type BoundedHash = BoundedVec<u8, ConstU32<32>>;
 /// the round number to track rounds of the beacon
 pub type RoundNumber = u64;
+/// the hash identifying a drand chain, as used in the drand api paths
+pub type ChainHash = [u8; 32];
 
 /// the expected response body from the drand api endpoint `api.drand.sh/{chainId}/info`
 #[freeze_struct("f9e09b3273fe00cd")]
@@ -199,10 +201,12 @@ pub struct Pulse {
 
 /// Payload used by to hold the pulse
 /// data required to submit a transaction.
-#[freeze_struct("d56228e0330b6598")]
+#[freeze_struct("52f8e8b42a0e21c6")]
 #[derive(Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub struct PulsesPayload<Public, BlockNumber> {
     pub block_number: BlockNumber,
     pub pulses: Vec<Pulse>,
+    /// pulses of the additional beacons, by chain hash
+    pub beacon_pulses: Vec<(ChainHash, Vec<Pulse>)>,
     pub public: Public,
 }
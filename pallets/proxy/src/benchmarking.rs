@@ -487,5 +487,61 @@ mod benchmarks {
         Ok(())
     }
 
+    #[benchmark]
+    fn set_allowance(a: Linear<0, { T::MaxAllowances::get() - 1 }>) -> Result<(), BenchmarkError> {
+        add_proxies::<T>(1, None)?;
+        let caller: T::AccountId = whitelisted_caller();
+        let delegate: T::AccountId = account("target", 0, SEED);
+        for i in 0..a {
+            Proxy::<T>::set_allowance(
+                RawOrigin::Signed(caller.clone()).into(),
+                T::Lookup::unlookup(delegate.clone()),
+                T::AllowanceFunds::benchmark_kind(i),
+                1u32.into(),
+                1u32.into(),
+            )?;
+        }
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            T::Lookup::unlookup(delegate.clone()),
+            T::AllowanceFunds::benchmark_kind(a),
+            1u32.into(),
+            1u32.into(),
+        );
+
+        assert_eq!(Allowances::<T>::get(caller, delegate).len() as u32, a + 1);
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn remove_allowance(a: Linear<1, { T::MaxAllowances::get() }>) -> Result<(), BenchmarkError> {
+        add_proxies::<T>(1, None)?;
+        let caller: T::AccountId = whitelisted_caller();
+        let delegate: T::AccountId = account("target", 0, SEED);
+        for i in 0..a {
+            Proxy::<T>::set_allowance(
+                RawOrigin::Signed(caller.clone()).into(),
+                T::Lookup::unlookup(delegate.clone()),
+                T::AllowanceFunds::benchmark_kind(i),
+                1u32.into(),
+                1u32.into(),
+            )?;
+        }
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            T::Lookup::unlookup(delegate.clone()),
+            T::AllowanceFunds::benchmark_kind(a - 1),
+        );
+
+        assert_eq!(Allowances::<T>::get(caller, delegate).len() as u32, a - 1);
+
+        Ok(())
+    }
+
     impl_benchmark_test_suite!(Proxy, crate::tests::new_test_ext(), crate::tests::Test);
 }
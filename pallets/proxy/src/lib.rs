@@ -23,6 +23,9 @@
 //! wish to execute some duration prior to execution happens. In this case, the target account may
 //! reject the announcement and in doing so, veto the execution.
 //!
+//! The accounts to which permission is delegated may also be given allowances, limiting the funds
+//! of each kind they can move out of the target account per period.
+//!
 //! - [`Config`]
 //! - [`Call`]
 
@@ -34,8 +37,9 @@ mod tests;
 pub mod weights;
 
 extern crate alloc;
-use alloc::{boxed::Box, vec};
+use alloc::{boxed::Box, vec, vec::Vec};
 use frame::{
+    deps::frame_support::storage::{TransactionOutcome, with_transaction},
     prelude::*,
     traits::{Currency, InstanceFilter, ReservableCurrency},
 };
@@ -91,6 +95,39 @@ pub struct Announcement<AccountId, Hash, BlockNumber> {
     height: BlockNumber,
 }
 
+/// A limit on the funds of some kind a proxy may move out of the account it acts for, per
+/// period.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[freeze_struct("9f116a7d3b01e67b")]
+pub struct ProxyAllowance<Kind, Balance, BlockNumber> {
+    /// The kind of funds that is limited.
+    pub kind: Kind,
+    /// The maximum amount moved in a period.
+    pub limit: Balance,
+    /// The length of a period, in blocks.
+    pub period: BlockNumber,
+    /// The amount moved in the current period.
+    pub spent: Balance,
+    /// The height at which the current period started.
+    pub period_start: BlockNumber,
+}
+
+/// Measures the funds of an account limited by proxy allowances. The funds moved by a call
+/// dispatched through a proxy are the decrease of the measured funds over the dispatch, so any
+/// call is charged, whatever the pallet it belongs to.
+pub trait AllowanceFunds<AccountId, Kind, Balance> {
+    /// Returns the funds of `kind` held by `who`, or `None` if they cannot be measured within
+    /// `weight`. Calls through a proxy limited on funds that cannot be measured fail.
+    fn funds(who: &AccountId, kind: &Kind) -> Option<Balance>;
+
+    /// The maximum weight of measuring the funds of one kind.
+    fn weight() -> Weight;
+
+    /// Returns distinct kinds of funds for the benchmarks.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn benchmark_kind(i: u32) -> Kind;
+}
+
 /// The type of deposit
 #[derive(
     Encode,
@@ -213,6 +250,16 @@ pub mod pallet {
         ///     providers can be used. Relay provider can be a bit better in cases where the
         ///     parachain is lagging its block production to avoid clock skew.
         type BlockNumberProvider: BlockNumberProvider;
+
+        /// The kinds of funds a proxy allowance can limit.
+        type AllowanceKind: Parameter + Member + Ord + MaxEncodedLen;
+
+        /// Measures the funds limited by the allowances of a proxy.
+        type AllowanceFunds: AllowanceFunds<Self::AccountId, Self::AllowanceKind, BalanceOf<Self>>;
+
+        /// The maximum amount of allowances set on a single proxy.
+        #[pallet::constant]
+        type MaxAllowances: Get<u32>;
     }
 
     #[pallet::call]
@@ -232,6 +279,7 @@ pub mod pallet {
 			(T::WeightInfo::proxy(T::MaxProxies::get())
 				 // AccountData for inner call origin accountdata.
 				.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+				.saturating_add(Self::allowances_weight(T::MaxAllowances::get()))
 				.saturating_add(di.call_weight),
 			di.class, di.pays_fee)
 		})]
@@ -240,15 +288,22 @@ pub mod pallet {
             real: AccountIdLookupOf<T>,
             force_proxy_type: Option<T::ProxyType>,
             call: Box<<T as Config>::RuntimeCall>,
-        ) -> DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
             let real = T::Lookup::lookup(real)?;
             let def = Self::find_proxy(&real, &who, force_proxy_type)?;
             ensure!(def.delay.is_zero(), Error::<T>::Unannounced);
 
-            Self::do_proxy(def, real, *call);
+            let di = call.get_dispatch_info();
+            let allowances = Self::do_proxy(def, real, *call);
 
-            Ok(())
+            Ok(Some(
+                T::WeightInfo::proxy(T::MaxProxies::get())
+                    .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+                    .saturating_add(Self::allowances_weight(allowances))
+                    .saturating_add(di.call_weight),
+            )
+            .into())
         }
 
         /// Register a proxy account for the sender that is able to make calls on its behalf.
@@ -396,6 +451,7 @@ pub mod pallet {
 
             let (_, deposit) = Proxies::<T>::take(&who);
             T::Currency::unreserve(&spawner, deposit);
+            let _ = Allowances::<T>::clear_prefix(&who, u32::MAX, None);
 
             Self::deposit_event(Event::PureKilled {
                 pure: who,
@@ -541,6 +597,7 @@ pub mod pallet {
 			(T::WeightInfo::proxy_announced(T::MaxPending::get(), T::MaxProxies::get())
 				 // AccountData for inner call origin accountdata.
 				.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+				.saturating_add(Self::allowances_weight(T::MaxAllowances::get()))
 				.saturating_add(di.call_weight),
 			di.class)
 		})]
@@ -550,7 +607,7 @@ pub mod pallet {
             real: AccountIdLookupOf<T>,
             force_proxy_type: Option<T::ProxyType>,
             call: Box<<T as Config>::RuntimeCall>,
-        ) -> DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             ensure_signed(origin)?;
             let delegate = T::Lookup::lookup(delegate)?;
             let real = T::Lookup::lookup(real)?;
@@ -565,9 +622,16 @@ pub mod pallet {
             })
             .map_err(|_| Error::<T>::Unannounced)?;
 
-            Self::do_proxy(def, real, *call);
+            let di = call.get_dispatch_info();
+            let allowances = Self::do_proxy(def, real, *call);
 
-            Ok(())
+            Ok(Some(
+                T::WeightInfo::proxy_announced(T::MaxPending::get(), T::MaxProxies::get())
+                    .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+                    .saturating_add(Self::allowances_weight(allowances))
+                    .saturating_add(di.call_weight),
+            )
+            .into())
         }
 
         /// Poke / Adjust deposits made for proxies and announcements based on current values.
@@ -672,6 +736,101 @@ pub mod pallet {
                 Pays::Yes.into()
             })
         }
+
+        /// Limit the funds of some kind a proxy of the sender may move per period.
+        ///
+        /// The allowance applies to all the proxy types of the proxy account. Replacing an
+        /// allowance keeps the amount already moved in the current period.
+        ///
+        /// The dispatch origin for this call must be _Signed_. A proxy can only set allowances
+        /// if it has full permissions and is not limited by allowances itself.
+        ///
+        /// Parameters:
+        /// - `delegate`: The proxy account to limit.
+        /// - `kind`: The kind of funds to limit.
+        /// - `limit`: The maximum amount moved in a period.
+        /// - `period`: The length of a period, in blocks. Must not be zero.
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::set_allowance(T::MaxAllowances::get()))]
+        pub fn set_allowance(
+            origin: OriginFor<T>,
+            delegate: AccountIdLookupOf<T>,
+            kind: T::AllowanceKind,
+            limit: BalanceOf<T>,
+            period: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let delegate = T::Lookup::lookup(delegate)?;
+            ensure!(!period.is_zero(), Error::<T>::ZeroPeriod);
+            Self::find_proxy(&who, &delegate, None)?;
+
+            Allowances::<T>::try_mutate(&who, &delegate, |allowances| -> DispatchResult {
+                match allowances.iter_mut().find(|a| a.kind == kind) {
+                    Some(allowance) => {
+                        allowance.limit = limit;
+                        allowance.period = period;
+                    }
+                    None => allowances
+                        .try_push(ProxyAllowance {
+                            kind: kind.clone(),
+                            limit,
+                            period,
+                            spent: Zero::zero(),
+                            period_start: T::BlockNumberProvider::current_block_number(),
+                        })
+                        .map_err(|_| Error::<T>::TooMany)?,
+                }
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::AllowanceSet {
+                delegator: who,
+                delegatee: delegate,
+                kind,
+                limit,
+                period,
+            });
+
+            Ok(())
+        }
+
+        /// Remove the limit on the funds of some kind a proxy of the sender may move.
+        ///
+        /// The dispatch origin for this call must be _Signed_. A proxy can only remove allowances
+        /// if it has full permissions and is not limited by allowances itself.
+        ///
+        /// Parameters:
+        /// - `delegate`: The proxy account.
+        /// - `kind`: The kind of funds no longer limited.
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::WeightInfo::remove_allowance(T::MaxAllowances::get()))]
+        pub fn remove_allowance(
+            origin: OriginFor<T>,
+            delegate: AccountIdLookupOf<T>,
+            kind: T::AllowanceKind,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let delegate = T::Lookup::lookup(delegate)?;
+
+            Allowances::<T>::try_mutate_exists(&who, &delegate, |x| -> DispatchResult {
+                let mut allowances = x.take().ok_or(Error::<T>::NotFound)?;
+                let len = allowances.len();
+                allowances.retain(|a| a.kind != kind);
+                ensure!(allowances.len() < len, Error::<T>::NotFound);
+                if !allowances.is_empty() {
+                    *x = Some(allowances);
+                }
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::AllowanceRemoved {
+                delegator: who,
+                delegatee: delegate,
+                kind,
+            });
+
+            Ok(())
+        }
     }
 
     #[pallet::event]
@@ -725,6 +884,20 @@ pub mod pallet {
             old_deposit: BalanceOf<T>,
             new_deposit: BalanceOf<T>,
         },
+        /// An allowance of a proxy was set.
+        AllowanceSet {
+            delegator: T::AccountId,
+            delegatee: T::AccountId,
+            kind: T::AllowanceKind,
+            limit: BalanceOf<T>,
+            period: BlockNumberFor<T>,
+        },
+        /// An allowance of a proxy was removed.
+        AllowanceRemoved {
+            delegator: T::AccountId,
+            delegatee: T::AccountId,
+            kind: T::AllowanceKind,
+        },
     }
 
     #[pallet::error]
@@ -745,6 +918,12 @@ pub mod pallet {
         Unannounced,
         /// Cannot add self as proxy.
         NoSelfProxy,
+        /// The call moves more funds than the allowances of the proxy have left.
+        AllowanceExceeded,
+        /// The funds limited by an allowance of the proxy cannot be measured.
+        UnmeasurableFunds,
+        /// The period of an allowance cannot be zero.
+        ZeroPeriod,
     }
 
     /// The set of account proxies. Maps the account which has delegated to the accounts
@@ -777,6 +956,22 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// The allowances limiting the funds the proxy (second key) may move out of the account
+    /// which has delegated to it (first key).
+    #[pallet::storage]
+    pub type Allowances<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        T::AccountId,
+        BoundedVec<
+            ProxyAllowance<T::AllowanceKind, BalanceOf<T>, BlockNumberFor<T>>,
+            T::MaxAllowances,
+        >,
+        ValueQuery,
+    >;
+
     #[pallet::view_functions_experimental]
     impl<T: Config> Pallet<T> {
         /// Check if a `RuntimeCall` is allowed for a given `ProxyType`.
@@ -922,6 +1117,9 @@ impl<T: Config> Pallet<T> {
                 .ok()
                 .ok_or(Error::<T>::NotFound)?;
             proxies.remove(i);
+            if !proxies.iter().any(|p| p.delegate == delegatee) {
+                Allowances::<T>::remove(delegator, &delegatee);
+            }
             let new_deposit = Self::deposit(proxies.len() as u32);
             if new_deposit > old_deposit {
                 T::Currency::reserve(delegator, new_deposit.saturating_sub(old_deposit))?;
@@ -1015,18 +1213,36 @@ impl<T: Config> Pallet<T> {
             .ok_or(Error::<T>::NotProxy)?)
     }
 
+    /// Dispatches `call` from `real` through the proxy `def`, charging the funds it moves to the
+    /// allowances of the proxy. Returns the amount of allowances charged.
     fn do_proxy(
         def: ProxyDefinition<T::AccountId, T::ProxyType, BlockNumberFor<T>>,
         real: T::AccountId,
         call: <T as Config>::RuntimeCall,
-    ) {
+    ) -> u32 {
         use frame::traits::{InstanceFilter as _, OriginTrait as _};
+        let delegate = def.delegate.clone();
+        let allowances = Allowances::<T>::get(&real, &delegate);
+        let funds: Vec<Option<BalanceOf<T>>> = allowances
+            .iter()
+            .map(|a| T::AllowanceFunds::funds(&real, &a.kind))
+            .collect();
+
         // This is a freshly authenticated new account, the origin restrictions doesn't apply.
-        let mut origin: T::RuntimeOrigin = frame_system::RawOrigin::Signed(real).into();
+        let mut origin: T::RuntimeOrigin = frame_system::RawOrigin::Signed(real.clone()).into();
+        let limited = !allowances.is_empty();
         origin.add_filter(move |c: &<T as frame_system::Config>::RuntimeCall| {
             let c = <T as Config>::RuntimeCall::from_ref(c);
             // We make sure the proxy call does access this pallet to change modify proxies.
             match c.is_sub_type() {
+                // Proxy call cannot change allowances unless it has full permissions, nor escape
+                // its own allowances by adding a proxy.
+                Some(Call::set_allowance { .. }) | Some(Call::remove_allowance { .. })
+                    if limited || def.proxy_type != T::ProxyType::default() =>
+                {
+                    false
+                }
+                Some(Call::add_proxy { .. }) if limited => false,
                 // Proxy call cannot add or remove a proxy with more permissions than it already
                 // has.
                 Some(Call::add_proxy { proxy_type, .. })
@@ -1045,10 +1261,70 @@ impl<T: Config> Pallet<T> {
                 _ => def.proxy_type.filter(c),
             }
         });
-        let e = call.dispatch(origin);
+        let measured = allowances.len() as u32;
+        // The call is reverted if it fails or moves more funds than the allowances have left.
+        let e = with_transaction(|| {
+            let e = call.dispatch(origin);
+            if e.is_err() {
+                return TransactionOutcome::Rollback(Ok(e));
+            }
+            match Self::charge_allowances(&real, &delegate, allowances, funds) {
+                Ok(()) => TransactionOutcome::Commit(Ok(e)),
+                Err(err) => TransactionOutcome::Rollback(Ok(Err(err.into()))),
+            }
+        })
+        .unwrap_or_else(|e: DispatchError| Err(e.into()));
         Self::deposit_event(Event::ProxyExecuted {
             result: e.map(|_| ()).map_err(|e| e.error),
         });
+        measured
+    }
+
+    /// Charges the funds moved out of `real` since they were measured as `funds_before` to the
+    /// allowances of `delegate`. Kinds of funds without an allowance are not limited.
+    fn charge_allowances(
+        real: &T::AccountId,
+        delegate: &T::AccountId,
+        mut allowances: BoundedVec<
+            ProxyAllowance<T::AllowanceKind, BalanceOf<T>, BlockNumberFor<T>>,
+            T::MaxAllowances,
+        >,
+        funds_before: Vec<Option<BalanceOf<T>>>,
+    ) -> DispatchResult {
+        if allowances.is_empty() {
+            return Ok(());
+        }
+
+        let now = T::BlockNumberProvider::current_block_number();
+        for (allowance, before) in allowances.iter_mut().zip(funds_before) {
+            let before = before.ok_or(Error::<T>::UnmeasurableFunds)?;
+            let after = T::AllowanceFunds::funds(real, &allowance.kind)
+                .ok_or(Error::<T>::UnmeasurableFunds)?;
+            let moved = before.saturating_sub(after);
+            if moved.is_zero() {
+                continue;
+            }
+            if now.saturating_sub(allowance.period_start) >= allowance.period {
+                allowance.period_start = now;
+                allowance.spent = Zero::zero();
+            }
+            let spent = allowance.spent.saturating_add(moved);
+            ensure!(spent <= allowance.limit, Error::<T>::AllowanceExceeded);
+            allowance.spent = spent;
+        }
+
+        // The call may have removed the proxy, and its allowances with it.
+        if Allowances::<T>::contains_key(real, delegate) {
+            Allowances::<T>::insert(real, delegate, allowances);
+        }
+        Ok(())
+    }
+
+    /// The weight of measuring and charging `allowances` allowances of a proxy.
+    fn allowances_weight(allowances: u32) -> Weight {
+        T::DbWeight::get().reads_writes(1, 1).saturating_add(
+            T::AllowanceFunds::weight().saturating_mul(u64::from(allowances).saturating_mul(2)),
+        )
     }
 
     /// Removes all proxy delegates for a given delegator.
@@ -1058,5 +1334,6 @@ impl<T: Config> Pallet<T> {
     pub fn remove_all_proxy_delegates(delegator: &T::AccountId) {
         let (_, old_deposit) = Proxies::<T>::take(delegator);
         T::Currency::unreserve(delegator, old_deposit);
+        let _ = Allowances::<T>::clear_prefix(delegator, u32::MAX, None);
     }
 }
@@ -58,6 +58,13 @@ impl pallet_utility::Config for Test {
     type RuntimeCall = RuntimeCall;
     type PalletsOrigin = OriginCaller;
     type WeightInfo = ();
+    type Currency = Balances;
+    type Condition = u32;
+    type ConditionChecker = ();
+    type ScheduledBatchDepositBase = ScheduledBatchDepositBase;
+    type ScheduledBatchDepositFactor = ScheduledBatchDepositFactor;
+    type MaxScheduledBatchesPerAccount = ConstU32<2>;
+    type MaxScheduleHorizon = ConstU64<100>;
 }
 
 #[derive(
@@ -101,6 +108,46 @@ impl frame::traits::InstanceFilter<RuntimeCall> for ProxyType {
         self == &ProxyType::Any || self == o
     }
 }
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    RuntimeDebug,
+    MaxEncodedLen,
+    scale_info::TypeInfo,
+)]
+pub enum AllowanceKind {
+    Balance,
+    Other,
+}
+pub struct TestAllowanceFunds;
+impl AllowanceFunds<u64, AllowanceKind, u64> for TestAllowanceFunds {
+    fn funds(who: &u64, kind: &AllowanceKind) -> Option<u64> {
+        match kind {
+            AllowanceKind::Balance => Some(Balances::free_balance(who)),
+            AllowanceKind::Other => None,
+        }
+    }
+
+    fn weight() -> Weight {
+        Weight::zero()
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn benchmark_kind(i: u32) -> AllowanceKind {
+        if i == 0 {
+            AllowanceKind::Balance
+        } else {
+            AllowanceKind::Other
+        }
+    }
+}
 pub struct BaseFilter;
 impl Contains<RuntimeCall> for BaseFilter {
     fn contains(c: &RuntimeCall) -> bool {
@@ -118,6 +165,8 @@ parameter_types! {
     pub static ProxyDepositFactor: u64 = 1;
     pub static AnnouncementDepositBase: u64 = 1;
     pub static AnnouncementDepositFactor: u64 = 1;
+    pub static ScheduledBatchDepositBase: u64 = 1;
+    pub static ScheduledBatchDepositFactor: u64 = 1;
 }
 
 impl Config for Test {
@@ -134,6 +183,9 @@ impl Config for Test {
     type AnnouncementDepositBase = AnnouncementDepositBase;
     type AnnouncementDepositFactor = AnnouncementDepositFactor;
     type BlockNumberProvider = frame_system::Pallet<Test>;
+    type AllowanceKind = AllowanceKind;
+    type AllowanceFunds = TestAllowanceFunds;
+    type MaxAllowances = ConstU32<2>;
 }
 
 use super::{Call as ProxyCall, Event as ProxyEvent};
@@ -1253,3 +1305,249 @@ fn poke_deposit_fails_for_unsigned_origin() {
         );
     });
 }
+
+#[test]
+fn allowances_limit_proxied_calls() {
+    new_test_ext().execute_with(|| {
+        Balances::make_free_balance_be(&1, 1000);
+        assert_ok!(Proxy::add_proxy(
+            RuntimeOrigin::signed(1),
+            2,
+            ProxyType::Any,
+            0
+        ));
+        assert_ok!(Proxy::add_proxy(
+            RuntimeOrigin::signed(1),
+            3,
+            ProxyType::JustTransfer,
+            0
+        ));
+
+        assert_noop!(
+            Proxy::set_allowance(RuntimeOrigin::signed(1), 2, AllowanceKind::Balance, 5, 0),
+            Error::<Test>::ZeroPeriod
+        );
+        assert_noop!(
+            Proxy::set_allowance(RuntimeOrigin::signed(1), 4, AllowanceKind::Balance, 5, 10),
+            Error::<Test>::NotProxy
+        );
+        assert_ok!(Proxy::set_allowance(
+            RuntimeOrigin::signed(1),
+            2,
+            AllowanceKind::Balance,
+            5,
+            10
+        ));
+        System::assert_last_event(
+            ProxyEvent::AllowanceSet {
+                delegator: 1,
+                delegatee: 2,
+                kind: AllowanceKind::Balance,
+                limit: 5,
+                period: 10,
+            }
+            .into(),
+        );
+
+        assert_ok!(Proxy::proxy(
+            RuntimeOrigin::signed(2),
+            1,
+            None,
+            Box::new(call_transfer(6, 3))
+        ));
+        assert_eq!(Balances::free_balance(6), 3);
+        let exceeded = ProxyEvent::ProxyExecuted {
+            result: Err(Error::<Test>::AllowanceExceeded.into()),
+        };
+        assert_ok!(Proxy::proxy(
+            RuntimeOrigin::signed(2),
+            1,
+            None,
+            Box::new(call_transfer(6, 3))
+        ));
+        System::assert_last_event(exceeded.clone().into());
+        assert_eq!(Balances::free_balance(6), 3);
+
+        // The funds moved by any call are measured, including the batched ones
+        let transfer_all = RuntimeCall::Balances(BalancesCall::transfer_all {
+            dest: 6,
+            keep_alive: true,
+        });
+        let batch = |calls| Box::new(RuntimeCall::Utility(UtilityCall::batch { calls }));
+        assert_ok!(Proxy::proxy(
+            RuntimeOrigin::signed(2),
+            1,
+            None,
+            batch(vec![transfer_all])
+        ));
+        System::assert_last_event(exceeded.clone().into());
+        assert_ok!(Proxy::proxy(
+            RuntimeOrigin::signed(2),
+            1,
+            None,
+            batch(vec![call_transfer(6, 2), call_transfer(6, 1)])
+        ));
+        System::assert_last_event(exceeded.into());
+        assert_eq!(Balances::free_balance(6), 3);
+        assert_ok!(Proxy::proxy(
+            RuntimeOrigin::signed(2),
+            1,
+            None,
+            batch(vec![call_transfer(6, 1), call_transfer(6, 1)])
+        ));
+        System::assert_last_event(ProxyEvent::ProxyExecuted { result: Ok(()) }.into());
+        assert_eq!(Balances::free_balance(6), 5);
+
+        assert_eq!(Allowances::<Test>::get(1, 2)[0].spent, 5);
+
+        // Other proxies are not limited
+        assert_ok!(Proxy::proxy(
+            RuntimeOrigin::signed(3),
+            1,
+            None,
+            Box::new(call_transfer(6, 10))
+        ));
+        assert_eq!(Balances::free_balance(6), 15);
+
+        // The allowance is renewed every period
+        System::set_block_number(11);
+        assert_ok!(Proxy::proxy(
+            RuntimeOrigin::signed(2),
+            1,
+            None,
+            Box::new(call_transfer(6, 5))
+        ));
+        assert_eq!(Balances::free_balance(6), 20);
+
+        // Failed calls don't use the allowance
+        assert_ok!(Proxy::set_allowance(
+            RuntimeOrigin::signed(1),
+            2,
+            AllowanceKind::Balance,
+            5000,
+            10
+        ));
+        System::set_block_number(21);
+        let allowances = Allowances::<Test>::get(1, 2);
+        assert_ok!(Proxy::proxy(
+            RuntimeOrigin::signed(2),
+            1,
+            None,
+            Box::new(call_transfer(6, 2000))
+        ));
+        assert!(matches!(
+            last_events(1)[0],
+            RuntimeEvent::Proxy(ProxyEvent::ProxyExecuted { result: Err(_) })
+        ));
+        assert_eq!(Allowances::<Test>::get(1, 2), allowances);
+
+        // Calls fail if the limited funds cannot be measured
+        assert_ok!(Proxy::set_allowance(
+            RuntimeOrigin::signed(1),
+            2,
+            AllowanceKind::Other,
+            5,
+            10
+        ));
+        assert_ok!(Proxy::proxy(
+            RuntimeOrigin::signed(2),
+            1,
+            None,
+            Box::new(call_transfer(6, 1))
+        ));
+        System::assert_last_event(
+            ProxyEvent::ProxyExecuted {
+                result: Err(Error::<Test>::UnmeasurableFunds.into()),
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(6), 20);
+    });
+}
+
+#[test]
+fn allowances_cannot_be_escaped() {
+    new_test_ext().execute_with(|| {
+        Balances::make_free_balance_be(&1, 1000);
+        assert_ok!(Proxy::add_proxy(
+            RuntimeOrigin::signed(1),
+            2,
+            ProxyType::Any,
+            0
+        ));
+        assert_ok!(Proxy::add_proxy(
+            RuntimeOrigin::signed(1),
+            3,
+            ProxyType::Any,
+            0
+        ));
+        assert_ok!(Proxy::set_allowance(
+            RuntimeOrigin::signed(1),
+            2,
+            AllowanceKind::Balance,
+            5,
+            10
+        ));
+
+        let filtered = ProxyEvent::ProxyExecuted {
+            result: Err(SystemError::CallFiltered.into()),
+        };
+        for call in [
+            ProxyCall::set_allowance {
+                delegate: 2,
+                kind: AllowanceKind::Balance,
+                limit: 1000,
+                period: 10,
+            },
+            ProxyCall::remove_allowance {
+                delegate: 2,
+                kind: AllowanceKind::Balance,
+            },
+            ProxyCall::add_proxy {
+                delegate: 4,
+                proxy_type: ProxyType::Any,
+                delay: 0,
+            },
+        ] {
+            assert_ok!(Proxy::proxy(
+                RuntimeOrigin::signed(2),
+                1,
+                None,
+                Box::new(RuntimeCall::Proxy(call))
+            ));
+            System::assert_last_event(filtered.clone().into());
+        }
+
+        // A proxy with full permissions and no allowance can remove it
+        assert_ok!(Proxy::proxy(
+            RuntimeOrigin::signed(3),
+            1,
+            None,
+            Box::new(RuntimeCall::Proxy(ProxyCall::remove_allowance {
+                delegate: 2,
+                kind: AllowanceKind::Balance,
+            }))
+        ));
+        assert!(!Allowances::<Test>::contains_key(1, 2));
+        assert_noop!(
+            Proxy::remove_allowance(RuntimeOrigin::signed(1), 2, AllowanceKind::Balance),
+            Error::<Test>::NotFound
+        );
+
+        // Allowances are removed with the proxy
+        assert_ok!(Proxy::set_allowance(
+            RuntimeOrigin::signed(1),
+            2,
+            AllowanceKind::Other,
+            5,
+            10
+        ));
+        assert_ok!(Proxy::remove_proxy(
+            RuntimeOrigin::signed(1),
+            2,
+            ProxyType::Any,
+            0
+        ));
+        assert!(!Allowances::<Test>::contains_key(1, 2));
+    });
+}
@@ -65,6 +65,8 @@ pub trait WeightInfo {
 	fn create_pure(p: u32, ) -> Weight;
 	fn kill_pure(p: u32, ) -> Weight;
 	fn poke_deposit() -> Weight;
+	fn set_allowance(a: u32, ) -> Weight;
+	fn remove_allowance(a: u32, ) -> Weight;
 }
 
 /// Weights for `pallet_proxy` using the Substrate node and recommended hardware.
@@ -256,6 +258,25 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(3_u64))
 			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
+	/// Storage: `Proxy::Proxies` (r:1 w:0)
+	/// Storage: `Proxy::Allowances` (r:1 w:1)
+	/// The range of component `a` is `[0, 3]`.
+	fn set_allowance(a: u32, ) -> Weight {
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 4706)
+			.saturating_add(Weight::from_parts(150_000, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Proxy::Allowances` (r:1 w:1)
+	/// The range of component `a` is `[1, 4]`.
+	fn remove_allowance(a: u32, ) -> Weight {
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(15_000_000, 3538)
+			.saturating_add(Weight::from_parts(120_000, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -446,4 +467,23 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3_u64))
 			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
+	/// Storage: `Proxy::Proxies` (r:1 w:0)
+	/// Storage: `Proxy::Allowances` (r:1 w:1)
+	/// The range of component `a` is `[0, 3]`.
+	fn set_allowance(a: u32, ) -> Weight {
+		// Minimum execution time: 19_000_000 picoseconds.
+		Weight::from_parts(20_000_000, 4706)
+			.saturating_add(Weight::from_parts(150_000, 0).saturating_mul(a.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Proxy::Allowances` (r:1 w:1)
+	/// The range of component `a` is `[1, 4]`.
+	fn remove_allowance(a: u32, ) -> Weight {
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(15_000_000, 3538)
+			.saturating_add(Weight::from_parts(120_000, 0).saturating_mul(a.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
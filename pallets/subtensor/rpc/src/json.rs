@@ -0,0 +1,71 @@
+//! JSON rendering of the `rpc_info` structs.
+//!
+//! The structs derive `Serialize`, so the JSON follows them as they are defined in the runtime:
+//!
+//! * `AccountId32` is rendered in SS58.
+//! * `AlphaCurrency`, `TaoCurrency` and `Compact` values are rendered as their inner number.
+//! * Fixed point numbers are rendered as decimal strings.
+//! * Struct fields become object keys and `Option` becomes `null` or the inner value.
+
+use serde::Serialize;
+use serde_json::{Value, json};
+
+/// Version of the JSON responses, bumped when the rendering rules change.
+pub const JSON_RESPONSE_VERSION: u32 = 1;
+
+/// Renders a value as a versioned JSON response: `{ "version": .., "data": .. }`.
+pub fn to_json<T: Serialize>(value: &T) -> Result<Value, String> {
+    let data = serde_json::to_value(value).map_err(|e| e.to_string())?;
+
+    Ok(json!({
+        "version": JSON_RESPONSE_VERSION,
+        "data": data,
+    }))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use codec::Compact;
+    use sp_runtime::AccountId32;
+    use subtensor_runtime_common::{AlphaCurrency, NetUid, TaoCurrency};
+
+    #[derive(Serialize)]
+    struct Info {
+        hotkey: AccountId32,
+        netuid: Compact<NetUid>,
+        stake: Compact<AlphaCurrency>,
+        tao: TaoCurrency,
+        weights: Vec<(u16, u16)>,
+        parent: Option<u16>,
+    }
+
+    #[test]
+    fn test_to_json() {
+        let hotkey = AccountId32::new([1; 32]);
+        let info = Info {
+            hotkey: hotkey.clone(),
+            netuid: NetUid::from(3).into(),
+            stake: AlphaCurrency::from(7).into(),
+            tao: TaoCurrency::from(5),
+            weights: vec![(1, 2)],
+            parent: None,
+        };
+
+        assert_eq!(
+            to_json(&vec![info]).unwrap(),
+            json!({
+                "version": JSON_RESPONSE_VERSION,
+                "data": [{
+                    "hotkey": serde_json::to_value(&hotkey).unwrap(),
+                    "netuid": 3,
+                    "stake": 7,
+                    "tao": 5,
+                    "weights": [[1, 2]],
+                    "parent": null,
+                }],
+            })
+        );
+    }
+}
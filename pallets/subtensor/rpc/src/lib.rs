@@ -6,12 +6,16 @@ use jsonrpsee::{
     proc_macros::rpc,
     types::{ErrorObjectOwned, error::ErrorObject},
 };
+use serde::Serialize;
+use serde_json::Value;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::{AccountId32, traits::Block as BlockT};
 use std::sync::Arc;
 use subtensor_runtime_common::{MechId, NetUid, TaoCurrency};
 
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiError, ProvideRuntimeApi};
+
+pub mod json;
 
 pub use subtensor_custom_rpc_runtime_api::{
     DelegateInfoRuntimeApi, NeuronInfoRuntimeApi, SubnetInfoRuntimeApi,
@@ -102,6 +106,107 @@ pub trait SubtensorCustomApi<BlockHash> {
     ) -> RpcResult<Vec<u8>>;
     #[method(name = "subnetInfo_getSubnetToPrune")]
     fn get_subnet_to_prune(&self, at: Option<BlockHash>) -> RpcResult<Option<NetUid>>;
+    #[method(name = "subnetInfo_getEpochDebug")]
+    fn get_epoch_debug(
+        &self,
+        netuid: NetUid,
+        mecid: MechId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<u8>>;
+
+    // JSON variants of the methods above, returning versioned JSON instead of SCALE bytes.
+    #[method(name = "delegateInfo_getDelegatesJson")]
+    fn get_delegates_json(&self, at: Option<BlockHash>) -> RpcResult<Value>;
+    #[method(name = "delegateInfo_getDelegateJson")]
+    fn get_delegate_json(
+        &self,
+        delegate_account_vec: Vec<u8>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Value>;
+    #[method(name = "delegateInfo_getDelegatedJson")]
+    fn get_delegated_json(
+        &self,
+        delegatee_account_vec: Vec<u8>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Value>;
+    #[method(name = "neuronInfo_getNeuronsLiteJson")]
+    fn get_neurons_lite_json(&self, netuid: NetUid, at: Option<BlockHash>) -> RpcResult<Value>;
+    #[method(name = "neuronInfo_getNeuronLiteJson")]
+    fn get_neuron_lite_json(
+        &self,
+        netuid: NetUid,
+        uid: u16,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Value>;
+    #[method(name = "neuronInfo_getNeuronsJson")]
+    fn get_neurons_json(&self, netuid: NetUid, at: Option<BlockHash>) -> RpcResult<Value>;
+    #[method(name = "neuronInfo_getNeuronJson")]
+    fn get_neuron_json(&self, netuid: NetUid, uid: u16, at: Option<BlockHash>) -> RpcResult<Value>;
+    #[method(name = "subnetInfo_getSubnetInfoJson")]
+    fn get_subnet_info_json(&self, netuid: NetUid, at: Option<BlockHash>) -> RpcResult<Value>;
+    #[method(name = "subnetInfo_getSubnetsInfoJson")]
+    fn get_subnets_info_json(&self, at: Option<BlockHash>) -> RpcResult<Value>;
+    #[method(name = "subnetInfo_getSubnetInfo_v2Json")]
+    fn get_subnet_info_v2_json(&self, netuid: NetUid, at: Option<BlockHash>) -> RpcResult<Value>;
+    #[method(name = "subnetInfo_getSubnetsInfo_v2Json")]
+    fn get_subnets_info_v2_json(&self, at: Option<BlockHash>) -> RpcResult<Value>;
+    #[method(name = "subnetInfo_getSubnetHyperparamsJson")]
+    fn get_subnet_hyperparams_json(
+        &self,
+        netuid: NetUid,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Value>;
+    #[method(name = "subnetInfo_getSubnetHyperparamsV2Json")]
+    fn get_subnet_hyperparams_v2_json(
+        &self,
+        netuid: NetUid,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Value>;
+    #[method(name = "subnetInfo_getAllDynamicInfoJson")]
+    fn get_all_dynamic_info_json(&self, at: Option<BlockHash>) -> RpcResult<Value>;
+    #[method(name = "subnetInfo_getDynamicInfoJson")]
+    fn get_dynamic_info_json(&self, netuid: NetUid, at: Option<BlockHash>) -> RpcResult<Value>;
+    #[method(name = "subnetInfo_getAllMetagraphsJson")]
+    fn get_all_metagraphs_json(&self, at: Option<BlockHash>) -> RpcResult<Value>;
+    #[method(name = "subnetInfo_getMetagraphJson")]
+    fn get_metagraph_json(&self, netuid: NetUid, at: Option<BlockHash>) -> RpcResult<Value>;
+    #[method(name = "subnetInfo_getAllMechagraphsJson")]
+    fn get_all_mechagraphs_json(&self, at: Option<BlockHash>) -> RpcResult<Value>;
+    #[method(name = "subnetInfo_getMechagraphJson")]
+    fn get_mechagraph_json(
+        &self,
+        netuid: NetUid,
+        mecid: MechId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Value>;
+    #[method(name = "subnetInfo_getSubnetStateJson")]
+    fn get_subnet_state_json(&self, netuid: NetUid, at: Option<BlockHash>) -> RpcResult<Value>;
+    #[method(name = "subnetInfo_getLockCostJson")]
+    fn get_network_lock_cost_json(&self, at: Option<BlockHash>) -> RpcResult<Value>;
+    #[method(name = "subnetInfo_getSelectiveMetagraphJson")]
+    fn get_selective_metagraph_json(
+        &self,
+        netuid: NetUid,
+        metagraph_index: Vec<u16>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Value>;
+    #[method(name = "subnetInfo_getSelectiveMechagraphJson")]
+    fn get_selective_mechagraph_json(
+        &self,
+        netuid: NetUid,
+        mecid: MechId,
+        metagraph_index: Vec<u16>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Value>;
+    #[method(name = "subnetInfo_getSubnetToPruneJson")]
+    fn get_subnet_to_prune_json(&self, at: Option<BlockHash>) -> RpcResult<Value>;
+    #[method(name = "subnetInfo_getEpochDebugJson")]
+    fn get_epoch_debug_json(
+        &self,
+        netuid: NetUid,
+        mecid: MechId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Value>;
 }
 
 pub struct SubtensorCustom<C, P> {
@@ -142,6 +247,12 @@ impl From<Error> for i32 {
     }
 }
 
+/// Renders the result of a runtime API call as a versioned JSON response.
+fn to_json_response<T: Serialize>(result: Result<T, ApiError>, context: &str) -> RpcResult<Value> {
+    let value = result.map_err(|e| Error::RuntimeError(format!("{context}: {e:?}")))?;
+    json::to_json(&value).map_err(|e| Error::RuntimeError(format!("{context}: {e}")).into())
+}
+
 impl<C, Block> SubtensorCustomApiServer<<Block as BlockT>::Hash> for SubtensorCustom<C, Block>
 where
     Block: BlockT,
@@ -506,4 +617,318 @@ where
             }
         }
     }
+
+    fn get_epoch_debug(
+        &self,
+        netuid: NetUid,
+        mecid: MechId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<u8>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        match api.get_epoch_debug(at, netuid, mecid) {
+            Ok(result) => Ok(result.encode()),
+            Err(e) => Err(Error::RuntimeError(format!("Unable to get epoch debug: {e:?}")).into()),
+        }
+    }
+
+    fn get_delegates_json(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(api.get_delegates(at), "Unable to get delegates info")
+    }
+
+    fn get_delegate_json(
+        &self,
+        delegate_account_vec: Vec<u8>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        let delegate_account = AccountId32::decode(&mut &delegate_account_vec[..])
+            .map_err(|e| Error::RuntimeError(format!("Unable to get delegates info: {e:?}")))?;
+        to_json_response(
+            api.get_delegate(at, delegate_account),
+            "Unable to get delegates info",
+        )
+    }
+
+    fn get_delegated_json(
+        &self,
+        delegatee_account_vec: Vec<u8>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        let delegatee_account = AccountId32::decode(&mut &delegatee_account_vec[..])
+            .map_err(|e| Error::RuntimeError(format!("Unable to get delegates info: {e:?}")))?;
+        to_json_response(
+            api.get_delegated(at, delegatee_account),
+            "Unable to get delegates info",
+        )
+    }
+
+    fn get_neurons_lite_json(
+        &self,
+        netuid: NetUid,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(
+            api.get_neurons_lite(at, netuid),
+            "Unable to get neurons lite info",
+        )
+    }
+
+    fn get_neuron_lite_json(
+        &self,
+        netuid: NetUid,
+        uid: u16,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(
+            api.get_neuron_lite(at, netuid, uid),
+            "Unable to get neurons lite info",
+        )
+    }
+
+    fn get_neurons_json(
+        &self,
+        netuid: NetUid,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(api.get_neurons(at, netuid), "Unable to get neurons info")
+    }
+
+    fn get_neuron_json(
+        &self,
+        netuid: NetUid,
+        uid: u16,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(api.get_neuron(at, netuid, uid), "Unable to get neuron info")
+    }
+
+    fn get_subnet_info_json(
+        &self,
+        netuid: NetUid,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(api.get_subnet_info(at, netuid), "Unable to get subnet info")
+    }
+
+    fn get_subnets_info_json(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(api.get_subnets_info(at), "Unable to get subnets info")
+    }
+
+    fn get_subnet_info_v2_json(
+        &self,
+        netuid: NetUid,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(
+            api.get_subnet_info_v2(at, netuid),
+            "Unable to get subnet info",
+        )
+    }
+
+    fn get_subnets_info_v2_json(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(api.get_subnets_info_v2(at), "Unable to get subnets info")
+    }
+
+    fn get_subnet_hyperparams_json(
+        &self,
+        netuid: NetUid,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(
+            api.get_subnet_hyperparams(at, netuid),
+            "Unable to get subnet info",
+        )
+    }
+
+    fn get_subnet_hyperparams_v2_json(
+        &self,
+        netuid: NetUid,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(
+            api.get_subnet_hyperparams_v2(at, netuid),
+            "Unable to get subnet info",
+        )
+    }
+
+    fn get_all_dynamic_info_json(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(
+            api.get_all_dynamic_info(at),
+            "Unable to get dynamic subnets info",
+        )
+    }
+
+    fn get_dynamic_info_json(
+        &self,
+        netuid: NetUid,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(
+            api.get_dynamic_info(at, netuid),
+            "Unable to get dynamic subnets info",
+        )
+    }
+
+    fn get_all_metagraphs_json(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(api.get_all_metagraphs(at), "Unable to get metagraphs")
+    }
+
+    fn get_metagraph_json(
+        &self,
+        netuid: NetUid,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(api.get_metagraph(at, netuid), "Unable to get metagraph")
+    }
+
+    fn get_all_mechagraphs_json(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(api.get_all_mechagraphs(at), "Unable to get mechagraphs")
+    }
+
+    fn get_mechagraph_json(
+        &self,
+        netuid: NetUid,
+        mecid: MechId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(
+            api.get_mechagraph(at, netuid, mecid),
+            "Unable to get mechagraph",
+        )
+    }
+
+    fn get_subnet_state_json(
+        &self,
+        netuid: NetUid,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(
+            api.get_subnet_state(at, netuid),
+            "Unable to get subnet state info",
+        )
+    }
+
+    fn get_network_lock_cost_json(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(
+            api.get_network_registration_cost(at),
+            "Unable to get subnet lock cost",
+        )
+    }
+
+    fn get_selective_metagraph_json(
+        &self,
+        netuid: NetUid,
+        metagraph_index: Vec<u16>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(
+            api.get_selective_metagraph(at, netuid, metagraph_index),
+            "Unable to get selective metagraph",
+        )
+    }
+
+    fn get_selective_mechagraph_json(
+        &self,
+        netuid: NetUid,
+        mecid: MechId,
+        metagraph_index: Vec<u16>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(
+            api.get_selective_mechagraph(at, netuid, mecid, metagraph_index),
+            "Unable to get selective metagraph",
+        )
+    }
+
+    fn get_subnet_to_prune_json(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(api.get_subnet_to_prune(at), "Unable to get subnet to prune")
+    }
+
+    fn get_epoch_debug_json(
+        &self,
+        netuid: NetUid,
+        mecid: MechId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Value> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        to_json_response(
+            api.get_epoch_debug(at, netuid, mecid),
+            "Unable to get epoch debug",
+        )
+    }
 }
@@ -2,9 +2,12 @@
 extern crate alloc;
 use alloc::vec::Vec;
 use codec::Compact;
+use pallet_commitments::{CommitmentSchema, DecodedCommitmentField};
+use pallet_subtensor::{EmissionLedgerEntry, EpochDebugSnapshot};
 use pallet_subtensor::rpc_info::{
     delegate_info::DelegateInfo,
     dynamic_info::DynamicInfo,
+    epoch_simulation::{EpochHyperparamOverrides, SimulatedEpochTerms},
     metagraph::{Metagraph, SelectiveMetagraph},
     neuron_info::{NeuronInfo, NeuronInfoLite},
     show_subnet::SubnetState,
@@ -47,6 +50,7 @@ sp_api::decl_runtime_apis! {
         fn get_selective_metagraph(netuid: NetUid, metagraph_indexes: Vec<u16>) -> Option<SelectiveMetagraph<AccountId32>>;
         fn get_selective_mechagraph(netuid: NetUid, subid: MechId, metagraph_indexes: Vec<u16>) -> Option<SelectiveMetagraph<AccountId32>>;
         fn get_subnet_to_prune() -> Option<NetUid>;
+        fn get_epoch_debug(netuid: NetUid, mecid: MechId) -> Option<EpochDebugSnapshot>;
     }
 
     pub trait StakeInfoRuntimeApi {
@@ -59,4 +63,34 @@ sp_api::decl_runtime_apis! {
     pub trait SubnetRegistrationRuntimeApi {
         fn get_network_registration_cost() -> TaoCurrency;
     }
+
+    pub trait FeeInAlphaRuntimeApi {
+        fn query_fee_in_alpha( account: AccountId32, tao_fee: u64 ) -> Option<(AccountId32, AccountId32, NetUid, AlphaCurrency)>;
+    }
+
+    pub trait LeaseDividendsRuntimeApi {
+        fn get_claimable_lease_dividends( coldkey_account: AccountId32 ) -> Vec<(u32, TaoCurrency)>;
+    }
+
+    pub trait EmissionLedgerRuntimeApi {
+        fn get_emission_ledger( netuid: NetUid, hotkey_account: AccountId32 ) -> Vec<EmissionLedgerEntry>;
+        fn get_emission_ledgers_for_hotkey( hotkey_account: AccountId32 ) -> Vec<(NetUid, Vec<EmissionLedgerEntry>)>;
+    }
+
+    pub trait CommitmentHistoryRuntimeApi {
+        fn get_commitment_history( netuid: NetUid, account: AccountId32, limit: u32 ) -> Vec<(u64, Vec<u8>)>;
+    }
+
+    pub trait CommitmentSchemaRuntimeApi {
+        fn get_commitment_schema( netuid: NetUid ) -> Option<CommitmentSchema>;
+        fn decode_commitment( netuid: NetUid, account: AccountId32 ) -> Option<Vec<DecodedCommitmentField>>;
+    }
+
+    pub trait CommitmentBlobRuntimeApi {
+        fn get_blob( hash: [u8; 32] ) -> Option<Vec<u8>>;
+    }
+
+    pub trait EpochSimulationRuntimeApi {
+        fn simulate_epoch( netuid: NetUid, mecid: MechId, rao_emission: AlphaCurrency, overrides: EpochHyperparamOverrides, weights: Vec<(u16, Vec<(u16, u16)>)> ) -> Option<Vec<SimulatedEpochTerms<AccountId32>>>;
+    }
 }
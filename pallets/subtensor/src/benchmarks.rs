@@ -942,6 +942,58 @@ mod pallet_benchmarks {
         );
     }
 
+    #[benchmark]
+    fn grant_vested_stake() {
+        let coldkey: T::AccountId = whitelisted_caller();
+        let beneficiary: T::AccountId = account("B", 0, 2);
+        let hot: T::AccountId = account("A", 0, 1);
+        let netuid = NetUid::from(1);
+
+        SubtokenEnabled::<T>::insert(netuid, true);
+        Subtensor::<T>::init_new_network(netuid, 1);
+
+        let reg_fee = Subtensor::<T>::get_burn(netuid);
+        let stake_tao = DefaultMinStake::<T>::get().saturating_mul(10.into());
+        let deposit = reg_fee.saturating_mul(2.into()).saturating_add(stake_tao);
+        Subtensor::<T>::add_balance_to_coldkey_account(&coldkey, deposit.into());
+
+        assert_ok!(Subtensor::<T>::burned_register(
+            RawOrigin::Signed(coldkey.clone()).into(),
+            netuid,
+            hot.clone()
+        ));
+
+        SubnetTAO::<T>::insert(netuid, deposit);
+        SubnetAlphaIn::<T>::insert(netuid, AlphaCurrency::from(deposit.to_u64()));
+        TotalStake::<T>::set(deposit);
+
+        assert_ok!(Subtensor::<T>::add_stake_limit(
+            RawOrigin::Signed(coldkey.clone()).into(),
+            hot.clone(),
+            netuid,
+            stake_tao,
+            TaoCurrency::MAX,
+            false
+        ));
+
+        let alpha_to_grant =
+            Subtensor::<T>::get_stake_for_hotkey_and_coldkey_on_subnet(&hot, &coldkey, netuid);
+
+        // Remove stake limit for benchmark
+        StakingOperationRateLimiter::<T>::remove((hot.clone(), coldkey.clone(), netuid));
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(coldkey.clone()),
+            beneficiary.clone(),
+            hot.clone(),
+            netuid,
+            alpha_to_grant,
+            100_u64,
+            1_000_u64,
+        );
+    }
+
     #[benchmark]
     fn swap_stake() {
         let coldkey: T::AccountId = whitelisted_caller();
@@ -1386,6 +1438,7 @@ mod pallet_benchmarks {
                 call: None,
                 target_address: None,
                 contributors_count: T::MaxContributors::get(),
+                milestones: None,
             },
         );
 
@@ -1452,6 +1505,7 @@ mod pallet_benchmarks {
                 call: None,
                 target_address: None,
                 contributors_count: T::MaxContributors::get(),
+                milestones: None,
             },
         );
 
@@ -1505,6 +1559,84 @@ mod pallet_benchmarks {
         assert!(!AccumulatedLeaseDividends::<T>::contains_key(lease_id));
     }
 
+    #[benchmark(extra)]
+    fn transfer_lease_share(k: Linear<2, { T::MaxContributors::get() - 1 }>) {
+        // Setup a crowdloan
+        let crowdloan_id = 0;
+        let beneficiary: T::AccountId = whitelisted_caller();
+        let deposit = 20_000_000_000; // 20 TAO
+        let now = frame_system::Pallet::<T>::block_number(); // not really important here
+        let crowdloan_end = now + T::MaximumBlockDuration::get();
+        let cap = 2_000_000_000_000; // 2000 TAO
+
+        let funds_account: T::AccountId = account("funds", 0, 0);
+        Subtensor::<T>::add_balance_to_coldkey_account(&funds_account, cap);
+
+        pallet_crowdloan::Crowdloans::<T>::insert(
+            crowdloan_id,
+            pallet_crowdloan::CrowdloanInfo {
+                creator: beneficiary.clone(),
+                deposit,
+                min_contribution: 0,
+                end: crowdloan_end,
+                cap,
+                raised: cap,
+                finalized: false,
+                funds_account: funds_account.clone(),
+                call: None,
+                target_address: None,
+                contributors_count: k,
+                milestones: None,
+            },
+        );
+
+        // Set the block to the end of the crowdloan
+        frame_system::Pallet::<T>::set_block_number(crowdloan_end);
+
+        // Simulate deposit
+        pallet_crowdloan::Contributions::<T>::insert(crowdloan_id, &beneficiary, deposit);
+
+        // Simulate k - 1 contributions, the deposit is already taken into account
+        let contributors = k - 1;
+        let amount = (cap - deposit) / contributors as u64;
+        for i in 0..contributors {
+            let contributor = account::<T::AccountId>("contributor", i.try_into().unwrap(), 0);
+            pallet_crowdloan::Contributions::<T>::insert(crowdloan_id, contributor, amount);
+        }
+
+        // Mark the crowdloan as finalizing
+        pallet_crowdloan::CurrentCrowdloanId::<T>::set(Some(0));
+
+        // Register the leased network
+        let emissions_share = Percent::from_percent(30);
+        assert_ok!(Subtensor::<T>::register_leased_network(
+            RawOrigin::Signed(beneficiary.clone()).into(),
+            emissions_share,
+            None,
+        ));
+
+        // The worst case is a partial transfer to a new shareholder
+        let lease_id = 0;
+        let from = account::<T::AccountId>("contributor", 0, 0);
+        let to = account::<T::AccountId>("buyer", 0, 0);
+        let share_before = SubnetLeaseShares::<T>::get(lease_id, &from);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(from.clone()),
+            lease_id,
+            to.clone(),
+            Percent::from_percent(50),
+        );
+
+        // Ensure the share has been transferred
+        assert_eq!(
+            SubnetLeaseShares::<T>::get(lease_id, &from)
+                + SubnetLeaseShares::<T>::get(lease_id, &to),
+            share_before
+        );
+    }
+
     #[benchmark]
     fn update_symbol() {
         let coldkey: T::AccountId = whitelisted_caller();
@@ -54,6 +54,10 @@ impl<T: Config + pallet_drand::Config> Pallet<T> {
             if current_block.saturating_sub(last_adjustment_block) >= adjustment_interval as u64 {
                 log::debug!("interval reached.");
 
+                // Resolve the burned registrations queued during the interval, they are
+                // counted in the registrations of the interval.
+                Self::resolve_pending_burned_registrations(netuid);
+
                 // --- 4. Get the current counters for this network w.r.t burn and difficulty values.
                 let current_burn = Self::get_burn(netuid);
                 let current_difficulty: u64 = Self::get_difficulty_as_u64(netuid);
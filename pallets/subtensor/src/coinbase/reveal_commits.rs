@@ -43,6 +43,7 @@ impl<T: Config> Pallet<T> {
 
         // Weights revealed must have been committed during epoch `cur_epoch - reveal_period`.
         let reveal_epoch = cur_epoch.saturating_sub(reveal_period);
+        let beacon = Self::get_timelocked_weights_beacon(netuid);
 
         // All mechanisms share the same epoch, so the reveal_period/reveal_epoch are also the same
         // Reveal for all mechanisms
@@ -70,7 +71,7 @@ impl<T: Config> Pallet<T> {
                 entries.pop_front()
             {
                 // Try to get the round number from pallet_drand.
-                let pulse = match pallet_drand::Pulses::<T>::get(round_number) {
+                let pulse = match pallet_drand::Pallet::<T>::pulse(beacon, round_number) {
                     Some(p) => p,
                     None => {
                         // Round number used was not found on the chain. Skip this commit.
@@ -467,6 +467,9 @@ impl<T: Config> Pallet<T> {
         SubnetOwnerHotkey::<T>::remove(netuid);
         NetworkRegistrationAllowed::<T>::remove(netuid);
         NetworkPowRegistrationAllowed::<T>::remove(netuid);
+        BurnedRegistrationModes::<T>::remove(netuid);
+        Self::refund_pending_burned_registrations(netuid);
+        Self::remove_subnet_paymaster(netuid, &owner_coldkey);
 
         // --- 14. Locks & toggles.
         TransferToggle::<T>::remove(netuid);
@@ -514,6 +517,7 @@ impl<T: Config> Pallet<T> {
         // --- 17. Subtoken / feature flags.
         LiquidAlphaOn::<T>::remove(netuid);
         Yuma3On::<T>::remove(netuid);
+        EpochDebugOn::<T>::remove(netuid);
         AlphaValues::<T>::remove(netuid);
         SubtokenEnabled::<T>::remove(netuid);
         ImmuneOwnerUidsLimit::<T>::remove(netuid);
@@ -529,6 +533,8 @@ impl<T: Config> Pallet<T> {
         let _ = Prometheus::<T>::clear_prefix(netuid, u32::MAX, None);
         let _ = AlphaDividendsPerSubnet::<T>::clear_prefix(netuid, u32::MAX, None);
         let _ = TaoDividendsPerSubnet::<T>::clear_prefix(netuid, u32::MAX, None);
+        let _ = EmissionLedger::<T>::clear_prefix(netuid, u32::MAX, None);
+        let _ = PendingChildkeyTake::<T>::clear_prefix(netuid, u32::MAX, None);
         let _ = PendingChildKeys::<T>::clear_prefix(netuid, u32::MAX, None);
         let _ = AssociatedEvmAddress::<T>::clear_prefix(netuid, u32::MAX, None);
 
@@ -538,6 +544,7 @@ impl<T: Config> Pallet<T> {
             let netuid_index = Self::get_mechanism_storage_index(netuid, subid.into());
             LastUpdate::<T>::remove(netuid_index);
             Incentive::<T>::remove(netuid_index);
+            EpochDebug::<T>::remove(netuid_index);
             let _ = WeightCommits::<T>::clear_prefix(netuid_index, u32::MAX, None);
             let _ = TimelockedWeightCommits::<T>::clear_prefix(netuid_index, u32::MAX, None);
             let _ = CRV3WeightCommits::<T>::clear_prefix(netuid_index, u32::MAX, None);
@@ -546,6 +553,7 @@ impl<T: Config> Pallet<T> {
             let _ = Weights::<T>::clear_prefix(netuid_index, u32::MAX, None);
         }
         RevealPeriodEpochs::<T>::remove(netuid);
+        TimelockedWeightsBeacon::<T>::remove(netuid);
         MechanismCountCurrent::<T>::remove(netuid);
         MechanismEmissionSplit::<T>::remove(netuid);
 
@@ -38,6 +38,11 @@ impl<T: Config> Pallet<T> {
             .collect();
         log::debug!("Subnets to emit to: {subnets_to_emit_to:?}");
 
+        // Accumulate the price oracle before emission and swaps move prices in this block.
+        for netuid_i in subnets_to_emit_to.iter() {
+            T::SwapInterface::record_price_observation(*netuid_i);
+        }
+
         // --- 2. Get sum of tao reserves ( in a later version we will switch to prices. )
         let mut acc_total_moving_prices = U96F32::saturating_from_num(0.0);
         // Only get price EMA for subnets that we emit to.
@@ -487,6 +492,9 @@ impl<T: Config> Pallet<T> {
             }
         }
 
+        // Payouts of this epoch per hotkey, recorded in the emission ledger.
+        let mut payouts: BTreeMap<T::AccountId, EmissionLedgerEntry> = BTreeMap::new();
+
         // Distribute mining incentives.
         let subnet_owner_coldkey = SubnetOwner::<T>::get(netuid);
         let owner_hotkeys = Self::get_owner_hotkeys(netuid, &subnet_owner_coldkey);
@@ -534,6 +542,7 @@ impl<T: Config> Pallet<T> {
                 netuid,
                 incentive,
             );
+            payouts.entry(hotkey).or_default().incentive = incentive;
         }
 
         // Distribute alpha divs.
@@ -562,7 +571,11 @@ impl<T: Config> Pallet<T> {
             // Record total hotkey alpha based on which this value of AlphaDividendsPerSubnet
             // was calculated
             let total_hotkey_alpha = TotalHotkeyAlpha::<T>::get(&hotkey, netuid);
-            TotalHotkeyAlphaLastEpoch::<T>::insert(hotkey, netuid, total_hotkey_alpha);
+            TotalHotkeyAlphaLastEpoch::<T>::insert(&hotkey, netuid, total_hotkey_alpha);
+            // Record the payout for the ledger.
+            let payout = payouts.entry(hotkey).or_default();
+            payout.alpha_dividends = tou64!(alpha_divs).into();
+            payout.alpha_delegate_take = tou64!(alpha_take).into();
         }
 
         // Distribute root tao divs.
@@ -597,7 +610,59 @@ impl<T: Config> Pallet<T> {
                     .saturating_add(validator_stake.to_u64().into())
                     .saturating_add(tou64!(root_tao).into());
             });
+            // Record the payout for the ledger.
+            let payout = payouts.entry(hotkey).or_default();
+            payout.tao_dividends = tou64!(root_tao).into();
+            payout.tao_delegate_take = tou64!(tao_take).into();
+        }
+
+        // Childkey take was accumulated while computing the parent dividends of this epoch.
+        for (hotkey, childkey_take) in PendingChildkeyTake::<T>::drain_prefix(netuid) {
+            payouts.entry(hotkey).or_default().childkey_take = childkey_take;
+        }
+
+        Self::record_emission_ledger(netuid, payouts);
+    }
+
+    /// Appends the payouts of the current epoch to the emission ledger of each hotkey on a subnet,
+    /// keeping only the last `EMISSION_LEDGER_LENGTH` entries.
+    pub fn record_emission_ledger(
+        netuid: NetUid,
+        payouts: BTreeMap<T::AccountId, EmissionLedgerEntry>,
+    ) {
+        let block = Self::get_current_block_as_u64();
+        for (hotkey, mut payout) in payouts {
+            payout.block = block;
+            EmissionLedger::<T>::mutate(netuid, &hotkey, |ledger| {
+                ledger.push_back(payout);
+                while ledger.len() > EMISSION_LEDGER_LENGTH as usize {
+                    ledger.pop_front();
+                }
+            });
+        }
+    }
+
+    /// Returns the weight of recording the emission ledger for the epochs that run at
+    /// `current_block`.
+    ///
+    /// Every neuron of a subnet running its epoch may receive a payout, which rewrites its whole
+    /// ledger of up to `EMISSION_LEDGER_LENGTH` entries.
+    pub fn emission_ledger_weight(current_block: u64) -> Weight {
+        let mut weight = T::DbWeight::get().reads(1);
+        for netuid in Self::get_all_subnet_netuids() {
+            // Tempo and SubnetworkN
+            weight.saturating_accrue(T::DbWeight::get().reads(2));
+            if Self::should_run_epoch(netuid, current_block) {
+                let neurons = u64::from(Self::get_subnetwork_n(netuid));
+                // Decoding and encoding a full ledger of about 3.6 KB
+                weight.saturating_accrue(
+                    Weight::from_parts(10_000_000, 3_600)
+                        .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+                        .saturating_mul(neurons),
+                );
+            }
         }
+        weight
     }
 
     pub fn get_stake_map(
@@ -869,6 +934,13 @@ impl<T: Config> Pallet<T> {
                 "Parent contribution for parent {parent:?} with contribution: {contribution:?}, of total: {total_contribution:?} ({emission_factor:?}), of emission: {validating_emission:?} gets: {parent_emission:?}",
             );
         }
+        // Record the childkey take for the emission ledger.
+        if total_child_take > U96F32::saturating_from_num(0) {
+            PendingChildkeyTake::<T>::mutate(netuid, hotkey, |take| {
+                *take = take.saturating_add(tou64!(total_child_take).into());
+            });
+        }
+
         // Calculate the final emission for the hotkey itself.
         // This includes the take left from the parents and the self contribution.
         let child_emission = remaining_emission
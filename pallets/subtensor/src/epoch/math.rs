@@ -74,6 +74,19 @@ pub fn vec_fixed_proportions_to_u16(vec: Vec<I32F32>) -> Vec<u16> {
     vec.into_iter().map(fixed_proportion_to_u16).collect()
 }
 
+pub fn sparse_fixed_proportions_to_u16(
+    sparse_matrix: &[Vec<(u16, I32F32)>],
+) -> Vec<Vec<(u16, u16)>> {
+    sparse_matrix
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|(j, value)| (*j, fixed_proportion_to_u16(*value)))
+                .collect()
+        })
+        .collect()
+}
+
 // Max-upscale vector and convert to u16 so max_value = u16::MAX. Assumes non-negative normalized input.
 pub fn vec_max_upscale_to_u16(vec: &[I32F32]) -> Vec<u16> {
     let u16_max: I32F32 = I32F32::saturating_from_num(u16::MAX);
@@ -574,6 +574,39 @@ impl<T: Config> Pallet<T> {
             .collect()
     }
 
+    /// Returns the weight of writing the epoch debug snapshots of the epochs that run at
+    /// `current_block`.
+    ///
+    /// A snapshot holds five sparse matrices per mechanism, each with up to one entry per pair of
+    /// neurons, so the cost grows with the square of the neuron count.
+    pub fn epoch_debug_weight(current_block: u64) -> Weight {
+        let mut weight = T::DbWeight::get().reads(1);
+        for netuid in Self::get_all_subnet_netuids() {
+            // EpochDebugOn
+            weight.saturating_accrue(T::DbWeight::get().reads(1));
+            if !EpochDebugOn::<T>::get(netuid) {
+                continue;
+            }
+
+            // Tempo, SubnetworkN and MechanismCountCurrent
+            weight.saturating_accrue(T::DbWeight::get().reads(3));
+            if !Self::should_run_epoch(netuid, current_block) {
+                continue;
+            }
+            let neurons = u64::from(Self::get_subnetwork_n(netuid));
+            let mechanisms = u64::from(u8::from(Self::get_current_mechanism_count(netuid)));
+            let entries = neurons.saturating_mul(neurons).saturating_mul(5);
+            // Converting and encoding the matrices, then writing the snapshot
+            weight.saturating_accrue(
+                Weight::from_parts(20_000, 0)
+                    .saturating_mul(entries)
+                    .saturating_add(T::DbWeight::get().writes(1))
+                    .saturating_mul(mechanisms),
+            );
+        }
+        weight
+    }
+
     /// Calculates reward consensus values, then updates rank, trust, consensus, incentive, dividend, pruning_score, emission and bonds, and
     /// returns the emissions for uids/hotkeys in a given `netuid`.
     ///
@@ -836,6 +869,9 @@ impl<T: Config> Pallet<T> {
         let weights_for_bonds: Vec<Vec<(u16, I32F32)>> =
             interpolate_sparse(&weights, &clipped_weights, n, bonds_penalty);
 
+        let epoch_debug_on: bool = EpochDebugOn::<T>::get(netuid);
+        let mut liquid_alphas: Vec<Vec<(u16, u16)>> = vec![];
+
         let mut dividends: Vec<I32F32>;
         let mut ema_bonds: Vec<Vec<(u16, I32F32)>>;
         if Yuma3On::<T>::get(netuid) {
@@ -861,6 +897,17 @@ impl<T: Config> Pallet<T> {
                 Self::compute_bonds_sparse(netuid_index, &weights_for_bonds, &bonds, &consensus);
             log::trace!("emaB: {:?}", &ema_bonds);
 
+            // Keep the liquid alphas used for the EMA for the epoch debug snapshot.
+            if epoch_debug_on {
+                liquid_alphas = Self::get_liquid_alphas_sparse(
+                    netuid,
+                    &weights_for_bonds,
+                    &bonds,
+                    &consensus,
+                    &ema_bonds,
+                );
+            }
+
             // Normalize EMA bonds.
             let mut ema_bonds_norm = ema_bonds.clone();
             inplace_col_normalize_sparse(&mut ema_bonds_norm, n); // sum_i b_ij = 1
@@ -1008,6 +1055,26 @@ impl<T: Config> Pallet<T> {
         let pruning_scores: Vec<I32F32> = normalized_combined_emission.clone();
         log::trace!("Pruning Scores: {:?}", &pruning_scores);
 
+        // ==========================
+        // == Epoch debug snapshot ==
+        // ==========================
+        if epoch_debug_on {
+            EpochDebug::<T>::insert(
+                netuid_index,
+                EpochDebugSnapshot {
+                    block: current_block,
+                    active_stake: vec_fixed_proportions_to_u16(active_stake.clone()),
+                    weights: sparse_fixed_proportions_to_u16(&weights),
+                    consensus: vec_fixed_proportions_to_u16(consensus.clone()),
+                    clipped_weights: sparse_fixed_proportions_to_u16(&clipped_weights),
+                    validator_trust: vec_fixed_proportions_to_u16(validator_trust.clone()),
+                    weights_for_bonds: sparse_fixed_proportions_to_u16(&weights_for_bonds),
+                    ema_bonds: sparse_fixed_proportions_to_u16(&ema_bonds),
+                    liquid_alphas,
+                },
+            );
+        }
+
         // ===========================
         // == Populate epoch output ==
         // ===========================
@@ -1092,6 +1159,40 @@ impl<T: Config> Pallet<T> {
         EpochOutput(terms_map)
     }
 
+    /// Returns the liquid alphas used by `compute_bonds_sparse` for each EMA bond, or an empty
+    /// matrix if liquid alpha was not used.
+    fn get_liquid_alphas_sparse(
+        netuid: NetUid,
+        weights: &[Vec<(u16, I32F32)>],
+        bonds: &[Vec<(u16, I32F32)>],
+        consensus: &[I32F32],
+        ema_bonds: &[Vec<(u16, I32F32)>],
+    ) -> Vec<Vec<(u16, u16)>> {
+        if !LiquidAlphaOn::<T>::get(netuid)
+            || !consensus
+                .iter()
+                .any(|&c| c != I32F32::saturating_from_num(0))
+        {
+            return vec![];
+        }
+
+        let alphas: Vec<Vec<I32F32>> =
+            Self::compute_liquid_alpha_values_sparse(netuid, weights, bonds, consensus);
+        ema_bonds
+            .iter()
+            .zip(alphas.iter())
+            .map(|(ema_row, alpha_row)| {
+                ema_row
+                    .iter()
+                    .map(|(j, _)| {
+                        let alpha = alpha_row.get(*j as usize).copied().unwrap_or_default();
+                        (*j, fixed_proportion_to_u16(alpha))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
     pub fn get_float_rho(netuid: NetUid) -> I32F32 {
         I32F32::saturating_from_num(Self::get_rho(netuid))
     }
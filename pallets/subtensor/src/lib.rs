@@ -15,7 +15,7 @@ use frame_support::{
     ensure,
     pallet_macros::import_section,
     pallet_prelude::*,
-    traits::tokens::fungible,
+    traits::tokens::{Preservation, fungible},
 };
 use pallet_balances::Call as BalancesCall;
 // use pallet_scheduler as Scheduler;
@@ -54,6 +54,33 @@ extern crate alloc;
 
 pub const MAX_CRV3_COMMIT_SIZE_BYTES: u32 = 5000;
 
+/// Maximum number of subnets in a stake swap route
+pub const MAX_SWAP_ROUTE_LENGTH: u32 = 6;
+
+/// Maximum number of active vesting schedules on a (hotkey, coldkey, netuid) stake position
+pub const MAX_VESTING_SCHEDULES: u32 = 16;
+
+/// Number of epoch payouts kept in the emission ledger of a hotkey on a subnet
+pub const EMISSION_LEDGER_LENGTH: u32 = 64;
+
+/// Maximum number of burned registrations queued on a subnet during an adjustment interval
+pub const MAX_PENDING_BURNED_REGISTRATIONS: u32 = 64;
+
+/// Maximum number of burned registrations a coldkey can queue on a subnet
+pub const MAX_PENDING_BURNED_REGISTRATIONS_PER_COLDKEY: u32 = 4;
+
+/// Maximum size in bytes of a sealed burned registration bid
+pub const MAX_SEALED_BID_SIZE_BYTES: u32 = 512;
+
+/// Number of drand quicknet rounds (3 seconds) per block (12 seconds)
+pub const DRAND_ROUNDS_PER_BLOCK: u64 = 4;
+
+/// Maximum number of stake positions a coldkey can register to pay transaction fees in alpha
+pub const MAX_FEE_ALPHA_SOURCES: u32 = 8;
+
+/// Maximum number of calls a subnet paymaster can sponsor
+pub const MAX_SPONSORED_CALLS: u32 = 8;
+
 #[allow(deprecated)]
 #[deny(missing_docs)]
 #[import_section(errors::errors)]
@@ -66,7 +93,10 @@ pub const MAX_CRV3_COMMIT_SIZE_BYTES: u32 = 5000;
 pub mod pallet {
     use crate::RateLimitKey;
     use crate::migrations;
-    use crate::subnets::leasing::{LeaseId, SubnetLeaseOf};
+    use crate::staking::vesting::VestingScheduleOf;
+    use crate::subnets::leasing::{LeaseAction, LeaseId, LeaseProposalOf, SubnetLeaseOf};
+    use crate::subnets::paymaster::SubnetPaymaster;
+    use crate::subnets::registration::{PendingBurnedRegistrationOf, SealedBid};
     use frame_support::Twox64Concat;
     use frame_support::{
         BoundedVec,
@@ -77,7 +107,7 @@ pub mod pallet {
         },
     };
     use frame_system::pallet_prelude::*;
-    use pallet_drand::types::RoundNumber;
+    use pallet_drand::types::{ChainHash, RoundNumber};
     use runtime_common::prod_or_fast;
     use sp_core::{ConstU32, H160, H256};
     use sp_runtime::traits::{Dispatchable, TrailingZeroInput};
@@ -129,8 +159,8 @@ pub mod pallet {
     pub type LocalCallOf<T> = <T as Config>::RuntimeCall;
 
     /// Data structure for Axon information.
-    #[crate::freeze_struct("3545cfb0cac4c1f5")]
-    #[derive(Encode, Decode, Default, TypeInfo, Clone, PartialEq, Eq, Debug)]
+    #[crate::freeze_struct("2075cd367af54314")]
+    #[derive(Encode, Decode, Default, TypeInfo, Clone, PartialEq, Eq, Debug, serde::Serialize)]
     pub struct AxonInfo {
         ///  Axon serving block.
         pub block: u64,
@@ -180,12 +210,57 @@ pub mod pallet {
         }
     }
 
+    /// Data structure for the payout of one epoch to a hotkey on a subnet.
+    #[freeze_struct("6806f1dc2c1040bc")]
+    #[derive(Encode, Decode, Default, TypeInfo, Clone, PartialEq, Eq, Debug)]
+    pub struct EmissionLedgerEntry {
+        /// Block at which the epoch payout was distributed.
+        pub block: u64,
+        /// Miner incentive staked for the hotkey.
+        pub incentive: AlphaCurrency,
+        /// Alpha dividends distributed to the hotkey's nominators.
+        pub alpha_dividends: AlphaCurrency,
+        /// Root TAO dividends distributed to the hotkey's root nominators.
+        pub tao_dividends: TaoCurrency,
+        /// Childkey take earned by the hotkey on the dividends of its parents.
+        pub childkey_take: AlphaCurrency,
+        /// Delegate take on alpha dividends paid to the hotkey owner.
+        pub alpha_delegate_take: AlphaCurrency,
+        /// Delegate take on root TAO dividends paid to the hotkey owner.
+        pub tao_delegate_take: TaoCurrency,
+    }
+
+    /// Intermediate Yuma terms of the last epoch of a subnet mechanism, kept when epoch debugging
+    /// is enabled on the subnet. Proportions are stored as u16, matrices in sparse row form.
+    #[freeze_struct("ded1769c40bd5dd2")]
+    #[derive(Encode, Decode, Default, TypeInfo, Clone, PartialEq, Eq, Debug, serde::Serialize)]
+    pub struct EpochDebugSnapshot {
+        /// Block at which the epoch ran.
+        pub block: u64,
+        /// Normalized stake of active validators with a permit.
+        pub active_stake: Vec<u16>,
+        /// Weights after permit, self-weight and outdated masking, row normalized.
+        pub weights: Vec<Vec<(u16, u16)>>,
+        /// Stake-weighted median of the weights on each miner.
+        pub consensus: Vec<u16>,
+        /// Weights clipped at consensus.
+        pub clipped_weights: Vec<Vec<(u16, u16)>>,
+        /// Sum of the clipped weights of each validator.
+        pub validator_trust: Vec<u16>,
+        /// Weights used for the bonds, after the bonds penalty.
+        pub weights_for_bonds: Vec<Vec<(u16, u16)>>,
+        /// Bonds after the exponential moving average.
+        pub ema_bonds: Vec<Vec<(u16, u16)>>,
+        /// Liquid alpha used for each EMA bond, empty if liquid alpha was not used.
+        pub liquid_alphas: Vec<Vec<(u16, u16)>>,
+    }
+
     ///  Struct for Prometheus.
     pub type PrometheusInfoOf = PrometheusInfo;
 
     /// Data structure for Prometheus information.
-    #[crate::freeze_struct("5dde687e63baf0cd")]
-    #[derive(Encode, Decode, Default, TypeInfo, Clone, PartialEq, Eq, Debug)]
+    #[crate::freeze_struct("e89f2e56c0982320")]
+    #[derive(Encode, Decode, Default, TypeInfo, Clone, PartialEq, Eq, Debug, serde::Serialize)]
     pub struct PrometheusInfo {
         /// Prometheus serving block.
         pub block: u64,
@@ -224,8 +299,8 @@ pub mod pallet {
     pub type ChainIdentityOfV2 = ChainIdentityV2;
 
     /// Data structure for Chain Identities.
-    #[crate::freeze_struct("ad72a270be7b59d7")]
-    #[derive(Encode, Decode, Default, TypeInfo, Clone, PartialEq, Eq, Debug)]
+    #[crate::freeze_struct("4d4de1b21f68364")]
+    #[derive(Encode, Decode, Default, TypeInfo, Clone, PartialEq, Eq, Debug, serde::Serialize)]
     pub struct ChainIdentityV2 {
         /// The name of the chain identity
         pub name: Vec<u8>,
@@ -282,9 +357,18 @@ pub mod pallet {
     ///  Struct for SubnetIdentitiesV3.
     pub type SubnetIdentityOfV3 = SubnetIdentityV3;
     /// Data structure for Subnet Identities
-    #[crate::freeze_struct("6a441335f985a0b")]
+    #[crate::freeze_struct("ec95b51279ca486a")]
     #[derive(
-        Encode, Decode, DecodeWithMemTracking, Default, TypeInfo, Clone, PartialEq, Eq, Debug,
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        Default,
+        TypeInfo,
+        Clone,
+        PartialEq,
+        Eq,
+        Debug,
+        serde::Serialize,
     )]
     pub struct SubnetIdentityV3 {
         /// The name of the subnet
@@ -314,6 +398,35 @@ pub mod pallet {
         Recycle,
     }
 
+    /// Enum for the resolution of the burned registrations of a subnet
+    #[derive(
+        TypeInfo, Encode, Decode, DecodeWithMemTracking, Clone, Copy, PartialEq, Eq, Debug,
+    )]
+    pub enum BurnedRegistrationMode {
+        /// Register immediately, first come first served
+        Immediate,
+        /// Queue the bids sealed until the end of the adjustment interval, the highest bids win
+        HighestBid,
+        /// Queue the registrations until the end of the adjustment interval, the winners are drawn
+        /// using the drand randomness
+        Random,
+    }
+
+    /// Enum for the calls whose transaction fees a subnet paymaster can pay
+    #[derive(
+        TypeInfo, Encode, Decode, DecodeWithMemTracking, Clone, Copy, PartialEq, Eq, Debug,
+    )]
+    pub enum SponsoredCall {
+        /// Burned registrations
+        BurnedRegister,
+        /// Queued burned registrations
+        QueueBurnedRegistration,
+        /// Serving axons, with or without TLS
+        ServeAxon,
+        /// Setting commitments in the commitments pallet
+        SetCommitment,
+    }
+
     /// ============================
     /// ==== Staking + Accounts ====
     /// ============================
@@ -547,6 +660,11 @@ pub mod pallet {
         T::InitialSubnetOwnerCut::get()
     }
     #[pallet::type_value]
+    /// Default value for the burned registration mode.
+    pub fn DefaultBurnedRegistrationMode<T: Config>() -> BurnedRegistrationMode {
+        BurnedRegistrationMode::Immediate
+    }
+    #[pallet::type_value]
     /// Default value for recycle or burn.
     pub fn DefaultRecycleOrBurn<T: Config>() -> RecycleOrBurnEnum {
         RecycleOrBurnEnum::Burn // default to burn
@@ -1081,6 +1199,29 @@ pub mod pallet {
         ValueQuery,
         DefaultZeroAlpha<T>,
     >;
+    #[pallet::storage]
+    /// --- DMAP ( netuid, hotkey ) --> VecDeque<entry> | Payouts of the last EMISSION_LEDGER_LENGTH epochs to this hotkey on this subnet.
+    pub type EmissionLedger<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        NetUid,
+        Blake2_128Concat,
+        T::AccountId,
+        VecDeque<EmissionLedgerEntry>,
+        ValueQuery,
+    >;
+    #[pallet::storage]
+    /// --- DMAP ( netuid, hotkey ) --> alpha | Childkey take accumulated by this hotkey in the running epoch.
+    pub type PendingChildkeyTake<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        NetUid,
+        Blake2_128Concat,
+        T::AccountId,
+        AlphaCurrency,
+        ValueQuery,
+        DefaultZeroAlpha<T>,
+    >;
 
     /// ==========================
     /// ==== Staking Counters ====
@@ -1143,6 +1284,14 @@ pub mod pallet {
     #[pallet::storage] // --- MAP ( cold ) --> hot | Returns the hotkey a coldkey will autostake to with mining rewards.
     pub type AutoStakeDestination<T: Config> =
         StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId, OptionQuery>;
+    #[pallet::storage] // --- MAP ( cold ) --> Vec<(hot, netuid)> | The stake positions a coldkey pays transaction fees in alpha from, in order of preference.
+    pub type FeeAlphaSources<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<(T::AccountId, NetUid), ConstU32<MAX_FEE_ALPHA_SOURCES>>,
+        ValueQuery,
+    >;
 
     #[pallet::storage] // --- DMAP ( cold ) --> (block_expected, new_coldkey) | Maps coldkey to the block to swap at and new coldkey.
     pub type ColdkeySwapScheduled<T: Config> = StorageMap<
@@ -1199,6 +1348,17 @@ pub mod pallet {
         U64F64, // Shares
         ValueQuery,
     >;
+    #[pallet::storage] // --- NMAP ( netuid, hot, cold ) --> Vec<vesting_schedule> | Returns the vesting schedules locking the alpha of a hotkey, coldkey, netuid triplet.
+    pub type VestingSchedules<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Identity, NetUid>,               // subnet
+            NMapKey<Blake2_128Concat, T::AccountId>, // hot
+            NMapKey<Blake2_128Concat, T::AccountId>, // cold
+        ),
+        BoundedVec<VestingScheduleOf<T>, ConstU32<MAX_VESTING_SCHEDULES>>,
+        ValueQuery,
+    >;
     #[pallet::storage] // --- MAP ( netuid ) --> token_symbol | Returns the token symbol for a subnet.
     pub type TokenSymbol<T: Config> =
         StorageMap<_, Identity, NetUid, Vec<u8>, ValueQuery, DefaultUnicodeVecU8<T>>;
@@ -1379,6 +1539,48 @@ pub mod pallet {
     pub type BurnRegistrationsThisInterval<T: Config> =
         StorageMap<_, Identity, NetUid, u16, ValueQuery>;
     #[pallet::storage]
+    /// --- MAP ( netuid ) --> burned_registration_mode
+    pub type BurnedRegistrationModes<T: Config> = StorageMap<
+        _,
+        Identity,
+        NetUid,
+        BurnedRegistrationMode,
+        ValueQuery,
+        DefaultBurnedRegistrationMode<T>,
+    >;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> pending_burned_registrations | The burned registrations queued until the end of the adjustment interval.
+    pub type PendingBurnedRegistrations<T: Config> = StorageMap<
+        _,
+        Identity,
+        NetUid,
+        BoundedVec<PendingBurnedRegistrationOf<T>, ConstU32<MAX_PENDING_BURNED_REGISTRATIONS>>,
+        ValueQuery,
+    >;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> round | The drand round drawing the order of the burned registrations queued in the random mode.
+    pub type PendingBurnedRegistrationRound<T: Config> =
+        StorageMap<_, Identity, NetUid, RoundNumber, OptionQuery>;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> paymaster | The transaction fees the paymaster of the subnet pays.
+    pub type SubnetPaymasters<T: Config> =
+        StorageMap<_, Identity, NetUid, SubnetPaymaster, OptionQuery>;
+    #[pallet::storage]
+    /// --- DMAP ( netuid, account ) --> fees | The transaction fees the paymaster of the subnet has paid for the account.
+    pub type PaymasterAccountFees<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        NetUid,
+        Blake2_128Concat,
+        T::AccountId,
+        TaoCurrency,
+        ValueQuery,
+    >;
+    #[pallet::storage]
+    /// --- MAP ( netuid ) --> (block, fees) | The transaction fees the paymaster of the subnet has paid in its last paying block.
+    pub type PaymasterBlockFees<T: Config> =
+        StorageMap<_, Identity, NetUid, (u64, TaoCurrency), ValueQuery>;
+    #[pallet::storage]
     /// --- MAP ( netuid ) --> min_allowed_uids
     pub type MinAllowedUids<T> =
         StorageMap<_, Identity, NetUid, u16, ValueQuery, DefaultMinAllowedUids<T>>;
@@ -1514,6 +1716,13 @@ pub mod pallet {
     pub type Yuma3On<T> =
         StorageMap<_, Blake2_128Concat, NetUid, bool, ValueQuery, DefaultYuma3<T>>;
     #[pallet::storage]
+    /// --- MAP ( netuid ) --> Whether or not the epoch debug snapshot is kept
+    pub type EpochDebugOn<T> = StorageMap<_, Identity, NetUid, bool, ValueQuery, DefaultFalse<T>>;
+    #[pallet::storage]
+    /// --- MAP ( netuid_index ) --> Intermediate Yuma terms of the last epoch
+    pub type EpochDebug<T> =
+        StorageMap<_, Identity, NetUidStorageIndex, EpochDebugSnapshot, OptionQuery>;
+    #[pallet::storage]
     ///  MAP ( netuid ) --> (alpha_low, alpha_high)
     pub type AlphaValues<T> =
         StorageMap<_, Identity, NetUid, (u16, u16), ValueQuery, DefaultAlphaValues<T>>;
@@ -1762,6 +1971,11 @@ pub mod pallet {
         ValueQuery,
     >;
     #[pallet::storage]
+    /// --- MAP (netuid) --> chain hash of the drand beacon the reveal rounds of timelocked
+    /// weight commits refer to, the default beacon if unset
+    pub type TimelockedWeightsBeacon<T: Config> =
+        StorageMap<_, Identity, NetUid, ChainHash, OptionQuery>;
+    #[pallet::storage]
     /// MAP (netuid, epoch) → VecDeque<(who, ciphertext, reveal_round)>
     /// DEPRECATED for CRV3WeightCommitsV2
     pub type CRV3WeightCommits<T: Config> = StorageDoubleMap<
@@ -1860,6 +2074,41 @@ pub mod pallet {
     pub type AccumulatedLeaseDividends<T: Config> =
         StorageMap<_, Twox64Concat, LeaseId, AlphaCurrency, ValueQuery, DefaultZeroAlpha<T>>;
 
+    #[pallet::storage]
+    /// --- MAP ( lease_id ) --> total_shares | The sum of the contributors shares for a given lease.
+    pub type SubnetLeaseTotalShares<T: Config> =
+        StorageMap<_, Twox64Concat, LeaseId, U64F64, ValueQuery>;
+
+    #[pallet::storage]
+    /// --- MAP ( lease_id ) --> dividends_per_share | The tao dividends distributed so far per unit of share for a given lease.
+    pub type LeaseDividendsPerShare<T: Config> =
+        StorageMap<_, Twox64Concat, LeaseId, U64F64, ValueQuery>;
+
+    #[pallet::storage]
+    /// --- DMAP ( lease_id, contributor ) --> dividends_per_share | The dividends per share of a lease when the contributor dividends were last paid.
+    pub type LeaseDividendsCheckpoint<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, LeaseId, Identity, T::AccountId, U64F64, ValueQuery>;
+
+    #[pallet::storage]
+    /// --- MAP ( lease_id ) --> enabled | Whether the contributors of a lease can vote on its actions.
+    pub type SubnetLeaseGovernance<T: Config> =
+        StorageMap<_, Twox64Concat, LeaseId, bool, ValueQuery>;
+
+    #[pallet::storage]
+    /// --- MAP ( lease_id ) --> proposal | The action proposed to the contributors of a lease.
+    pub type LeaseProposals<T: Config> =
+        StorageMap<_, Twox64Concat, LeaseId, LeaseProposalOf<T>, OptionQuery>;
+
+    #[pallet::storage]
+    /// --- DMAP ( lease_id, contributor ) --> aye | The votes of the contributors on the proposal of a lease.
+    pub type LeaseProposalVotes<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, LeaseId, Identity, T::AccountId, bool, OptionQuery>;
+
+    #[pallet::storage]
+    /// --- MAP ( account ) --> block | The block at which the account last proposed a lease action.
+    pub type LastLeaseProposalBlock<T: Config> =
+        StorageMap<_, Identity, T::AccountId, BlockNumberFor<T>, OptionQuery>;
+
     #[pallet::storage]
     /// --- ITEM ( CommitRevealWeightsVersion )
     pub type CommitRevealWeightsVersion<T> =
@@ -2190,6 +2439,21 @@ impl<T: Config + pallet_balances::Config<Balance = u64>>
         Self::remove_balance_from_coldkey_account(coldkey, tao.into())
     }
 
+    fn transfer_balance(
+        from: &T::AccountId,
+        to: &T::AccountId,
+        tao: TaoCurrency,
+        preservation: Preservation,
+    ) -> DispatchResult {
+        <<T as Config>::Currency as fungible::Mutate<_>>::transfer(
+            from,
+            to,
+            tao.into(),
+            preservation,
+        )?;
+        Ok(())
+    }
+
     fn increase_stake(
         coldkey: &T::AccountId,
         hotkey: &T::AccountId,
@@ -2222,6 +2486,9 @@ impl<T: Config + pallet_balances::Config<Balance = u64>>
             Error::<T>::HotKeyAccountNotExists
         );
 
+        // Ensure that the amount is not locked by a vesting schedule
+        Self::ensure_stake_unlocked(hotkey, coldkey, netuid, alpha)?;
+
         // Decrese alpha out counter
         SubnetAlphaOut::<T>::mutate(netuid, |total| {
             *total = total.saturating_sub(alpha);
@@ -2247,6 +2514,28 @@ impl<T: Config + pallet_balances::Config<Balance = u64>>
     fn decrease_provided_alpha_reserve(netuid: NetUid, alpha: AlphaCurrency) {
         Self::decrease_provided_alpha_reserve(netuid, alpha);
     }
+
+    fn settle_buy_order_fill(
+        escrow: &T::AccountId,
+        coldkey: &T::AccountId,
+        hotkey: &T::AccountId,
+        netuid: NetUid,
+        tao: TaoCurrency,
+        tao_fee: TaoCurrency,
+        alpha: AlphaCurrency,
+    ) -> DispatchResult {
+        Self::settle_buy_order_fill(escrow, coldkey, hotkey, netuid, tao, tao_fee, alpha)
+    }
+
+    fn settle_sell_order_fill(
+        coldkey: &T::AccountId,
+        netuid: NetUid,
+        alpha: AlphaCurrency,
+        alpha_fee: AlphaCurrency,
+        tao: TaoCurrency,
+    ) -> DispatchResult {
+        Self::settle_sell_order_fill(coldkey, netuid, alpha, alpha_fee, tao)
+    }
 }
 
 /// Enum that defines types of rate limited operations for
@@ -2283,7 +2572,13 @@ impl<T> ProxyInterface<T> for () {
     }
 }
 
-/// Pallets that hold per-subnet commitments implement this to purge all state for `netuid`.
+/// Pallets that hold per-subnet commitments implement this to purge all state for `netuid`
+/// and to configure the commitments of a subnet.
 pub trait CommitmentsInterface {
     fn purge_netuid(netuid: NetUid);
+    fn set_commitment_history_depth(netuid: NetUid, depth: u32) -> DispatchResult;
+    fn set_commitment_schema(
+        netuid: NetUid,
+        schema: Option<pallet_commitments::CommitmentSchema>,
+    ) -> DispatchResult;
 }
@@ -261,6 +261,18 @@ mod config {
         /// Number of blocks between dividends distribution.
         #[pallet::constant]
         type LeaseDividendsDistributionInterval: Get<BlockNumberFor<Self>>;
+        /// Number of blocks during which the contributors of a lease can vote on a proposal.
+        #[pallet::constant]
+        type LeaseProposalVotingPeriod: Get<BlockNumberFor<Self>>;
+        /// Maximum change of the emissions share of a lease that can be proposed at once.
+        #[pallet::constant]
+        type MaxLeaseEmissionsShareChange: Get<Percent>;
+        /// Minimum share of a lease, as a percentage of its total shares, a contributor must hold to propose an action.
+        #[pallet::constant]
+        type MinLeaseProposalShare: Get<Percent>;
+        /// Number of blocks an account must wait between two lease proposals.
+        #[pallet::constant]
+        type LeaseProposalCooldown: Get<BlockNumberFor<Self>>;
         /// Maximum percentage of immune UIDs.
         #[pallet::constant]
         type MaxImmuneUidsPercentage: Get<Percent>;
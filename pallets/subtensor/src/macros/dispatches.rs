@@ -12,7 +12,7 @@ mod dispatches {
     use sp_core::ecdsa::Signature;
     use sp_runtime::{Percent, traits::Saturating};
 
-    use crate::MAX_CRV3_COMMIT_SIZE_BYTES;
+    use crate::{MAX_CRV3_COMMIT_SIZE_BYTES, MAX_SWAP_ROUTE_LENGTH};
     /// Dispatchable functions allow users to interact with the pallet and invoke state changes.
     /// These functions materialize as "extrinsics", which are often compared to transactions.
     /// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
@@ -1087,7 +1087,8 @@ mod dispatches {
         #[pallet::call_index(71)]
         #[pallet::weight((Weight::from_parts(161_700_000, 0)
         .saturating_add(T::DbWeight::get().reads(15_u64))
-        .saturating_add(T::DbWeight::get().writes(9)), DispatchClass::Operational, Pays::No))]
+        .saturating_add(T::DbWeight::get().writes(9))
+        .saturating_add(T::SwapInterface::swap_coldkey_positions_weight()), DispatchClass::Operational, Pays::No))]
         pub fn swap_coldkey(
             origin: OriginFor<T>,
             old_coldkey: T::AccountId,
@@ -2366,5 +2367,368 @@ mod dispatches {
             ensure_root(origin)?;
             Self::do_dissolve_network(netuid)
         }
+
+        /// Swaps stake along a route of subnets, while keeping the same coldkey and hotkey.
+        ///
+        /// Every leg `route[i] -> route[i + 1]` is executed like `swap_stake` with the full output
+        /// of the previous leg. The whole route is atomic: if any leg fails or the final amount is
+        /// below `min_alpha_out`, nothing is moved.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the transaction, which must be signed by the coldkey that owns the `hotkey`.
+        /// * `hotkey` - The hotkey whose stake is being swapped.
+        /// * `route` - The ordered list of subnets to swap through, starting at the origin subnet.
+        /// * `alpha_amount` - The amount of origin subnet stake to swap.
+        /// * `min_alpha_out` - The minimum amount of stake to receive on the last subnet of the route.
+        ///
+        /// # Errors
+        /// Returns an error if:
+        /// * The route contains less than two subnets.
+        /// * Any of the legs fails the same checks as `swap_stake`.
+        /// * The amount received on the last subnet is below `min_alpha_out`.
+        ///
+        /// # Events
+        /// May emit a `StakeRouteSwapped` event on success.
+        #[pallet::call_index(121)]
+        #[pallet::weight((
+            Weight::from_parts(351_300_000, 0)
+            .saturating_add(T::DbWeight::get().reads(37_u64))
+            .saturating_add(T::DbWeight::get().writes(22_u64))
+            .saturating_mul(route.len().saturating_sub(1) as u64),
+            DispatchClass::Normal,
+            Pays::Yes
+        ))]
+        pub fn swap_stake_route(
+            origin: T::RuntimeOrigin,
+            hotkey: T::AccountId,
+            route: BoundedVec<NetUid, ConstU32<MAX_SWAP_ROUTE_LENGTH>>,
+            alpha_amount: AlphaCurrency,
+            min_alpha_out: AlphaCurrency,
+        ) -> DispatchResult {
+            Self::do_swap_stake_route(
+                origin,
+                hotkey,
+                route.into_inner(),
+                alpha_amount,
+                min_alpha_out,
+            )
+        }
+
+        /// Grants alpha stake to a beneficiary coldkey under a vesting schedule.
+        ///
+        /// The stake is transferred from the caller to the beneficiary on the same hotkey and
+        /// subnet. Nothing unlocks before `cliff` blocks have passed, after which the stake unlocks
+        /// linearly until `duration` blocks have passed. Locked stake cannot be removed, moved,
+        /// transferred or swapped by the beneficiary.
+        ///
+        /// # Arguments
+        /// * `origin` - The origin of the transaction, which must be signed by the grantor coldkey.
+        /// * `beneficiary` - The coldkey receiving the vested stake.
+        /// * `hotkey` - The hotkey associated with the stake.
+        /// * `netuid` - The subnet of the stake.
+        /// * `amount` - The amount of alpha to grant.
+        /// * `cliff` - The number of blocks before anything unlocks.
+        /// * `duration` - The number of blocks until the whole amount is unlocked.
+        ///
+        /// # Errors
+        /// Returns an error if:
+        /// * The schedule is invalid or the beneficiary is the caller.
+        /// * The beneficiary already has `MAX_VESTING_SCHEDULES` active schedules on the position.
+        /// * The granted amount is not above the minimum stake in TAO equivalent.
+        /// * Any of the `transfer_stake` checks fails.
+        ///
+        /// # Events
+        /// May emit a `StakeVestingGranted` event on success.
+        #[pallet::call_index(122)]
+        #[pallet::weight((Weight::from_parts(175_000_000, 0)
+        .saturating_add(T::DbWeight::get().reads(18_u64))
+        .saturating_add(T::DbWeight::get().writes(7_u64)), DispatchClass::Normal, Pays::Yes))]
+        pub fn grant_vested_stake(
+            origin: T::RuntimeOrigin,
+            beneficiary: T::AccountId,
+            hotkey: T::AccountId,
+            netuid: NetUid,
+            amount: AlphaCurrency,
+            cliff: u64,
+            duration: u64,
+        ) -> DispatchResult {
+            Self::do_grant_vested_stake(
+                origin,
+                beneficiary,
+                hotkey,
+                netuid,
+                amount,
+                cliff,
+                duration,
+            )
+        }
+
+        /// Transfer a portion of the caller's share of a lease dividends to another account.
+        ///
+        /// The sum of the shares is unchanged so the dividends distribution is not affected.
+        /// A share transferred to the beneficiary is merged into the beneficiary's leftover share.
+        ///
+        /// # Args:
+        /// * `origin` - (<T as frame_system::Config>::Origin):
+        ///     - The signature of the caller's coldkey, holding a share of the lease.
+        ///
+        /// * `lease_id` (LeaseId):
+        ///     - The ID of the lease to transfer the share of.
+        ///
+        /// * `to` (T::AccountId):
+        ///     - The account receiving the share.
+        ///
+        /// * `portion` (Percent):
+        ///     - The portion of the caller's share to transfer.
+        #[pallet::call_index(123)]
+        #[pallet::weight(SubnetLeasingWeightInfo::<T>::do_transfer_lease_share(T::MaxContributors::get()))]
+        pub fn transfer_lease_share(
+            origin: T::RuntimeOrigin,
+            lease_id: LeaseId,
+            to: T::AccountId,
+            portion: Percent,
+        ) -> DispatchResultWithPostInfo {
+            Self::do_transfer_lease_share(origin, lease_id, to, portion)
+        }
+
+        /// Claim the dividends of a lease earned by the caller's share since its last claim.
+        ///
+        /// The contributors dividends are kept in the lease coldkey when distributed and each
+        /// contributor claims its part, optionally staking it into a hotkey on the leased subnet.
+        ///
+        /// # Args:
+        /// * `origin` - (<T as frame_system::Config>::Origin):
+        ///     - The signature of the caller's coldkey, holding a share of the lease.
+        ///
+        /// * `lease_id` (LeaseId):
+        ///     - The ID of the lease to claim the dividends of.
+        ///
+        /// * `hotkey` (Option<T::AccountId>):
+        ///     - The hotkey to stake the claimed dividends into on the leased subnet, if any.
+        #[pallet::call_index(124)]
+        #[pallet::weight({
+            let weight = SubnetLeasingWeightInfo::<T>::do_claim_lease_dividends();
+            if hotkey.is_some() {
+                weight.saturating_add(
+                    Weight::from_parts(340_800_000, 0)
+                        .saturating_add(T::DbWeight::get().reads(26))
+                        .saturating_add(T::DbWeight::get().writes(15)),
+                )
+            } else {
+                weight
+            }
+        })]
+        pub fn claim_lease_dividends(
+            origin: T::RuntimeOrigin,
+            lease_id: LeaseId,
+            hotkey: Option<T::AccountId>,
+        ) -> DispatchResult {
+            Self::do_claim_lease_dividends(origin, lease_id, hotkey)
+        }
+
+        /// Enable the governance of a lease by its contributors, it can't be disabled.
+        ///
+        /// The contributors can then vote, weighted by their shares, to replace the beneficiary,
+        /// change the emissions share within bounds or end the lease early.
+        ///
+        /// # Args:
+        /// * `origin` - (<T as frame_system::Config>::Origin):
+        ///     - The signature of the lease beneficiary.
+        ///
+        /// * `lease_id` (LeaseId):
+        ///     - The ID of the lease to enable the governance of.
+        #[pallet::call_index(125)]
+        #[pallet::weight(SubnetLeasingWeightInfo::<T>::do_enable_lease_governance())]
+        pub fn enable_lease_governance(
+            origin: T::RuntimeOrigin,
+            lease_id: LeaseId,
+        ) -> DispatchResult {
+            Self::do_enable_lease_governance(origin, lease_id)
+        }
+
+        /// Propose an action to the contributors of a lease, the proposer votes for it.
+        ///
+        /// # Args:
+        /// * `origin` - (<T as frame_system::Config>::Origin):
+        ///     - The signature of the caller's coldkey, holding a share of the lease.
+        ///
+        /// * `lease_id` (LeaseId):
+        ///     - The ID of the lease to propose the action for.
+        ///
+        /// * `action` (LeaseAction<T::AccountId>):
+        ///     - The action to execute if the proposal is approved.
+        #[pallet::call_index(126)]
+        #[pallet::weight(SubnetLeasingWeightInfo::<T>::do_propose_lease_action())]
+        pub fn propose_lease_action(
+            origin: T::RuntimeOrigin,
+            lease_id: LeaseId,
+            action: LeaseAction<T::AccountId>,
+        ) -> DispatchResult {
+            Self::do_propose_lease_action(origin, lease_id, action)
+        }
+
+        /// Vote on the proposal of a lease, the vote is weighted by the caller's share.
+        ///
+        /// # Args:
+        /// * `origin` - (<T as frame_system::Config>::Origin):
+        ///     - The signature of the caller's coldkey, holding a share of the lease.
+        ///
+        /// * `lease_id` (LeaseId):
+        ///     - The ID of the lease to vote on the proposal of.
+        ///
+        /// * `aye` (bool):
+        ///     - Whether the caller votes for the action.
+        #[pallet::call_index(127)]
+        #[pallet::weight(SubnetLeasingWeightInfo::<T>::do_vote_lease_proposal())]
+        pub fn vote_lease_proposal(
+            origin: T::RuntimeOrigin,
+            lease_id: LeaseId,
+            aye: bool,
+        ) -> DispatchResult {
+            Self::do_vote_lease_proposal(origin, lease_id, aye)
+        }
+
+        /// Conclude the proposal of a lease and execute its action if approved.
+        ///
+        /// The proposal is approved when the contributors who voted for it hold more than half
+        /// of the contributors shares, it can be concluded early once its outcome is decided.
+        ///
+        /// # Args:
+        /// * `origin` - (<T as frame_system::Config>::Origin):
+        ///     - The signature of any account.
+        ///
+        /// * `lease_id` (LeaseId):
+        ///     - The ID of the lease to conclude the proposal of.
+        #[pallet::call_index(128)]
+        #[pallet::weight(SubnetLeasingWeightInfo::<T>::do_conclude_lease_proposal(T::MaxContributors::get()))]
+        pub fn conclude_lease_proposal(
+            origin: T::RuntimeOrigin,
+            lease_id: LeaseId,
+        ) -> DispatchResultWithPostInfo {
+            Self::do_conclude_lease_proposal(origin, lease_id)
+        }
+
+        /// Queue a burned registration until the end of the adjustment interval of a subnet,
+        /// on subnets where the burned registrations are not immediate.
+        ///
+        /// At the end of the interval up to `TargetRegistrationsPerInterval` queued registrations
+        /// are performed, picking the highest sealed bids or drawing the winners using the drand
+        /// randomness depending on the subnet mode. The others are refunded.
+        ///
+        /// # Args:
+        /// * `origin` - (<T as frame_system::Config>::Origin):
+        ///     - The signature of the coldkey paying for the registration.
+        ///
+        /// * `netuid` (NetUid):
+        ///     - The subnet to register on.
+        ///
+        /// * `hotkey` (T::AccountId):
+        ///     - The hotkey to register.
+        ///
+        /// * `bid` (TaoCurrency):
+        ///     - The maximum amount paid for the registration, held until the end of the interval.
+        ///       It can't be lower than the current burn.
+        ///
+        /// * `sealed_bid` (Option<SealedBid>):
+        ///     - The bid in the highest bid mode, ignored in the random mode. The steps are:
+        ///       1. Instantiate [`crate::subnets::registration::BurnedRegistrationBidTlockPayload`]
+        ///       2. Serialize it using the `parity_scale_codec::Encode` trait
+        ///       3. Encrypt it to a drand round after the current one and before the end of the
+        ///          interval, to produce a [`TLECiphertext<TinyBLS381>`] type.
+        ///       4. Serialize and compress using the `ark-serialize` `CanonicalSerialize` trait.
+        ///
+        ///       A bid that can't be revealed, is higher than `bid` or lower than the burn at the
+        ///       end of the interval is refunded.
+        #[pallet::call_index(129)]
+        #[pallet::weight((Weight::from_parts(48_630_000, 0)
+		.saturating_add(T::DbWeight::get().reads(10_u64))
+		.saturating_add(T::DbWeight::get().writes(4_u64)), DispatchClass::Normal, Pays::Yes))]
+        pub fn queue_burned_registration(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            hotkey: T::AccountId,
+            bid: TaoCurrency,
+            sealed_bid: Option<SealedBid>,
+        ) -> DispatchResult {
+            Self::do_queue_burned_registration(origin, netuid, hotkey, bid, sealed_bid)
+        }
+
+        /// Set the stake positions the coldkey pays transaction fees in alpha from.
+        ///
+        /// When the free balance of the coldkey can't cover the fee of a call, the fee is charged
+        /// in alpha, sold on the pool of the subnet. The stake positions implied by the call
+        /// (unstaking, moving, swapping...) are tried first, then these positions in order. The
+        /// first position holding enough alpha to cover the whole fee is charged.
+        ///
+        /// The positions staking to a hotkey owned by the coldkey also pay the fees of the calls
+        /// signed by that hotkey (weights, axon...) when its free balance can't cover them.
+        ///
+        /// The fee of this call can be paid in alpha from the first of the given sources, so that
+        /// coldkeys holding only alpha can set their sources.
+        ///
+        /// # Args:
+        /// * `origin` - (<T as frame_system::Config>::Origin):
+        ///     - The signature of the caller's coldkey.
+        ///
+        /// * `sources` (BoundedVec<(T::AccountId, NetUid), MAX_FEE_ALPHA_SOURCES>):
+        ///     - The (hotkey, netuid) stake positions, in order of preference. An empty list
+        ///       clears the sources.
+        #[pallet::call_index(130)]
+        #[pallet::weight((Weight::from_parts(8_340_000, 0)
+		.saturating_add(T::DbWeight::get().reads(MAX_FEE_ALPHA_SOURCES as u64))
+		.saturating_add(T::DbWeight::get().writes(1_u64)), DispatchClass::Normal, Pays::Yes))]
+        pub fn set_fee_alpha_sources(
+            origin: OriginFor<T>,
+            sources: BoundedVec<(T::AccountId, NetUid), ConstU32<MAX_FEE_ALPHA_SOURCES>>,
+        ) -> DispatchResult {
+            Self::do_set_fee_alpha_sources(origin, sources)
+        }
+
+        /// Transfer TAO to the paymaster of a subnet, which pays the transaction fees of the
+        /// calls sponsored by the subnet.
+        ///
+        /// # Args:
+        /// * `origin` - (<T as frame_system::Config>::Origin):
+        ///     - The signature of the account funding the paymaster.
+        ///
+        /// * `netuid` (NetUid):
+        ///     - The subnet of the paymaster.
+        ///
+        /// * `amount` (TaoCurrency):
+        ///     - The amount of TAO to transfer.
+        #[pallet::call_index(131)]
+        #[pallet::weight((Weight::from_parts(46_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(3_u64))
+		.saturating_add(T::DbWeight::get().writes(2_u64)), DispatchClass::Normal, Pays::Yes))]
+        pub fn fund_paymaster(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            amount: TaoCurrency,
+        ) -> DispatchResult {
+            Self::do_fund_paymaster(origin, netuid, amount)
+        }
+
+        /// Transfer TAO from the paymaster of a subnet to the subnet owner.
+        ///
+        /// # Args:
+        /// * `origin` - (<T as frame_system::Config>::Origin):
+        ///     - The signature of the subnet owner.
+        ///
+        /// * `netuid` (NetUid):
+        ///     - The subnet of the paymaster.
+        ///
+        /// * `amount` (TaoCurrency):
+        ///     - The amount of TAO to withdraw.
+        #[pallet::call_index(132)]
+        #[pallet::weight((Weight::from_parts(46_000_000, 0)
+		.saturating_add(T::DbWeight::get().reads(3_u64))
+		.saturating_add(T::DbWeight::get().writes(2_u64)), DispatchClass::Normal, Pays::Yes))]
+        pub fn withdraw_paymaster(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            amount: TaoCurrency,
+        ) -> DispatchResult {
+            Self::do_withdraw_paymaster(origin, netuid, amount)
+        }
     }
 }
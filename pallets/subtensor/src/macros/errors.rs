@@ -260,5 +260,69 @@ mod errors {
         UidMapCouldNotBeCleared,
         /// Trimming would exceed the max immune neurons percentage
         TrimmingWouldExceedMaxImmunePercentage,
+        /// The stake swap route must contain at least two subnets
+        InvalidSwapRoute,
+        /// The stake is locked by a vesting schedule
+        StakeVestingLocked,
+        /// The vesting schedule parameters are invalid
+        InvalidVestingSchedule,
+        /// The stake position has reached the maximum number of vesting schedules
+        TooManyVestingSchedules,
+        /// The drand beacon is not tracked by the drand pallet
+        UnknownDrandBeacon,
+        /// The subnet has timelocked weight commits that are not revealed yet
+        TimelockedWeightCommitsPending,
+        /// The account has no share in the lease.
+        NoLeaseShare,
+        /// The lease share transfer is invalid.
+        InvalidLeaseShareTransfer,
+        /// The lease has reached the maximum number of shareholders.
+        TooManyLeaseShareholders,
+        /// There are no lease dividends to claim.
+        NoLeaseDividendsToClaim,
+        /// The lease governance is not enabled.
+        LeaseGovernanceNotEnabled,
+        /// The lease governance is already enabled.
+        LeaseGovernanceAlreadyEnabled,
+        /// The lease has ended.
+        LeaseHasEnded,
+        /// The lease action is invalid.
+        InvalidLeaseAction,
+        /// A lease proposal is in progress.
+        LeaseProposalInProgress,
+        /// The lease has no proposal.
+        NoLeaseProposal,
+        /// The voting on the lease proposal has ended.
+        LeaseProposalVotingEnded,
+        /// The voting on the lease proposal has not ended.
+        LeaseProposalVotingNotEnded,
+        /// The account has already voted on the lease proposal.
+        AlreadyVotedOnLeaseProposal,
+        /// The share of the account is too low to propose a lease action.
+        LeaseShareTooLowToPropose,
+        /// The account proposed a lease action too recently.
+        LeaseProposalRateLimitExceeded,
+        /// The burned registrations on the subnet are queued until the end of the adjustment interval.
+        BurnedRegistrationIsQueued,
+        /// The burned registrations on the subnet are not queued.
+        BurnedRegistrationNotQueued,
+        /// The hotkey already has a queued burned registration on the subnet.
+        BurnedRegistrationAlreadyQueued,
+        /// The bid is lower than the burn of the subnet.
+        BurnedRegistrationBidTooLow,
+        /// Too many burned registrations are queued on the subnet.
+        TooManyPendingBurnedRegistrations,
+        /// Too many burned registrations are queued on the subnet by the coldkey.
+        TooManyPendingBurnedRegistrationsForColdkey,
+        /// The drand round drawing the queued burned registrations is known, the queue is closed until they are resolved.
+        BurnedRegistrationQueueClosed,
+        /// The bids of the queued burned registrations on the subnet must be sealed.
+        SealedBidRequired,
+        /// The sealed bid must be revealed at a drand round after the current one and before the end of the adjustment interval.
+        InvalidSealedBidRevealRound,
+        /// The same fee alpha source has been provided more than once.
+        DuplicateFeeAlphaSource,
+        /// Too many calls sponsored by the subnet paymaster.
+        TooManySponsoredCalls,
     }
 }
@@ -300,6 +300,36 @@ mod events {
         /// (coldkey, hotkey, origin_netuid, destination_netuid, amount)
         StakeSwapped(T::AccountId, T::AccountId, NetUid, NetUid, TaoCurrency),
 
+        /// Stake has been swapped along a route of subnets for the same coldkey-hotkey pair.
+        ///
+        /// Parameters:
+        /// (coldkey, hotkey, route, alpha_in, alpha_out)
+        StakeRouteSwapped(
+            T::AccountId,
+            T::AccountId,
+            Vec<NetUid>,
+            AlphaCurrency,
+            AlphaCurrency,
+        ),
+
+        /// Stake has been granted to a beneficiary under a vesting schedule.
+        StakeVestingGranted {
+            /// Coldkey that granted the stake.
+            grantor: T::AccountId,
+            /// Coldkey receiving the vested stake.
+            beneficiary: T::AccountId,
+            /// Hotkey the stake is delegated to.
+            hotkey: T::AccountId,
+            /// Subnet identifier.
+            netuid: NetUid,
+            /// Amount of alpha granted.
+            amount: AlphaCurrency,
+            /// Number of blocks before anything unlocks.
+            cliff: u64,
+            /// Number of blocks until the whole amount is unlocked.
+            duration: u64,
+        },
+
         /// Event called when transfer is toggled on a subnet.
         ///
         /// Parameters:
@@ -391,6 +421,121 @@ mod events {
             netuid: NetUid,
         },
 
+        /// A share of a subnet lease has been transferred.
+        LeaseShareTransferred {
+            /// The lease ID
+            lease_id: LeaseId,
+            /// The account transferring the share.
+            from: T::AccountId,
+            /// The account receiving the share.
+            to: T::AccountId,
+            /// The transferred share of the contributors dividends.
+            share: U64F64,
+        },
+
+        /// The dividends of a subnet lease have been claimed by a contributor.
+        LeaseDividendsClaimed {
+            /// The lease ID
+            lease_id: LeaseId,
+            /// The contributor claiming the dividends.
+            contributor: T::AccountId,
+            /// The amount of tao claimed.
+            amount: TaoCurrency,
+            /// The hotkey the dividends have been staked into, if any.
+            hotkey: Option<T::AccountId>,
+        },
+
+        /// The governance of a subnet lease by its contributors has been enabled.
+        LeaseGovernanceEnabled {
+            /// The lease ID
+            lease_id: LeaseId,
+        },
+
+        /// An action has been proposed to the contributors of a subnet lease.
+        LeaseActionProposed {
+            /// The lease ID
+            lease_id: LeaseId,
+            /// The contributor proposing the action.
+            proposer: T::AccountId,
+            /// The proposed action.
+            action: LeaseAction<T::AccountId>,
+        },
+
+        /// A contributor has voted on the proposal of a subnet lease.
+        LeaseProposalVoted {
+            /// The lease ID
+            lease_id: LeaseId,
+            /// The contributor voting.
+            voter: T::AccountId,
+            /// Whether the contributor voted for the action.
+            aye: bool,
+            /// The share of the contributor weighting the vote.
+            share: U64F64,
+        },
+
+        /// The proposal of a subnet lease has been concluded.
+        LeaseProposalConcluded {
+            /// The lease ID
+            lease_id: LeaseId,
+            /// The proposed action.
+            action: LeaseAction<T::AccountId>,
+            /// Whether the action has been approved and executed.
+            approved: bool,
+        },
+
+        /// A burned registration has been queued until the end of the adjustment interval.
+        BurnedRegistrationQueued {
+            /// The subnet ID
+            netuid: NetUid,
+            /// The coldkey paying for the registration.
+            coldkey: T::AccountId,
+            /// The hotkey to register.
+            hotkey: T::AccountId,
+            /// The amount of tao held in escrow from the coldkey.
+            amount: TaoCurrency,
+        },
+
+        /// The tao held for a queued burned registration has been refunded, all of it if the
+        /// registration has not been performed, the excess over the revealed bid otherwise.
+        BurnedRegistrationRefunded {
+            /// The subnet ID
+            netuid: NetUid,
+            /// The coldkey refunded.
+            coldkey: T::AccountId,
+            /// The hotkey of the registration.
+            hotkey: T::AccountId,
+            /// The amount of tao refunded.
+            amount: TaoCurrency,
+        },
+
+        /// The stake positions a coldkey pays transaction fees in alpha from have been set.
+        FeeAlphaSourcesSet {
+            /// The coldkey paying the fees.
+            coldkey: T::AccountId,
+            /// The (hotkey, netuid) stake positions, in order of preference.
+            sources: Vec<(T::AccountId, NetUid)>,
+        },
+
+        /// The paymaster of a subnet has been funded.
+        PaymasterFunded {
+            /// The subnet ID
+            netuid: NetUid,
+            /// The account funding the paymaster.
+            who: T::AccountId,
+            /// The amount of tao transferred to the paymaster.
+            amount: TaoCurrency,
+        },
+
+        /// The subnet owner has withdrawn funds from the paymaster of the subnet.
+        PaymasterWithdrawn {
+            /// The subnet ID
+            netuid: NetUid,
+            /// The subnet owner.
+            who: T::AccountId,
+            /// The amount of tao transferred from the paymaster.
+            amount: TaoCurrency,
+        },
+
         /// The symbol for a subnet has been updated.
         SymbolUpdated {
             /// The subnet ID
@@ -442,5 +587,11 @@ mod events {
 
         /// The minimum allowed UIDs for a subnet have been set.
         MinAllowedUidsSet(NetUid, u16),
+
+        /// The drand beacon of timelocked weight commits has been set.
+        ///
+        /// - **netuid**: The network identifier.
+        /// - **beacon**: The chain hash of the beacon, `None` for the default beacon.
+        TimelockedWeightsBeaconSet(NetUid, Option<ChainHash>),
     }
 }
@@ -16,6 +16,14 @@ mod hooks {
         // 		- The number of the block we are initializing.
         fn on_initialize(block_number: BlockNumberFor<T>) -> Weight {
             let hotkey_swap_clean_up_weight = Self::clean_up_hotkey_swap_records(block_number);
+            // Computed before the block step because the epochs reset their counters.
+            let emission_ledger_weight =
+                Self::emission_ledger_weight(Self::get_current_block_as_u64());
+            // Computed before the block step because the adjustments resolve the queues.
+            let pending_burned_registrations_weight =
+                Self::pending_burned_registrations_weight(Self::get_current_block_as_u64());
+            // Computed before the block step because the epochs reset their counters.
+            let epoch_debug_weight = Self::epoch_debug_weight(Self::get_current_block_as_u64());
 
             let block_step_result = Self::block_step();
             match block_step_result {
@@ -26,6 +34,9 @@ mod hooks {
                         .saturating_add(T::DbWeight::get().reads(8304_u64))
                         .saturating_add(T::DbWeight::get().writes(110_u64))
                         .saturating_add(hotkey_swap_clean_up_weight)
+                        .saturating_add(emission_ledger_weight)
+                        .saturating_add(pending_burned_registrations_weight)
+                        .saturating_add(epoch_debug_weight)
                 }
                 Err(e) => {
                     // --- If the block step was unsuccessful, return the weight anyway.
@@ -34,6 +45,9 @@ mod hooks {
                         .saturating_add(T::DbWeight::get().reads(8304_u64))
                         .saturating_add(T::DbWeight::get().writes(110_u64))
                         .saturating_add(hotkey_swap_clean_up_weight)
+                        .saturating_add(emission_ledger_weight)
+                        .saturating_add(pending_burned_registrations_weight)
+                        .saturating_add(epoch_debug_weight)
                 }
             }
         }
@@ -147,7 +161,9 @@ mod hooks {
                 // Migrate Lock Reduction Interval
                 .saturating_add(migrations::migrate_network_lock_reduction_interval::migrate_network_lock_reduction_interval::<T>())
                 // Migrate subnet locked balances
-                .saturating_add(migrations::migrate_subnet_locked::migrate_restore_subnet_locked::<T>());
+                .saturating_add(migrations::migrate_subnet_locked::migrate_restore_subnet_locked::<T>())
+                // Migrate the total shares of the leases
+                .saturating_add(migrations::migrate_lease_total_shares::migrate_lease_total_shares::<T>());
             weight
         }
 
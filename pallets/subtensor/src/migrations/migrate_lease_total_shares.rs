@@ -0,0 +1,46 @@
+use super::*;
+use frame_support::{traits::Get, weights::Weight};
+use log;
+use scale_info::prelude::string::String;
+use substrate_fixed::types::U64F64;
+
+/// Populate the total shares of the existing leases, used to keep the contributors
+/// dividends in the lease coldkey until they are claimed.
+pub fn migrate_lease_total_shares<T: Config>() -> Weight {
+    let migration_name = b"migrate_lease_total_shares".to_vec();
+    let mut weight = T::DbWeight::get().reads(1);
+
+    if HasMigrationRun::<T>::get(&migration_name) {
+        log::info!(
+            target: "runtime",
+            "Migration '{}' already run - skipping.",
+            String::from_utf8_lossy(&migration_name)
+        );
+        return weight;
+    }
+
+    for lease_id in SubnetLeases::<T>::iter_keys() {
+        let mut shareholders = 0u64;
+        let total_shares = SubnetLeaseShares::<T>::iter_prefix_values(lease_id).fold(
+            U64F64::from_num(0),
+            |total, share| {
+                shareholders = shareholders.saturating_add(1);
+                total.saturating_add(share)
+            },
+        );
+        SubnetLeaseTotalShares::<T>::insert(lease_id, total_shares);
+        weight = weight
+            .saturating_add(T::DbWeight::get().reads_writes(shareholders.saturating_add(1), 1));
+    }
+
+    HasMigrationRun::<T>::insert(&migration_name, true);
+    weight = weight.saturating_add(T::DbWeight::get().writes(1));
+
+    log::info!(
+        target: "runtime",
+        "Migration '{}' completed.",
+        String::from_utf8_lossy(&migration_name),
+    );
+
+    weight
+}
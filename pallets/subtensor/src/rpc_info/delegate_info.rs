@@ -2,14 +2,15 @@ use super::*;
 use frame_support::IterableStorageMap;
 use frame_support::pallet_prelude::{Decode, Encode};
 use safe_math::*;
+use serde::Serialize;
 use substrate_fixed::types::U64F64;
 extern crate alloc;
 use alloc::collections::BTreeMap;
 use codec::Compact;
 use subtensor_runtime_common::{AlphaCurrency, NetUid};
 
-#[freeze_struct("1fafc4fcf28cba7a")]
-#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+#[freeze_struct("d31c6548912c6cc4")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, Serialize, TypeInfo)]
 pub struct DelegateInfo<AccountId: TypeInfo + Encode + Decode> {
     pub delegate_ss58: AccountId,
     pub take: Compact<u16>,
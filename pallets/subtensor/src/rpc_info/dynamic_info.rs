@@ -2,12 +2,13 @@ use super::*;
 extern crate alloc;
 use codec::Compact;
 use frame_support::pallet_prelude::{Decode, Encode};
+use serde::Serialize;
 use substrate_fixed::types::I96F32;
 use subtensor_macros::freeze_struct;
 use subtensor_runtime_common::{AlphaCurrency, NetUid, TaoCurrency};
 
-#[freeze_struct("e526a1c6d2303d32")]
-#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+#[freeze_struct("9c7597e7f3711a33")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, Serialize, TypeInfo)]
 pub struct DynamicInfo<AccountId: TypeInfo + Encode + Decode> {
     netuid: Compact<NetUid>,
     owner_hotkey: AccountId,
@@ -29,6 +30,7 @@ pub struct DynamicInfo<AccountId: TypeInfo + Encode + Decode> {
     subnet_volume: Compact<u128>,
     network_registered_at: Compact<u64>,
     subnet_identity: Option<SubnetIdentityV3>,
+    #[serde(serialize_with = "serialize_fixed")]
     moving_price: I96F32,
 }
 
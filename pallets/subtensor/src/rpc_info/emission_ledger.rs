@@ -0,0 +1,24 @@
+use super::*;
+extern crate alloc;
+use subtensor_runtime_common::NetUid;
+
+impl<T: Config> Pallet<T> {
+    /// Returns the payouts of the last `EMISSION_LEDGER_LENGTH` epochs to a hotkey on a subnet,
+    /// oldest first.
+    pub fn get_emission_ledger(netuid: NetUid, hotkey: T::AccountId) -> Vec<EmissionLedgerEntry> {
+        EmissionLedger::<T>::get(netuid, hotkey).into()
+    }
+
+    /// Returns the emission ledgers of a hotkey on every subnet it has been paid on.
+    pub fn get_emission_ledgers_for_hotkey(
+        hotkey: T::AccountId,
+    ) -> Vec<(NetUid, Vec<EmissionLedgerEntry>)> {
+        Self::get_all_subnet_netuids()
+            .into_iter()
+            .filter_map(|netuid| {
+                let ledger = Self::get_emission_ledger(netuid, hotkey.clone());
+                (!ledger.is_empty()).then_some((netuid, ledger))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,11 @@
+use super::*;
+use subtensor_runtime_common::{MechId, NetUid};
+
+impl<T: Config> Pallet<T> {
+    /// Returns the intermediate Yuma terms of the last epoch of a subnet mechanism, if epoch
+    /// debugging is enabled on the subnet and an epoch ran since.
+    pub fn get_epoch_debug(netuid: NetUid, mecid: MechId) -> Option<EpochDebugSnapshot> {
+        Self::ensure_mechanism_exists(netuid, mecid).ok()?;
+        EpochDebug::<T>::get(Self::get_mechanism_storage_index(netuid, mecid))
+    }
+}
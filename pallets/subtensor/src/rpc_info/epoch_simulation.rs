@@ -0,0 +1,151 @@
+use super::*;
+extern crate alloc;
+use codec::Compact;
+use frame_support::pallet_prelude::{Decode, Encode};
+use frame_support::storage::{TransactionOutcome, with_transaction};
+use subtensor_runtime_common::{AlphaCurrency, MechId, NetUid};
+
+/// Hyperparameter values to use instead of the current ones when simulating an epoch.
+/// `None` keeps the current value of the subnet.
+#[freeze_struct("f7bbe9abfc5b4f2f")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, Default, TypeInfo)]
+pub struct EpochHyperparamOverrides {
+    pub kappa: Option<u16>,
+    pub rho: Option<u16>,
+    pub bonds_moving_average: Option<u64>,
+    pub bonds_penalty: Option<u16>,
+    pub alpha_values: Option<(u16, u16)>,
+    pub alpha_sigmoid_steepness: Option<i16>,
+    pub liquid_alpha_enabled: Option<bool>,
+    pub yuma3_enabled: Option<bool>,
+    pub activity_cutoff: Option<u16>,
+    pub max_allowed_validators: Option<u16>,
+}
+
+/// Epoch output of one UID, as computed by a simulated epoch.
+#[freeze_struct("79dbd10906f9119a")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+pub struct SimulatedEpochTerms<AccountId: TypeInfo + Encode + Decode> {
+    pub hotkey: AccountId,
+    pub uid: Compact<u16>,
+    pub active: bool,
+    pub stake_weight: Compact<u16>,
+    pub rank: Compact<u16>,
+    pub trust: Compact<u16>,
+    pub consensus: Compact<u16>,
+    pub incentive: Compact<u16>,
+    pub dividend: Compact<u16>,
+    pub validator_trust: Compact<u16>,
+    pub pruning_score: Compact<u16>,
+    pub new_validator_permit: bool,
+    pub emission: Compact<AlphaCurrency>,
+    pub server_emission: Compact<AlphaCurrency>,
+    pub validator_emission: Compact<AlphaCurrency>,
+    pub bonds: Vec<(Compact<u16>, Compact<u16>)>,
+}
+
+impl<T: Config> Pallet<T> {
+    /// Runs the epoch of a subnet mechanism on the current state with the given hyperparameter
+    /// and weight overrides, and returns the per-UID epoch terms sorted by UID.
+    ///
+    /// Weight overrides replace the weights row of the given validator UIDs, as if they had been
+    /// set in the current block. Nothing is persisted: all overrides and epoch side effects are rolled back.
+    ///
+    /// Returns `None` if the mechanism does not exist or a weight override references a
+    /// non-existent UID.
+    pub fn simulate_epoch(
+        netuid: NetUid,
+        mecid: MechId,
+        rao_emission: AlphaCurrency,
+        overrides: EpochHyperparamOverrides,
+        weights: Vec<(u16, Vec<(u16, u16)>)>,
+    ) -> Option<Vec<SimulatedEpochTerms<T::AccountId>>> {
+        if Self::ensure_mechanism_exists(netuid, mecid).is_err() {
+            return None;
+        }
+
+        let n = Self::get_subnetwork_n(netuid);
+        let weights_valid = weights
+            .iter()
+            .all(|(uid, row)| *uid < n && row.iter().all(|(dest, _)| *dest < n));
+        if !weights_valid {
+            return None;
+        }
+
+        with_transaction(|| {
+            Self::apply_epoch_hyperparam_overrides(netuid, &overrides);
+
+            // Overridden weights are treated as if they were set in the current block.
+            let netuid_index = Self::get_mechanism_storage_index(netuid, mecid);
+            let current_block = Self::get_current_block_as_u64();
+            for (uid, row) in weights.iter() {
+                Weights::<T>::insert(netuid_index, uid, row.clone());
+                Self::set_last_update_for_uid(netuid_index, *uid, current_block);
+            }
+
+            let output = Self::epoch_mechanism(netuid, mecid, rao_emission);
+            let mut terms: Vec<SimulatedEpochTerms<T::AccountId>> = output
+                .into_iter()
+                .map(|(hotkey, terms)| SimulatedEpochTerms {
+                    hotkey,
+                    uid: u16::try_from(terms.uid).unwrap_or_default().into(),
+                    active: terms.active,
+                    stake_weight: terms.stake_weight.into(),
+                    rank: terms.rank.into(),
+                    trust: terms.trust.into(),
+                    consensus: terms.consensus.into(),
+                    incentive: terms.incentive.into(),
+                    dividend: terms.dividend.into(),
+                    validator_trust: terms.validator_trust.into(),
+                    pruning_score: terms.pruning_score.into(),
+                    new_validator_permit: terms.new_validator_permit,
+                    emission: terms.emission.into(),
+                    server_emission: terms.server_emission.into(),
+                    validator_emission: terms.validator_emission.into(),
+                    bonds: terms
+                        .bond
+                        .into_iter()
+                        .map(|(uid, bond)| (uid.into(), bond.into()))
+                        .collect(),
+                })
+                .collect();
+            terms.sort_by_key(|t| t.uid.0);
+
+            TransactionOutcome::Rollback(Ok::<_, DispatchError>(terms))
+        })
+        .ok()
+    }
+
+    fn apply_epoch_hyperparam_overrides(netuid: NetUid, overrides: &EpochHyperparamOverrides) {
+        if let Some(kappa) = overrides.kappa {
+            Kappa::<T>::insert(netuid, kappa);
+        }
+        if let Some(rho) = overrides.rho {
+            Rho::<T>::insert(netuid, rho);
+        }
+        if let Some(bonds_moving_average) = overrides.bonds_moving_average {
+            BondsMovingAverage::<T>::insert(netuid, bonds_moving_average);
+        }
+        if let Some(bonds_penalty) = overrides.bonds_penalty {
+            BondsPenalty::<T>::insert(netuid, bonds_penalty);
+        }
+        if let Some(alpha_values) = overrides.alpha_values {
+            AlphaValues::<T>::insert(netuid, alpha_values);
+        }
+        if let Some(steepness) = overrides.alpha_sigmoid_steepness {
+            AlphaSigmoidSteepness::<T>::insert(netuid, steepness);
+        }
+        if let Some(enabled) = overrides.liquid_alpha_enabled {
+            LiquidAlphaOn::<T>::insert(netuid, enabled);
+        }
+        if let Some(enabled) = overrides.yuma3_enabled {
+            Yuma3On::<T>::insert(netuid, enabled);
+        }
+        if let Some(activity_cutoff) = overrides.activity_cutoff {
+            ActivityCutoff::<T>::insert(netuid, activity_cutoff);
+        }
+        if let Some(max_allowed_validators) = overrides.max_allowed_validators {
+            MaxAllowedValidators::<T>::insert(netuid, max_allowed_validators);
+        }
+    }
+}
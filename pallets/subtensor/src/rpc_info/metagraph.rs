@@ -5,13 +5,14 @@ use codec::Compact;
 use frame_support::IterableStorageDoubleMap;
 use frame_support::pallet_prelude::{Decode, Encode};
 use pallet_commitments::GetCommitments;
+use serde::Serialize;
 use substrate_fixed::types::I64F64;
 use substrate_fixed::types::I96F32;
 use subtensor_macros::freeze_struct;
 use subtensor_runtime_common::{AlphaCurrency, MechId, NetUid, NetUidStorageIndex, TaoCurrency};
 
-#[freeze_struct("6fc49d5a7dc0e339")]
-#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+#[freeze_struct("7816ae50037da0d7")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, Serialize, TypeInfo)]
 pub struct Metagraph<AccountId: TypeInfo + Encode + Decode> {
     // Subnet index
     netuid: Compact<NetUid>,
@@ -43,7 +44,8 @@ pub struct Metagraph<AccountId: TypeInfo + Encode + Decode> {
     pending_alpha_emission: Compact<AlphaCurrency>, // pending alpha to be distributed
     pending_root_emission: Compact<TaoCurrency>, // pending tao for root divs to be distributed
     subnet_volume: Compact<u128>,      // volume of the subnet in TAO
-    moving_price: I96F32,              // subnet moving price.
+    #[serde(serialize_with = "serialize_fixed")]
+    moving_price: I96F32, // subnet moving price.
 
     // Hparams for epoch
     rho: Compact<u16>,   // subnet rho param
@@ -110,8 +112,8 @@ pub struct Metagraph<AccountId: TypeInfo + Encode + Decode> {
     alpha_dividends_per_hotkey: Vec<(AccountId, Compact<AlphaCurrency>)>, // List of dividend payout in alpha via subnet.
 }
 
-#[freeze_struct("56156d51c66190e8")]
-#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+#[freeze_struct("82345ac063aeea9a")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, Serialize, TypeInfo)]
 pub struct SelectiveMetagraph<AccountId: TypeInfo + Encode + Decode + Clone> {
     // Subnet index
     netuid: Compact<NetUid>,
@@ -143,7 +145,8 @@ pub struct SelectiveMetagraph<AccountId: TypeInfo + Encode + Decode + Clone> {
     pending_alpha_emission: Option<Compact<AlphaCurrency>>, // pending alpha to be distributed
     pending_root_emission: Option<Compact<TaoCurrency>>, // panding tao for root divs to be distributed
     subnet_volume: Option<Compact<u128>>,                // volume of the subnet in TAO
-    moving_price: Option<I96F32>,                        // subnet moving price.
+    #[serde(serialize_with = "serialize_optional_fixed")]
+    moving_price: Option<I96F32>, // subnet moving price.
 
     // Hparams for epoch
     rho: Option<Compact<u16>>,   // subnet rho param
@@ -1,8 +1,34 @@
 use super::*;
 pub mod delegate_info;
 pub mod dynamic_info;
+pub mod emission_ledger;
+pub mod epoch_debug;
+pub mod epoch_simulation;
 pub mod metagraph;
 pub mod neuron_info;
 pub mod show_subnet;
 pub mod stake_info;
 pub mod subnet_info;
+
+use core::fmt::Display;
+use serde::Serializer;
+
+/// Serializes a fixed point number as a decimal string, so that JSON clients don't lose
+/// precision.
+pub(crate) fn serialize_fixed<S: Serializer, F: Display>(
+    value: &F,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+/// Serializes an optional fixed point number as a decimal string or `null`.
+pub(crate) fn serialize_optional_fixed<S: Serializer, F: Display>(
+    value: &Option<F>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => serializer.collect_str(value),
+        None => serializer.serialize_none(),
+    }
+}
@@ -1,11 +1,12 @@
 use super::*;
 use frame_support::pallet_prelude::{Decode, Encode};
+use serde::Serialize;
 extern crate alloc;
 use codec::Compact;
 use subtensor_runtime_common::{AlphaCurrency, NetUid, NetUidStorageIndex};
 
-#[freeze_struct("9e5a291e7e71482d")]
-#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+#[freeze_struct("a7dc0ecc30251d0")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, Serialize, TypeInfo)]
 pub struct NeuronInfo<AccountId: TypeInfo + Encode + Decode> {
     hotkey: AccountId,
     coldkey: AccountId,
@@ -29,8 +30,8 @@ pub struct NeuronInfo<AccountId: TypeInfo + Encode + Decode> {
     pruning_score: Compact<u16>,
 }
 
-#[freeze_struct("b9fdff7fc6e023c7")]
-#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+#[freeze_struct("6d32c1f1b8f2efb1")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, Serialize, TypeInfo)]
 pub struct NeuronInfoLite<AccountId: TypeInfo + Encode + Decode> {
     hotkey: AccountId,
     coldkey: AccountId,
@@ -3,11 +3,12 @@ extern crate alloc;
 use crate::epoch::math::*;
 use codec::Compact;
 use frame_support::pallet_prelude::{Decode, Encode};
+use serde::Serialize;
 use substrate_fixed::types::I64F64;
 use subtensor_runtime_common::{AlphaCurrency, NetUid, NetUidStorageIndex, TaoCurrency};
 
-#[freeze_struct("9354762261420485")]
-#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+#[freeze_struct("42a09a553edda189")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, Serialize, TypeInfo)]
 pub struct SubnetState<AccountId: TypeInfo + Encode + Decode> {
     netuid: Compact<NetUid>,
     hotkeys: Vec<AccountId>,
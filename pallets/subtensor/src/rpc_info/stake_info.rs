@@ -46,12 +46,15 @@ impl<T: Config> Pallet<T> {
                     let tao_emission = TaoDividendsPerSubnet::<T>::get(*netuid_i, &hotkey_i);
                     let is_registered: bool =
                         Self::is_hotkey_registered_on_network(*netuid_i, hotkey_i);
+                    let locked = Self::get_locked_stake_for_hotkey_and_coldkey_on_subnet(
+                        hotkey_i, coldkey_i, *netuid_i,
+                    );
                     stake_info_for_coldkey.push(StakeInfo {
                         hotkey: hotkey_i.clone(),
                         coldkey: coldkey_i.clone(),
                         netuid: (*netuid_i).into(),
                         stake: alpha.into(),
-                        locked: 0.into(),
+                        locked: locked.to_u64().into(),
                         emission: emission.into(),
                         tao_emission: tao_emission.into(),
                         drain: 0.into(),
@@ -103,13 +106,18 @@ impl<T: Config> Pallet<T> {
         let emission = AlphaDividendsPerSubnet::<T>::get(netuid, &hotkey_account);
         let tao_emission = TaoDividendsPerSubnet::<T>::get(netuid, &hotkey_account);
         let is_registered: bool = Self::is_hotkey_registered_on_network(netuid, &hotkey_account);
+        let locked = Self::get_locked_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey_account,
+            &coldkey_account,
+            netuid,
+        );
 
         Some(StakeInfo {
             hotkey: hotkey_account,
             coldkey: coldkey_account,
             netuid: (netuid).into(),
             stake: alpha.into(),
-            locked: 0.into(),
+            locked: locked.to_u64().into(),
             emission: emission.into(),
             tao_emission: tao_emission.into(),
             drain: 0.into(),
@@ -1,13 +1,14 @@
 use super::*;
 use frame_support::pallet_prelude::{Decode, Encode};
 use frame_support::storage::IterableStorageMap;
+use serde::Serialize;
 extern crate alloc;
 use codec::Compact;
 use substrate_fixed::types::I32F32;
 use subtensor_runtime_common::{NetUid, TaoCurrency};
 
-#[freeze_struct("edd6bd3273dfea76")]
-#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+#[freeze_struct("ac548faad1387dce")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, Serialize, TypeInfo)]
 pub struct SubnetInfo<AccountId: TypeInfo + Encode + Decode> {
     netuid: Compact<NetUid>,
     rho: Compact<u16>,
@@ -29,8 +30,8 @@ pub struct SubnetInfo<AccountId: TypeInfo + Encode + Decode> {
     owner: AccountId,
 }
 
-#[freeze_struct("e5f66b14b33331c3")]
-#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+#[freeze_struct("1423e0c468f961c4")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, Serialize, TypeInfo)]
 pub struct SubnetInfov2<AccountId: TypeInfo + Encode + Decode> {
     netuid: Compact<NetUid>,
     rho: Compact<u16>,
@@ -53,8 +54,8 @@ pub struct SubnetInfov2<AccountId: TypeInfo + Encode + Decode> {
     identity: Option<SubnetIdentityV3>,
 }
 
-#[freeze_struct("24f0815487879ed3")]
-#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+#[freeze_struct("25904dfd0cbe0540")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, Serialize, TypeInfo)]
 pub struct SubnetHyperparams {
     rho: Compact<u16>,
     kappa: Compact<u16>,
@@ -85,8 +86,8 @@ pub struct SubnetHyperparams {
     liquid_alpha_enabled: bool,
 }
 
-#[freeze_struct("2153c3f3bb01ef66")]
-#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+#[freeze_struct("240f06fe1e792df9")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, Serialize, TypeInfo)]
 pub struct SubnetHyperparamsV2 {
     rho: Compact<u16>,
     kappa: Compact<u16>,
@@ -115,6 +116,7 @@ pub struct SubnetHyperparamsV2 {
     alpha_high: Compact<u16>,
     alpha_low: Compact<u16>,
     liquid_alpha_enabled: bool,
+    #[serde(serialize_with = "serialize_fixed")]
     alpha_sigmoid_steepness: I32F32,
     yuma_version: Compact<u16>,
     subnet_is_active: bool,
@@ -0,0 +1,154 @@
+use super::*;
+use crate::{Error, system::ensure_signed};
+use frame_support::storage::with_storage_layer;
+use substrate_fixed::types::U96F32;
+use subtensor_runtime_common::{AlphaCurrency, NetUid};
+use subtensor_swap_interface::{OrderType, SwapHandler};
+
+impl<T: Config> Pallet<T> {
+    /// Sets the stake positions a coldkey pays transaction fees in alpha from when its free
+    /// balance can't cover them.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - The origin of the call (must be signed by the coldkey)
+    /// * `sources` - The (hotkey, netuid) stake positions, in order of preference. An empty
+    ///   list disables paying fees in alpha for the calls that don't imply a stake position.
+    ///
+    /// # Returns
+    ///
+    /// * `DispatchResult` - Success or error
+    pub(crate) fn do_set_fee_alpha_sources(
+        origin: T::RuntimeOrigin,
+        sources: BoundedVec<(T::AccountId, NetUid), ConstU32<MAX_FEE_ALPHA_SOURCES>>,
+    ) -> DispatchResult {
+        let coldkey = ensure_signed(origin)?;
+
+        for (i, source) in sources.iter().enumerate() {
+            ensure!(Self::if_subnet_exist(source.1), Error::<T>::SubnetNotExists);
+            ensure!(
+                !sources
+                    .iter()
+                    .skip(i.saturating_add(1))
+                    .any(|s| s == source),
+                Error::<T>::DuplicateFeeAlphaSource
+            );
+        }
+
+        let sources_vec = sources.to_vec();
+        if sources.is_empty() {
+            FeeAlphaSources::<T>::remove(&coldkey);
+        } else {
+            FeeAlphaSources::<T>::insert(&coldkey, sources);
+        }
+
+        log::debug!("FeeAlphaSourcesSet( coldkey:{coldkey:?}, sources:{sources_vec:?} )");
+        Self::deposit_event(Event::FeeAlphaSourcesSet {
+            coldkey,
+            sources: sources_vec,
+        });
+
+        Ok(())
+    }
+
+    /// Returns the stake position a transaction fee of `tao_fee` signed by `who` would be paid
+    /// from, as (coldkey, hotkey, netuid), and the amount of alpha charged, sold on the pool of
+    /// the subnet for at least `tao_fee`.
+    ///
+    /// The fee alpha sources of `who` are tried in order. If `owner_pays` is set and `who` is
+    /// a hotkey, the sources of its owner staking to it are tried next, so that the owner can
+    /// pay the fees of the hotkey (weights, axon...). The first position holding enough alpha
+    /// to cover the whole fee is selected. Returns `None` if none of them can.
+    pub fn get_fee_in_alpha(
+        who: &T::AccountId,
+        tao_fee: u64,
+        owner_pays: bool,
+    ) -> Option<(T::AccountId, T::AccountId, NetUid, AlphaCurrency)> {
+        let own_sources = FeeAlphaSources::<T>::get(who)
+            .into_iter()
+            .map(|(hotkey, netuid)| (who.clone(), hotkey, netuid));
+
+        let owner_sources = owner_pays
+            .then(|| Owner::<T>::try_get(who).ok())
+            .flatten()
+            .filter(|owner| owner != who)
+            .map(|owner| {
+                FeeAlphaSources::<T>::get(&owner)
+                    .into_iter()
+                    .filter(|(hotkey, _)| hotkey == who)
+                    .map(|(hotkey, netuid)| (owner.clone(), hotkey, netuid))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        own_sources
+            .chain(owner_sources)
+            .filter(|(_, _, netuid)| SubtokenEnabled::<T>::get(netuid))
+            .find_map(|(coldkey, hotkey, netuid)| {
+                let alpha_balance = Self::get_unlocked_stake_for_hotkey_and_coldkey_on_subnet(
+                    &hotkey, &coldkey, netuid,
+                );
+                let alpha_fee = Self::get_alpha_for_tao_fee(netuid, tao_fee)?;
+
+                (alpha_fee <= alpha_balance).then_some((coldkey, hotkey, netuid, alpha_fee))
+            })
+    }
+
+    /// Charges a transaction fee of `tao_fee` signed by `who` in alpha from the fee alpha
+    /// sources, see `get_fee_in_alpha`. The alpha is sold on the pool of the subnet and the tao
+    /// bought is burned. Returns whether the fee was charged.
+    pub fn withdraw_fee_in_alpha(who: &T::AccountId, tao_fee: u64, owner_pays: bool) -> bool {
+        let Some((coldkey, hotkey, netuid, alpha_fee)) =
+            Self::get_fee_in_alpha(who, tao_fee, owner_pays)
+        else {
+            return false;
+        };
+
+        let result = with_storage_layer(|| {
+            Self::unstake_from_subnet(
+                &hotkey,
+                &coldkey,
+                netuid,
+                alpha_fee,
+                T::SwapInterface::min_price().into(),
+                false,
+            )
+        });
+        match result {
+            Ok(tao) => {
+                TotalIssuance::<T>::mutate(|total| *total = total.saturating_sub(tao));
+                true
+            }
+            Err(e) => {
+                log::debug!("Failed to charge a fee of {tao_fee} in alpha on {netuid:?}: {e:?}");
+                false
+            }
+        }
+    }
+
+    /// The alpha to sell on the pool of a subnet to buy at least `tao_fee`, simulating the swap.
+    fn get_alpha_for_tao_fee(netuid: NetUid, tao_fee: u64) -> Option<AlphaCurrency> {
+        let alpha_price = T::SwapInterface::current_alpha_price(netuid.into());
+        let mut alpha_fee = U96F32::saturating_from_num(tao_fee)
+            .checked_div(alpha_price)?
+            .saturating_to_num::<u64>()
+            .max(1);
+
+        // The swap fees and slippage are small for transaction fees, so scaling the alpha up
+        // once by the missing proportion is enough to cover the fee
+        for _ in 0..2 {
+            let tao = T::SwapInterface::sim_swap(netuid.into(), OrderType::Sell, alpha_fee)
+                .ok()?
+                .amount_paid_out;
+            if tao >= tao_fee {
+                return Some(alpha_fee.into());
+            }
+            alpha_fee = u128::from(alpha_fee)
+                .saturating_mul(u128::from(tao_fee))
+                .checked_div(u128::from(tao.max(1)))
+                .and_then(|alpha| u64::try_from(alpha).ok())?
+                .saturating_add(1);
+        }
+        None
+    }
+}
@@ -2,6 +2,7 @@ use super::*;
 pub mod account;
 pub mod add_stake;
 pub mod decrease_take;
+pub mod fee_alpha;
 pub mod helpers;
 pub mod increase_take;
 pub mod move_stake;
@@ -9,3 +10,4 @@ pub mod recycle_alpha;
 pub mod remove_stake;
 pub mod set_children;
 pub mod stake_utils;
+pub mod vesting;
@@ -293,9 +293,99 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Swaps stake of the same `(coldkey, hotkey)` pair along a route of subnets, moving the
+    /// whole amount through every leg `route[i] -> route[i + 1]` in order.
+    ///
+    /// # Arguments
+    /// * `origin` - The origin of the transaction, which must be signed by the coldkey.
+    /// * `hotkey` - The hotkey whose stake is being swapped.
+    /// * `route` - The ordered list of subnets, starting at the origin subnet.
+    /// * `alpha_amount` - The amount of origin subnet Alpha to swap.
+    /// * `min_alpha_out` - The minimum amount of Alpha to receive on the last subnet.
+    ///
+    /// # Returns
+    /// * `DispatchResult` - Indicates success or failure.
+    ///
+    /// # Errors
+    /// This function returns an error if:
+    /// * The route contains less than two subnets.
+    /// * Any leg fails for the same reasons as `do_swap_stake`.
+    /// * The Alpha received on the last subnet is below `min_alpha_out`.
+    ///
+    /// # Events
+    /// Emits a `StakeRouteSwapped` event upon successful completion.
+    pub fn do_swap_stake_route(
+        origin: T::RuntimeOrigin,
+        hotkey: T::AccountId,
+        route: Vec<NetUid>,
+        alpha_amount: AlphaCurrency,
+        min_alpha_out: AlphaCurrency,
+    ) -> dispatch::DispatchResult {
+        // Ensure the extrinsic is signed by the coldkey.
+        let coldkey = ensure_signed(origin)?;
+
+        ensure!(route.len() >= 2, Error::<T>::InvalidSwapRoute);
+        let legs = route.len().saturating_sub(1);
+
+        // Move the stake leg by leg. Only the last leg sets the staking rate limit, the
+        // intermediate subnets have to be unstaked from again in the same block.
+        let mut leg_amount = alpha_amount;
+        for (index, leg) in route.windows(2).enumerate() {
+            let [origin_netuid, destination_netuid] = *leg else {
+                return Err(Error::<T>::InvalidSwapRoute.into());
+            };
+            let is_last_leg = index.saturating_add(1) == legs;
+
+            let alpha_before = Self::get_stake_for_hotkey_and_coldkey_on_subnet(
+                &hotkey,
+                &coldkey,
+                destination_netuid,
+            );
+
+            Self::transition_stake_internal(
+                &coldkey,
+                &coldkey,
+                &hotkey,
+                &hotkey,
+                origin_netuid,
+                destination_netuid,
+                leg_amount,
+                None,
+                None,
+                false,
+                is_last_leg,
+            )?;
+
+            leg_amount = Self::get_stake_for_hotkey_and_coldkey_on_subnet(
+                &hotkey,
+                &coldkey,
+                destination_netuid,
+            )
+            .saturating_sub(alpha_before);
+            ensure!(!leg_amount.is_zero(), Error::<T>::AmountTooLow);
+        }
+
+        // One slippage bound for the whole route
+        ensure!(leg_amount >= min_alpha_out, Error::<T>::SlippageTooHigh);
+
+        // Emit an event for logging.
+        log::debug!(
+            "StakeRouteSwapped(coldkey: {coldkey:?}, hotkey: {hotkey:?}, route: {route:?}, alpha_in: {alpha_amount:?}, alpha_out: {leg_amount:?})"
+        );
+        Self::deposit_event(Event::StakeRouteSwapped(
+            coldkey,
+            hotkey,
+            route,
+            alpha_amount,
+            leg_amount,
+        ));
+
+        Ok(())
+    }
+
     // If limit_price is None, this is a regular operation, otherwise, it is slippage-protected
     // by setting limit price between origin_netuid and destination_netuid token
-    fn transition_stake_internal(
+    pub(crate) fn transition_stake_internal(
         origin_coldkey: &T::AccountId,
         destination_coldkey: &T::AccountId,
         origin_hotkey: &T::AccountId,
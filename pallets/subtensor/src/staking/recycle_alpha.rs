@@ -45,6 +45,9 @@ impl<T: Config> Pallet<T> {
             Self::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid);
         let amount = amount.min(alpha_available);
 
+        // Ensure that the amount is not locked by a vesting schedule.
+        Self::ensure_stake_unlocked(&hotkey, &coldkey, netuid, amount)?;
+
         ensure!(
             SubnetAlphaOut::<T>::get(netuid) >= amount,
             Error::<T>::InsufficientLiquidity
@@ -110,6 +113,9 @@ impl<T: Config> Pallet<T> {
             Self::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid);
         let amount = amount.min(alpha_available);
 
+        // Ensure that the amount is not locked by a vesting schedule.
+        Self::ensure_stake_unlocked(&hotkey, &coldkey, netuid, amount)?;
+
         ensure!(
             SubnetAlphaOut::<T>::get(netuid) >= amount,
             Error::<T>::InsufficientLiquidity
@@ -143,9 +143,10 @@ impl<T: Config> Pallet<T> {
             if !SubtokenEnabled::<T>::get(netuid) {
                 continue;
             }
-            // Ensure that the hotkey has enough stake to withdraw.
-            let alpha_unstaked =
-                Self::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid);
+            // Only unstake the part of the stake that is not locked by vesting.
+            let alpha_unstaked = Self::get_unlocked_stake_for_hotkey_and_coldkey_on_subnet(
+                &hotkey, &coldkey, netuid,
+            );
 
             if Self::validate_remove_stake(
                 &coldkey,
@@ -236,9 +237,10 @@ impl<T: Config> Pallet<T> {
             }
             // If not Root network.
             if !netuid.is_root() {
-                // Ensure that the hotkey has enough stake to withdraw.
-                let alpha_unstaked =
-                    Self::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid);
+                // Only unstake the part of the stake that is not locked by vesting.
+                let alpha_unstaked = Self::get_unlocked_stake_for_hotkey_and_coldkey_on_subnet(
+                    &hotkey, &coldkey, netuid,
+                );
 
                 if Self::validate_remove_stake(
                     &coldkey,
@@ -584,6 +586,8 @@ impl<T: Config> Pallet<T> {
         for (hot, cold) in keys_to_remove {
             Alpha::<T>::remove((hot, cold, netuid));
         }
+        // 7.a.1) Remove vesting schedules, the vested α has been paid out above.
+        let _ = VestingSchedules::<T>::clear_prefix((netuid,), u32::MAX, None);
         // 7.b) Clear share‑pool totals for each hotkey on this subnet.
         for hot in hotkeys_seen {
             TotalHotkeyAlpha::<T>::remove(&hot, netuid);
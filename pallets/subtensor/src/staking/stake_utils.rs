@@ -1,4 +1,5 @@
 use super::*;
+use frame_support::traits::tokens::{Fortitude, Precision, Preservation, fungible::Balanced as _};
 use safe_math::*;
 use share_pool::{SharePool, SharePoolDataOperations};
 use sp_std::ops::Neg;
@@ -604,6 +605,18 @@ impl<T: Config> Pallet<T> {
             }
         };
 
+        Self::apply_tao_for_alpha_swap(netuid, tao, &swap_result);
+
+        Ok(swap_result)
+    }
+
+    /// Updates TaoIn, AlphaIn, AlphaOut, the total stake and the subnet volume for a swap of `tao`
+    /// for alpha.
+    pub(crate) fn apply_tao_for_alpha_swap(
+        netuid: NetUid,
+        tao: TaoCurrency,
+        swap_result: &SwapResult,
+    ) {
         let alpha_decrease = AlphaCurrency::from(swap_result.alpha_reserve_delta.unsigned_abs());
 
         // Decrease Alpha reserves.
@@ -628,8 +641,6 @@ impl<T: Config> Pallet<T> {
         SubnetVolume::<T>::mutate(netuid, |total| {
             *total = total.saturating_add(tao.to_u64() as u128);
         });
-
-        Ok(swap_result)
     }
 
     /// Swaps a subnet's Alpha token for TAO.
@@ -666,6 +677,15 @@ impl<T: Config> Pallet<T> {
             }
         };
 
+        Self::apply_alpha_for_tao_swap(netuid, &swap_result);
+
+        // Return the tao received.
+        Ok(swap_result)
+    }
+
+    /// Updates TaoIn, AlphaIn, AlphaOut, the total stake and the subnet volume for a swap of alpha
+    /// for TAO.
+    pub(crate) fn apply_alpha_for_tao_swap(netuid: NetUid, swap_result: &SwapResult) {
         // Increase only the protocol Alpha reserve. We only use the sum of
         // (SubnetAlphaIn + SubnetAlphaInProvided) in alpha_reserve(), so it is irrelevant
         // which one to increase.
@@ -699,9 +719,6 @@ impl<T: Config> Pallet<T> {
         SubnetVolume::<T>::mutate(netuid, |total| {
             *total = total.saturating_add(swap_result.amount_paid_out.into())
         });
-
-        // Return the tao received.
-        Ok(swap_result)
     }
 
     /// Unstakes alpha from a subnet for a given hotkey and coldkey pair.
@@ -842,6 +859,96 @@ impl<T: Config> Pallet<T> {
         Ok(swap_result.amount_paid_out.into())
     }
 
+    /// Settles the fill of a resting buy order of the swap pallet.
+    ///
+    /// The TAO paid by the fill (`tao` and the `tao_fee`) leaves the escrow account into the pool,
+    /// and the `alpha` paid out is staked to the hotkey, like a `stake_into_subnet` of the same
+    /// swap.
+    pub fn settle_buy_order_fill(
+        escrow: &T::AccountId,
+        coldkey: &T::AccountId,
+        hotkey: &T::AccountId,
+        netuid: NetUid,
+        tao: TaoCurrency,
+        tao_fee: TaoCurrency,
+        alpha: AlphaCurrency,
+    ) -> DispatchResult {
+        ensure!(
+            Self::hotkey_account_exists(hotkey),
+            Error::<T>::HotKeyAccountNotExists
+        );
+        ensure!(
+            Self::try_increase_stake_for_hotkey_and_coldkey_on_subnet(hotkey, netuid, alpha),
+            Error::<T>::InsufficientLiquidity
+        );
+
+        let tao_in = tao.saturating_add(tao_fee);
+        let _ = <T as Config>::Currency::withdraw(
+            escrow,
+            tao_in.into(),
+            Precision::Exact,
+            Preservation::Expendable,
+            Fortitude::Polite,
+        )
+        .map_err(|_| Error::<T>::BalanceWithdrawalError)?;
+
+        Self::apply_tao_for_alpha_swap(
+            netuid,
+            tao_in,
+            &SwapResult {
+                amount_paid_in: tao.into(),
+                amount_paid_out: alpha.into(),
+                fee_paid: tao_fee.into(),
+                tao_reserve_delta: tao.to_u64() as i64,
+                alpha_reserve_delta: (alpha.to_u64() as i64).neg(),
+            },
+        );
+
+        Self::increase_stake_for_hotkey_and_coldkey_on_subnet(hotkey, coldkey, netuid, alpha);
+
+        let mut staking_hotkeys = StakingHotkeys::<T>::get(coldkey);
+        if !staking_hotkeys.contains(hotkey) {
+            staking_hotkeys.push(hotkey.clone());
+            StakingHotkeys::<T>::insert(coldkey, staking_hotkeys);
+        }
+
+        LastColdkeyHotkeyStakeBlock::<T>::insert(coldkey, hotkey, Self::get_current_block_as_u64());
+
+        Ok(())
+    }
+
+    /// Settles the fill of a resting sell order of the swap pallet.
+    ///
+    /// The escrowed alpha paid by the fill (`alpha` and the `alpha_fee`) enters the pool, and the
+    /// `tao` paid out is credited to the coldkey, like an `unstake_from_subnet` of the same swap.
+    pub fn settle_sell_order_fill(
+        coldkey: &T::AccountId,
+        netuid: NetUid,
+        alpha: AlphaCurrency,
+        alpha_fee: AlphaCurrency,
+        tao: TaoCurrency,
+    ) -> DispatchResult {
+        // The escrowed alpha left the outstanding alpha when the order was placed
+        SubnetAlphaOut::<T>::mutate(netuid, |total| {
+            *total = total.saturating_add(alpha.saturating_add(alpha_fee));
+        });
+
+        Self::apply_alpha_for_tao_swap(
+            netuid,
+            &SwapResult {
+                amount_paid_in: alpha.into(),
+                amount_paid_out: tao.into(),
+                fee_paid: alpha_fee.into(),
+                tao_reserve_delta: (tao.to_u64() as i64).neg(),
+                alpha_reserve_delta: alpha.to_u64() as i64,
+            },
+        );
+
+        Self::add_balance_to_coldkey_account(coldkey, tao.into());
+
+        Ok(())
+    }
+
     /// Transfers stake between coldkeys and/or hotkey within one subnet without running it
     /// through swap.
     ///
@@ -1041,6 +1148,9 @@ impl<T: Config> Pallet<T> {
             Err(_) => return Err(Error::<T>::InsufficientLiquidity),
         }
 
+        // Ensure that the unstaked amount is not locked by a vesting schedule.
+        Self::ensure_stake_unlocked(hotkey, coldkey, netuid, alpha_unstaked)?;
+
         // Ensure that if partial execution is not allowed, the amount will not cause
         // slippage over desired
         if !allow_partial {
@@ -1078,8 +1188,9 @@ impl<T: Config> Pallet<T> {
                 continue;
             }
 
-            // Get user's stake in this subnet
-            let alpha = Self::get_stake_for_hotkey_and_coldkey_on_subnet(hotkey, coldkey, *netuid);
+            // Get user's stake in this subnet that is not locked by vesting
+            let alpha =
+                Self::get_unlocked_stake_for_hotkey_and_coldkey_on_subnet(hotkey, coldkey, *netuid);
 
             if Self::validate_remove_stake(coldkey, hotkey, *netuid, alpha, alpha, false).is_ok() {
                 unstaking_any = true;
@@ -1163,6 +1274,9 @@ impl<T: Config> Pallet<T> {
             Error::<T>::NotEnoughStakeToWithdraw
         );
 
+        // Ensure that the moved amount is not locked by a vesting schedule.
+        Self::ensure_stake_unlocked(origin_hotkey, origin_coldkey, origin_netuid, alpha_amount)?;
+
         // If origin and destination netuid are different, do the swap-related checks
         if origin_netuid != destination_netuid {
             // Ensure that the stake amount to be removed is above the minimum in tao equivalent.
@@ -0,0 +1,282 @@
+//! This file defines vesting schedules for subnet alpha stake.
+//!
+//! A coldkey can grant part of its alpha stake on a (hotkey, netuid) to a beneficiary coldkey with
+//! a cliff and a linear unlock. The granted stake is transferred to the beneficiary right away and
+//! keeps earning emissions, but the locked part cannot leave the (hotkey, coldkey, netuid) position
+//! through `remove_stake`, `move_stake`, `transfer_stake`, `swap_stake`, `unstake_all`,
+//! `recycle_alpha`, `burn_alpha`, swap liquidity and limit orders, or alpha fees until it unlocks.
+//!
+//! Grants must be worth more than the minimum stake in TAO so that a position cannot be filled
+//! with dust schedules by third parties.
+//!
+//! Nothing unlocks before `start + cliff`, after which the schedule unlocks linearly until
+//! `start + duration`. Schedules follow the stake through hotkey and coldkey swaps and are removed
+//! when the subnet is dissolved.
+
+use super::*;
+use frame_system::pallet_prelude::*;
+use subtensor_runtime_common::{AlphaCurrency, Currency, NetUid, TaoCurrency};
+use subtensor_swap_interface::{OrderType, SwapHandler};
+
+#[freeze_struct("77ab38b68931f4f7")]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct VestingSchedule<AccountId> {
+    /// The coldkey that granted the stake.
+    pub grantor: AccountId,
+    /// The total amount of alpha granted by this schedule.
+    pub total: AlphaCurrency,
+    /// The block at which the schedule starts.
+    pub start: u64,
+    /// The number of blocks after `start` before anything unlocks.
+    pub cliff: u64,
+    /// The number of blocks after `start` at which the whole amount is unlocked.
+    pub duration: u64,
+}
+
+pub type VestingScheduleOf<T> = VestingSchedule<<T as frame_system::Config>::AccountId>;
+
+impl<AccountId> VestingSchedule<AccountId> {
+    /// Returns the amount of alpha that is still locked at the given block.
+    pub fn locked_at(&self, now: u64) -> AlphaCurrency {
+        let elapsed = now.saturating_sub(self.start);
+        if elapsed < self.cliff {
+            return self.total;
+        }
+        if elapsed >= self.duration {
+            return AlphaCurrency::ZERO;
+        }
+
+        let unlocked = u128::from(self.total.to_u64())
+            .saturating_mul(u128::from(elapsed))
+            .checked_div(u128::from(self.duration))
+            .unwrap_or_default();
+        let unlocked = u64::try_from(unlocked).unwrap_or(u64::MAX);
+
+        self.total.saturating_sub(unlocked.into())
+    }
+
+    /// Returns true if the schedule is fully unlocked at the given block.
+    pub fn is_fully_vested(&self, now: u64) -> bool {
+        now.saturating_sub(self.start) >= self.duration
+    }
+
+    /// Merges `other` into this schedule. The merged schedule locks the sum of both totals,
+    /// starts with the later start and ends its cliff and its unlock with the later of each, so
+    /// it never locks less than the two schedules at any block.
+    pub fn merge(self, other: &Self) -> Self {
+        let start = self.start.max(other.start);
+        let cliff_end = self
+            .start
+            .saturating_add(self.cliff)
+            .max(other.start.saturating_add(other.cliff));
+        let end = self
+            .start
+            .saturating_add(self.duration)
+            .max(other.start.saturating_add(other.duration));
+
+        Self {
+            grantor: self.grantor,
+            total: self.total.saturating_add(other.total),
+            start,
+            cliff: cliff_end.saturating_sub(start),
+            duration: end.saturating_sub(start),
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Grants alpha stake to a beneficiary under a vesting schedule.
+    ///
+    /// The stake is transferred from the grantor to the beneficiary on the same hotkey and subnet,
+    /// and is locked on the beneficiary's (hotkey, coldkey, netuid) position until it vests.
+    ///
+    /// # Arguments
+    /// * `origin` - The origin of the call, which must be signed by the grantor coldkey.
+    /// * `beneficiary` - The coldkey receiving the vested stake.
+    /// * `hotkey` - The hotkey the stake is delegated to.
+    /// * `netuid` - The subnet of the stake.
+    /// * `amount` - The amount of alpha to grant.
+    /// * `cliff` - The number of blocks before anything unlocks.
+    /// * `duration` - The number of blocks until the whole amount is unlocked.
+    ///
+    /// # Errors
+    /// * `InvalidVestingSchedule` - The duration is zero or shorter than the cliff, or the
+    ///   beneficiary is the grantor.
+    /// * `TooManyVestingSchedules` - The beneficiary position already has the maximum number of
+    ///   active schedules.
+    /// * `AmountTooLow` - The granted alpha is not worth more than the minimum stake in TAO.
+    /// * Any error of `transfer_stake` on the same subnet.
+    ///
+    /// # Events
+    /// Emits a `StakeVestingGranted` event on success.
+    pub fn do_grant_vested_stake(
+        origin: OriginFor<T>,
+        beneficiary: T::AccountId,
+        hotkey: T::AccountId,
+        netuid: NetUid,
+        amount: AlphaCurrency,
+        cliff: u64,
+        duration: u64,
+    ) -> DispatchResult {
+        let grantor = ensure_signed(origin)?;
+
+        ensure!(
+            grantor != beneficiary && duration > 0 && cliff <= duration,
+            Error::<T>::InvalidVestingSchedule
+        );
+        ensure!(!netuid.is_root(), Error::<T>::InvalidVestingSchedule);
+
+        // Fully vested schedules no longer lock anything and can be dropped.
+        let now = Self::get_current_block_as_u64();
+        let mut schedules = VestingSchedules::<T>::get((netuid, &hotkey, &beneficiary));
+        schedules.retain(|schedule| !schedule.is_fully_vested(now));
+        ensure!(
+            schedules.len() < MAX_VESTING_SCHEDULES as usize,
+            Error::<T>::TooManyVestingSchedules
+        );
+
+        // Ensure that the grant is above the minimum in tao equivalent.
+        let tao_equivalent =
+            T::SwapInterface::sim_swap(netuid.into(), OrderType::Sell, amount.into())
+                .map(|res| res.amount_paid_out)
+                .map_err(|_| Error::<T>::InsufficientLiquidity)?;
+        ensure!(
+            TaoCurrency::from(tao_equivalent) > DefaultMinStake::<T>::get(),
+            Error::<T>::AmountTooLow
+        );
+
+        // Move the stake to the beneficiary, this checks that the grantor stake is unlocked.
+        let alpha_before =
+            Self::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &beneficiary, netuid);
+        Self::transition_stake_internal(
+            &grantor,
+            &beneficiary,
+            &hotkey,
+            &hotkey,
+            netuid,
+            netuid,
+            amount,
+            None,
+            None,
+            true,
+            false,
+        )?;
+        let alpha_granted =
+            Self::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &beneficiary, netuid)
+                .saturating_sub(alpha_before);
+
+        schedules
+            .try_push(VestingSchedule {
+                grantor: grantor.clone(),
+                total: alpha_granted,
+                start: now,
+                cliff,
+                duration,
+            })
+            .map_err(|_| Error::<T>::TooManyVestingSchedules)?;
+        VestingSchedules::<T>::insert((netuid, &hotkey, &beneficiary), schedules);
+
+        log::debug!(
+            "StakeVestingGranted(grantor: {grantor:?}, beneficiary: {beneficiary:?}, hotkey: {hotkey:?}, netuid: {netuid:?}, amount: {alpha_granted:?}, cliff: {cliff:?}, duration: {duration:?})"
+        );
+        Self::deposit_event(Event::StakeVestingGranted {
+            grantor,
+            beneficiary,
+            hotkey,
+            netuid,
+            amount: alpha_granted,
+            cliff,
+            duration,
+        });
+
+        Ok(())
+    }
+
+    /// Returns the amount of alpha stake locked by vesting schedules on a (hotkey, coldkey, netuid)
+    /// position, capped at the current stake of the position.
+    pub fn get_locked_stake_for_hotkey_and_coldkey_on_subnet(
+        hotkey: &T::AccountId,
+        coldkey: &T::AccountId,
+        netuid: NetUid,
+    ) -> AlphaCurrency {
+        let schedules = VestingSchedules::<T>::get((netuid, hotkey, coldkey));
+        if schedules.is_empty() {
+            return AlphaCurrency::ZERO;
+        }
+
+        let now = Self::get_current_block_as_u64();
+        let locked = schedules.iter().fold(AlphaCurrency::ZERO, |acc, schedule| {
+            acc.saturating_add(schedule.locked_at(now))
+        });
+
+        locked.min(Self::get_stake_for_hotkey_and_coldkey_on_subnet(
+            hotkey, coldkey, netuid,
+        ))
+    }
+
+    /// Returns the amount of alpha stake on a (hotkey, coldkey, netuid) position that is not locked
+    /// by vesting schedules.
+    pub fn get_unlocked_stake_for_hotkey_and_coldkey_on_subnet(
+        hotkey: &T::AccountId,
+        coldkey: &T::AccountId,
+        netuid: NetUid,
+    ) -> AlphaCurrency {
+        Self::get_stake_for_hotkey_and_coldkey_on_subnet(hotkey, coldkey, netuid).saturating_sub(
+            Self::get_locked_stake_for_hotkey_and_coldkey_on_subnet(hotkey, coldkey, netuid),
+        )
+    }
+
+    /// Ensures that `amount` of alpha can leave the (hotkey, coldkey, netuid) position without
+    /// touching stake locked by vesting schedules.
+    pub fn ensure_stake_unlocked(
+        hotkey: &T::AccountId,
+        coldkey: &T::AccountId,
+        netuid: NetUid,
+        amount: AlphaCurrency,
+    ) -> Result<(), Error<T>> {
+        ensure!(
+            amount
+                <= Self::get_unlocked_stake_for_hotkey_and_coldkey_on_subnet(
+                    hotkey, coldkey, netuid
+                ),
+            Error::<T>::StakeVestingLocked
+        );
+        Ok(())
+    }
+
+    /// Moves the vesting schedules of a (hotkey, coldkey, netuid) position to another position,
+    /// appending them to the schedules already there.
+    ///
+    /// Used when the stake itself is moved by a hotkey or coldkey swap. Fully vested schedules are
+    /// dropped, and the last schedules are merged when more than `MAX_VESTING_SCHEDULES` remain.
+    pub fn swap_vesting_schedules(
+        old_hotkey: &T::AccountId,
+        old_coldkey: &T::AccountId,
+        new_hotkey: &T::AccountId,
+        new_coldkey: &T::AccountId,
+        netuid: NetUid,
+        weight: &mut Weight,
+    ) {
+        let old_schedules = VestingSchedules::<T>::take((netuid, old_hotkey, old_coldkey));
+        weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+        if old_schedules.is_empty() {
+            return;
+        }
+
+        let now = Self::get_current_block_as_u64();
+        VestingSchedules::<T>::mutate((netuid, new_hotkey, new_coldkey), |schedules| {
+            let mut merged: Vec<VestingScheduleOf<T>> = core::mem::take(schedules)
+                .into_iter()
+                .chain(old_schedules)
+                .filter(|schedule| !schedule.is_fully_vested(now))
+                .collect();
+            while merged.len() > MAX_VESTING_SCHEDULES as usize {
+                if let (Some(last), Some(previous)) = (merged.pop(), merged.pop()) {
+                    merged.push(previous.merge(&last));
+                }
+            }
+            *schedules = BoundedVec::truncate_from(merged);
+        });
+        weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+    }
+}
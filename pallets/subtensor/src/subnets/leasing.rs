@@ -8,14 +8,27 @@
 //! The crowdloan's contributions are used to compute the share of the emissions that the contributors
 //! will receive as dividends. The leftover cap is refunded to the contributors and the beneficiary.
 //!
+//! The contributors dividends are kept in the lease coldkey and tracked by an accumulator of the
+//! dividends per share, each contributor claims its dividends using the `claim_lease_dividends`
+//! extrinsic, optionally staking them into a chosen hotkey on the leased subnet.
+//!
 //! The lease can have a defined end block, after which the lease will be terminated and the subnet
 //! will be transferred to the beneficiary. In case the lease is perpetual, the lease will never be
 //! terminated and emissions will continue to be distributed to the contributors.
 //!
 //! The lease can be terminated by the beneficiary after the end block has passed (if any) and the subnet
 //! ownership will be transferred to the beneficiary.
+//!
+//! The contributors can transfer their shares of the dividends to other accounts, a share transferred
+//! to the beneficiary is merged into the beneficiary's leftover share.
+//!
+//! The beneficiary can enable the governance of the lease by its contributors, who can then propose and
+//! vote, weighted by their shares, to replace the beneficiary, change the emissions share within bounds or
+//! end the lease early. A proposal is approved when its supporters hold more than half of the contributors
+//! shares, and the shares can't be transferred while a proposal is in progress.
 
 use super::*;
+use codec::DecodeWithMemTracking;
 use frame_support::{
     dispatch::RawOrigin,
     traits::{Defensive, fungible::*, tokens::Preservation},
@@ -57,6 +70,35 @@ pub struct SubnetLease<AccountId, BlockNumber, Balance> {
 pub type SubnetLeaseOf<T> =
     SubnetLease<<T as frame_system::Config>::AccountId, BlockNumberFor<T>, BalanceOf<T>>;
 
+/// An action on a lease decided by its contributors when the lease governance is enabled.
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum LeaseAction<AccountId> {
+    /// Replace the beneficiary of the lease, moving the beneficiary proxy to the new beneficiary.
+    ReplaceBeneficiary(AccountId),
+    /// Change the share of the emissions that the contributors receive.
+    SetEmissionsShare(Percent),
+    /// End the lease at the current block so it can be terminated.
+    Terminate,
+}
+
+#[freeze_struct("fd04d9398fe064a6")]
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct LeaseProposal<AccountId, BlockNumber> {
+    /// The contributor who proposed the action.
+    pub proposer: AccountId,
+    /// The action to execute if the proposal is approved.
+    pub action: LeaseAction<AccountId>,
+    /// The block at which the voting ends.
+    pub voting_end: BlockNumber,
+    /// The sum of the shares of the contributors who voted for the action.
+    pub ayes: U64F64,
+    /// The sum of the shares of the contributors who voted against the action.
+    pub nays: U64F64,
+}
+
+pub type LeaseProposalOf<T> =
+    LeaseProposal<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+
 impl<T: Config> Pallet<T> {
     /// Register a new leased network through a crowdloan. A new subnet will be registered
     /// paying the lock cost using the crowdloan funds and a proxy will be created for the beneficiary
@@ -137,10 +179,12 @@ impl<T: Config> Pallet<T> {
             .filter(|(contributor, _)| contributor != &who);
 
         let mut refunded_cap = 0u64;
+        let mut total_shares = U64F64::from_num(0);
         for (contributor, amount) in contributions {
             // Compute the share of the contributor to the lease
             let share: U64F64 = U64F64::from(amount).saturating_div(U64F64::from(crowdloan.raised));
             SubnetLeaseShares::<T>::insert(lease_id, &contributor, share);
+            total_shares = total_shares.saturating_add(share);
 
             // Refund the unused part of the cap to the contributor relative to their share
             let contributor_refund = share
@@ -156,6 +200,8 @@ impl<T: Config> Pallet<T> {
             refunded_cap = refunded_cap.saturating_add(contributor_refund);
         }
 
+        SubnetLeaseTotalShares::<T>::insert(lease_id, total_shares);
+
         // Refund what's left after refunding the contributors to the beneficiary
         let beneficiary_refund = leftover_cap.saturating_sub(refunded_cap);
         <T as Config>::Currency::transfer(
@@ -217,6 +263,17 @@ impl<T: Config> Pallet<T> {
         SubnetOwner::<T>::insert(lease.netuid, lease.beneficiary.clone());
         Self::set_subnet_owner_hotkey(lease.netuid, &hotkey);
 
+        // Pay the dividends that have not been claimed by the contributors, a payout that
+        // can't be made (e.g. below the existential deposit of a reaped account) must not
+        // prevent the termination so it is left on the lease coldkey
+        for contributor in SubnetLeaseShares::<T>::iter_key_prefix(lease_id) {
+            if let Err(err) = Self::pay_lease_dividends(&lease, lease_id, &contributor) {
+                log::warn!(
+                    "Couldn't pay the dividends of {contributor:?} for lease {lease_id}: {err:?}"
+                );
+            }
+        }
+
         // Stop tracking the lease coldkey and hotkey
         let _ = frame_system::Pallet::<T>::dec_providers(&lease.coldkey).defensive();
         let _ = frame_system::Pallet::<T>::dec_providers(&lease.hotkey).defensive();
@@ -224,6 +281,13 @@ impl<T: Config> Pallet<T> {
         // Remove the lease, its contributors and accumulated dividends from storage
         let clear_result =
             SubnetLeaseShares::<T>::clear_prefix(lease_id, T::MaxContributors::get(), None);
+        let _ =
+            LeaseDividendsCheckpoint::<T>::clear_prefix(lease_id, T::MaxContributors::get(), None);
+        let _ = LeaseProposalVotes::<T>::clear_prefix(lease_id, T::MaxContributors::get(), None);
+        LeaseProposals::<T>::remove(lease_id);
+        SubnetLeaseGovernance::<T>::remove(lease_id);
+        SubnetLeaseTotalShares::<T>::remove(lease_id);
+        LeaseDividendsPerShare::<T>::remove(lease_id);
         AccumulatedLeaseDividends::<T>::remove(lease_id);
         SubnetLeases::<T>::remove(lease_id);
 
@@ -247,9 +311,378 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Transfer a portion of the caller's share of a lease to another account.
+    ///
+    /// The sum of the shares is kept unchanged so the dividends distribution is not affected,
+    /// a share transferred to the beneficiary is merged into its leftover share.
+    pub fn do_transfer_lease_share(
+        origin: T::RuntimeOrigin,
+        lease_id: LeaseId,
+        to: T::AccountId,
+        portion: Percent,
+    ) -> DispatchResultWithPostInfo {
+        let who = ensure_signed(origin)?;
+
+        let lease = SubnetLeases::<T>::get(lease_id).ok_or(Error::<T>::LeaseDoesNotExist)?;
+        ensure!(
+            who != to && to != lease.coldkey,
+            Error::<T>::InvalidLeaseShareTransfer
+        );
+        ensure!(
+            SubnetLeaseShares::<T>::contains_key(lease_id, &who),
+            Error::<T>::NoLeaseShare
+        );
+        ensure!(!portion.is_zero(), Error::<T>::InvalidLeaseShareTransfer);
+        ensure!(
+            !LeaseProposals::<T>::contains_key(lease_id),
+            Error::<T>::LeaseProposalInProgress
+        );
+
+        // Transfer the whole share if requested to avoid leaving dust behind
+        let share = SubnetLeaseShares::<T>::get(lease_id, &who);
+        let transferred = if portion.is_one() {
+            share
+        } else {
+            share
+                .saturating_mul(U64F64::from(portion.deconstruct()))
+                .saturating_div(U64F64::from(Percent::one().deconstruct()))
+        };
+        let remaining = share.saturating_sub(transferred);
+
+        // Ensure a new shareholder doesn't exceed the maximum number of shareholders
+        let mut shareholders = 0;
+        if to != lease.beneficiary && !SubnetLeaseShares::<T>::contains_key(lease_id, &to) {
+            shareholders = SubnetLeaseShares::<T>::iter_key_prefix(lease_id).count() as u32;
+            ensure!(
+                remaining == U64F64::from_num(0) || shareholders < T::MaxContributors::get(),
+                Error::<T>::TooManyLeaseShareholders
+            );
+        }
+
+        // Pay the dividends earned with the shares before they change
+        Self::pay_lease_dividends(&lease, lease_id, &who)?;
+        if remaining == U64F64::from_num(0) {
+            SubnetLeaseShares::<T>::remove(lease_id, &who);
+            LeaseDividendsCheckpoint::<T>::remove(lease_id, &who);
+        } else {
+            SubnetLeaseShares::<T>::insert(lease_id, &who, remaining);
+        }
+        if to != lease.beneficiary {
+            Self::pay_lease_dividends(&lease, lease_id, &to)?;
+            SubnetLeaseShares::<T>::mutate(lease_id, &to, |share| {
+                *share = share.saturating_add(transferred)
+            });
+        } else {
+            SubnetLeaseTotalShares::<T>::mutate(lease_id, |total_shares| {
+                *total_shares = total_shares.saturating_sub(transferred)
+            });
+        }
+
+        Self::deposit_event(Event::LeaseShareTransferred {
+            lease_id,
+            from: who,
+            to,
+            share: transferred,
+        });
+
+        if shareholders < T::MaxContributors::get() {
+            // We have iterated less than the max number of shareholders, so we need to refund the difference
+            Ok(Some(SubnetLeasingWeightInfo::<T>::do_transfer_lease_share(
+                shareholders,
+            ))
+            .into())
+        } else {
+            Ok(().into())
+        }
+    }
+
+    /// Claim the dividends of a lease earned by the caller's share since its last claim.
+    ///
+    /// The dividends are transferred from the lease coldkey to the caller and staked
+    /// into the provided hotkey on the leased subnet if any.
+    pub fn do_claim_lease_dividends(
+        origin: T::RuntimeOrigin,
+        lease_id: LeaseId,
+        hotkey: Option<T::AccountId>,
+    ) -> DispatchResult {
+        let who = ensure_signed(origin)?;
+
+        let lease = SubnetLeases::<T>::get(lease_id).ok_or(Error::<T>::LeaseDoesNotExist)?;
+        ensure!(
+            SubnetLeaseShares::<T>::contains_key(lease_id, &who),
+            Error::<T>::NoLeaseShare
+        );
+
+        let claimed = Self::pay_lease_dividends(&lease, lease_id, &who)?;
+        ensure!(claimed > 0, Error::<T>::NoLeaseDividendsToClaim);
+
+        // Compound the dividends into the leased subnet if requested
+        if let Some(ref hotkey) = hotkey {
+            Self::do_add_stake(
+                RawOrigin::Signed(who.clone()).into(),
+                hotkey.clone(),
+                lease.netuid,
+                claimed.into(),
+            )?;
+        }
+
+        Self::deposit_event(Event::LeaseDividendsClaimed {
+            lease_id,
+            contributor: who,
+            amount: claimed.into(),
+            hotkey,
+        });
+
+        Ok(())
+    }
+
+    /// The tao dividends of a lease earned by a contributor since its dividends were last paid.
+    pub fn get_claimable_lease_dividends(lease_id: LeaseId, who: &T::AccountId) -> TaoCurrency {
+        let share = SubnetLeaseShares::<T>::get(lease_id, who);
+        let dividends_per_share = LeaseDividendsPerShare::<T>::get(lease_id)
+            .saturating_sub(LeaseDividendsCheckpoint::<T>::get(lease_id, who));
+        share
+            .saturating_mul(dividends_per_share)
+            .floor()
+            .saturating_to_num::<u64>()
+            .into()
+    }
+
+    /// The tao dividends claimable by an account for each lease it has a share of.
+    pub fn get_claimable_lease_dividends_for_account(
+        who: &T::AccountId,
+    ) -> Vec<(LeaseId, TaoCurrency)> {
+        SubnetLeases::<T>::iter_keys()
+            .filter(|lease_id| SubnetLeaseShares::<T>::contains_key(lease_id, who))
+            .map(|lease_id| (lease_id, Self::get_claimable_lease_dividends(lease_id, who)))
+            .collect()
+    }
+
+    /// Enable the governance of a lease by its contributors.
+    ///
+    /// The contributors can then vote, weighted by their shares, to replace the beneficiary,
+    /// change the emissions share or end the lease. The governance can't be disabled.
+    pub fn do_enable_lease_governance(
+        origin: T::RuntimeOrigin,
+        lease_id: LeaseId,
+    ) -> DispatchResult {
+        let who = ensure_signed(origin)?;
+
+        let lease = SubnetLeases::<T>::get(lease_id).ok_or(Error::<T>::LeaseDoesNotExist)?;
+        ensure!(
+            lease.beneficiary == who,
+            Error::<T>::ExpectedBeneficiaryOrigin
+        );
+        ensure!(
+            !SubnetLeaseGovernance::<T>::get(lease_id),
+            Error::<T>::LeaseGovernanceAlreadyEnabled
+        );
+
+        SubnetLeaseGovernance::<T>::insert(lease_id, true);
+
+        Self::deposit_event(Event::LeaseGovernanceEnabled { lease_id });
+
+        Ok(())
+    }
+
+    /// Propose an action on a lease to its contributors, the proposer votes for it.
+    ///
+    /// Only one proposal can be voted on at a time and the shares can't be transferred
+    /// while it is in progress. The proposer must hold at least `MinLeaseProposalShare` of the
+    /// shares and can propose once every `LeaseProposalCooldown` blocks.
+    pub fn do_propose_lease_action(
+        origin: T::RuntimeOrigin,
+        lease_id: LeaseId,
+        action: LeaseAction<T::AccountId>,
+    ) -> DispatchResult {
+        let who = ensure_signed(origin)?;
+        let now = frame_system::Pallet::<T>::block_number();
+
+        let lease = SubnetLeases::<T>::get(lease_id).ok_or(Error::<T>::LeaseDoesNotExist)?;
+        ensure!(
+            SubnetLeaseGovernance::<T>::get(lease_id),
+            Error::<T>::LeaseGovernanceNotEnabled
+        );
+        ensure!(
+            SubnetLeaseShares::<T>::contains_key(lease_id, &who),
+            Error::<T>::NoLeaseShare
+        );
+        ensure!(
+            !LeaseProposals::<T>::contains_key(lease_id),
+            Error::<T>::LeaseProposalInProgress
+        );
+        ensure!(
+            lease.end_block.is_none_or(|end_block| now < end_block),
+            Error::<T>::LeaseHasEnded
+        );
+
+        let share = SubnetLeaseShares::<T>::get(lease_id, &who);
+        let min_share = SubnetLeaseTotalShares::<T>::get(lease_id)
+            .saturating_mul(U64F64::from_num(
+                T::MinLeaseProposalShare::get().deconstruct(),
+            ))
+            .saturating_div(U64F64::from_num(100));
+        ensure!(share >= min_share, Error::<T>::LeaseShareTooLowToPropose);
+        ensure!(
+            LastLeaseProposalBlock::<T>::get(&who).is_none_or(|last_block| {
+                now >= last_block.saturating_add(T::LeaseProposalCooldown::get())
+            }),
+            Error::<T>::LeaseProposalRateLimitExceeded
+        );
+
+        match &action {
+            LeaseAction::ReplaceBeneficiary(beneficiary) => ensure!(
+                beneficiary != &lease.beneficiary && beneficiary != &lease.coldkey,
+                Error::<T>::InvalidLeaseAction
+            ),
+            LeaseAction::SetEmissionsShare(emissions_share) => {
+                let change = emissions_share
+                    .deconstruct()
+                    .abs_diff(lease.emissions_share.deconstruct());
+                ensure!(
+                    change > 0 && change <= T::MaxLeaseEmissionsShareChange::get().deconstruct(),
+                    Error::<T>::InvalidLeaseAction
+                );
+            }
+            LeaseAction::Terminate => {}
+        }
+
+        LeaseProposals::<T>::insert(
+            lease_id,
+            LeaseProposal {
+                proposer: who.clone(),
+                action: action.clone(),
+                voting_end: now.saturating_add(T::LeaseProposalVotingPeriod::get()),
+                ayes: share,
+                nays: U64F64::from_num(0),
+            },
+        );
+        LeaseProposalVotes::<T>::insert(lease_id, &who, true);
+        LastLeaseProposalBlock::<T>::insert(&who, now);
+
+        Self::deposit_event(Event::LeaseActionProposed {
+            lease_id,
+            proposer: who,
+            action,
+        });
+
+        Ok(())
+    }
+
+    /// Vote on the proposal of a lease, the vote is weighted by the caller's share.
+    pub fn do_vote_lease_proposal(
+        origin: T::RuntimeOrigin,
+        lease_id: LeaseId,
+        aye: bool,
+    ) -> DispatchResult {
+        let who = ensure_signed(origin)?;
+        let now = frame_system::Pallet::<T>::block_number();
+
+        let mut proposal = LeaseProposals::<T>::get(lease_id).ok_or(Error::<T>::NoLeaseProposal)?;
+        ensure!(
+            now < proposal.voting_end,
+            Error::<T>::LeaseProposalVotingEnded
+        );
+        ensure!(
+            SubnetLeaseShares::<T>::contains_key(lease_id, &who),
+            Error::<T>::NoLeaseShare
+        );
+        ensure!(
+            !LeaseProposalVotes::<T>::contains_key(lease_id, &who),
+            Error::<T>::AlreadyVotedOnLeaseProposal
+        );
+
+        let share = SubnetLeaseShares::<T>::get(lease_id, &who);
+        if aye {
+            proposal.ayes = proposal.ayes.saturating_add(share);
+        } else {
+            proposal.nays = proposal.nays.saturating_add(share);
+        }
+        LeaseProposals::<T>::insert(lease_id, proposal);
+        LeaseProposalVotes::<T>::insert(lease_id, &who, aye);
+
+        Self::deposit_event(Event::LeaseProposalVoted {
+            lease_id,
+            voter: who,
+            aye,
+            share,
+        });
+
+        Ok(())
+    }
+
+    /// Conclude the proposal of a lease and execute its action if approved.
+    ///
+    /// The proposal is approved when the contributors who voted for it hold more than half of the
+    /// contributors shares. It can be concluded before the end of the voting once its outcome is decided.
+    pub fn do_conclude_lease_proposal(
+        origin: T::RuntimeOrigin,
+        lease_id: LeaseId,
+    ) -> DispatchResultWithPostInfo {
+        ensure_signed(origin)?;
+        let now = frame_system::Pallet::<T>::block_number();
+
+        let mut lease = SubnetLeases::<T>::get(lease_id).ok_or(Error::<T>::LeaseDoesNotExist)?;
+        let proposal = LeaseProposals::<T>::get(lease_id).ok_or(Error::<T>::NoLeaseProposal)?;
+
+        let half_shares =
+            SubnetLeaseTotalShares::<T>::get(lease_id).saturating_div(U64F64::from_num(2));
+        let approved = proposal.ayes > half_shares;
+        let rejected = proposal.nays >= half_shares;
+        ensure!(
+            approved || rejected || now >= proposal.voting_end,
+            Error::<T>::LeaseProposalVotingNotEnded
+        );
+
+        if approved {
+            match &proposal.action {
+                LeaseAction::ReplaceBeneficiary(beneficiary) => {
+                    T::ProxyInterface::remove_lease_beneficiary_proxy(
+                        &lease.coldkey,
+                        &lease.beneficiary,
+                    )?;
+                    T::ProxyInterface::add_lease_beneficiary_proxy(&lease.coldkey, beneficiary)?;
+                    lease.beneficiary = beneficiary.clone();
+                }
+                LeaseAction::SetEmissionsShare(emissions_share) => {
+                    lease.emissions_share = *emissions_share;
+                }
+                LeaseAction::Terminate => {
+                    lease.end_block = Some(now);
+                }
+            }
+            SubnetLeases::<T>::insert(lease_id, lease);
+        }
+
+        let clear_result =
+            LeaseProposalVotes::<T>::clear_prefix(lease_id, T::MaxContributors::get(), None);
+        LeaseProposals::<T>::remove(lease_id);
+
+        Self::deposit_event(Event::LeaseProposalConcluded {
+            lease_id,
+            action: proposal.action,
+            approved,
+        });
+
+        if clear_result.unique < T::MaxContributors::get() {
+            // We have cleared less than the max number of votes, so we need to refund the difference
+            Ok(
+                Some(SubnetLeasingWeightInfo::<T>::do_conclude_lease_proposal(
+                    clear_result.unique,
+                ))
+                .into(),
+            )
+        } else {
+            Ok(().into())
+        }
+    }
+
     /// Hook used when the subnet owner's cut is distributed to split the amount into dividends
     /// for the contributors and the beneficiary in shares relative to their initial contributions.
     ///
+    /// The beneficiary's dividends are paid directly while the contributors' dividends are kept in
+    /// the lease coldkey and added to the dividends per share, to be claimed by the contributors.
+    ///
     /// It will ensure the subnet has enough alpha in its liquidity pool before swapping it to tao to be distributed,
     /// and if not enough liquidity is available, it will accumulate the dividends for later distribution.
     pub fn distribute_leased_network_dividends(lease_id: LeaseId, owner_cut_alpha: AlphaCurrency) {
@@ -321,26 +754,53 @@ impl<T: Config> Pallet<T> {
             }
         };
 
-        // Distribute the contributors cut to the contributors and accumulate the tao
-        // distributed so far to obtain how much tao is left to distribute to the beneficiary
-        let mut tao_distributed = TaoCurrency::ZERO;
-        for (contributor, share) in SubnetLeaseShares::<T>::iter_prefix(lease_id) {
-            let tao_for_contributor = share
-                .saturating_mul(U64F64::from(tao_unstaked.to_u64()))
-                .floor()
-                .saturating_to_num::<u64>();
-            Self::add_balance_to_coldkey_account(&contributor, tao_for_contributor);
-            tao_distributed = tao_distributed.saturating_add(tao_for_contributor.into());
-        }
+        // Keep the contributors cut in the lease coldkey to be claimed, we voluntarily
+        // round up so the claims of the contributors are always covered
+        let contributors_cut_tao = SubnetLeaseTotalShares::<T>::get(lease_id)
+            .saturating_mul(U64F64::from(tao_unstaked.to_u64()))
+            .ceil()
+            .saturating_to_num::<u64>()
+            .min(tao_unstaked.to_u64());
+        Self::add_balance_to_coldkey_account(&lease.coldkey, contributors_cut_tao);
+        LeaseDividendsPerShare::<T>::mutate(lease_id, |dividends_per_share| {
+            *dividends_per_share =
+                dividends_per_share.saturating_add(U64F64::from(tao_unstaked.to_u64()))
+        });
 
         // Distribute the leftover tao to the beneficiary
-        let beneficiary_cut_tao = tao_unstaked.saturating_sub(tao_distributed);
+        let beneficiary_cut_tao = tao_unstaked.saturating_sub(contributors_cut_tao.into());
         Self::add_balance_to_coldkey_account(&lease.beneficiary, beneficiary_cut_tao.into());
 
         // Reset the accumulated dividends
         AccumulatedLeaseDividends::<T>::insert(lease_id, AlphaCurrency::ZERO);
     }
 
+    // Pay the claimable dividends of a contributor from the lease coldkey and
+    // checkpoint the dividends per share
+    fn pay_lease_dividends(
+        lease: &SubnetLeaseOf<T>,
+        lease_id: LeaseId,
+        who: &T::AccountId,
+    ) -> Result<u64, DispatchError> {
+        let claimable = Self::get_claimable_lease_dividends(lease_id, who).to_u64();
+        LeaseDividendsCheckpoint::<T>::insert(
+            lease_id,
+            who,
+            LeaseDividendsPerShare::<T>::get(lease_id),
+        );
+
+        if claimable > 0 {
+            <T as Config>::Currency::transfer(
+                &lease.coldkey,
+                who,
+                claimable,
+                Preservation::Expendable,
+            )?;
+        }
+
+        Ok(claimable)
+    }
+
     fn lease_coldkey(lease_id: LeaseId) -> T::AccountId {
         let entropy = ("leasing/coldkey", lease_id).using_encoded(blake2_256);
         Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
@@ -399,12 +859,54 @@ impl<T: frame_system::Config> SubnetLeasingWeightInfo<T> {
             .saturating_add(Weight::from_parts(0, 2579).saturating_mul(k.into()))
     }
 
-    pub fn do_terminate_lease(k: u32) -> Weight {
-        Weight::from_parts(56_635_122, 6148)
-            .saturating_add(Weight::from_parts(912_993, 0).saturating_mul(k.into()))
-            .saturating_add(T::DbWeight::get().reads(4_u64))
+    pub fn do_transfer_lease_share(k: u32) -> Weight {
+        Weight::from_parts(24_306_000, 3996)
+            .saturating_add(Weight::from_parts(1_102_000, 0).saturating_mul(k.into()))
+            .saturating_add(T::DbWeight::get().reads(3_u64))
             .saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(k.into())))
-            .saturating_add(T::DbWeight::get().writes(6_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+            .saturating_add(Weight::from_parts(0, 2529).saturating_mul(k.into()))
+    }
+
+    pub fn do_terminate_lease(k: u32) -> Weight {
+        Weight::from_parts(58_104_361, 6148)
+            .saturating_add(Weight::from_parts(15_730_458, 0).saturating_mul(k.into()))
+            .saturating_add(T::DbWeight::get().reads(6_u64))
+            .saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(k.into())))
+            .saturating_add(T::DbWeight::get().writes(8_u64))
+            .saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(k.into())))
+            .saturating_add(Weight::from_parts(0, 2579).saturating_mul(k.into()))
+    }
+
+    pub fn do_claim_lease_dividends() -> Weight {
+        Weight::from_parts(45_212_000, 6148)
+            .saturating_add(T::DbWeight::get().reads(6_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+
+    pub fn do_enable_lease_governance() -> Weight {
+        Weight::from_parts(15_340_000, 3553)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+
+    pub fn do_propose_lease_action() -> Weight {
+        Weight::from_parts(27_350_000, 3621)
+            .saturating_add(T::DbWeight::get().reads(6_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+
+    pub fn do_vote_lease_proposal() -> Weight {
+        Weight::from_parts(21_806_000, 3621)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+
+    pub fn do_conclude_lease_proposal(k: u32) -> Weight {
+        Weight::from_parts(62_480_000, 6148)
+            .saturating_add(Weight::from_parts(1_012_345, 0).saturating_mul(k.into()))
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
             .saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(k.into())))
             .saturating_add(Weight::from_parts(0, 2529).saturating_mul(k.into()))
     }
@@ -154,6 +154,9 @@ impl<T: Config> Pallet<T> {
                     // Cleanup TimelockedWeightCommits
                     let _ =
                         TimelockedWeightCommits::<T>::clear_prefix(netuid_index, u32::MAX, None);
+
+                    // Cleanup EpochDebug
+                    EpochDebug::<T>::remove(netuid_index);
                 }
             }
 
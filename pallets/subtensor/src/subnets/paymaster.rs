@@ -0,0 +1,207 @@
+//! # Subnet paymasters
+//!
+//! A subnet paymaster pays the transaction fees of a whitelist of calls on its subnet (the
+//! burned registrations, serving axons and setting commitments) so that new miners don't need to
+//! hold TAO for the fees before joining.
+//!
+//! The paymaster holds its funds in an account derived from the netuid. Anyone can fund it,
+//! only the subnet owner can withdraw from it. The owner (or root) sets the sponsored calls and
+//! the budgets:
+//! - the account budget caps the fees paid for a single account on the subnet,
+//! - the block budget caps the fees paid for all the accounts in a single block.
+//!
+//! New accounts are free to create, so the fees are only paid for accounts holding something
+//! scarce: the signer of a burned registration must hold the burn, the signer of the other calls
+//! must be a hotkey registered on the subnet. The proof of work registrations are not sponsored,
+//! their work is only checked when dispatched.
+//!
+//! The fees are charged by the transaction fee handler of the runtime, which asks the
+//! paymaster before charging the signer. Tips are never sponsored. Calls of other pallets (like
+//! `set_commitment`) are mapped to their subnet by the `SponsoredCalls` of the runtime.
+use super::*;
+use frame_support::traits::{fungible::*, tokens::Preservation};
+use frame_system::pallet_prelude::*;
+use sp_core::blake2_256;
+use sp_runtime::traits::TrailingZeroInput;
+use subtensor_runtime_common::{Currency, NetUid, TaoCurrency};
+
+/// The fees a subnet paymaster pays.
+#[freeze_struct("46cbc5276345f5f")]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct SubnetPaymaster {
+    /// The calls whose fees are paid.
+    pub calls: BoundedVec<SponsoredCall, ConstU32<MAX_SPONSORED_CALLS>>,
+    /// The maximum fees paid for a single account.
+    pub account_budget: TaoCurrency,
+    /// The maximum fees paid in a single block.
+    pub block_budget: TaoCurrency,
+}
+
+impl<T: Config> Pallet<T> {
+    /// Transfers TAO from the caller to the paymaster of a subnet.
+    pub fn do_fund_paymaster(
+        origin: T::RuntimeOrigin,
+        netuid: NetUid,
+        amount: TaoCurrency,
+    ) -> DispatchResult {
+        let who = ensure_signed(origin)?;
+        ensure!(Self::if_subnet_exist(netuid), Error::<T>::SubnetNotExists);
+        ensure!(!amount.is_zero(), Error::<T>::AmountTooLow);
+
+        <T as Config>::Currency::transfer(
+            &who,
+            &Self::paymaster_account(netuid),
+            amount.into(),
+            Preservation::Expendable,
+        )?;
+
+        Self::deposit_event(Event::PaymasterFunded {
+            netuid,
+            who,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Transfers TAO from the paymaster of a subnet to the subnet owner.
+    pub fn do_withdraw_paymaster(
+        origin: T::RuntimeOrigin,
+        netuid: NetUid,
+        amount: TaoCurrency,
+    ) -> DispatchResult {
+        let owner = Self::ensure_subnet_owner(origin, netuid)?;
+        ensure!(!amount.is_zero(), Error::<T>::AmountTooLow);
+
+        <T as Config>::Currency::transfer(
+            &Self::paymaster_account(netuid),
+            &owner,
+            amount.into(),
+            Preservation::Expendable,
+        )?;
+
+        Self::deposit_event(Event::PaymasterWithdrawn {
+            netuid,
+            who: owner,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Sets the calls sponsored by the paymaster of a subnet and its budgets. An empty list of
+    /// calls disables the paymaster, the funds stay in the paymaster account.
+    pub fn set_subnet_paymaster(
+        netuid: NetUid,
+        calls: Vec<SponsoredCall>,
+        account_budget: TaoCurrency,
+        block_budget: TaoCurrency,
+    ) -> DispatchResult {
+        if calls.is_empty() {
+            SubnetPaymasters::<T>::remove(netuid);
+            return Ok(());
+        }
+
+        let mut unique_calls: Vec<SponsoredCall> = Vec::new();
+        for call in calls {
+            if !unique_calls.contains(&call) {
+                unique_calls.push(call);
+            }
+        }
+
+        let calls = unique_calls
+            .try_into()
+            .map_err(|_| Error::<T>::TooManySponsoredCalls)?;
+        SubnetPaymasters::<T>::insert(
+            netuid,
+            SubnetPaymaster {
+                calls,
+                account_budget,
+                block_budget,
+            },
+        );
+        Ok(())
+    }
+
+    /// Returns true if the budgets of the paymaster of a subnet allow paying the fee of a call
+    /// signed by `who`, and `who` can hold the burn of the subnet for a registration or is
+    /// registered on the subnet for the other calls. The balance of the paymaster account is
+    /// checked by the caller.
+    pub fn can_sponsor_fee(
+        netuid: NetUid,
+        call: SponsoredCall,
+        who: &T::AccountId,
+        fee: TaoCurrency,
+    ) -> bool {
+        let Some(paymaster) = SubnetPaymasters::<T>::get(netuid) else {
+            return false;
+        };
+        if !paymaster.calls.contains(&call) {
+            return false;
+        }
+
+        let eligible = match call {
+            SponsoredCall::BurnedRegister | SponsoredCall::QueueBurnedRegistration => {
+                Self::can_remove_balance_from_coldkey_account(who, Self::get_burn(netuid).into())
+            }
+            SponsoredCall::ServeAxon | SponsoredCall::SetCommitment => {
+                Uids::<T>::contains_key(netuid, who)
+            }
+        };
+        if !eligible {
+            return false;
+        }
+
+        let account_fees = PaymasterAccountFees::<T>::get(netuid, who);
+        if account_fees.saturating_add(fee) > paymaster.account_budget {
+            return false;
+        }
+
+        let (block, block_fees) = PaymasterBlockFees::<T>::get(netuid);
+        let block_fees = if block == Self::get_current_block_as_u64() {
+            block_fees
+        } else {
+            TaoCurrency::ZERO
+        };
+        block_fees.saturating_add(fee) <= paymaster.block_budget
+    }
+
+    /// Records a fee paid by the paymaster of a subnet for `who` against the budgets.
+    pub fn record_sponsored_fee(netuid: NetUid, who: &T::AccountId, fee: TaoCurrency) {
+        PaymasterAccountFees::<T>::mutate(netuid, who, |fees| {
+            *fees = fees.saturating_add(fee);
+        });
+
+        let current_block = Self::get_current_block_as_u64();
+        PaymasterBlockFees::<T>::mutate(netuid, |(block, fees)| {
+            if *block != current_block {
+                *block = current_block;
+                *fees = TaoCurrency::ZERO;
+            }
+            *fees = fees.saturating_add(fee);
+        });
+    }
+
+    /// Removes the paymaster of a subnet and sends its funds to the subnet owner.
+    pub fn remove_subnet_paymaster(netuid: NetUid, owner: &T::AccountId) {
+        SubnetPaymasters::<T>::remove(netuid);
+        let _ = PaymasterAccountFees::<T>::clear_prefix(netuid, u32::MAX, None);
+        PaymasterBlockFees::<T>::remove(netuid);
+
+        let paymaster_account = Self::paymaster_account(netuid);
+        let balance = <T as Config>::Currency::balance(&paymaster_account);
+        if balance > 0 {
+            let _ = <T as Config>::Currency::transfer(
+                &paymaster_account,
+                owner,
+                balance,
+                Preservation::Expendable,
+            );
+        }
+    }
+
+    /// The account holding the funds of the paymaster of a subnet.
+    pub fn paymaster_account(netuid: NetUid) -> T::AccountId {
+        let entropy = ("subnet/paymaster", netuid).using_encoded(blake2_256);
+        Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+            .expect("infinite length input; no invalid inputs for type; qed")
+    }
+}
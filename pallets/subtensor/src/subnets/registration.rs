@@ -1,13 +1,67 @@
 use super::*;
+use ark_serialize::CanonicalDeserialize;
+use codec::DecodeWithMemTracking;
+use frame_support::storage::{TransactionOutcome, with_transaction};
+use frame_support::traits::tokens::{
+    Fortitude, Precision,
+    fungible::{Balanced as _, Mutate as _},
+};
+use pallet_drand::types::{Pulse, RoundNumber};
 use sp_core::{H256, U256};
-use sp_io::hashing::{keccak_256, sha2_256};
-use sp_runtime::Saturating;
-use subtensor_runtime_common::{Currency, NetUid};
+use sp_io::hashing::{blake2_256, keccak_256, sha2_256};
+use sp_runtime::{Saturating, traits::TrailingZeroInput};
+use subtensor_runtime_common::{Currency, NetUid, TaoCurrency};
 use subtensor_swap_interface::SwapHandler;
 use system::pallet_prelude::BlockNumberFor;
+use tle::{
+    curves::drand::TinyBLS381,
+    stream_ciphers::AESGCMStreamCipherProvider,
+    tlock::{TLECiphertext, tld},
+};
+use w3f_bls::EngineBLS;
 
 const LOG_TARGET: &str = "runtime::subtensor::registration";
 
+/// A burned registration queued until the end of the adjustment interval of a subnet.
+#[freeze_struct("6f8c8a33002c4a2f")]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct PendingBurnedRegistration<AccountId> {
+    /// The coldkey paying for the registration.
+    pub coldkey: AccountId,
+    /// The hotkey to register.
+    pub hotkey: AccountId,
+    /// The amount of tao held in escrow from the coldkey, the burn in the random mode and the
+    /// maximum bid in the highest bid mode.
+    pub amount: TaoCurrency,
+    /// The bid of the registration in the highest bid mode, sealed until the end of the interval.
+    pub sealed_bid: Option<SealedBid>,
+}
+
+/// A burned registration bid encrypted to a drand round.
+#[freeze_struct("9e4cdb8a50d1a7cb")]
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub struct SealedBid {
+    /// The encrypted compressed [`BurnedRegistrationBidTlockPayload`].
+    pub commit: BoundedVec<u8, ConstU32<MAX_SEALED_BID_SIZE_BYTES>>,
+    /// The drand round the bid is revealed at.
+    pub reveal_round: RoundNumber,
+}
+
+/// The payload of a sealed bid.
+///
+/// It should be encoded, encrypted to the reveal round, compressed and serialized the same
+/// way as the [`crate::coinbase::reveal_commits::WeightsTlockPayload`] of the commit-reveal v3
+/// weights.
+#[derive(Encode, Decode)]
+#[freeze_struct("e0942bc97a830d0c")]
+pub struct BurnedRegistrationBidTlockPayload {
+    pub hotkey: Vec<u8>,
+    pub bid: u64,
+}
+
+pub type PendingBurnedRegistrationOf<T> =
+    PendingBurnedRegistration<<T as frame_system::Config>::AccountId>;
+
 impl<T: Config> Pallet<T> {
     pub fn register_neuron(netuid: NetUid, hotkey: &T::AccountId) -> u16 {
         // Init param
@@ -85,6 +139,10 @@ impl<T: Config> Pallet<T> {
             Self::get_network_registration_allowed(netuid),
             Error::<T>::SubNetRegistrationDisabled
         );
+        ensure!(
+            BurnedRegistrationModes::<T>::get(netuid) == BurnedRegistrationMode::Immediate,
+            Error::<T>::BurnedRegistrationIsQueued
+        );
 
         // --- 4. Ensure we are not exceeding the max allowed registrations per block.
         ensure!(
@@ -161,6 +219,455 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Queue a burned registration until the end of the adjustment interval of a subnet.
+    ///
+    /// The tao is held in escrow until the registration is resolved, the bid being the maximum
+    /// the caller is willing to pay. In the `HighestBid` mode the actual bid is sealed, encrypted
+    /// to a drand round before the end of the interval, and the maximum bid is held. When the
+    /// queue is full the lowest maximum bid is refunded if the new one is higher. In the `Random`
+    /// mode the current burn is held. The first registration of the queue commits to the drand
+    /// round expected at the end of the interval, and the queue closes once the pulse of this
+    /// round is known.
+    ///
+    /// The queued registrations are resolved by `resolve_pending_burned_registrations`.
+    pub fn do_queue_burned_registration(
+        origin: T::RuntimeOrigin,
+        netuid: NetUid,
+        hotkey: T::AccountId,
+        bid: TaoCurrency,
+        sealed_bid: Option<SealedBid>,
+    ) -> DispatchResult {
+        let coldkey = ensure_signed(origin)?;
+
+        ensure!(
+            !netuid.is_root(),
+            Error::<T>::RegistrationNotPermittedOnRootSubnet
+        );
+        ensure!(Self::if_subnet_exist(netuid), Error::<T>::SubnetNotExists);
+        ensure!(
+            Self::get_network_registration_allowed(netuid),
+            Error::<T>::SubNetRegistrationDisabled
+        );
+
+        let mode = BurnedRegistrationModes::<T>::get(netuid);
+        ensure!(
+            mode != BurnedRegistrationMode::Immediate,
+            Error::<T>::BurnedRegistrationNotQueued
+        );
+        ensure!(
+            !Uids::<T>::contains_key(netuid, &hotkey),
+            Error::<T>::HotKeyAlreadyRegisteredInSubNet
+        );
+
+        let mut pending = PendingBurnedRegistrations::<T>::get(netuid);
+        ensure!(
+            !pending
+                .iter()
+                .any(|registration| registration.hotkey == hotkey),
+            Error::<T>::BurnedRegistrationAlreadyQueued
+        );
+        ensure!(
+            pending
+                .iter()
+                .filter(|registration| registration.coldkey == coldkey)
+                .count()
+                < MAX_PENDING_BURNED_REGISTRATIONS_PER_COLDKEY as usize,
+            Error::<T>::TooManyPendingBurnedRegistrationsForColdkey
+        );
+
+        // The drand round expected at the end of the interval
+        let last_round = pallet_drand::Pallet::<T>::last_stored_round(None);
+        let interval_end = Self::get_last_adjustment_block(netuid)
+            .saturating_add(u64::from(Self::get_adjustment_interval(netuid)));
+        let blocks_left = interval_end
+            .saturating_sub(Self::get_current_block_as_u64())
+            .max(1);
+        let interval_end_round =
+            last_round.saturating_add(blocks_left.saturating_mul(DRAND_ROUNDS_PER_BLOCK));
+
+        let round = match (mode, PendingBurnedRegistrationRound::<T>::get(netuid)) {
+            (BurnedRegistrationMode::Random, Some(round)) => {
+                ensure!(
+                    round > last_round,
+                    Error::<T>::BurnedRegistrationQueueClosed
+                );
+                None
+            }
+            (BurnedRegistrationMode::Random, None) => Some(interval_end_round),
+            _ => None,
+        };
+        let sealed_bid = match mode {
+            BurnedRegistrationMode::HighestBid => {
+                let sealed_bid = sealed_bid.ok_or(Error::<T>::SealedBidRequired)?;
+                ensure!(
+                    sealed_bid.reveal_round > last_round
+                        && sealed_bid.reveal_round <= interval_end_round,
+                    Error::<T>::InvalidSealedBidRevealRound
+                );
+                Some(sealed_bid)
+            }
+            _ => None,
+        };
+
+        let burn = Self::get_burn(netuid);
+        ensure!(bid >= burn, Error::<T>::BurnedRegistrationBidTooLow);
+        let amount = match mode {
+            BurnedRegistrationMode::HighestBid => bid,
+            _ => burn,
+        };
+        ensure!(
+            Self::can_remove_balance_from_coldkey_account(&coldkey, amount.into()),
+            Error::<T>::NotEnoughBalanceToStake
+        );
+
+        Self::create_account_if_non_existent(&coldkey, &hotkey);
+        ensure!(
+            Self::coldkey_owns_hotkey(&coldkey, &hotkey),
+            Error::<T>::NonAssociatedColdKey
+        );
+        ensure!(
+            Self::get_max_allowed_uids(netuid) != 0,
+            Error::<T>::NoNeuronIdAvailable
+        );
+
+        // Make room for a higher maximum bid by refunding the lowest one, the latest on a tie
+        if pending.len() >= MAX_PENDING_BURNED_REGISTRATIONS as usize {
+            let lowest = pending
+                .iter()
+                .enumerate()
+                .rev()
+                .min_by_key(|(_, registration)| registration.amount)
+                .map(|(index, _)| index);
+            let evicted = match (mode, lowest) {
+                (BurnedRegistrationMode::HighestBid, Some(index))
+                    if pending
+                        .get(index)
+                        .is_some_and(|lowest| lowest.amount < amount) =>
+                {
+                    pending.remove(index)
+                }
+                _ => return Err(Error::<T>::TooManyPendingBurnedRegistrations.into()),
+            };
+            Self::refund_pending_burned_registration(netuid, evicted);
+        }
+
+        <T as Config>::Currency::transfer(
+            &coldkey,
+            &Self::burned_registration_escrow(),
+            amount.into(),
+            Preservation::Preserve,
+        )?;
+        pending
+            .try_push(PendingBurnedRegistration {
+                coldkey: coldkey.clone(),
+                hotkey: hotkey.clone(),
+                amount,
+                sealed_bid,
+            })
+            .map_err(|_| Error::<T>::TooManyPendingBurnedRegistrations)?;
+        PendingBurnedRegistrations::<T>::insert(netuid, pending);
+        if let Some(round) = round {
+            PendingBurnedRegistrationRound::<T>::insert(netuid, round);
+        }
+
+        Self::deposit_event(Event::BurnedRegistrationQueued {
+            netuid,
+            coldkey,
+            hotkey,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Resolve the burned registrations queued on a subnet, called at the end of its adjustment interval.
+    ///
+    /// The sealed bids are revealed, then up to `TargetRegistrationsPerInterval` registrations
+    /// are performed, by decreasing bid or in an order drawn from the drand round committed to
+    /// when queuing. The other queued registrations, including the bids that can't be revealed
+    /// or exceed the maximum bid held, are refunded. If a drand pulse is not known yet, the
+    /// queue is kept until the end of the next interval.
+    pub fn resolve_pending_burned_registrations(netuid: NetUid) {
+        let pending = PendingBurnedRegistrations::<T>::take(netuid);
+        if pending.is_empty() {
+            return;
+        }
+
+        let mode = BurnedRegistrationModes::<T>::get(netuid);
+        let round = PendingBurnedRegistrationRound::<T>::get(netuid);
+        let missing_round = |round: RoundNumber| {
+            log::debug!(
+                target: LOG_TARGET,
+                "Missing drand round {round} to resolve the queued registrations on netuid {netuid:?}"
+            );
+        };
+        let seed = match (mode, round) {
+            (BurnedRegistrationMode::Random, Some(round)) => {
+                let Some(pulse) = pallet_drand::Pallet::<T>::pulse(None, round) else {
+                    missing_round(round);
+                    PendingBurnedRegistrations::<T>::insert(netuid, &pending);
+                    return;
+                };
+                Some((pulse.randomness, netuid, round).using_encoded(blake2_256))
+            }
+            _ => None,
+        };
+
+        // The price of each registration, none if its sealed bid is invalid
+        let mut prices = Vec::with_capacity(pending.len());
+        for registration in pending.iter() {
+            let price = match &registration.sealed_bid {
+                Some(sealed_bid) => {
+                    let Some(pulse) =
+                        pallet_drand::Pallet::<T>::pulse(None, sealed_bid.reveal_round)
+                    else {
+                        missing_round(sealed_bid.reveal_round);
+                        PendingBurnedRegistrations::<T>::insert(netuid, &pending);
+                        return;
+                    };
+                    let bid = Self::reveal_sealed_bid(netuid, registration, sealed_bid, &pulse);
+                    if bid.is_none() {
+                        log::debug!(
+                            target: LOG_TARGET,
+                            "Invalid sealed bid of queued hotkey {:?} on netuid {netuid:?}",
+                            registration.hotkey
+                        );
+                    }
+                    bid
+                }
+                None => Some(registration.amount),
+            };
+            prices.push(price);
+        }
+        let mut pending: Vec<_> = pending.into_iter().zip(prices).collect();
+
+        match seed {
+            Some(seed) => {
+                // Fisher-Yates shuffle of the queued registrations
+                for index in 0..pending.len() {
+                    let draw = (seed, index as u32).using_encoded(blake2_256);
+                    let draw = draw
+                        .first_chunk::<8>()
+                        .map(|bytes| u64::from_le_bytes(*bytes))
+                        .unwrap_or_default();
+                    let remaining = pending.len().saturating_sub(index) as u64;
+                    let offset = draw.checked_rem(remaining).unwrap_or_default() as usize;
+                    pending.swap(index, index.saturating_add(offset));
+                }
+            }
+            // The registrations queued before the subnet switched modes are resolved as bids
+            None => {
+                // The earliest bid wins on a tie, the invalid bids are last
+                pending.sort_by(|(_, a), (_, b)| b.cmp(a));
+            }
+        }
+        PendingBurnedRegistrationRound::<T>::remove(netuid);
+
+        let winners = Self::get_target_registrations_per_interval(netuid);
+        let mut registered = 0u16;
+        for (mut registration, price) in pending {
+            if let Some(price) = price.filter(|_| registered < winners) {
+                let result =
+                    with_transaction(|| {
+                        match Self::register_pending_burned_registration(
+                            netuid,
+                            &registration,
+                            price,
+                        ) {
+                            Ok(()) => TransactionOutcome::Commit(Ok(())),
+                            Err(e) => TransactionOutcome::Rollback(Err(e)),
+                        }
+                    });
+                match result {
+                    Ok(()) => {
+                        registered = registered.saturating_add(1);
+                        // Refund the excess over the bid
+                        registration.amount = registration.amount.saturating_sub(price);
+                        if registration.amount.is_zero() {
+                            continue;
+                        }
+                    }
+                    Err(e) => log::debug!(
+                        target: LOG_TARGET,
+                        "Failed to register queued hotkey {:?} on netuid {netuid:?}: {e:?}",
+                        registration.hotkey
+                    ),
+                }
+            }
+            Self::refund_pending_burned_registration(netuid, registration);
+        }
+    }
+
+    /// Refund the burned registrations queued on a subnet.
+    pub fn refund_pending_burned_registrations(netuid: NetUid) {
+        PendingBurnedRegistrationRound::<T>::remove(netuid);
+        for registration in PendingBurnedRegistrations::<T>::take(netuid) {
+            Self::refund_pending_burned_registration(netuid, registration);
+        }
+    }
+
+    /// The weight of resolving the burned registrations queued on the subnets reaching the end
+    /// of their adjustment interval at the given block.
+    pub fn pending_burned_registrations_weight(current_block: u64) -> Weight {
+        let mut weight = T::DbWeight::get().reads(1);
+        for netuid in Self::get_all_subnet_netuids() {
+            // LastAdjustmentBlock, AdjustmentInterval and the length of the queue
+            weight.saturating_accrue(T::DbWeight::get().reads(3));
+            let interval_ended = current_block
+                .saturating_sub(Self::get_last_adjustment_block(netuid))
+                >= u64::from(Self::get_adjustment_interval(netuid));
+            let queued = PendingBurnedRegistrations::<T>::decode_len(netuid).unwrap_or_default();
+            if !interval_ended || queued == 0 {
+                continue;
+            }
+
+            let sealed = PendingBurnedRegistrations::<T>::get(netuid)
+                .iter()
+                .filter(|registration| registration.sealed_bid.is_some())
+                .count() as u64;
+            let queued = queued as u64;
+            let registered = queued.min(u64::from(Self::get_target_registrations_per_interval(
+                netuid,
+            )));
+            // Taking the queue, its mode, round and drand pulse
+            weight.saturating_accrue(
+                Weight::from_parts(5_000_000, 0)
+                    .saturating_add(T::DbWeight::get().reads_writes(4, 2))
+                    .saturating_add(Weight::from_parts(1_000_000, 0).saturating_mul(queued)),
+            );
+            // Reading the drand pulses and decrypting the sealed bids
+            weight.saturating_accrue(
+                Weight::from_parts(5_000_000_000, 0)
+                    .saturating_add(T::DbWeight::get().reads(1))
+                    .saturating_mul(sealed),
+            );
+            // Same as a burned registration, withdrawing from the escrow instead of the coldkey
+            weight.saturating_accrue(
+                Weight::from_parts(354_200_000, 0)
+                    .saturating_add(T::DbWeight::get().reads_writes(50, 43))
+                    .saturating_mul(registered),
+            );
+            // Refunding the other registrations
+            weight.saturating_accrue(
+                Weight::from_parts(10_000_000, 0)
+                    .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+                    .saturating_mul(queued.saturating_sub(registered)),
+            );
+        }
+        weight
+    }
+
+    /// The account holding the tao of the queued burned registrations.
+    pub fn burned_registration_escrow() -> T::AccountId {
+        let entropy = b"registration/escrow".using_encoded(blake2_256);
+        Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+            .expect("infinite length input; no invalid inputs for type; qed")
+    }
+
+    // Reveal the sealed bid of a queued registration, the bid must be for the queued hotkey,
+    // at least the current burn and at most the amount held
+    fn reveal_sealed_bid(
+        netuid: NetUid,
+        registration: &PendingBurnedRegistrationOf<T>,
+        sealed_bid: &SealedBid,
+        pulse: &Pulse,
+    ) -> Option<TaoCurrency> {
+        let commit =
+            TLECiphertext::<TinyBLS381>::deserialize_compressed(&mut &sealed_bid.commit[..])
+                .ok()?;
+        let signature_bytes = pulse
+            .signature
+            .strip_prefix(b"0x")
+            .unwrap_or(&pulse.signature);
+        let signature = <TinyBLS381 as EngineBLS>::SignatureGroup::deserialize_compressed(
+            &mut &signature_bytes[..],
+        )
+        .ok()?;
+        let decrypted_bytes =
+            tld::<TinyBLS381, AESGCMStreamCipherProvider>(commit, signature).ok()?;
+
+        let payload = BurnedRegistrationBidTlockPayload::decode(&mut &decrypted_bytes[..]).ok()?;
+        let hotkey = T::AccountId::decode(&mut &payload.hotkey[..]).ok()?;
+        let bid = TaoCurrency::from(payload.bid);
+        (hotkey == registration.hotkey
+            && bid >= Self::get_burn(netuid)
+            && bid <= registration.amount)
+            .then_some(bid)
+    }
+
+    fn refund_pending_burned_registration(
+        netuid: NetUid,
+        registration: PendingBurnedRegistrationOf<T>,
+    ) {
+        if let Err(e) = <T as Config>::Currency::transfer(
+            &Self::burned_registration_escrow(),
+            &registration.coldkey,
+            registration.amount.into(),
+            Preservation::Expendable,
+        ) {
+            log::error!(
+                target: LOG_TARGET,
+                "Failed to refund the queued registration of hotkey {:?} on netuid {netuid:?}: {e:?}",
+                registration.hotkey
+            );
+            return;
+        }
+        Self::deposit_event(Event::BurnedRegistrationRefunded {
+            netuid,
+            coldkey: registration.coldkey,
+            hotkey: registration.hotkey,
+            amount: registration.amount,
+        });
+    }
+
+    fn register_pending_burned_registration(
+        netuid: NetUid,
+        registration: &PendingBurnedRegistrationOf<T>,
+        price: TaoCurrency,
+    ) -> DispatchResult {
+        ensure!(
+            Self::get_network_registration_allowed(netuid),
+            Error::<T>::SubNetRegistrationDisabled
+        );
+        ensure!(
+            !Uids::<T>::contains_key(netuid, &registration.hotkey),
+            Error::<T>::HotKeyAlreadyRegisteredInSubNet
+        );
+        ensure!(
+            Self::get_max_allowed_uids(netuid) != 0,
+            Error::<T>::NoNeuronIdAvailable
+        );
+
+        // Tokens are withdrawn from the escrow, swapped and then burned.
+        let _ = <T as Config>::Currency::withdraw(
+            &Self::burned_registration_escrow(),
+            price.into(),
+            Precision::Exact,
+            Preservation::Expendable,
+            Fortitude::Polite,
+        )?;
+        let burned_alpha =
+            Self::swap_tao_for_alpha(netuid, price, T::SwapInterface::max_price().into(), false)?
+                .amount_paid_out;
+        SubnetAlphaOut::<T>::mutate(netuid, |total| {
+            *total = total.saturating_sub(burned_alpha.into())
+        });
+
+        let neuron_uid = Self::register_neuron(netuid, &registration.hotkey);
+
+        BurnRegistrationsThisInterval::<T>::mutate(netuid, |val| val.saturating_inc());
+        RegistrationsThisInterval::<T>::mutate(netuid, |val| val.saturating_inc());
+        Self::increase_rao_recycled(netuid, price);
+
+        Self::deposit_event(Event::NeuronRegistered(
+            netuid,
+            neuron_uid,
+            registration.hotkey.clone(),
+        ));
+
+        Ok(())
+    }
+
     /// ---- The implementation for the extrinsic do_registration.
     ///
     /// # Args:
@@ -88,8 +88,9 @@ impl<T: Config> Pallet<T> {
         BlockAtRegistration::<T>::insert(netuid, uid_to_replace, block_number); // Fill block at registration.
         IsNetworkMember::<T>::insert(new_hotkey.clone(), netuid, true); // Fill network is member.
 
-        // 4. Clear neuron certificates
+        // 4. Clear neuron certificates and the emission ledger
         NeuronCertificates::<T>::remove(netuid, old_hotkey.clone());
+        EmissionLedger::<T>::remove(netuid, old_hotkey.clone());
 
         // 5. Reset new neuron's values.
         Self::clear_neuron(netuid, uid_to_replace);
@@ -204,6 +205,7 @@ impl<T: Config> Pallet<T> {
                         Axons::<T>::remove(netuid, &hotkey);
                         NeuronCertificates::<T>::remove(netuid, &hotkey);
                         Prometheus::<T>::remove(netuid, &hotkey);
+                        EmissionLedger::<T>::remove(netuid, &hotkey);
                     }
 
                     // Remove all storage items associated with this uid
@@ -3,6 +3,7 @@ use crate::epoch::math::*;
 use crate::{Error, MAX_COMMIT_REVEAL_PEROIDS, MIN_COMMIT_REVEAL_PEROIDS};
 use codec::Compact;
 use frame_support::dispatch::DispatchResult;
+use pallet_drand::types::ChainHash;
 use safe_math::*;
 use sp_core::{ConstU32, H256};
 use sp_runtime::{
@@ -1306,6 +1307,40 @@ impl<T: Config> Pallet<T> {
         RevealPeriodEpochs::<T>::get(netuid)
     }
 
+    /// Sets the drand beacon the reveal rounds of timelocked weight commits refer to, `None`
+    /// for the default beacon. Pending commits would otherwise be revealed against rounds of
+    /// the new beacon, so the beacon can only change once they are all revealed.
+    pub fn set_timelocked_weights_beacon(
+        netuid: NetUid,
+        beacon: Option<ChainHash>,
+    ) -> DispatchResult {
+        if let Some(chain_hash) = beacon.as_ref() {
+            ensure!(
+                pallet_drand::Pallet::<T>::is_tracked_beacon(chain_hash),
+                Error::<T>::UnknownDrandBeacon
+            );
+        }
+
+        let has_pending_commits =
+            (0..MechanismCountCurrent::<T>::get(netuid).into()).any(|mecid: u8| {
+                let netuid_index = Self::get_mechanism_storage_index(netuid, mecid.into());
+                TimelockedWeightCommits::<T>::iter_prefix(netuid_index)
+                    .any(|(_, commits)| !commits.is_empty())
+            });
+        ensure!(
+            !has_pending_commits,
+            Error::<T>::TimelockedWeightCommitsPending
+        );
+
+        TimelockedWeightsBeacon::<T>::set(netuid, beacon);
+
+        Self::deposit_event(Event::TimelockedWeightsBeaconSet(netuid, beacon));
+        Ok(())
+    }
+    pub fn get_timelocked_weights_beacon(netuid: NetUid) -> Option<ChainHash> {
+        TimelockedWeightsBeacon::<T>::get(netuid)
+    }
+
     pub fn get_first_block_of_epoch(netuid: NetUid, epoch: u64) -> u64 {
         let tempo: u64 = Self::get_tempo(netuid) as u64;
         let tempo_plus_one: u64 = tempo.saturating_add(1);
@@ -2,6 +2,7 @@ use super::*;
 use frame_support::weights::Weight;
 use sp_core::Get;
 use substrate_fixed::types::U64F64;
+use subtensor_swap_interface::SwapHandler;
 
 impl<T: Config> Pallet<T> {
     /// Swaps the coldkey associated with a set of hotkeys from an old coldkey to a new coldkey.
@@ -21,6 +22,7 @@ impl<T: Config> Pallet<T> {
     /// - The caller is not a valid signed origin.
     /// - The old coldkey (caller) is in arbitration.
     /// - The new coldkey is already associated with other hotkeys or is a hotkey itself.
+    /// - The liquidity positions of both coldkeys exceed the per account limit.
     /// - There's not enough balance to pay for the swap.
     ///
     /// # Events
@@ -51,6 +53,10 @@ impl<T: Config> Pallet<T> {
         );
         weight = weight.saturating_add(T::DbWeight::get().reads(1));
 
+        // Ensure the liquidity positions fit under the limit of the new coldkey
+        T::SwapInterface::ensure_can_swap_coldkey_positions(old_coldkey, new_coldkey)?;
+        weight = weight.saturating_add(T::DbWeight::get().reads(2));
+
         // 5. Swap the identity if the old coldkey has one
         if let Some(identity) = IdentitiesV2::<T>::take(old_coldkey) {
             IdentitiesV2::<T>::insert(new_coldkey, identity);
@@ -112,7 +118,7 @@ impl<T: Config> Pallet<T> {
     ///    - For each subnet, if the old coldkey is the owner, transfer ownership to the new coldkey.
     ///
     /// 3. Swap Stakes:
-    ///    - For each hotkey staking for the old coldkey, transfer its stake to the new coldkey.
+    ///    - For each hotkey staking for the old coldkey, transfer its stake and vesting schedules to the new coldkey.
     ///
     /// 4. Swap total coldkey stake:
     ///    - Transfer the total stake from the old coldkey to the new coldkey.
@@ -127,6 +133,10 @@ impl<T: Config> Pallet<T> {
     /// 7. Transfer remaining balance:
     ///    - Transfer any remaining balance from the old coldkey to the new coldkey.
     ///
+    /// 8. Swap liquidity positions and limit orders:
+    ///    - Transfer all liquidity positions and open limit orders of the old coldkey to the new
+    ///      coldkey on every subnet.
+    ///
     /// Throughout the process, the function updates the transaction weight to reflect the operations performed.
     ///
     /// # Notes
@@ -173,6 +183,15 @@ impl<T: Config> Pallet<T> {
                 );
                 // Remove the value from the old account.
                 Alpha::<T>::remove((&hotkey, old_coldkey, netuid));
+                // Vesting schedules follow the stake they lock.
+                Self::swap_vesting_schedules(
+                    &hotkey,
+                    old_coldkey,
+                    &hotkey,
+                    new_coldkey,
+                    netuid,
+                    weight,
+                );
             }
             // Add the weight for the read and write.
             weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 2));
@@ -183,6 +202,19 @@ impl<T: Config> Pallet<T> {
             AutoStakeDestination::<T>::insert(new_coldkey, old_auto_stake_hotkey);
         }
 
+        // The fee alpha sources follow the stake of the coldkey, after the sources of the new
+        // coldkey and within the bound.
+        if FeeAlphaSources::<T>::contains_key(old_coldkey) {
+            let old_sources = FeeAlphaSources::<T>::take(old_coldkey);
+            FeeAlphaSources::<T>::mutate(new_coldkey, |sources| {
+                for source in old_sources {
+                    if !sources.contains(&source) && sources.try_push(source).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
         // 4. Swap TotalColdkeyAlpha (DEPRECATED)
         // for netuid in Self::get_all_subnet_netuids() {
         //     let old_alpha_stake: u64 = TotalColdkeyAlpha::<T>::get(old_coldkey, netuid);
@@ -239,6 +271,13 @@ impl<T: Config> Pallet<T> {
         }
         weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 2));
 
+        // 8. Swap liquidity positions and limit orders.
+        // Positions: MAP ( netuid, coldkey, position_id ) --> Position | Liquidity positions of the coldkey.
+        weight.saturating_accrue(T::SwapInterface::swap_coldkey_positions(
+            old_coldkey,
+            new_coldkey,
+        ));
+
         // Return ok.
         Ok(())
     }
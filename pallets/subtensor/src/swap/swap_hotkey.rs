@@ -567,6 +567,19 @@ impl<T: Config> Pallet<T> {
         );
         weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 2));
 
+        // 8.4 Swap EmissionLedger
+        let old_hotkey_ledger = EmissionLedger::<T>::take(netuid, old_hotkey);
+        if !old_hotkey_ledger.is_empty() {
+            EmissionLedger::<T>::mutate(netuid, new_hotkey, |ledger| {
+                ledger.extend(old_hotkey_ledger);
+                ledger.make_contiguous().sort_by_key(|entry| entry.block);
+                while ledger.len() > EMISSION_LEDGER_LENGTH as usize {
+                    ledger.pop_front();
+                }
+            });
+        }
+        weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 2));
+
         // 9. Swap Alpha
         // Alpha( hotkey, coldkey, netuid ) -> alpha
         let old_alpha_values: Vec<((T::AccountId, NetUid), U64F64)> =
@@ -585,6 +598,11 @@ impl<T: Config> Pallet<T> {
                 );
                 weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 2));
 
+                // Vesting schedules follow the stake they lock.
+                Self::swap_vesting_schedules(
+                    old_hotkey, &coldkey, new_hotkey, &coldkey, netuid, weight,
+                );
+
                 // Swap StakingHotkeys.
                 // StakingHotkeys( coldkey ) --> Vec<hotkey> -- the hotkeys that the coldkey stakes.
                 let mut staking_hotkeys = StakingHotkeys::<T>::get(&coldkey);
@@ -2875,3 +2875,123 @@ fn test_incentive_goes_to_hotkey_when_no_autostake_destination() {
         );
     });
 }
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::coinbase::test_distribute_dividends_records_emission_ledger --exact --show-output --nocapture
+#[test]
+fn test_distribute_dividends_records_emission_ledger() {
+    new_test_ext(1).execute_with(|| {
+        let subnet_owner_ck = U256::from(0);
+        let subnet_owner_hk = U256::from(1);
+        let coldkey = U256::from(2);
+        let hotkey = U256::from(3);
+        Owner::<Test>::insert(hotkey, coldkey);
+        Delegates::<Test>::insert(hotkey, u16::MAX / 10);
+
+        let netuid = add_dynamic_network(&subnet_owner_hk, &subnet_owner_ck);
+
+        let incentive = AlphaCurrency::from(1_000_000);
+        let alpha_divs = U96F32::from_num(2_000_000);
+        let tao_divs = U96F32::from_num(3_000_000);
+        let take = SubtensorModule::get_hotkey_take_float(&hotkey);
+        let alpha_take = take.saturating_mul(alpha_divs);
+        let tao_take = take.saturating_mul(tao_divs);
+
+        // Childkey take is accumulated while the parent dividends are computed
+        PendingChildkeyTake::<Test>::insert(netuid, hotkey, AlphaCurrency::from(500));
+
+        let distribute = || {
+            SubtensorModule::distribute_dividends_and_incentives(
+                netuid,
+                AlphaCurrency::ZERO,
+                BTreeMap::from([(hotkey, incentive)]),
+                BTreeMap::from([(hotkey, alpha_divs)]),
+                BTreeMap::from([(hotkey, tao_divs)]),
+            );
+        };
+        distribute();
+
+        assert_eq!(
+            SubtensorModule::get_emission_ledger(netuid, hotkey),
+            vec![EmissionLedgerEntry {
+                block: 1,
+                incentive,
+                alpha_dividends: alpha_divs
+                    .saturating_sub(alpha_take)
+                    .saturating_to_num::<u64>()
+                    .into(),
+                tao_dividends: tao_divs
+                    .saturating_sub(tao_take)
+                    .saturating_to_num::<u64>()
+                    .into(),
+                childkey_take: 500.into(),
+                alpha_delegate_take: alpha_take.saturating_to_num::<u64>().into(),
+                tao_delegate_take: tao_take.saturating_to_num::<u64>().into(),
+            }]
+        );
+        assert!(
+            PendingChildkeyTake::<Test>::iter_prefix(netuid)
+                .next()
+                .is_none()
+        );
+        assert_eq!(
+            SubtensorModule::get_emission_ledgers_for_hotkey(hotkey)
+                .into_iter()
+                .map(|(netuid, _)| netuid)
+                .collect::<Vec<_>>(),
+            vec![netuid]
+        );
+
+        // Only the last EMISSION_LEDGER_LENGTH epochs are kept
+        for block in 2..=(EMISSION_LEDGER_LENGTH as u64 + 1) {
+            System::set_block_number(block);
+            distribute();
+        }
+        let ledger = SubtensorModule::get_emission_ledger(netuid, hotkey);
+        assert_eq!(ledger.len(), EMISSION_LEDGER_LENGTH as usize);
+        assert_eq!(ledger.first().unwrap().block, 2);
+        assert_eq!(
+            ledger.last().unwrap().block,
+            EMISSION_LEDGER_LENGTH as u64 + 1
+        );
+        assert_eq!(ledger.last().unwrap().childkey_take, AlphaCurrency::ZERO);
+    });
+}
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::coinbase::test_emission_ledger_cleanup_and_weight --exact --show-output --nocapture
+#[test]
+fn test_emission_ledger_cleanup_and_weight() {
+    new_test_ext(1).execute_with(|| {
+        let subnet_owner_ck = U256::from(0);
+        let subnet_owner_hk = U256::from(1);
+        let coldkey = U256::from(2);
+        let hotkey = U256::from(3);
+        let new_hotkey = U256::from(4);
+        let netuid = add_dynamic_network(&subnet_owner_hk, &subnet_owner_ck);
+        register_ok_neuron(netuid, hotkey, coldkey, 0);
+        let uid = SubtensorModule::get_uid_for_net_and_hotkey(netuid, &hotkey).unwrap();
+
+        SubtensorModule::record_emission_ledger(
+            netuid,
+            BTreeMap::from([(hotkey, EmissionLedgerEntry::default())]),
+        );
+        assert_eq!(
+            SubtensorModule::get_emission_ledger(netuid, hotkey).len(),
+            1
+        );
+
+        // The ledger is charged only on the epoch block of the subnet
+        let tempo = SubtensorModule::get_tempo(netuid) as u64;
+        let epoch_block = (0..=tempo)
+            .find(|block| SubtensorModule::should_run_epoch(netuid, *block))
+            .unwrap();
+        let idle_block = epoch_block + 1;
+        assert!(
+            SubtensorModule::emission_ledger_weight(epoch_block)
+                .all_gt(SubtensorModule::emission_ledger_weight(idle_block))
+        );
+
+        // Deregistration drops the ledger of the replaced hotkey
+        SubtensorModule::replace_neuron(netuid, uid, &new_hotkey, 2);
+        assert!(SubtensorModule::get_emission_ledger(netuid, hotkey).is_empty());
+    });
+}
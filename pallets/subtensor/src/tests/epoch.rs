@@ -11,11 +11,12 @@ use frame_support::{assert_err, assert_ok};
 use rand::{Rng, SeedableRng, distributions::Uniform, rngs::StdRng, seq::SliceRandom, thread_rng};
 use sp_core::{Get, U256};
 use substrate_fixed::types::I32F32;
-use subtensor_runtime_common::{AlphaCurrency, NetUidStorageIndex, TaoCurrency};
+use subtensor_runtime_common::{AlphaCurrency, MechId, NetUidStorageIndex, TaoCurrency};
 use subtensor_swap_interface::SwapHandler;
 
 use super::mock::*;
 use crate::epoch::math::{fixed, u16_proportion_to_fixed};
+use crate::rpc_info::epoch_simulation::EpochHyperparamOverrides;
 use crate::tests::math::{assert_mat_compare, vec_to_fixed, vec_to_mat_fixed};
 use crate::*;
 
@@ -3883,3 +3884,160 @@ fn test_last_update_size_mismatch() {
         assert_eq!(SubtensorModule::get_dividends_for_uid(netuid, uid), 0);
     });
 }
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::epoch::test_simulate_epoch_does_not_persist --exact --show-output --nocapture
+#[test]
+fn test_simulate_epoch_does_not_persist() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        let mecid = MechId::from(0);
+        let netuid_index = SubtensorModule::get_mechanism_storage_index(netuid, mecid);
+        let validator = U256::from(0);
+        let server = U256::from(1);
+        let stake_amount: u64 = 1_000_000_000;
+        add_network_disable_commit_reveal(netuid, u16::MAX - 1, 0);
+        SubtensorModule::set_max_allowed_uids(netuid, 2);
+        register_ok_neuron(netuid, validator, validator, 1);
+        register_ok_neuron(netuid, server, server, 2);
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &validator,
+            &validator,
+            netuid,
+            stake_amount.into(),
+        );
+        ValidatorPermit::<Test>::insert(netuid, vec![true, false]);
+        run_to_block(2);
+
+        let kappa = SubtensorModule::get_kappa(netuid);
+        let last_update = SubtensorModule::get_last_update(netuid_index);
+        let overrides = EpochHyperparamOverrides {
+            kappa: Some(kappa.saturating_add(1)),
+            ..Default::default()
+        };
+        let weights = vec![(0, vec![(1, u16::MAX)])];
+
+        let terms = SubtensorModule::simulate_epoch(
+            netuid,
+            mecid,
+            1_000_000_000.into(),
+            overrides,
+            weights,
+        )
+        .unwrap();
+
+        // Both UIDs are returned in order, and the overridden weights are used.
+        assert_eq!(terms.len(), 2);
+        assert_eq!(terms[0].hotkey, validator);
+        assert_eq!(terms[1].hotkey, server);
+        assert_eq!(terms[1].incentive.0, u16::MAX);
+        assert!(terms[0].dividend.0 > 0);
+
+        // Nothing is persisted.
+        assert_eq!(SubtensorModule::get_kappa(netuid), kappa);
+        assert_eq!(SubtensorModule::get_last_update(netuid_index), last_update);
+        assert!(Weights::<Test>::get(netuid_index, 0).is_empty());
+        assert_eq!(SubtensorModule::get_incentive_for_uid(netuid_index, 1), 0);
+        assert_eq!(SubtensorModule::get_dividends_for_uid(netuid, 0), 0);
+
+        // Unknown mechanisms and UIDs are rejected.
+        assert!(
+            SubtensorModule::simulate_epoch(
+                netuid,
+                MechId::from(1),
+                1_000_000_000.into(),
+                EpochHyperparamOverrides::default(),
+                vec![]
+            )
+            .is_none()
+        );
+        assert!(
+            SubtensorModule::simulate_epoch(
+                netuid,
+                mecid,
+                1_000_000_000.into(),
+                EpochHyperparamOverrides::default(),
+                vec![(0, vec![(2, u16::MAX)])]
+            )
+            .is_none()
+        );
+    });
+}
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::epoch::test_epoch_debug_snapshot --exact --show-output --nocapture
+#[test]
+fn test_epoch_debug_snapshot() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        let mecid = MechId::from(0);
+        let netuid_index = SubtensorModule::get_mechanism_storage_index(netuid, mecid);
+        let validator = U256::from(0);
+        let server = U256::from(1);
+        add_network_disable_commit_reveal(netuid, u16::MAX - 1, 0);
+        SubtensorModule::set_max_allowed_uids(netuid, 2);
+        register_ok_neuron(netuid, validator, validator, 1);
+        register_ok_neuron(netuid, server, server, 2);
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &validator,
+            &validator,
+            netuid,
+            1_000_000_000.into(),
+        );
+        ValidatorPermit::<Test>::insert(netuid, vec![true, false]);
+        run_to_block(2);
+        Weights::<Test>::insert(netuid_index, 0, vec![(1, u16::MAX)]);
+        SubtensorModule::set_last_update_for_uid(netuid_index, 0, 2);
+
+        // Nothing is kept while epoch debugging is disabled.
+        SubtensorModule::epoch(netuid, 1_000_000_000.into());
+        assert!(SubtensorModule::get_epoch_debug(netuid, mecid).is_none());
+
+        SubtensorModule::set_epoch_debug_enabled(netuid, true);
+        SubtensorModule::epoch(netuid, 1_000_000_000.into());
+        let snapshot = SubtensorModule::get_epoch_debug(netuid, mecid).unwrap();
+        assert_eq!(snapshot.block, 2);
+        assert_eq!(snapshot.active_stake, vec![u16::MAX, 0]);
+        assert_eq!(snapshot.weights, vec![vec![(1, u16::MAX)], vec![]]);
+        assert_eq!(snapshot.consensus, vec![0, u16::MAX]);
+        assert_eq!(snapshot.clipped_weights, snapshot.weights);
+        assert_eq!(snapshot.validator_trust, vec![u16::MAX, 0]);
+        assert_eq!(snapshot.ema_bonds.len(), 2);
+        assert!(snapshot.liquid_alphas.is_empty());
+
+        // Disabling removes the snapshot.
+        SubtensorModule::set_epoch_debug_enabled(netuid, false);
+        assert!(SubtensorModule::get_epoch_debug(netuid, mecid).is_none());
+    });
+}
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::epoch::test_epoch_debug_weight --exact --show-output --nocapture
+#[test]
+fn test_epoch_debug_weight() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        add_network_disable_commit_reveal(netuid, 10, 0);
+        SubtensorModule::set_max_allowed_uids(netuid, 4);
+        SubtensorModule::set_max_registrations_per_block(netuid, 8);
+        SubtensorModule::set_target_registrations_per_interval(netuid, 8);
+        for i in 0..4 {
+            register_ok_neuron(netuid, U256::from(i), U256::from(i), i as u64);
+        }
+        let epoch_block = (0..=10)
+            .find(|block| SubtensorModule::should_run_epoch(netuid, *block))
+            .unwrap();
+        let other_block = epoch_block + 1;
+
+        // Snapshots are only charged on the epoch blocks of subnets with debugging enabled
+        let disabled = SubtensorModule::epoch_debug_weight(epoch_block).ref_time();
+        SubtensorModule::set_epoch_debug_enabled(netuid, true);
+        let four_neurons = SubtensorModule::epoch_debug_weight(epoch_block).ref_time();
+        assert!(SubtensorModule::epoch_debug_weight(other_block).ref_time() < four_neurons);
+        assert!(disabled < four_neurons);
+
+        // The charge grows with the neuron count
+        SubtensorModule::set_max_allowed_uids(netuid, 8);
+        for i in 4..8 {
+            register_ok_neuron(netuid, U256::from(i), U256::from(i), i as u64);
+        }
+        assert!(four_neurons < SubtensorModule::epoch_debug_weight(epoch_block).ref_time());
+    });
+}
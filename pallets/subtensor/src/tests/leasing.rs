@@ -4,12 +4,15 @@
     clippy::indexing_slicing
 )]
 use super::mock::*;
-use crate::{subnets::leasing::SubnetLeaseOf, *};
+use crate::{
+    subnets::leasing::{LeaseAction, SubnetLeaseOf},
+    *,
+};
 use frame_support::{StorageDoubleMap, assert_err, assert_ok};
 use sp_core::U256;
 use sp_runtime::Percent;
 use substrate_fixed::types::U64F64;
-use subtensor_runtime_common::AlphaCurrency;
+use subtensor_runtime_common::{AlphaCurrency, TaoCurrency};
 
 #[test]
 fn test_register_leased_network_works() {
@@ -64,6 +67,10 @@ fn test_register_leased_network_works() {
             SubnetLeaseShares::<Test>::get(lease_id, contributions[1].0),
             contributor2_share
         );
+        assert_eq!(
+            SubnetLeaseTotalShares::<Test>::get(lease_id),
+            contributor1_share + contributor2_share
+        );
 
         // Ensure each contributor and beneficiary has been refunded their share of the leftover cap
         let leftover_cap = cap.saturating_sub(lease.cost);
@@ -277,6 +284,9 @@ fn test_terminate_lease_works() {
         // Ensure everything has been cleaned up
         assert_eq!(SubnetLeases::<Test>::get(lease_id), None);
         assert!(!SubnetLeaseShares::<Test>::contains_prefix(lease_id));
+        assert!(!LeaseDividendsCheckpoint::<Test>::contains_prefix(lease_id));
+        assert!(!SubnetLeaseTotalShares::<Test>::contains_key(lease_id));
+        assert!(!LeaseDividendsPerShare::<Test>::contains_key(lease_id));
         assert!(!AccumulatedLeaseDividends::<Test>::contains_key(lease_id));
 
         // Ensure the beneficiary has been removed as a proxy
@@ -294,6 +304,103 @@ fn test_terminate_lease_works() {
     });
 }
 
+#[test]
+fn test_terminate_lease_pays_unclaimed_dividends() {
+    new_test_ext(1).execute_with(|| {
+        // Setup a crowdloan
+        let crowdloan_id = 0;
+        let beneficiary = U256::from(1);
+        let deposit = 10_000_000_000; // 10 TAO
+        let cap = 1_000_000_000_000; // 1000 TAO
+        let contributions = vec![(U256::from(2), 990_000_000_000)]; // 990 TAO
+        setup_crowdloan(crowdloan_id, deposit, cap, beneficiary, &contributions);
+
+        // Setup a leased network
+        let end_block = 500;
+        let tao_to_stake = 100_000_000_000; // 100 TAO
+        let emissions_share = Percent::from_percent(30);
+        let (lease_id, _) = setup_leased_network(
+            beneficiary,
+            emissions_share,
+            Some(end_block),
+            Some(tao_to_stake),
+        );
+
+        // Distribute some dividends without claiming them
+        run_to_block(<Test as Config>::LeaseDividendsDistributionInterval::get() as u64);
+        SubtensorModule::distribute_leased_network_dividends(
+            lease_id,
+            AlphaCurrency::from(5_000_000),
+        );
+        let claimable =
+            SubtensorModule::get_claimable_lease_dividends(lease_id, &contributions[0].0);
+        assert!(claimable > TaoCurrency::ZERO);
+        let contributor_balance_before = SubtensorModule::get_coldkey_balance(&contributions[0].0);
+
+        // Terminate the lease
+        run_to_block(end_block);
+        let hotkey = U256::from(3);
+        SubtensorModule::create_account_if_non_existent(&beneficiary, &hotkey);
+        assert_ok!(SubtensorModule::terminate_lease(
+            RuntimeOrigin::signed(beneficiary),
+            lease_id,
+            hotkey,
+        ));
+
+        // Ensure the unclaimed dividends have been paid to the contributor
+        assert_eq!(
+            SubtensorModule::get_coldkey_balance(&contributions[0].0),
+            contributor_balance_before + claimable.to_u64()
+        );
+    });
+}
+
+#[test]
+fn test_terminate_lease_works_if_dividends_payout_fails() {
+    new_test_ext(1).execute_with(|| {
+        // Setup a crowdloan
+        let crowdloan_id = 0;
+        let beneficiary = U256::from(1);
+        let deposit = 10_000_000_000; // 10 TAO
+        let cap = 1_000_000_000_000; // 1000 TAO
+        let contributions = vec![(U256::from(2), 990_000_000_000)]; // 990 TAO
+        setup_crowdloan(crowdloan_id, deposit, cap, beneficiary, &contributions);
+
+        // Setup a leased network
+        let end_block = 500;
+        let (lease_id, lease) = setup_leased_network(
+            beneficiary,
+            Percent::from_percent(30),
+            Some(end_block),
+            None,
+        );
+
+        // Make the claimable dividends exceed the lease coldkey balance
+        LeaseDividendsPerShare::<Test>::insert(lease_id, U64F64::from_num(u64::MAX / 2));
+        let claimable =
+            SubtensorModule::get_claimable_lease_dividends(lease_id, &contributions[0].0);
+        assert!(claimable.to_u64() > SubtensorModule::get_coldkey_balance(&lease.coldkey));
+        let contributor_balance_before = SubtensorModule::get_coldkey_balance(&contributions[0].0);
+
+        // Terminate the lease
+        run_to_block(end_block);
+        let hotkey = U256::from(3);
+        SubtensorModule::create_account_if_non_existent(&beneficiary, &hotkey);
+        assert_ok!(SubtensorModule::terminate_lease(
+            RuntimeOrigin::signed(beneficiary),
+            lease_id,
+            hotkey,
+        ));
+
+        // Ensure the lease has been terminated without paying the contributor
+        assert!(SubnetLeases::<Test>::get(lease_id).is_none());
+        assert_eq!(
+            SubtensorModule::get_coldkey_balance(&contributions[0].0),
+            contributor_balance_before
+        );
+    });
+}
+
 #[test]
 fn test_terminate_lease_fails_if_bad_origin() {
     new_test_ext(1).execute_with(|| {
@@ -508,6 +615,7 @@ fn test_distribute_lease_network_dividends_multiple_contributors_works() {
         let contributor1_balance_before = SubtensorModule::get_coldkey_balance(&contributions[0].0);
         let contributor2_balance_before = SubtensorModule::get_coldkey_balance(&contributions[1].0);
         let beneficiary_balance_before = SubtensorModule::get_coldkey_balance(&beneficiary);
+        let lease_coldkey_balance_before = SubtensorModule::get_coldkey_balance(&lease.coldkey);
 
         // Setup some previously accumulated dividends
         let accumulated_dividends = AlphaCurrency::from(5_000_000);
@@ -517,39 +625,83 @@ fn test_distribute_lease_network_dividends_multiple_contributors_works() {
         let owner_cut_alpha = AlphaCurrency::from(5_000_000);
         SubtensorModule::distribute_leased_network_dividends(lease_id, owner_cut_alpha);
 
-        // Ensure the dividends were distributed correctly relative to their shares
+        // Ensure the contributors dividends are kept in the lease coldkey until claimed
         let distributed_tao = subnet_tao_before - SubnetTAO::<Test>::get(lease.netuid);
-        let contributor1_balance_delta = SubtensorModule::get_coldkey_balance(&contributions[0].0)
-            .saturating_sub(contributor1_balance_before);
-        let contributor2_balance_delta = SubtensorModule::get_coldkey_balance(&contributions[1].0)
-            .saturating_sub(contributor2_balance_before);
+        let contributors_cut_tao = SubnetLeaseTotalShares::<Test>::get(lease_id)
+            .saturating_mul(U64F64::from(distributed_tao.to_u64()))
+            .ceil()
+            .to_num::<u64>();
+        assert_eq!(
+            SubtensorModule::get_coldkey_balance(&lease.coldkey),
+            lease_coldkey_balance_before + contributors_cut_tao
+        );
+        assert_eq!(
+            SubtensorModule::get_coldkey_balance(&contributions[0].0),
+            contributor1_balance_before
+        );
+        assert_eq!(
+            SubtensorModule::get_coldkey_balance(&contributions[1].0),
+            contributor2_balance_before
+        );
+
+        // The beneficiary should have received the remaining dividends
         let beneficiary_balance_delta = SubtensorModule::get_coldkey_balance(&beneficiary)
             .saturating_sub(beneficiary_balance_before);
-
         assert_eq!(
-            distributed_tao,
-            (beneficiary_balance_delta + contributor1_balance_delta + contributor2_balance_delta)
-                .into()
+            beneficiary_balance_delta,
+            distributed_tao.to_u64() - contributors_cut_tao
         );
 
+        // Ensure the contributors can claim the dividends relative to their shares
         let expected_contributor1_balance =
             SubnetLeaseShares::<Test>::get(lease_id, contributions[0].0)
                 .saturating_mul(U64F64::from(distributed_tao.to_u64()))
                 .floor()
                 .to_num::<u64>();
-        assert_eq!(contributor1_balance_delta, expected_contributor1_balance);
-
         let expected_contributor2_balance =
             SubnetLeaseShares::<Test>::get(lease_id, contributions[1].0)
                 .saturating_mul(U64F64::from(distributed_tao.to_u64()))
                 .floor()
                 .to_num::<u64>();
+        assert_eq!(
+            SubtensorModule::get_claimable_lease_dividends_for_account(&contributions[0].0),
+            vec![(lease_id, expected_contributor1_balance.into())]
+        );
+        assert_eq!(
+            SubtensorModule::get_claimable_lease_dividends(lease_id, &contributions[1].0),
+            expected_contributor2_balance.into()
+        );
+
+        assert_ok!(SubtensorModule::claim_lease_dividends(
+            RuntimeOrigin::signed(contributions[0].0),
+            lease_id,
+            None,
+        ));
+        assert_ok!(SubtensorModule::claim_lease_dividends(
+            RuntimeOrigin::signed(contributions[1].0),
+            lease_id,
+            None,
+        ));
+
+        let contributor1_balance_delta = SubtensorModule::get_coldkey_balance(&contributions[0].0)
+            .saturating_sub(contributor1_balance_before);
+        let contributor2_balance_delta = SubtensorModule::get_coldkey_balance(&contributions[1].0)
+            .saturating_sub(contributor2_balance_before);
+        assert_eq!(contributor1_balance_delta, expected_contributor1_balance);
         assert_eq!(contributor2_balance_delta, expected_contributor2_balance);
 
-        // The beneficiary should have received the remaining dividends
-        let expected_beneficiary_balance = distributed_tao.to_u64()
-            - (expected_contributor1_balance + expected_contributor2_balance);
-        assert_eq!(beneficiary_balance_delta, expected_beneficiary_balance);
+        // Only the rounding dust is left in the lease coldkey
+        let dust = SubtensorModule::get_coldkey_balance(&lease.coldkey)
+            .saturating_sub(lease_coldkey_balance_before);
+        assert!(dust <= 2);
+        assert_eq!(
+            distributed_tao,
+            (beneficiary_balance_delta
+                + contributor1_balance_delta
+                + contributor2_balance_delta
+                + dust)
+                .into()
+        );
 
         // Ensure nothing was accumulated for later distribution
         assert_eq!(
@@ -855,6 +1007,698 @@ fn test_distribute_lease_network_dividends_accumulates_if_insufficient_liquidity
     });
 }
 
+#[test]
+fn test_transfer_lease_share_works() {
+    new_test_ext(1).execute_with(|| {
+        // Setup a crowdloan
+        let crowdloan_id = 0;
+        let beneficiary = U256::from(1);
+        let deposit = 10_000_000_000; // 10 TAO
+        let cap = 1_000_000_000_000; // 1000 TAO
+        let contributions = vec![
+            (U256::from(2), 600_000_000_000), // 600 TAO
+            (U256::from(3), 390_000_000_000), // 390 TAO
+        ];
+        setup_crowdloan(crowdloan_id, deposit, cap, beneficiary, &contributions);
+
+        // Setup a leased network
+        let end_block = 500;
+        let emissions_share = Percent::from_percent(30);
+        let tao_to_stake = 100_000_000_000; // 100 TAO
+        let (lease_id, _) = setup_leased_network(
+            beneficiary,
+            emissions_share,
+            Some(end_block),
+            Some(tao_to_stake),
+        );
+
+        let total_shares = |lease_id| {
+            SubnetLeaseShares::<Test>::iter_prefix(lease_id)
+                .fold(U64F64::from_num(0), |total, (_, share)| total + share)
+        };
+        let total_shares_before = total_shares(lease_id);
+        let share_before = SubnetLeaseShares::<Test>::get(lease_id, contributions[0].0);
+
+        // Transfer half of the first contributor share to a new account
+        let buyer = U256::from(5);
+        assert_ok!(SubtensorModule::transfer_lease_share(
+            RuntimeOrigin::signed(contributions[0].0),
+            lease_id,
+            buyer,
+            Percent::from_percent(50),
+        ));
+
+        let transferred = SubnetLeaseShares::<Test>::get(lease_id, buyer);
+        assert_eq!(transferred, share_before / U64F64::from_num(2));
+        assert_eq!(
+            SubnetLeaseShares::<Test>::get(lease_id, contributions[0].0),
+            share_before - transferred
+        );
+        assert_eq!(total_shares(lease_id), total_shares_before);
+        assert_eq!(
+            last_event(),
+            crate::Event::<Test>::LeaseShareTransferred {
+                lease_id,
+                from: contributions[0].0,
+                to: buyer,
+                share: transferred,
+            }
+            .into()
+        );
+
+        // Transfer the whole second contributor share to the beneficiary, it is merged
+        // into the beneficiary's leftover share
+        assert_ok!(SubtensorModule::transfer_lease_share(
+            RuntimeOrigin::signed(contributions[1].0),
+            lease_id,
+            beneficiary,
+            Percent::from_percent(100),
+        ));
+        assert!(!SubnetLeaseShares::<Test>::contains_key(
+            lease_id,
+            contributions[1].0
+        ));
+        assert!(!SubnetLeaseShares::<Test>::contains_key(
+            lease_id,
+            beneficiary
+        ));
+
+        // Ensure the dividends are distributed to the new shareholder
+        run_to_block(<Test as Config>::LeaseDividendsDistributionInterval::get() as u64);
+        let buyer_balance_before = SubtensorModule::get_coldkey_balance(&buyer);
+        let contributor2_balance_before = SubtensorModule::get_coldkey_balance(&contributions[1].0);
+        SubtensorModule::distribute_leased_network_dividends(
+            lease_id,
+            AlphaCurrency::from(20_000_000),
+        );
+        assert_ok!(SubtensorModule::claim_lease_dividends(
+            RuntimeOrigin::signed(buyer),
+            lease_id,
+            None,
+        ));
+        assert!(SubtensorModule::get_coldkey_balance(&buyer) > buyer_balance_before);
+        assert_eq!(
+            SubtensorModule::get_coldkey_balance(&contributions[1].0),
+            contributor2_balance_before
+        );
+    });
+}
+
+#[test]
+fn test_transfer_lease_share_fails_if_invalid() {
+    new_test_ext(1).execute_with(|| {
+        let beneficiary = U256::from(1);
+        let contributor = U256::from(2);
+
+        // The lease must exist
+        assert_err!(
+            SubtensorModule::transfer_lease_share(
+                RuntimeOrigin::signed(contributor),
+                0,
+                U256::from(5),
+                Percent::from_percent(50),
+            ),
+            Error::<Test>::LeaseDoesNotExist
+        );
+
+        // Setup a leased network
+        setup_crowdloan(
+            0,
+            10_000_000_000,
+            1_000_000_000_000,
+            beneficiary,
+            &[(contributor, 990_000_000_000)],
+        );
+        let (lease_id, _) =
+            setup_leased_network(beneficiary, Percent::from_percent(30), None, None);
+
+        // The share can't be transferred to its holder
+        assert_err!(
+            SubtensorModule::transfer_lease_share(
+                RuntimeOrigin::signed(contributor),
+                lease_id,
+                contributor,
+                Percent::from_percent(50),
+            ),
+            Error::<Test>::InvalidLeaseShareTransfer
+        );
+
+        // The share can't be transferred to the lease coldkey
+        let lease = SubnetLeases::<Test>::get(lease_id).unwrap();
+        assert_err!(
+            SubtensorModule::transfer_lease_share(
+                RuntimeOrigin::signed(contributor),
+                lease_id,
+                lease.coldkey,
+                Percent::from_percent(50),
+            ),
+            Error::<Test>::InvalidLeaseShareTransfer
+        );
+
+        // The portion must be non zero
+        assert_err!(
+            SubtensorModule::transfer_lease_share(
+                RuntimeOrigin::signed(contributor),
+                lease_id,
+                U256::from(5),
+                Percent::zero(),
+            ),
+            Error::<Test>::InvalidLeaseShareTransfer
+        );
+
+        // The beneficiary and other accounts have no share to transfer
+        for who in [beneficiary, U256::from(6)] {
+            assert_err!(
+                SubtensorModule::transfer_lease_share(
+                    RuntimeOrigin::signed(who),
+                    lease_id,
+                    U256::from(5),
+                    Percent::from_percent(50),
+                ),
+                Error::<Test>::NoLeaseShare
+            );
+        }
+    });
+}
+
+#[test]
+fn test_claim_lease_dividends_works() {
+    new_test_ext(1).execute_with(|| {
+        // Setup a crowdloan
+        let crowdloan_id = 0;
+        let beneficiary = U256::from(1);
+        let deposit = 10_000_000_000; // 10 TAO
+        let cap = 1_000_000_000_000; // 1000 TAO
+        let contributions = vec![
+            (U256::from(2), 600_000_000_000), // 600 TAO
+            (U256::from(3), 390_000_000_000), // 390 TAO
+        ];
+        setup_crowdloan(crowdloan_id, deposit, cap, beneficiary, &contributions);
+
+        // Setup a leased network
+        let end_block = 500;
+        let emissions_share = Percent::from_percent(30);
+        let tao_to_stake = 100_000_000_000; // 100 TAO
+        let (lease_id, lease) = setup_leased_network(
+            beneficiary,
+            emissions_share,
+            Some(end_block),
+            Some(tao_to_stake),
+        );
+
+        // Distribute the dividends twice without claiming
+        let interval = <Test as Config>::LeaseDividendsDistributionInterval::get() as u64;
+        run_to_block(interval);
+        SubtensorModule::distribute_leased_network_dividends(
+            lease_id,
+            AlphaCurrency::from(20_000_000),
+        );
+        let claimable_first =
+            SubtensorModule::get_claimable_lease_dividends(lease_id, &contributions[1].0);
+        run_to_block(interval * 2);
+        SubtensorModule::distribute_leased_network_dividends(
+            lease_id,
+            AlphaCurrency::from(20_000_000),
+        );
+        let claimable =
+            SubtensorModule::get_claimable_lease_dividends(lease_id, &contributions[1].0);
+        assert!(claimable > claimable_first);
+
+        // Claim the accumulated dividends of the second contributor
+        let contributor_balance_before = SubtensorModule::get_coldkey_balance(&contributions[1].0);
+        assert_ok!(SubtensorModule::claim_lease_dividends(
+            RuntimeOrigin::signed(contributions[1].0),
+            lease_id,
+            None,
+        ));
+        assert_eq!(
+            SubtensorModule::get_coldkey_balance(&contributions[1].0),
+            contributor_balance_before + claimable.to_u64()
+        );
+        assert_eq!(
+            SubtensorModule::get_claimable_lease_dividends(lease_id, &contributions[1].0),
+            TaoCurrency::ZERO
+        );
+        assert_eq!(
+            last_event(),
+            crate::Event::<Test>::LeaseDividendsClaimed {
+                lease_id,
+                contributor: contributions[1].0,
+                amount: claimable,
+                hotkey: None,
+            }
+            .into()
+        );
+
+        // Claim and stake the dividends of the first contributor into the lease hotkey
+        let claimable =
+            SubtensorModule::get_claimable_lease_dividends(lease_id, &contributions[0].0);
+        let contributor_balance_before = SubtensorModule::get_coldkey_balance(&contributions[0].0);
+        assert_ok!(SubtensorModule::claim_lease_dividends(
+            RuntimeOrigin::signed(contributions[0].0),
+            lease_id,
+            Some(lease.hotkey),
+        ));
+        assert_eq!(
+            SubtensorModule::get_coldkey_balance(&contributions[0].0),
+            contributor_balance_before
+        );
+        assert!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+                &lease.hotkey,
+                &contributions[0].0,
+                lease.netuid
+            ) > AlphaCurrency::ZERO
+        );
+        assert_eq!(
+            last_event(),
+            crate::Event::<Test>::LeaseDividendsClaimed {
+                lease_id,
+                contributor: contributions[0].0,
+                amount: claimable,
+                hotkey: Some(lease.hotkey),
+            }
+            .into()
+        );
+    });
+}
+
+#[test]
+fn test_claim_lease_dividends_fails_if_invalid() {
+    new_test_ext(1).execute_with(|| {
+        let beneficiary = U256::from(1);
+        let contributor = U256::from(2);
+
+        // The lease must exist
+        assert_err!(
+            SubtensorModule::claim_lease_dividends(RuntimeOrigin::signed(contributor), 0, None),
+            Error::<Test>::LeaseDoesNotExist
+        );
+
+        // Setup a leased network
+        setup_crowdloan(
+            0,
+            10_000_000_000,
+            1_000_000_000_000,
+            beneficiary,
+            &[(contributor, 990_000_000_000)],
+        );
+        let (lease_id, _) =
+            setup_leased_network(beneficiary, Percent::from_percent(30), None, None);
+
+        // The beneficiary and other accounts have no share to claim dividends for
+        for who in [beneficiary, U256::from(6)] {
+            assert_err!(
+                SubtensorModule::claim_lease_dividends(RuntimeOrigin::signed(who), lease_id, None),
+                Error::<Test>::NoLeaseShare
+            );
+        }
+
+        // Nothing has been distributed yet
+        assert_err!(
+            SubtensorModule::claim_lease_dividends(
+                RuntimeOrigin::signed(contributor),
+                lease_id,
+                None
+            ),
+            Error::<Test>::NoLeaseDividendsToClaim
+        );
+    });
+}
+
+#[test]
+fn test_lease_governance_replace_beneficiary_works() {
+    new_test_ext(1).execute_with(|| {
+        // Setup a crowdloan
+        let crowdloan_id = 0;
+        let beneficiary = U256::from(1);
+        let deposit = 10_000_000_000; // 10 TAO
+        let cap = 1_000_000_000_000; // 1000 TAO
+        let contributions = vec![
+            (U256::from(2), 600_000_000_000), // 600 TAO
+            (U256::from(3), 390_000_000_000), // 390 TAO
+        ];
+        setup_crowdloan(crowdloan_id, deposit, cap, beneficiary, &contributions);
+
+        // Setup a leased network
+        let (lease_id, lease) =
+            setup_leased_network(beneficiary, Percent::from_percent(30), None, None);
+
+        // Enable the governance of the lease
+        assert_ok!(SubtensorModule::enable_lease_governance(
+            RuntimeOrigin::signed(beneficiary),
+            lease_id
+        ));
+        assert!(SubnetLeaseGovernance::<Test>::get(lease_id));
+        assert_eq!(
+            last_event(),
+            crate::Event::<Test>::LeaseGovernanceEnabled { lease_id }.into()
+        );
+
+        // The first contributor proposes to replace the beneficiary and votes for it
+        let new_beneficiary = U256::from(7);
+        let action = LeaseAction::ReplaceBeneficiary(new_beneficiary);
+        assert_ok!(SubtensorModule::propose_lease_action(
+            RuntimeOrigin::signed(contributions[0].0),
+            lease_id,
+            action.clone(),
+        ));
+        let proposal = LeaseProposals::<Test>::get(lease_id).unwrap();
+        assert_eq!(proposal.proposer, contributions[0].0);
+        assert_eq!(
+            proposal.ayes,
+            SubnetLeaseShares::<Test>::get(lease_id, contributions[0].0)
+        );
+        assert_eq!(
+            LeaseProposalVotes::<Test>::get(lease_id, contributions[0].0),
+            Some(true)
+        );
+        assert_eq!(
+            last_event(),
+            crate::Event::<Test>::LeaseActionProposed {
+                lease_id,
+                proposer: contributions[0].0,
+                action: action.clone(),
+            }
+            .into()
+        );
+
+        // The proposer holds the majority of the shares so the proposal can be concluded early
+        assert_ok!(SubtensorModule::conclude_lease_proposal(
+            RuntimeOrigin::signed(U256::from(42)),
+            lease_id
+        ));
+
+        // Ensure the beneficiary and its proxy have been replaced
+        let lease_after = SubnetLeases::<Test>::get(lease_id).unwrap();
+        assert_eq!(lease_after.beneficiary, new_beneficiary);
+        assert_eq!(
+            PROXIES.with_borrow(|proxies| proxies.0.clone()),
+            vec![(lease.coldkey, new_beneficiary)]
+        );
+
+        // Ensure the proposal has been cleaned up
+        assert!(!LeaseProposals::<Test>::contains_key(lease_id));
+        assert!(!LeaseProposalVotes::<Test>::contains_prefix(lease_id));
+        assert_eq!(
+            last_event(),
+            crate::Event::<Test>::LeaseProposalConcluded {
+                lease_id,
+                action,
+                approved: true,
+            }
+            .into()
+        );
+    });
+}
+
+#[test]
+fn test_lease_governance_votes_work() {
+    new_test_ext(1).execute_with(|| {
+        // Setup a crowdloan
+        let crowdloan_id = 0;
+        let beneficiary = U256::from(1);
+        let deposit = 10_000_000_000; // 10 TAO
+        let cap = 1_000_000_000_000; // 1000 TAO
+        let contributions = vec![
+            (U256::from(2), 600_000_000_000), // 600 TAO
+            (U256::from(3), 390_000_000_000), // 390 TAO
+        ];
+        setup_crowdloan(crowdloan_id, deposit, cap, beneficiary, &contributions);
+
+        // Setup a leased network with governance enabled
+        let emissions_share = Percent::from_percent(30);
+        let (lease_id, _) = setup_leased_network(beneficiary, emissions_share, None, None);
+        assert_ok!(SubtensorModule::enable_lease_governance(
+            RuntimeOrigin::signed(beneficiary),
+            lease_id
+        ));
+
+        // The second contributor proposes to raise the emissions share
+        let action = LeaseAction::SetEmissionsShare(Percent::from_percent(35));
+        assert_ok!(SubtensorModule::propose_lease_action(
+            RuntimeOrigin::signed(contributions[1].0),
+            lease_id,
+            action.clone(),
+        ));
+
+        // The outcome is not decided yet
+        assert_err!(
+            SubtensorModule::conclude_lease_proposal(
+                RuntimeOrigin::signed(contributions[1].0),
+                lease_id
+            ),
+            Error::<Test>::LeaseProposalVotingNotEnded
+        );
+
+        // The first contributor votes against it
+        assert_ok!(SubtensorModule::vote_lease_proposal(
+            RuntimeOrigin::signed(contributions[0].0),
+            lease_id,
+            false,
+        ));
+        assert_eq!(
+            last_event(),
+            crate::Event::<Test>::LeaseProposalVoted {
+                lease_id,
+                voter: contributions[0].0,
+                aye: false,
+                share: SubnetLeaseShares::<Test>::get(lease_id, contributions[0].0),
+            }
+            .into()
+        );
+
+        // The proposal is rejected and the emissions share unchanged
+        assert_ok!(SubtensorModule::conclude_lease_proposal(
+            RuntimeOrigin::signed(contributions[1].0),
+            lease_id
+        ));
+        assert_eq!(
+            SubnetLeases::<Test>::get(lease_id).unwrap().emissions_share,
+            emissions_share
+        );
+        assert_eq!(
+            last_event(),
+            crate::Event::<Test>::LeaseProposalConcluded {
+                lease_id,
+                action,
+                approved: false,
+            }
+            .into()
+        );
+
+        // The proposer must wait for the cooldown before proposing again
+        assert_err!(
+            SubtensorModule::propose_lease_action(
+                RuntimeOrigin::signed(contributions[1].0),
+                lease_id,
+                LeaseAction::Terminate,
+            ),
+            Error::<Test>::LeaseProposalRateLimitExceeded
+        );
+        let last_block = LastLeaseProposalBlock::<Test>::get(contributions[1].0).unwrap();
+        run_to_block(last_block + <Test as Config>::LeaseProposalCooldown::get());
+
+        // A proposal without a majority is rejected at the end of the voting
+        assert_ok!(SubtensorModule::propose_lease_action(
+            RuntimeOrigin::signed(contributions[1].0),
+            lease_id,
+            LeaseAction::Terminate,
+        ));
+        let voting_end = LeaseProposals::<Test>::get(lease_id).unwrap().voting_end;
+        run_to_block(voting_end);
+        assert_err!(
+            SubtensorModule::vote_lease_proposal(
+                RuntimeOrigin::signed(contributions[0].0),
+                lease_id,
+                true,
+            ),
+            Error::<Test>::LeaseProposalVotingEnded
+        );
+        assert_ok!(SubtensorModule::conclude_lease_proposal(
+            RuntimeOrigin::signed(contributions[1].0),
+            lease_id
+        ));
+        assert_eq!(SubnetLeases::<Test>::get(lease_id).unwrap().end_block, None);
+
+        // The contributors vote to end the lease early
+        assert_ok!(SubtensorModule::propose_lease_action(
+            RuntimeOrigin::signed(contributions[1].0),
+            lease_id,
+            LeaseAction::Terminate,
+        ));
+        assert_ok!(SubtensorModule::vote_lease_proposal(
+            RuntimeOrigin::signed(contributions[0].0),
+            lease_id,
+            true,
+        ));
+        assert_ok!(SubtensorModule::conclude_lease_proposal(
+            RuntimeOrigin::signed(contributions[0].0),
+            lease_id
+        ));
+        let now = frame_system::Pallet::<Test>::block_number();
+        assert_eq!(
+            SubnetLeases::<Test>::get(lease_id).unwrap().end_block,
+            Some(now)
+        );
+
+        // Ensure the beneficiary can now terminate the lease, cleaning up the governance
+        let hotkey = U256::from(8);
+        SubtensorModule::create_account_if_non_existent(&beneficiary, &hotkey);
+        assert_ok!(SubtensorModule::terminate_lease(
+            RuntimeOrigin::signed(beneficiary),
+            lease_id,
+            hotkey,
+        ));
+        assert!(!SubnetLeaseGovernance::<Test>::contains_key(lease_id));
+    });
+}
+
+#[test]
+fn test_lease_governance_fails_if_invalid() {
+    new_test_ext(1).execute_with(|| {
+        // Setup a crowdloan
+        let crowdloan_id = 0;
+        let beneficiary = U256::from(1);
+        let deposit = 10_000_000_000; // 10 TAO
+        let cap = 1_000_000_000_000; // 1000 TAO
+        let contributions = vec![
+            (U256::from(2), 600_000_000_000), // 600 TAO
+            (U256::from(3), 380_000_000_000), // 380 TAO
+            (U256::from(4), 10_000_000_000),  // 10 TAO
+        ];
+        setup_crowdloan(crowdloan_id, deposit, cap, beneficiary, &contributions);
+
+        // Setup a leased network
+        let (lease_id, lease) =
+            setup_leased_network(beneficiary, Percent::from_percent(30), None, None);
+
+        // The governance must be enabled
+        assert_err!(
+            SubtensorModule::propose_lease_action(
+                RuntimeOrigin::signed(contributions[0].0),
+                lease_id,
+                LeaseAction::Terminate,
+            ),
+            Error::<Test>::LeaseGovernanceNotEnabled
+        );
+
+        // Only the beneficiary can enable the governance, once
+        assert_err!(
+            SubtensorModule::enable_lease_governance(
+                RuntimeOrigin::signed(contributions[0].0),
+                lease_id
+            ),
+            Error::<Test>::ExpectedBeneficiaryOrigin
+        );
+        assert_ok!(SubtensorModule::enable_lease_governance(
+            RuntimeOrigin::signed(beneficiary),
+            lease_id
+        ));
+        assert_err!(
+            SubtensorModule::enable_lease_governance(RuntimeOrigin::signed(beneficiary), lease_id),
+            Error::<Test>::LeaseGovernanceAlreadyEnabled
+        );
+
+        // Only the contributors can propose
+        assert_err!(
+            SubtensorModule::propose_lease_action(
+                RuntimeOrigin::signed(beneficiary),
+                lease_id,
+                LeaseAction::Terminate,
+            ),
+            Error::<Test>::NoLeaseShare
+        );
+
+        // The proposer must hold a large enough share
+        assert_err!(
+            SubtensorModule::propose_lease_action(
+                RuntimeOrigin::signed(contributions[2].0),
+                lease_id,
+                LeaseAction::Terminate,
+            ),
+            Error::<Test>::LeaseShareTooLowToPropose
+        );
+
+        // The action must change the lease within bounds
+        for action in [
+            LeaseAction::ReplaceBeneficiary(beneficiary),
+            LeaseAction::ReplaceBeneficiary(lease.coldkey),
+            LeaseAction::SetEmissionsShare(Percent::from_percent(30)),
+            LeaseAction::SetEmissionsShare(Percent::from_percent(50)),
+        ] {
+            assert_err!(
+                SubtensorModule::propose_lease_action(
+                    RuntimeOrigin::signed(contributions[0].0),
+                    lease_id,
+                    action,
+                ),
+                Error::<Test>::InvalidLeaseAction
+            );
+        }
+
+        // There is no proposal to vote on or conclude
+        assert_err!(
+            SubtensorModule::vote_lease_proposal(
+                RuntimeOrigin::signed(contributions[0].0),
+                lease_id,
+                true,
+            ),
+            Error::<Test>::NoLeaseProposal
+        );
+        assert_err!(
+            SubtensorModule::conclude_lease_proposal(
+                RuntimeOrigin::signed(contributions[0].0),
+                lease_id
+            ),
+            Error::<Test>::NoLeaseProposal
+        );
+
+        assert_ok!(SubtensorModule::propose_lease_action(
+            RuntimeOrigin::signed(contributions[1].0),
+            lease_id,
+            LeaseAction::SetEmissionsShare(Percent::from_percent(25)),
+        ));
+
+        // Only one proposal at a time and the shares are locked during the voting
+        assert_err!(
+            SubtensorModule::propose_lease_action(
+                RuntimeOrigin::signed(contributions[0].0),
+                lease_id,
+                LeaseAction::Terminate,
+            ),
+            Error::<Test>::LeaseProposalInProgress
+        );
+        assert_err!(
+            SubtensorModule::transfer_lease_share(
+                RuntimeOrigin::signed(contributions[0].0),
+                lease_id,
+                U256::from(5),
+                Percent::from_percent(50),
+            ),
+            Error::<Test>::LeaseProposalInProgress
+        );
+
+        // Only the contributors can vote, once
+        assert_err!(
+            SubtensorModule::vote_lease_proposal(
+                RuntimeOrigin::signed(beneficiary),
+                lease_id,
+                true
+            ),
+            Error::<Test>::NoLeaseShare
+        );
+        assert_err!(
+            SubtensorModule::vote_lease_proposal(
+                RuntimeOrigin::signed(contributions[1].0),
+                lease_id,
+                false,
+            ),
+            Error::<Test>::AlreadyVotedOnLeaseProposal
+        );
+    });
+}
+
 fn setup_crowdloan(
     id: u32,
     deposit: u64,
@@ -878,6 +1722,7 @@ fn setup_crowdloan(
             call: None,
             target_address: None,
             contributors_count: 1 + contributions.len() as u32,
+            milestones: None,
         },
     );
 
@@ -20,8 +20,8 @@ use scale_info::prelude::collections::VecDeque;
 use sp_core::{H256, U256, crypto::Ss58Codec};
 use sp_io::hashing::twox_128;
 use sp_runtime::traits::Zero;
-use substrate_fixed::types::I96F32;
 use substrate_fixed::types::extra::U2;
+use substrate_fixed::types::{I96F32, U64F64};
 use subtensor_runtime_common::{NetUidStorageIndex, TaoCurrency};
 
 #[allow(clippy::arithmetic_side_effects)]
@@ -1940,3 +1940,38 @@ fn test_migrate_restore_subnet_locked_65_128() {
         );
     });
 }
+
+#[test]
+fn test_migrate_lease_total_shares() {
+    new_test_ext(1).execute_with(|| {
+        let name = b"migrate_lease_total_shares".to_vec();
+        let lease_id = 0;
+        SubnetLeases::<Test>::insert(
+            lease_id,
+            crate::subnets::leasing::SubnetLease {
+                beneficiary: U256::from(1),
+                coldkey: U256::from(2),
+                hotkey: U256::from(3),
+                emissions_share: sp_runtime::Percent::from_percent(30),
+                end_block: None,
+                netuid: NetUid::from(1),
+                cost: 1_000,
+            },
+        );
+        let share1 = U64F64::from_num(0.6);
+        let share2 = U64F64::from_num(0.39);
+        SubnetLeaseShares::<Test>::insert(lease_id, U256::from(4), share1);
+        SubnetLeaseShares::<Test>::insert(lease_id, U256::from(5), share2);
+        assert!(!HasMigrationRun::<Test>::get(name.clone()));
+
+        let weight =
+            crate::migrations::migrate_lease_total_shares::migrate_lease_total_shares::<Test>();
+        assert!(!weight.is_zero());
+
+        assert_eq!(
+            SubnetLeaseTotalShares::<Test>::get(lease_id),
+            share1 + share2
+        );
+        assert!(HasMigrationRun::<Test>::get(name));
+    });
+}
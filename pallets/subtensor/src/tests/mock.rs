@@ -25,7 +25,7 @@ use sp_runtime::{
     traits::{BlakeTwo256, IdentityLookup},
 };
 use sp_std::{cell::RefCell, cmp::Ordering};
-use subtensor_runtime_common::{NetUid, TaoCurrency};
+use subtensor_runtime_common::{BatchCondition, NetUid, TaoCurrency};
 use subtensor_swap_interface::{OrderType, SwapHandler};
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -225,6 +225,10 @@ parameter_types! {
     pub const HotkeySwapOnSubnetInterval: u64 = 15; // 15 block, should be bigger than subnet number, then trigger clean up for all subnets
     pub const MaxContributorsPerLeaseToRemove: u32 = 3;
     pub const LeaseDividendsDistributionInterval: u32 = 100;
+    pub const LeaseProposalVotingPeriod: u32 = 10;
+    pub const MaxLeaseEmissionsShareChange: Percent = Percent::from_percent(10);
+    pub const MinLeaseProposalShare: Percent = Percent::from_percent(5);
+    pub const LeaseProposalCooldown: u32 = 10;
     pub const MaxImmuneUidsPercentage: Percent = Percent::from_percent(80);
     pub const EvmKeyAssociateRateLimit: u64 = 10;
 }
@@ -462,6 +466,10 @@ impl crate::Config for Test {
     type HotkeySwapOnSubnetInterval = HotkeySwapOnSubnetInterval;
     type ProxyInterface = FakeProxier;
     type LeaseDividendsDistributionInterval = LeaseDividendsDistributionInterval;
+    type LeaseProposalVotingPeriod = LeaseProposalVotingPeriod;
+    type MaxLeaseEmissionsShareChange = MaxLeaseEmissionsShareChange;
+    type MinLeaseProposalShare = MinLeaseProposalShare;
+    type LeaseProposalCooldown = LeaseProposalCooldown;
     type GetCommitments = ();
     type MaxImmuneUidsPercentage = MaxImmuneUidsPercentage;
     type CommitmentsInterface = CommitmentsI;
@@ -473,8 +481,13 @@ parameter_types! {
     pub const SwapProtocolId: PalletId = PalletId(*b"ten/swap");
     pub const SwapMaxFeeRate: u16 = 10000; // 15.26%
     pub const SwapMaxPositions: u32 = 100;
+    pub const SwapMaxPositionsPerAccount: u32 = 256;
     pub const SwapMinimumLiquidity: u64 = 1_000;
     pub const SwapMinimumReserve: NonZeroU64 = NonZeroU64::new(100).unwrap();
+    pub const SwapMaxLimitOrders: u32 = 100;
+    pub const SwapMaxLimitOrdersPerAccount: u32 = 10;
+    pub const SwapMaxPriceObservations: u32 = 100;
+    pub const SwapMinLimitOrderAmount: u64 = 1_000;
 }
 
 impl pallet_subtensor_swap::Config for Test {
@@ -484,8 +497,14 @@ impl pallet_subtensor_swap::Config for Test {
     type ProtocolId = SwapProtocolId;
     type MaxFeeRate = SwapMaxFeeRate;
     type MaxPositions = SwapMaxPositions;
+    type MaxPositionsPerAccount = SwapMaxPositionsPerAccount;
     type MinimumLiquidity = SwapMinimumLiquidity;
     type MinimumReserve = SwapMinimumReserve;
+    type MaxLimitOrders = SwapMaxLimitOrders;
+    type MaxLimitOrdersPerAccount = SwapMaxLimitOrdersPerAccount;
+    type MinLimitOrderAmount = SwapMinLimitOrderAmount;
+    type MinStake = DefaultMinStake<Test>;
+    type MaxPriceObservations = SwapMaxPriceObservations;
     type WeightInfo = ();
 }
 
@@ -500,6 +519,17 @@ impl PrivilegeCmp<OriginCaller> for OriginPrivilegeCmp {
 pub struct CommitmentsI;
 impl CommitmentsInterface for CommitmentsI {
     fn purge_netuid(_netuid: NetUid) {}
+
+    fn set_commitment_history_depth(_netuid: NetUid, _depth: u32) -> DispatchResult {
+        Ok(())
+    }
+
+    fn set_commitment_schema(
+        _netuid: NetUid,
+        _schema: Option<pallet_commitments::CommitmentSchema>,
+    ) -> DispatchResult {
+        Ok(())
+    }
 }
 
 parameter_types! {
@@ -528,6 +558,13 @@ impl pallet_utility::Config for Test {
     type RuntimeCall = RuntimeCall;
     type PalletsOrigin = OriginCaller;
     type WeightInfo = pallet_utility::weights::SubstrateWeight<Test>;
+    type Currency = Balances;
+    type Condition = BatchCondition<U256>;
+    type ConditionChecker = ();
+    type ScheduledBatchDepositBase = ConstU64<1>;
+    type ScheduledBatchDepositFactor = ConstU64<1>;
+    type MaxScheduledBatchesPerAccount = frame_support::traits::ConstU32<16>;
+    type MaxScheduleHorizon = frame_support::traits::ConstU64<7200>;
 }
 
 parameter_types! {
@@ -577,6 +614,7 @@ parameter_types! {
     pub const MaximumBlockDuration: u64 = 100;
     pub const RefundContributorsLimit: u32 = 5;
     pub const MaxContributors: u32 = 10;
+    pub const MilestoneVotingPeriod: u64 = 10;
 }
 
 impl pallet_crowdloan::Config for Test {
@@ -592,6 +630,7 @@ impl pallet_crowdloan::Config for Test {
     type MaximumBlockDuration = MaximumBlockDuration;
     type RefundContributorsLimit = RefundContributorsLimit;
     type MaxContributors = MaxContributors;
+    type MilestoneVotingPeriod = MilestoneVotingPeriod;
 }
 
 mod test_crypto {
@@ -634,6 +673,7 @@ impl pallet_drand::Config for Test {
     type Verifier = pallet_drand::verifier::QuicknetVerifier;
     type UnsignedPriority = ConstU64<{ 1 << 20 }>;
     type HttpFetchTimeout = ConstU64<1_000>;
+    type BeaconUsage = ();
 }
 
 impl frame_system::offchain::SigningTypes for Test {
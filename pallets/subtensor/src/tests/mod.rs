@@ -16,6 +16,7 @@ mod mock;
 mod move_stake;
 mod networks;
 mod neuron_info;
+mod paymaster;
 mod recycle_alpha;
 mod registration;
 mod senate;
@@ -27,4 +28,5 @@ mod swap_coldkey;
 mod swap_hotkey;
 mod swap_hotkey_with_subnet;
 mod uids;
+mod vesting;
 mod weights;
@@ -3,6 +3,7 @@
 use approx::assert_abs_diff_eq;
 use frame_support::{assert_err, assert_noop, assert_ok};
 use sp_core::{Get, U256};
+use sp_runtime::traits::Dispatchable;
 use substrate_fixed::types::{U64F64, U96F32};
 use subtensor_runtime_common::TaoCurrency;
 use subtensor_swap_interface::SwapHandler;
@@ -1928,3 +1929,202 @@ fn test_swap_stake_limits_destination_netuid() {
         )));
     });
 }
+
+#[test]
+fn test_swap_stake_route_success() {
+    new_test_ext(1).execute_with(|| {
+        let subnet_owner_coldkey = U256::from(1001);
+        let subnet_owner_hotkey = U256::from(1002);
+        let netuid1 = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+        let netuid2 = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+        let netuid3 = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+        let stake_amount = DefaultMinStake::<Test>::get().to_u64() * 10;
+
+        SubtensorModule::create_account_if_non_existent(&coldkey, &hotkey);
+        SubtensorModule::stake_into_subnet(
+            &hotkey,
+            &coldkey,
+            netuid1,
+            stake_amount.into(),
+            <Test as Config>::SwapInterface::max_price().into(),
+            false,
+            false,
+        )
+        .unwrap();
+        let alpha_before =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid1);
+
+        let (tao_1, _) = mock::swap_alpha_to_tao_ext(netuid1, alpha_before, true);
+        let (alpha_2, _) = mock::swap_tao_to_alpha(netuid2, tao_1);
+        let (tao_2, _) = mock::swap_alpha_to_tao_ext(netuid2, alpha_2, true);
+        let (expected_alpha, _) = mock::swap_tao_to_alpha(netuid3, tao_2);
+
+        assert_ok!(SubtensorModule::do_swap_stake_route(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            vec![netuid1, netuid2, netuid3],
+            alpha_before,
+            AlphaCurrency::ZERO,
+        ));
+
+        // Nothing is left on the origin and intermediate subnets
+        assert_eq!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid1),
+            AlphaCurrency::ZERO
+        );
+        assert_eq!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid2),
+            AlphaCurrency::ZERO
+        );
+
+        let alpha_after =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid3);
+        assert_abs_diff_eq!(alpha_after, expected_alpha, epsilon = 1000.into());
+
+        // Only the final destination is rate limited
+        assert!(!StakingOperationRateLimiter::<Test>::contains_key((
+            hotkey, coldkey, netuid2
+        )));
+        assert!(StakingOperationRateLimiter::<Test>::contains_key((
+            hotkey, coldkey, netuid3
+        )));
+
+        System::assert_last_event(
+            Event::StakeRouteSwapped(
+                coldkey,
+                hotkey,
+                vec![netuid1, netuid2, netuid3],
+                alpha_before,
+                alpha_after,
+            )
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn test_swap_stake_route_too_short() {
+    new_test_ext(1).execute_with(|| {
+        let subnet_owner_coldkey = U256::from(1001);
+        let subnet_owner_hotkey = U256::from(1002);
+        let netuid = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+
+        assert_noop!(
+            SubtensorModule::do_swap_stake_route(
+                RuntimeOrigin::signed(coldkey),
+                hotkey,
+                vec![netuid],
+                1_000.into(),
+                AlphaCurrency::ZERO,
+            ),
+            Error::<Test>::InvalidSwapRoute
+        );
+    });
+}
+
+#[test]
+fn test_swap_stake_route_slippage() {
+    new_test_ext(1).execute_with(|| {
+        let subnet_owner_coldkey = U256::from(1001);
+        let subnet_owner_hotkey = U256::from(1002);
+        let netuid1 = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+        let netuid2 = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+        let netuid3 = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+        let stake_amount = DefaultMinStake::<Test>::get().to_u64() * 10;
+
+        SubtensorModule::create_account_if_non_existent(&coldkey, &hotkey);
+        SubtensorModule::stake_into_subnet(
+            &hotkey,
+            &coldkey,
+            netuid1,
+            stake_amount.into(),
+            <Test as Config>::SwapInterface::max_price().into(),
+            false,
+            false,
+        )
+        .unwrap();
+        let alpha_before =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid1);
+
+        // Dispatch through the call so that the failed route is rolled back as a whole
+        let call = RuntimeCall::SubtensorModule(crate::Call::swap_stake_route {
+            hotkey,
+            route: vec![netuid1, netuid2, netuid3].try_into().unwrap(),
+            alpha_amount: alpha_before,
+            min_alpha_out: u64::MAX.into(),
+        });
+        assert_noop!(
+            call.dispatch(RuntimeOrigin::signed(coldkey)),
+            Error::<Test>::SlippageTooHigh
+        );
+
+        assert_eq!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid1),
+            alpha_before
+        );
+    });
+}
+
+#[test]
+fn test_swap_stake_route_matches_quote() {
+    new_test_ext(1).execute_with(|| {
+        let subnet_owner_coldkey = U256::from(1001);
+        let subnet_owner_hotkey = U256::from(1002);
+        let netuid1 = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+        let netuid2 = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+        let netuid3 = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+        let route = vec![netuid1, netuid2, netuid3];
+
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+        let stake_amount = DefaultMinStake::<Test>::get().to_u64() * 10;
+
+        SubtensorModule::create_account_if_non_existent(&coldkey, &hotkey);
+        SubtensorModule::stake_into_subnet(
+            &hotkey,
+            &coldkey,
+            netuid1,
+            stake_amount.into(),
+            <Test as Config>::SwapInterface::max_price().into(),
+            false,
+            false,
+        )
+        .unwrap();
+        let alpha_before =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid1);
+
+        // Quoting more than the available stake is capped like the execution
+        let requested = alpha_before.saturating_add(1_000_000.into());
+        let legs = pallet_subtensor_swap::Pallet::<Test>::sim_swap_route(
+            &coldkey,
+            &hotkey,
+            &route,
+            requested.into(),
+        )
+        .unwrap();
+        let [first, last] = legs.as_slice() else {
+            panic!("two legs expected");
+        };
+        assert_eq!(first.alpha_in, alpha_before);
+
+        assert_ok!(SubtensorModule::do_swap_stake_route(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            route,
+            requested,
+            AlphaCurrency::ZERO,
+        ));
+
+        let alpha_after =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid3);
+        assert_eq!(last.alpha_out, alpha_after);
+    });
+}
@@ -0,0 +1,260 @@
+#![allow(clippy::arithmetic_side_effects)]
+use frame_support::{assert_noop, assert_ok};
+use sp_core::U256;
+use sp_runtime::DispatchError;
+use subtensor_runtime_common::TaoCurrency;
+
+use super::mock::*;
+use crate::*;
+
+#[test]
+fn test_fund_and_withdraw_paymaster_works() {
+    new_test_ext(1).execute_with(|| {
+        let owner_coldkey = U256::from(1);
+        let owner_hotkey = U256::from(2);
+        let funder = U256::from(3);
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        let paymaster_account = SubtensorModule::paymaster_account(netuid);
+        SubtensorModule::add_balance_to_coldkey_account(&funder, 1_000_000);
+
+        assert_ok!(SubtensorModule::fund_paymaster(
+            RuntimeOrigin::signed(funder),
+            netuid,
+            600_000.into()
+        ));
+        assert_eq!(
+            SubtensorModule::get_coldkey_balance(&paymaster_account),
+            600_000
+        );
+        assert_eq!(SubtensorModule::get_coldkey_balance(&funder), 400_000);
+        assert_eq!(
+            last_event(),
+            RuntimeEvent::SubtensorModule(Event::PaymasterFunded {
+                netuid,
+                who: funder,
+                amount: 600_000.into(),
+            })
+        );
+
+        // Only the subnet owner can withdraw
+        assert_noop!(
+            SubtensorModule::withdraw_paymaster(
+                RuntimeOrigin::signed(funder),
+                netuid,
+                100_000.into()
+            ),
+            DispatchError::BadOrigin
+        );
+
+        let owner_balance_before = SubtensorModule::get_coldkey_balance(&owner_coldkey);
+        assert_ok!(SubtensorModule::withdraw_paymaster(
+            RuntimeOrigin::signed(owner_coldkey),
+            netuid,
+            100_000.into()
+        ));
+        assert_eq!(
+            SubtensorModule::get_coldkey_balance(&paymaster_account),
+            500_000
+        );
+        assert_eq!(
+            SubtensorModule::get_coldkey_balance(&owner_coldkey),
+            owner_balance_before + 100_000
+        );
+
+        // The funds go back to the owner when the subnet is removed
+        assert_ok!(SubtensorModule::set_subnet_paymaster(
+            netuid,
+            vec![SponsoredCall::BurnedRegister],
+            1_000.into(),
+            1_000.into(),
+        ));
+        SubtensorModule::record_sponsored_fee(netuid, &funder, 100.into());
+        SubtensorModule::remove_network(netuid);
+
+        assert_eq!(SubtensorModule::get_coldkey_balance(&paymaster_account), 0);
+        assert_eq!(
+            SubtensorModule::get_coldkey_balance(&owner_coldkey),
+            owner_balance_before + 600_000
+        );
+        assert!(!SubnetPaymasters::<Test>::contains_key(netuid));
+        assert!(!PaymasterAccountFees::<Test>::contains_key(netuid, funder));
+        assert!(!PaymasterBlockFees::<Test>::contains_key(netuid));
+    });
+}
+
+#[test]
+fn test_fund_paymaster_fails_if_invalid() {
+    new_test_ext(1).execute_with(|| {
+        let owner_coldkey = U256::from(1);
+        let owner_hotkey = U256::from(2);
+        let funder = U256::from(3);
+        let netuid = add_dynamic_network(&owner_hotkey, &owner_coldkey);
+        SubtensorModule::add_balance_to_coldkey_account(&funder, 1_000_000);
+
+        assert_noop!(
+            SubtensorModule::fund_paymaster(
+                RuntimeOrigin::signed(funder),
+                NetUid::from(42),
+                1_000.into()
+            ),
+            Error::<Test>::SubnetNotExists
+        );
+        assert_noop!(
+            SubtensorModule::fund_paymaster(RuntimeOrigin::signed(funder), netuid, 0.into()),
+            Error::<Test>::AmountTooLow
+        );
+        assert!(
+            SubtensorModule::fund_paymaster(
+                RuntimeOrigin::signed(funder),
+                netuid,
+                2_000_000.into()
+            )
+            .is_err()
+        );
+    });
+}
+
+#[test]
+fn test_can_sponsor_fee_respects_budgets() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = add_dynamic_network(&U256::from(1), &U256::from(2));
+        let (alice, bob, charlie) = (U256::from(10), U256::from(11), U256::from(12));
+        let fee = TaoCurrency::from(60);
+        SubtensorModule::set_burn(netuid, 1_000.into());
+        for who in [alice, bob, charlie] {
+            SubtensorModule::add_balance_to_coldkey_account(&who, 1_000);
+        }
+
+        // No paymaster
+        assert!(!SubtensorModule::can_sponsor_fee(
+            netuid,
+            SponsoredCall::BurnedRegister,
+            &alice,
+            fee
+        ));
+
+        assert_ok!(SubtensorModule::set_subnet_paymaster(
+            netuid,
+            vec![SponsoredCall::BurnedRegister],
+            100.into(),
+            150.into(),
+        ));
+
+        // Call not sponsored
+        assert!(!SubtensorModule::can_sponsor_fee(
+            netuid,
+            SponsoredCall::ServeAxon,
+            &alice,
+            fee
+        ));
+
+        // Account budget
+        assert!(SubtensorModule::can_sponsor_fee(
+            netuid,
+            SponsoredCall::BurnedRegister,
+            &alice,
+            fee
+        ));
+        SubtensorModule::record_sponsored_fee(netuid, &alice, fee);
+        assert!(!SubtensorModule::can_sponsor_fee(
+            netuid,
+            SponsoredCall::BurnedRegister,
+            &alice,
+            fee
+        ));
+
+        // Block budget
+        assert!(SubtensorModule::can_sponsor_fee(
+            netuid,
+            SponsoredCall::BurnedRegister,
+            &bob,
+            fee
+        ));
+        SubtensorModule::record_sponsored_fee(netuid, &bob, fee);
+        assert!(!SubtensorModule::can_sponsor_fee(
+            netuid,
+            SponsoredCall::BurnedRegister,
+            &charlie,
+            fee
+        ));
+
+        // The block budget is renewed on the next block, not the account budget
+        step_block(1);
+        assert!(SubtensorModule::can_sponsor_fee(
+            netuid,
+            SponsoredCall::BurnedRegister,
+            &charlie,
+            fee
+        ));
+        assert!(!SubtensorModule::can_sponsor_fee(
+            netuid,
+            SponsoredCall::BurnedRegister,
+            &alice,
+            fee
+        ));
+    });
+}
+
+#[test]
+fn test_can_sponsor_fee_requires_burn_or_registration() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = add_dynamic_network(&U256::from(1), &U256::from(2));
+        let (coldkey, hotkey) = (U256::from(10), U256::from(11));
+        let fee = TaoCurrency::from(60);
+        SubtensorModule::set_burn(netuid, 1_000.into());
+
+        assert_ok!(SubtensorModule::set_subnet_paymaster(
+            netuid,
+            vec![
+                SponsoredCall::BurnedRegister,
+                SponsoredCall::QueueBurnedRegistration,
+                SponsoredCall::ServeAxon,
+            ],
+            1_000.into(),
+            1_000.into(),
+        ));
+
+        // The registrations are only sponsored for coldkeys holding the burn
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, 999);
+        assert!(!SubtensorModule::can_sponsor_fee(
+            netuid,
+            SponsoredCall::BurnedRegister,
+            &coldkey,
+            fee
+        ));
+        assert!(!SubtensorModule::can_sponsor_fee(
+            netuid,
+            SponsoredCall::QueueBurnedRegistration,
+            &coldkey,
+            fee
+        ));
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, 1);
+        assert!(SubtensorModule::can_sponsor_fee(
+            netuid,
+            SponsoredCall::BurnedRegister,
+            &coldkey,
+            fee
+        ));
+        assert!(SubtensorModule::can_sponsor_fee(
+            netuid,
+            SponsoredCall::QueueBurnedRegistration,
+            &coldkey,
+            fee
+        ));
+
+        // The other calls are only sponsored for hotkeys registered on the subnet
+        assert!(!SubtensorModule::can_sponsor_fee(
+            netuid,
+            SponsoredCall::ServeAxon,
+            &hotkey,
+            fee
+        ));
+        register_ok_neuron(netuid, hotkey, coldkey, 0);
+        assert!(SubtensorModule::can_sponsor_fee(
+            netuid,
+            SponsoredCall::ServeAxon,
+            &hotkey,
+            fee
+        ));
+    });
+}
@@ -13,8 +13,19 @@ use subtensor_runtime_common::{AlphaCurrency, Currency as CurrencyT, NetUid, Net
 
 use super::mock;
 use super::mock::*;
+use crate::subnets::registration::{BurnedRegistrationBidTlockPayload, SealedBid};
 use crate::transaction_extension::SubtensorTransactionExtension;
 use crate::{AxonInfoOf, CustomTransactionError, Error};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use codec::Encode;
+use pallet_drand::types::Pulse;
+use rand_chacha::{ChaCha20Rng, rand_core::SeedableRng};
+use sha2::Digest;
+use tle::{
+    curves::drand::TinyBLS381, ibe::fullident::Identity,
+    stream_ciphers::AESGCMStreamCipherProvider, tlock::tle,
+};
+use w3f_bls::EngineBLS;
 
 /********************************************
     subscribing::subscribe() tests
@@ -607,6 +618,424 @@ fn test_burn_adjustment() {
     });
 }
 
+#[test]
+fn test_queue_burned_registration_highest_bid() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        let tempo: u16 = 13;
+        let burn_cost = 1000;
+        add_network(netuid, tempo, 0);
+        SubtensorModule::set_burn(netuid, burn_cost.into());
+        SubtensorModule::set_adjustment_interval(netuid, 1);
+        SubtensorModule::set_target_registrations_per_interval(netuid, 1);
+        pallet_drand::LastStoredRound::<Test>::set(SEALED_BID_ROUND - 1);
+
+        let reserve = 1_000_000_000_000;
+        mock::setup_reserves(netuid, reserve.into(), reserve.into());
+
+        let (coldkey_1, hotkey_1) = (U256::from(667), U256::from(1));
+        let (coldkey_2, hotkey_2) = (U256::from(668), U256::from(2));
+        let (coldkey_3, hotkey_3) = (U256::from(669), U256::from(3));
+        for coldkey in [coldkey_1, coldkey_2, coldkey_3] {
+            SubtensorModule::add_balance_to_coldkey_account(&coldkey, 10_000);
+        }
+        let total_issuance = Balances::total_issuance();
+
+        // The registrations are immediate by default
+        assert_noop!(
+            SubtensorModule::queue_burned_registration(
+                RuntimeOrigin::signed(coldkey_1),
+                netuid,
+                hotkey_1,
+                burn_cost.into(),
+                None
+            ),
+            Error::<Test>::BurnedRegistrationNotQueued
+        );
+
+        SubtensorModule::set_burned_registration_mode(netuid, BurnedRegistrationMode::HighestBid);
+        assert_noop!(
+            SubtensorModule::burned_register(RuntimeOrigin::signed(coldkey_1), netuid, hotkey_1),
+            Error::<Test>::BurnedRegistrationIsQueued
+        );
+
+        // The bids must be sealed until the end of the interval
+        assert_noop!(
+            SubtensorModule::queue_burned_registration(
+                RuntimeOrigin::signed(coldkey_1),
+                netuid,
+                hotkey_1,
+                1500.into(),
+                None
+            ),
+            Error::<Test>::SealedBidRequired
+        );
+        for reveal_round in [SEALED_BID_ROUND - 1, SEALED_BID_ROUND + 100] {
+            let mut sealed = sealed_bid(hotkey_1, 1200);
+            sealed.reveal_round = reveal_round;
+            assert_noop!(
+                SubtensorModule::queue_burned_registration(
+                    RuntimeOrigin::signed(coldkey_1),
+                    netuid,
+                    hotkey_1,
+                    1500.into(),
+                    Some(sealed)
+                ),
+                Error::<Test>::InvalidSealedBidRevealRound
+            );
+        }
+
+        // The maximum bids can't be lower than the burn
+        assert_noop!(
+            SubtensorModule::queue_burned_registration(
+                RuntimeOrigin::signed(coldkey_1),
+                netuid,
+                hotkey_1,
+                (burn_cost - 1).into(),
+                Some(sealed_bid(hotkey_1, burn_cost - 1))
+            ),
+            Error::<Test>::BurnedRegistrationBidTooLow
+        );
+
+        // The maximum bids are held in escrow, the third bid exceeds its maximum
+        for (coldkey, hotkey, max_bid, bid) in [
+            (coldkey_1, hotkey_1, 1500, 1200),
+            (coldkey_2, hotkey_2, 2500, 2000),
+            (coldkey_3, hotkey_3, 3000, 5000),
+        ] {
+            assert_ok!(SubtensorModule::queue_burned_registration(
+                RuntimeOrigin::signed(coldkey),
+                netuid,
+                hotkey,
+                max_bid.into(),
+                Some(sealed_bid(hotkey, bid))
+            ));
+            assert_eq!(
+                SubtensorModule::get_coldkey_balance(&coldkey),
+                10_000 - max_bid
+            );
+        }
+        assert_noop!(
+            SubtensorModule::queue_burned_registration(
+                RuntimeOrigin::signed(coldkey_2),
+                netuid,
+                hotkey_2,
+                2500.into(),
+                Some(sealed_bid(hotkey_2, 2500))
+            ),
+            Error::<Test>::BurnedRegistrationAlreadyQueued
+        );
+        let escrow = SubtensorModule::burned_registration_escrow();
+        assert_eq!(SubtensorModule::get_coldkey_balance(&escrow), 7_000);
+        assert_eq!(Balances::total_issuance(), total_issuance);
+
+        // The queue is kept until the bids can be revealed
+        step_block(1);
+        assert_eq!(SubtensorModule::get_subnetwork_n(netuid), 0);
+        assert_eq!(PendingBurnedRegistrations::<Test>::get(netuid).len(), 3);
+
+        // The highest valid bid is registered at the end of the interval, paying its bid, the
+        // other ones are refunded
+        insert_sealed_bid_pulse();
+        let rao_recycled_before = SubtensorModule::get_rao_recycled(netuid);
+        step_block(1);
+
+        assert!(SubtensorModule::get_uid_for_net_and_hotkey(netuid, &hotkey_2).is_ok());
+        assert!(SubtensorModule::get_uid_for_net_and_hotkey(netuid, &hotkey_1).is_err());
+        assert!(SubtensorModule::get_uid_for_net_and_hotkey(netuid, &hotkey_3).is_err());
+        assert_eq!(SubtensorModule::get_coldkey_balance(&coldkey_1), 10_000);
+        assert_eq!(SubtensorModule::get_coldkey_balance(&coldkey_2), 8_000);
+        assert_eq!(SubtensorModule::get_coldkey_balance(&coldkey_3), 10_000);
+        assert_eq!(SubtensorModule::get_coldkey_balance(&escrow), 0);
+        assert_eq!(
+            SubtensorModule::get_rao_recycled(netuid),
+            rao_recycled_before + 2000.into()
+        );
+        assert!(PendingBurnedRegistrations::<Test>::get(netuid).is_empty());
+    });
+}
+
+#[test]
+fn test_queue_burned_registration_random() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        let tempo: u16 = 13;
+        let burn_cost = 1000;
+        add_network(netuid, tempo, 0);
+        SubtensorModule::set_burn(netuid, burn_cost.into());
+        SubtensorModule::set_adjustment_interval(netuid, 1);
+        SubtensorModule::set_target_registrations_per_interval(netuid, 2);
+        SubtensorModule::set_burned_registration_mode(netuid, BurnedRegistrationMode::Random);
+
+        let reserve = 1_000_000_000_000;
+        mock::setup_reserves(netuid, reserve.into(), reserve.into());
+
+        // Only the burn is withdrawn, the bid being the maximum accepted
+        let registrations: Vec<(U256, U256)> = (0u64..4)
+            .map(|i| (U256::from(100 + i), U256::from(200 + i)))
+            .collect();
+        for (coldkey, hotkey) in registrations.iter() {
+            SubtensorModule::add_balance_to_coldkey_account(coldkey, 10_000);
+            assert_ok!(SubtensorModule::queue_burned_registration(
+                RuntimeOrigin::signed(*coldkey),
+                netuid,
+                *hotkey,
+                5000.into(),
+                None
+            ));
+            assert_eq!(
+                SubtensorModule::get_coldkey_balance(coldkey),
+                10_000 - burn_cost
+            );
+        }
+
+        // The queue commits to a drand round after the current one
+        let round = PendingBurnedRegistrationRound::<Test>::get(netuid).unwrap();
+        assert!(round > pallet_drand::LastStoredRound::<Test>::get());
+
+        // The queue is kept until the pulse of the round is known
+        step_block(1);
+        assert_eq!(SubtensorModule::get_subnetwork_n(netuid), 0);
+        assert_eq!(PendingBurnedRegistrations::<Test>::get(netuid).len(), 4);
+
+        // Once the pulse is known, no registration can be queued until the queue is resolved
+        pallet_drand::LastStoredRound::<Test>::set(round);
+        pallet_drand::Pulses::<Test>::insert(
+            round,
+            Pulse {
+                round,
+                randomness: vec![7; 32].try_into().unwrap(),
+                signature: Default::default(),
+            },
+        );
+        let (coldkey, hotkey) = (U256::from(104), U256::from(204));
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, 10_000);
+        assert_noop!(
+            SubtensorModule::queue_burned_registration(
+                RuntimeOrigin::signed(coldkey),
+                netuid,
+                hotkey,
+                5000.into(),
+                None
+            ),
+            Error::<Test>::BurnedRegistrationQueueClosed
+        );
+
+        step_block(1);
+
+        // Two registrations are drawn, the others are refunded
+        assert_eq!(SubtensorModule::get_subnetwork_n(netuid), 2);
+        for (coldkey, hotkey) in registrations.iter() {
+            let expected_balance =
+                if SubtensorModule::get_uid_for_net_and_hotkey(netuid, hotkey).is_ok() {
+                    10_000 - burn_cost
+                } else {
+                    10_000
+                };
+            assert_eq!(
+                SubtensorModule::get_coldkey_balance(coldkey),
+                expected_balance
+            );
+        }
+        assert!(PendingBurnedRegistrations::<Test>::get(netuid).is_empty());
+        assert!(!PendingBurnedRegistrationRound::<Test>::contains_key(
+            netuid
+        ));
+    });
+}
+
+#[test]
+fn test_queue_burned_registration_limit_per_coldkey() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        let tempo: u16 = 13;
+        let burn_cost = 1000;
+        add_network(netuid, tempo, 0);
+        SubtensorModule::set_burn(netuid, burn_cost.into());
+        SubtensorModule::set_burned_registration_mode(netuid, BurnedRegistrationMode::HighestBid);
+        pallet_drand::LastStoredRound::<Test>::set(SEALED_BID_ROUND - 1);
+
+        let coldkey = U256::from(667);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, 100_000);
+        for i in 0..MAX_PENDING_BURNED_REGISTRATIONS_PER_COLDKEY as u64 {
+            assert_ok!(SubtensorModule::queue_burned_registration(
+                RuntimeOrigin::signed(coldkey),
+                netuid,
+                U256::from(1000 + i),
+                burn_cost.into(),
+                Some(sealed_bid(U256::from(1000 + i), burn_cost))
+            ));
+        }
+        assert_noop!(
+            SubtensorModule::queue_burned_registration(
+                RuntimeOrigin::signed(coldkey),
+                netuid,
+                U256::from(2000),
+                burn_cost.into(),
+                Some(sealed_bid(U256::from(2000), burn_cost))
+            ),
+            Error::<Test>::TooManyPendingBurnedRegistrationsForColdkey
+        );
+    });
+}
+
+#[test]
+fn test_pending_burned_registrations_weight() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        let tempo: u16 = 13;
+        let burn_cost = 1000;
+        add_network(netuid, tempo, 0);
+        SubtensorModule::set_burn(netuid, burn_cost.into());
+        SubtensorModule::set_adjustment_interval(netuid, 10);
+        SubtensorModule::set_target_registrations_per_interval(netuid, 1);
+        SubtensorModule::set_burned_registration_mode(netuid, BurnedRegistrationMode::HighestBid);
+        pallet_drand::LastStoredRound::<Test>::set(SEALED_BID_ROUND - 1);
+
+        let current_block = SubtensorModule::get_current_block_as_u64();
+        let interval_end = SubtensorModule::get_last_adjustment_block(netuid) + 10;
+        let empty_weight = SubtensorModule::pending_burned_registrations_weight(interval_end);
+
+        for i in 0..3u64 {
+            let coldkey = U256::from(100 + i);
+            SubtensorModule::add_balance_to_coldkey_account(&coldkey, 10_000);
+            assert_ok!(SubtensorModule::queue_burned_registration(
+                RuntimeOrigin::signed(coldkey),
+                netuid,
+                U256::from(200 + i),
+                burn_cost.into(),
+                Some(sealed_bid(U256::from(200 + i), burn_cost))
+            ));
+        }
+
+        // The queue only weighs on the block ending the interval
+        assert_eq!(
+            SubtensorModule::pending_burned_registrations_weight(current_block),
+            empty_weight
+        );
+        // The sealed bids are revealed and at least one burned registration is performed
+        let weight = SubtensorModule::pending_burned_registrations_weight(interval_end);
+        assert!(weight.ref_time() > empty_weight.ref_time() + 3 * 5_000_000_000 + 354_200_000);
+    });
+}
+
+#[test]
+fn test_queue_burned_registration_full_queue() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        let tempo: u16 = 13;
+        let burn_cost = 1000;
+        add_network(netuid, tempo, 0);
+        SubtensorModule::set_burn(netuid, burn_cost.into());
+        SubtensorModule::set_burned_registration_mode(netuid, BurnedRegistrationMode::HighestBid);
+        pallet_drand::LastStoredRound::<Test>::set(SEALED_BID_ROUND - 1);
+
+        for i in 0..MAX_PENDING_BURNED_REGISTRATIONS as u64 {
+            let coldkey = U256::from(1000 + i);
+            SubtensorModule::add_balance_to_coldkey_account(&coldkey, 10_000);
+            assert_ok!(SubtensorModule::queue_burned_registration(
+                RuntimeOrigin::signed(coldkey),
+                netuid,
+                U256::from(2000 + i),
+                burn_cost.into(),
+                Some(sealed_bid(U256::from(2000 + i), burn_cost))
+            ));
+        }
+
+        // A new maximum bid must be higher than the lowest one
+        let coldkey = U256::from(3000);
+        SubtensorModule::add_balance_to_coldkey_account(&coldkey, 10_000);
+        assert_noop!(
+            SubtensorModule::queue_burned_registration(
+                RuntimeOrigin::signed(coldkey),
+                netuid,
+                U256::from(4000),
+                burn_cost.into(),
+                Some(sealed_bid(U256::from(4000), burn_cost))
+            ),
+            Error::<Test>::TooManyPendingBurnedRegistrations
+        );
+        assert_ok!(SubtensorModule::queue_burned_registration(
+            RuntimeOrigin::signed(coldkey),
+            netuid,
+            U256::from(4000),
+            (burn_cost + 1).into(),
+            Some(sealed_bid(U256::from(4000), burn_cost + 1))
+        ));
+
+        // The latest of the lowest maximum bids has been refunded
+        let evicted = U256::from(1000 + MAX_PENDING_BURNED_REGISTRATIONS as u64 - 1);
+        assert_eq!(SubtensorModule::get_coldkey_balance(&evicted), 10_000);
+        let pending = PendingBurnedRegistrations::<Test>::get(netuid);
+        assert_eq!(pending.len(), MAX_PENDING_BURNED_REGISTRATIONS as usize);
+        assert!(
+            !pending
+                .iter()
+                .any(|registration| registration.coldkey == evicted)
+        );
+        assert_eq!(
+            last_event(),
+            Event::BurnedRegistrationQueued {
+                netuid,
+                coldkey,
+                hotkey: U256::from(4000),
+                amount: (burn_cost + 1).into(),
+            }
+            .into()
+        );
+    });
+}
+
+const SEALED_BID_ROUND: u64 = 1000;
+
+// Seal a bid to the drand quicknet round `SEALED_BID_ROUND`
+fn sealed_bid(hotkey: U256, bid: u64) -> SealedBid {
+    let payload = BurnedRegistrationBidTlockPayload {
+        hotkey: hotkey.encode(),
+        bid,
+    };
+
+    let esk = [2; 32];
+    let rng = ChaCha20Rng::seed_from_u64(0);
+    let pk_bytes = hex::decode("83cf0f2896adee7eb8b5f01fcad3912212c437e0073e911fb90022d3e760183c8c4b450b6a0a6c3ac6a5776a2d1064510d1fec758c921cc22b0e17e63aaf4bcb5ed66304de9cf809bd274ca73bab4af5a6e9c76a4bc09e76eae8991ef5ece45a").unwrap();
+    let pub_key =
+        <TinyBLS381 as EngineBLS>::PublicKeyGroup::deserialize_compressed(&*pk_bytes).unwrap();
+    let message = {
+        let mut hasher = sha2::Sha256::new();
+        hasher.update(SEALED_BID_ROUND.to_be_bytes());
+        hasher.finalize().to_vec()
+    };
+    let identity = Identity::new(b"", vec![message]);
+
+    let ct = tle::<TinyBLS381, AESGCMStreamCipherProvider, ChaCha20Rng>(
+        pub_key,
+        esk,
+        &payload.encode(),
+        identity,
+        rng,
+    )
+    .unwrap();
+    let mut commit = Vec::new();
+    ct.serialize_compressed(&mut commit).unwrap();
+
+    SealedBid {
+        commit: commit.try_into().unwrap(),
+        reveal_round: SEALED_BID_ROUND,
+    }
+}
+
+fn insert_sealed_bid_pulse() {
+    let signature = hex::decode("b44679b9a59af2ec876b1a6b1ad52ea9b1615fc3982b19576350f93447cb1125e342b73a8dd2bacbe47e4b6b63ed5e39").unwrap();
+    pallet_drand::Pulses::<Test>::insert(
+        SEALED_BID_ROUND,
+        Pulse {
+            round: SEALED_BID_ROUND,
+            randomness: vec![0; 32].try_into().unwrap(),
+            signature: signature.try_into().unwrap(),
+        },
+    );
+}
+
 #[test]
 fn test_burn_registration_pruning_scenarios() {
     new_test_ext(1).execute_with(|| {
@@ -5554,3 +5554,71 @@ fn test_remove_root_updates_counters() {
         );
     });
 }
+
+#[test]
+fn test_limit_order_fill_keeps_total_issuance() {
+    new_test_ext(1).execute_with(|| {
+        let subnet_owner_coldkey = U256::from(1);
+        let subnet_owner_hotkey = U256::from(2);
+        let hotkey = U256::from(3);
+        let coldkey = U256::from(4);
+        let buyer = U256::from(5);
+        let netuid = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+        register_ok_neuron(netuid, hotkey, coldkey, 192213123);
+
+        let order_amount = 1_000_000_000;
+        mock::setup_reserves(
+            netuid,
+            (order_amount * 100).into(),
+            (order_amount * 100).into(),
+        );
+        TotalStake::<Test>::put(TaoCurrency::from(order_amount * 1_000));
+        SubtensorModule::add_balance_to_coldkey_account(&buyer, order_amount * 2);
+        let alpha = AlphaCurrency::from(order_amount * 30);
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey, &coldkey, netuid, alpha,
+        );
+
+        // A buy order below the current price of 1.0
+        let tick = TickIndex::new_unchecked(-1000);
+        assert_ok!(Swap::place_limit_order(
+            RuntimeOrigin::signed(buyer),
+            hotkey,
+            netuid,
+            OrderType::Buy,
+            tick,
+            order_amount,
+        ));
+        let escrow = pallet_subtensor_swap::Pallet::<Test>::limit_order_escrow_account_id();
+        assert_eq!(Balances::free_balance(escrow), order_amount);
+
+        let issuance_before = Balances::total_issuance() + TotalStake::<Test>::get().to_u64();
+
+        // The unstake crosses the order tick and fills the order in the same call
+        remove_stake_rate_limit_for_tests(&hotkey, &coldkey, netuid);
+        assert_ok!(SubtensorModule::remove_stake(
+            RuntimeOrigin::signed(coldkey),
+            hotkey,
+            netuid,
+            alpha,
+        ));
+
+        assert!(
+            pallet_subtensor_swap::LimitOrders::<Test>::iter_prefix_values((netuid,))
+                .next()
+                .is_none()
+        );
+        assert_eq!(Balances::free_balance(escrow), 0);
+        assert!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &buyer, netuid)
+                > AlphaCurrency::ZERO
+        );
+        assert!(StakingHotkeys::<Test>::get(buyer).contains(&hotkey));
+
+        // The escrowed TAO moved into the stake like an add_stake
+        assert_eq!(
+            Balances::total_issuance() + TotalStake::<Test>::get().to_u64(),
+            issuance_before
+        );
+    });
+}
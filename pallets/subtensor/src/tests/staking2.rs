@@ -1,13 +1,13 @@
 #![allow(clippy::unwrap_used)]
 
 use frame_support::{
-    assert_ok,
+    assert_noop, assert_ok,
     dispatch::{GetDispatchInfo, Pays},
     weights::Weight,
 };
 use sp_core::U256;
 use subtensor_runtime_common::{AlphaCurrency, Currency, TaoCurrency};
-use subtensor_swap_interface::SwapHandler;
+use subtensor_swap_interface::{OrderType, SwapHandler};
 
 use super::mock;
 use super::mock::*;
@@ -876,3 +876,206 @@ fn test_stake_fee_calculation() {
         assert_ne!(stake_fee, default_fee);
     });
 }
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::staking2::test_set_fee_alpha_sources_works --exact --nocapture
+#[test]
+fn test_set_fee_alpha_sources_works() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+        let netuid1 = add_dynamic_network(&U256::from(10), &U256::from(11));
+        let netuid2 = add_dynamic_network(&U256::from(20), &U256::from(21));
+        let sources = vec![(hotkey, netuid2), (hotkey, netuid1)];
+
+        assert_ok!(SubtensorModule::set_fee_alpha_sources(
+            RuntimeOrigin::signed(coldkey),
+            sources.clone().try_into().unwrap()
+        ));
+        assert_eq!(FeeAlphaSources::<Test>::get(coldkey).into_inner(), sources);
+        assert_eq!(
+            last_event(),
+            RuntimeEvent::SubtensorModule(Event::FeeAlphaSourcesSet { coldkey, sources })
+        );
+
+        // The sources follow the coldkey when it is swapped
+        let new_coldkey = U256::from(3);
+        let mut weight = Weight::zero();
+        assert_ok!(SubtensorModule::perform_swap_coldkey(
+            &coldkey,
+            &new_coldkey,
+            &mut weight
+        ));
+        assert!(!FeeAlphaSources::<Test>::contains_key(coldkey));
+        assert_eq!(FeeAlphaSources::<Test>::get(new_coldkey).len(), 2);
+
+        // An empty list clears the sources
+        assert_ok!(SubtensorModule::set_fee_alpha_sources(
+            RuntimeOrigin::signed(new_coldkey),
+            BoundedVec::new()
+        ));
+        assert!(!FeeAlphaSources::<Test>::contains_key(new_coldkey));
+    });
+}
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::staking2::test_set_fee_alpha_sources_fails_if_invalid --exact --nocapture
+#[test]
+fn test_set_fee_alpha_sources_fails_if_invalid() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+        let netuid = add_dynamic_network(&U256::from(10), &U256::from(11));
+
+        assert_noop!(
+            SubtensorModule::set_fee_alpha_sources(
+                RuntimeOrigin::signed(coldkey),
+                vec![(hotkey, NetUid::from(42))].try_into().unwrap()
+            ),
+            Error::<Test>::SubnetNotExists
+        );
+        assert_noop!(
+            SubtensorModule::set_fee_alpha_sources(
+                RuntimeOrigin::signed(coldkey),
+                vec![(hotkey, netuid), (hotkey, netuid)].try_into().unwrap()
+            ),
+            Error::<Test>::DuplicateFeeAlphaSource
+        );
+    });
+}
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::staking2::test_get_fee_in_alpha --exact --nocapture
+#[test]
+fn test_get_fee_in_alpha() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+        let netuid1 = add_dynamic_network(&U256::from(10), &U256::from(11));
+        let netuid2 = add_dynamic_network(&U256::from(20), &U256::from(21));
+        let tao_fee = 1_000_000;
+
+        // Alpha is worth 2 TAO on both subnets
+        setup_reserves(netuid1, 2_000_000_000.into(), 1_000_000_000.into());
+        setup_reserves(netuid2, 2_000_000_000.into(), 1_000_000_000.into());
+
+        // Not enough alpha on the first source to pay the fee, enough on the second one
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &coldkey,
+            netuid1,
+            AlphaCurrency::from(tao_fee * 3 / 10),
+        );
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &coldkey,
+            netuid2,
+            AlphaCurrency::from(tao_fee),
+        );
+
+        // The alpha charged is sold on the pool for at least the fee, the swap fee and the
+        // slippage being charged on top of its value at the current price
+        let assert_fee_in_alpha = |who: &U256, tao_fee: u64, netuid: NetUid| {
+            let (fee_coldkey, fee_hotkey, fee_netuid, alpha_fee) =
+                SubtensorModule::get_fee_in_alpha(who, tao_fee, true).unwrap();
+            assert_eq!(
+                (fee_coldkey, fee_hotkey, fee_netuid),
+                (coldkey, hotkey, netuid)
+            );
+            let alpha_fee = u64::from(alpha_fee);
+            assert!(alpha_fee >= tao_fee / 2 && alpha_fee <= tao_fee / 2 * 101 / 100);
+            let tao = <Test as Config>::SwapInterface::sim_swap(
+                netuid.into(),
+                OrderType::Sell,
+                alpha_fee,
+            )
+            .unwrap()
+            .amount_paid_out;
+            assert!(tao >= tao_fee);
+        };
+
+        assert_eq!(
+            SubtensorModule::get_fee_in_alpha(&coldkey, tao_fee, true),
+            None
+        );
+
+        assert_ok!(SubtensorModule::set_fee_alpha_sources(
+            RuntimeOrigin::signed(coldkey),
+            vec![(hotkey, netuid1), (hotkey, netuid2)]
+                .try_into()
+                .unwrap()
+        ));
+        assert_fee_in_alpha(&coldkey, tao_fee, netuid2);
+
+        // The first source can pay a smaller fee
+        assert_fee_in_alpha(&coldkey, tao_fee / 2, netuid1);
+
+        // Subnets with a disabled subtoken are skipped
+        SubtokenEnabled::<Test>::insert(netuid1, false);
+        assert_fee_in_alpha(&coldkey, tao_fee / 2, netuid2);
+
+        // The hotkey pays from the sources of its owner staking to it
+        assert_eq!(
+            SubtensorModule::get_fee_in_alpha(&hotkey, tao_fee, true),
+            None
+        );
+        Owner::<Test>::insert(hotkey, coldkey);
+        assert_fee_in_alpha(&hotkey, tao_fee, netuid2);
+
+        // Only when the owner pays for the call
+        assert_eq!(
+            SubtensorModule::get_fee_in_alpha(&hotkey, tao_fee, false),
+            None
+        );
+
+        // But not from the sources staking to other hotkeys
+        let other_hotkey = U256::from(3);
+        Owner::<Test>::insert(other_hotkey, coldkey);
+        assert_eq!(
+            SubtensorModule::get_fee_in_alpha(&other_hotkey, tao_fee, true),
+            None
+        );
+    });
+}
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::staking2::test_withdraw_fee_in_alpha_swaps_and_burns --exact --nocapture
+#[test]
+fn test_withdraw_fee_in_alpha_swaps_and_burns() {
+    new_test_ext(1).execute_with(|| {
+        let coldkey = U256::from(1);
+        let hotkey = U256::from(2);
+        let netuid = add_dynamic_network(&U256::from(10), &U256::from(11));
+        let tao_fee = 1_000_000;
+        setup_reserves(netuid, 2_000_000_000.into(), 1_000_000_000.into());
+        SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &coldkey,
+            netuid,
+            AlphaCurrency::from(tao_fee),
+        );
+
+        assert!(!SubtensorModule::withdraw_fee_in_alpha(
+            &coldkey, tao_fee, false
+        ));
+
+        assert_ok!(SubtensorModule::set_fee_alpha_sources(
+            RuntimeOrigin::signed(coldkey),
+            vec![(hotkey, netuid)].try_into().unwrap()
+        ));
+        let (_, _, _, alpha_fee) =
+            SubtensorModule::get_fee_in_alpha(&coldkey, tao_fee, false).unwrap();
+        let alpha_before =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid);
+        let subnet_tao_before = SubnetTAO::<Test>::get(netuid);
+        let issuance_before = TotalIssuance::<Test>::get();
+
+        assert!(SubtensorModule::withdraw_fee_in_alpha(
+            &coldkey, tao_fee, false
+        ));
+
+        // The alpha is sold on the pool and the tao bought is burned
+        let alpha_after =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid);
+        assert_eq!(alpha_before - alpha_after, alpha_fee);
+        let tao_sold = subnet_tao_before - SubnetTAO::<Test>::get(netuid);
+        assert!(tao_sold >= tao_fee.into());
+        assert_eq!(issuance_before - TotalIssuance::<Test>::get(), tao_sold);
+    });
+}
@@ -172,6 +172,48 @@ fn test_swap_hotkey_owners() {
         assert_eq!(OwnedHotkeys::<Test>::get(new_coldkey), vec![hotkey]);
     });
 }
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --test swap_coldkey -- test_swap_fee_alpha_sources --exact --nocapture
+#[test]
+fn test_swap_fee_alpha_sources() {
+    new_test_ext(1).execute_with(|| {
+        let old_coldkey = U256::from(1);
+        let new_coldkey = U256::from(2);
+        let source = |i: u16| (U256::from(100 + i), NetUid::from(i));
+
+        // The sources of the old coldkey are appended to the ones of the new coldkey, without
+        // duplicates and within the bound
+        let old_sources: Vec<_> = (0..MAX_FEE_ALPHA_SOURCES as u16)
+            .rev()
+            .map(source)
+            .collect();
+        let new_sources: Vec<_> = (MAX_FEE_ALPHA_SOURCES as u16 - 2..MAX_FEE_ALPHA_SOURCES as u16)
+            .map(source)
+            .collect();
+        FeeAlphaSources::<Test>::insert(old_coldkey, BoundedVec::truncate_from(old_sources));
+        FeeAlphaSources::<Test>::insert(
+            new_coldkey,
+            BoundedVec::truncate_from(new_sources.clone()),
+        );
+
+        let mut weight = Weight::zero();
+        assert_ok!(SubtensorModule::perform_swap_coldkey(
+            &old_coldkey,
+            &new_coldkey,
+            &mut weight
+        ));
+
+        let expected: Vec<_> = new_sources
+            .into_iter()
+            .chain((0..MAX_FEE_ALPHA_SOURCES as u16 - 2).rev().map(source))
+            .collect();
+        assert!(!FeeAlphaSources::<Test>::contains_key(old_coldkey));
+        assert_eq!(
+            FeeAlphaSources::<Test>::get(new_coldkey).into_inner(),
+            expected
+        );
+    });
+}
 // SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --test swap_coldkey -- test_transfer_remaining_balance --exact --nocapture
 #[test]
 fn test_transfer_remaining_balance() {
@@ -2619,3 +2661,59 @@ fn test_coldkey_in_swap_schedule_prevents_critical_calls() {
         );
     });
 }
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::swap_coldkey::test_swap_coldkey_liquidity_positions --exact --show-output
+#[test]
+fn test_swap_coldkey_liquidity_positions() {
+    new_test_ext(1).execute_with(|| {
+        let subnet_owner_coldkey = U256::from(1001);
+        let subnet_owner_hotkey = U256::from(1002);
+        let old_coldkey = U256::from(1);
+        let new_coldkey = U256::from(2);
+        let hotkey = U256::from(3);
+        let netuid = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+
+        // Stake to get Alpha for the position and to initialize the pool
+        SubtensorModule::create_account_if_non_existent(&old_coldkey, &hotkey);
+        SubtensorModule::add_balance_to_coldkey_account(&old_coldkey, 1_000_000_000_000);
+        SubtensorModule::stake_into_subnet(
+            &hotkey,
+            &old_coldkey,
+            netuid,
+            100_000_000_000.into(),
+            <Test as crate::Config>::SwapInterface::max_price().into(),
+            false,
+            false,
+        )
+        .unwrap();
+
+        pallet_subtensor_swap::EnabledUserLiquidity::<Test>::insert(netuid, true);
+        let (position_id, _, _) = pallet_subtensor_swap::Pallet::<Test>::do_add_liquidity(
+            netuid,
+            &old_coldkey,
+            &hotkey,
+            pallet_subtensor_swap::tick::TickIndex::new_unchecked(-1000),
+            pallet_subtensor_swap::tick::TickIndex::new_unchecked(1000),
+            1_000_000,
+        )
+        .unwrap();
+
+        let mut weight = Weight::zero();
+        assert_ok!(SubtensorModule::perform_swap_coldkey(
+            &old_coldkey,
+            &new_coldkey,
+            &mut weight
+        ));
+
+        assert!(!pallet_subtensor_swap::Positions::<Test>::contains_key((
+            netuid,
+            old_coldkey,
+            position_id
+        )));
+        assert!(pallet_subtensor_swap::Positions::<Test>::contains_key((
+            netuid,
+            new_coldkey,
+            position_id
+        )));
+    });
+}
@@ -0,0 +1,534 @@
+#![allow(clippy::unwrap_used)]
+
+use approx::assert_abs_diff_eq;
+use frame_support::weights::Weight;
+use frame_support::{assert_noop, assert_ok};
+use sp_core::U256;
+use subtensor_runtime_common::{AlphaCurrency, BalanceOps, Currency, NetUid};
+
+use super::mock;
+use super::mock::*;
+use crate::staking::vesting::VestingSchedule;
+use crate::*;
+
+const STAKE: u64 = 100_000_000_000;
+const CLIFF: u64 = 100;
+const DURATION: u64 = 1_000;
+
+/// Sets up a subnet with a hotkey and some alpha stake owned by the grantor coldkey.
+fn setup_vesting() -> (NetUid, U256, U256, U256) {
+    let subnet_owner_coldkey = U256::from(1001);
+    let subnet_owner_hotkey = U256::from(1002);
+    let netuid = add_dynamic_network(&subnet_owner_hotkey, &subnet_owner_coldkey);
+    let reserve = 1_000_000_000_000_u64;
+    mock::setup_reserves(netuid, reserve.into(), reserve.into());
+
+    let grantor = U256::from(1);
+    let beneficiary = U256::from(2);
+    let hotkey = U256::from(3);
+    SubtensorModule::create_account_if_non_existent(&grantor, &hotkey);
+    SubtensorModule::increase_stake_for_hotkey_and_coldkey_on_subnet(
+        &hotkey,
+        &grantor,
+        netuid,
+        STAKE.into(),
+    );
+
+    (netuid, grantor, beneficiary, hotkey)
+}
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::vesting::test_grant_vested_stake_ok --exact --show-output
+#[test]
+fn test_grant_vested_stake_ok() {
+    new_test_ext(1).execute_with(|| {
+        let (netuid, grantor, beneficiary, hotkey) = setup_vesting();
+        let amount = AlphaCurrency::from(STAKE / 2);
+
+        assert_ok!(SubtensorModule::grant_vested_stake(
+            RuntimeOrigin::signed(grantor),
+            beneficiary,
+            hotkey,
+            netuid,
+            amount,
+            CLIFF,
+            DURATION,
+        ));
+
+        let granted = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &beneficiary,
+            netuid,
+        );
+        assert_abs_diff_eq!(granted, amount, epsilon = amount / 1000.into());
+        assert_abs_diff_eq!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &grantor, netuid),
+            AlphaCurrency::from(STAKE) - amount,
+            epsilon = amount / 1000.into()
+        );
+        assert_eq!(
+            VestingSchedules::<Test>::get((netuid, hotkey, beneficiary)),
+            vec![VestingSchedule {
+                grantor,
+                total: granted,
+                start: 1,
+                cliff: CLIFF,
+                duration: DURATION,
+            }]
+        );
+
+        // The whole grant is locked, the grantor stake is not
+        assert_eq!(
+            SubtensorModule::get_locked_stake_for_hotkey_and_coldkey_on_subnet(
+                &hotkey,
+                &beneficiary,
+                netuid
+            ),
+            granted
+        );
+        assert!(
+            SubtensorModule::get_locked_stake_for_hotkey_and_coldkey_on_subnet(
+                &hotkey, &grantor, netuid
+            )
+            .is_zero()
+        );
+
+        System::assert_last_event(
+            Event::StakeVestingGranted {
+                grantor,
+                beneficiary,
+                hotkey,
+                netuid,
+                amount: granted,
+                cliff: CLIFF,
+                duration: DURATION,
+            }
+            .into(),
+        );
+    });
+}
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::vesting::test_grant_vested_stake_invalid --exact --show-output
+#[test]
+fn test_grant_vested_stake_invalid() {
+    new_test_ext(1).execute_with(|| {
+        let (netuid, grantor, beneficiary, hotkey) = setup_vesting();
+        let amount = AlphaCurrency::from(STAKE / 2);
+
+        for (who, netuid, cliff, duration) in [
+            (grantor, netuid, CLIFF, DURATION),
+            (beneficiary, netuid, CLIFF, 0),
+            (beneficiary, netuid, DURATION + 1, DURATION),
+            (beneficiary, NetUid::ROOT, CLIFF, DURATION),
+        ] {
+            assert_noop!(
+                SubtensorModule::grant_vested_stake(
+                    RuntimeOrigin::signed(grantor),
+                    who,
+                    hotkey,
+                    netuid,
+                    amount,
+                    cliff,
+                    duration,
+                ),
+                Error::<Test>::InvalidVestingSchedule
+            );
+        }
+
+        // Dust grants are rejected
+        assert_noop!(
+            SubtensorModule::grant_vested_stake(
+                RuntimeOrigin::signed(grantor),
+                beneficiary,
+                hotkey,
+                netuid,
+                AlphaCurrency::from(1),
+                CLIFF,
+                DURATION,
+            ),
+            Error::<Test>::AmountTooLow
+        );
+
+        // Too many active schedules on the beneficiary position
+        let schedule = VestingSchedule {
+            grantor,
+            total: AlphaCurrency::from(1),
+            start: 1,
+            cliff: CLIFF,
+            duration: DURATION,
+        };
+        VestingSchedules::<Test>::insert(
+            (hotkey, beneficiary, netuid),
+            BoundedVec::truncate_from(vec![schedule; MAX_VESTING_SCHEDULES as usize]),
+        );
+        assert_noop!(
+            SubtensorModule::grant_vested_stake(
+                RuntimeOrigin::signed(grantor),
+                beneficiary,
+                hotkey,
+                netuid,
+                amount,
+                CLIFF,
+                DURATION,
+            ),
+            Error::<Test>::TooManyVestingSchedules
+        );
+
+        // Fully vested schedules are dropped on the next grant
+        System::set_block_number(1 + DURATION);
+        assert_ok!(SubtensorModule::grant_vested_stake(
+            RuntimeOrigin::signed(grantor),
+            beneficiary,
+            hotkey,
+            netuid,
+            amount,
+            CLIFF,
+            DURATION,
+        ));
+        assert_eq!(
+            VestingSchedules::<Test>::get((netuid, hotkey, beneficiary)).len(),
+            1
+        );
+    });
+}
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::vesting::test_vested_stake_unlocks_linearly --exact --show-output
+#[test]
+fn test_vested_stake_unlocks_linearly() {
+    new_test_ext(1).execute_with(|| {
+        let (netuid, grantor, beneficiary, hotkey) = setup_vesting();
+        let other_hotkey = U256::from(4);
+        SubtensorModule::create_account_if_non_existent(&beneficiary, &other_hotkey);
+
+        assert_ok!(SubtensorModule::grant_vested_stake(
+            RuntimeOrigin::signed(grantor),
+            beneficiary,
+            hotkey,
+            netuid,
+            AlphaCurrency::from(STAKE),
+            CLIFF,
+            DURATION,
+        ));
+        let granted = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &beneficiary,
+            netuid,
+        );
+        let quarter = granted / 4.into();
+
+        // Nothing can leave the position before the cliff
+        System::set_block_number(CLIFF);
+        assert_noop!(
+            SubtensorModule::remove_stake(
+                RuntimeOrigin::signed(beneficiary),
+                hotkey,
+                netuid,
+                quarter
+            ),
+            Error::<Test>::StakeVestingLocked
+        );
+        assert_noop!(
+            SubtensorModule::transfer_stake(
+                RuntimeOrigin::signed(beneficiary),
+                grantor,
+                hotkey,
+                netuid,
+                netuid,
+                quarter
+            ),
+            Error::<Test>::StakeVestingLocked
+        );
+        assert_noop!(
+            SubtensorModule::move_stake(
+                RuntimeOrigin::signed(beneficiary),
+                hotkey,
+                other_hotkey,
+                netuid,
+                netuid,
+                quarter
+            ),
+            Error::<Test>::StakeVestingLocked
+        );
+        assert_noop!(
+            SubtensorModule::grant_vested_stake(
+                RuntimeOrigin::signed(beneficiary),
+                grantor,
+                hotkey,
+                netuid,
+                quarter,
+                CLIFF,
+                DURATION,
+            ),
+            Error::<Test>::StakeVestingLocked
+        );
+        assert_noop!(
+            SubtensorModule::recycle_alpha(
+                RuntimeOrigin::signed(beneficiary),
+                hotkey,
+                quarter,
+                netuid
+            ),
+            Error::<Test>::StakeVestingLocked
+        );
+        assert_noop!(
+            SubtensorModule::burn_alpha(
+                RuntimeOrigin::signed(beneficiary),
+                hotkey,
+                quarter,
+                netuid
+            ),
+            Error::<Test>::StakeVestingLocked
+        );
+        // Swap liquidity and limit orders take the stake through `BalanceOps`
+        assert_noop!(
+            <SubtensorModule as BalanceOps<U256>>::decrease_stake(
+                &beneficiary,
+                &hotkey,
+                netuid,
+                quarter
+            ),
+            Error::<Test>::StakeVestingLocked
+        );
+
+        // Half of the grant is unlocked halfway through the schedule
+        System::set_block_number(1 + DURATION / 2);
+        assert_eq!(
+            SubtensorModule::get_unlocked_stake_for_hotkey_and_coldkey_on_subnet(
+                &hotkey,
+                &beneficiary,
+                netuid
+            ),
+            granted / 2.into()
+        );
+        assert_noop!(
+            SubtensorModule::remove_stake(
+                RuntimeOrigin::signed(beneficiary),
+                hotkey,
+                netuid,
+                granted / 2.into() + 1.into()
+            ),
+            Error::<Test>::StakeVestingLocked
+        );
+        assert_ok!(SubtensorModule::remove_stake(
+            RuntimeOrigin::signed(beneficiary),
+            hotkey,
+            netuid,
+            quarter
+        ));
+
+        // The lock is capped at the remaining stake of the position
+        assert_eq!(
+            SubtensorModule::get_locked_stake_for_hotkey_and_coldkey_on_subnet(
+                &hotkey,
+                &beneficiary,
+                netuid
+            ),
+            granted - granted / 2.into()
+        );
+
+        // Everything is unlocked at the end of the schedule
+        System::set_block_number(1 + DURATION);
+        remove_stake_rate_limit_for_tests(&hotkey, &beneficiary, netuid);
+        assert!(
+            SubtensorModule::get_locked_stake_for_hotkey_and_coldkey_on_subnet(
+                &hotkey,
+                &beneficiary,
+                netuid
+            )
+            .is_zero()
+        );
+        let remaining = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &beneficiary,
+            netuid,
+        );
+        assert_ok!(SubtensorModule::move_stake(
+            RuntimeOrigin::signed(beneficiary),
+            hotkey,
+            other_hotkey,
+            netuid,
+            netuid,
+            remaining
+        ));
+    });
+}
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::vesting::test_unstake_all_keeps_vested_stake --exact --show-output
+#[test]
+fn test_unstake_all_keeps_vested_stake() {
+    new_test_ext(1).execute_with(|| {
+        let (netuid, grantor, beneficiary, hotkey) = setup_vesting();
+
+        assert_ok!(SubtensorModule::grant_vested_stake(
+            RuntimeOrigin::signed(grantor),
+            beneficiary,
+            hotkey,
+            netuid,
+            AlphaCurrency::from(STAKE / 2),
+            0,
+            DURATION,
+        ));
+        System::set_block_number(1 + DURATION / 2);
+        let locked = SubtensorModule::get_locked_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &beneficiary,
+            netuid,
+        );
+        assert!(!locked.is_zero());
+
+        assert_ok!(SubtensorModule::unstake_all(
+            RuntimeOrigin::signed(beneficiary),
+            hotkey
+        ));
+
+        // Only the unlocked part has been unstaked
+        assert_abs_diff_eq!(
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+                &hotkey,
+                &beneficiary,
+                netuid
+            ),
+            locked,
+            epsilon = locked / 1000.into()
+        );
+        assert!(SubtensorModule::get_coldkey_balance(&beneficiary) > 0);
+    });
+}
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::vesting::test_vesting_follows_swaps --exact --show-output
+#[test]
+fn test_vesting_follows_swaps() {
+    new_test_ext(1).execute_with(|| {
+        let (netuid, grantor, beneficiary, hotkey) = setup_vesting();
+        let new_beneficiary = U256::from(5);
+        let new_hotkey = U256::from(6);
+
+        assert_ok!(SubtensorModule::grant_vested_stake(
+            RuntimeOrigin::signed(grantor),
+            beneficiary,
+            hotkey,
+            netuid,
+            AlphaCurrency::from(STAKE / 2),
+            CLIFF,
+            DURATION,
+        ));
+        let schedules = VestingSchedules::<Test>::get((netuid, hotkey, beneficiary));
+        let locked = SubtensorModule::get_locked_stake_for_hotkey_and_coldkey_on_subnet(
+            &hotkey,
+            &beneficiary,
+            netuid,
+        );
+
+        // Coldkey swap
+        let mut weight = Weight::zero();
+        assert_ok!(SubtensorModule::perform_swap_coldkey(
+            &beneficiary,
+            &new_beneficiary,
+            &mut weight
+        ));
+        assert!(!VestingSchedules::<Test>::contains_key((
+            netuid,
+            hotkey,
+            beneficiary
+        )));
+        assert_eq!(
+            VestingSchedules::<Test>::get((netuid, hotkey, new_beneficiary)),
+            schedules
+        );
+        assert_eq!(
+            SubtensorModule::get_locked_stake_for_hotkey_and_coldkey_on_subnet(
+                &hotkey,
+                &new_beneficiary,
+                netuid
+            ),
+            locked
+        );
+
+        // Hotkey swap
+        SubtensorModule::perform_hotkey_swap_on_one_subnet(
+            &hotkey,
+            &new_hotkey,
+            &mut weight,
+            netuid,
+        );
+        assert!(!VestingSchedules::<Test>::contains_key((
+            netuid,
+            hotkey,
+            new_beneficiary
+        )));
+        assert_eq!(
+            VestingSchedules::<Test>::get((netuid, new_hotkey, new_beneficiary)),
+            schedules
+        );
+
+        // Subnet dissolution
+        assert_ok!(SubtensorModule::do_dissolve_network(netuid));
+        assert!(
+            VestingSchedules::<Test>::iter_prefix((netuid,))
+                .next()
+                .is_none()
+        );
+    });
+}
+
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::vesting::test_vesting_swap_merges_excess_schedules --exact --show-output
+#[test]
+fn test_vesting_swap_merges_excess_schedules() {
+    new_test_ext(1).execute_with(|| {
+        let netuid = NetUid::from(1);
+        let grantor = U256::from(1);
+        let hotkey = U256::from(2);
+        let old_coldkey = U256::from(3);
+        let new_coldkey = U256::from(4);
+        let schedule = |start: u64, cliff: u64| VestingSchedule {
+            grantor,
+            total: AlphaCurrency::from(1_000),
+            start,
+            cliff,
+            duration: DURATION,
+        };
+
+        // Both positions are full, one of the old schedules is fully vested
+        let mut old_schedules = vec![schedule(1, CLIFF); MAX_VESTING_SCHEDULES as usize - 1];
+        old_schedules.push(schedule(0, 0));
+        VestingSchedules::<Test>::insert(
+            (netuid, hotkey, old_coldkey),
+            BoundedVec::truncate_from(old_schedules),
+        );
+        VestingSchedules::<Test>::insert(
+            (netuid, hotkey, new_coldkey),
+            BoundedVec::truncate_from(vec![schedule(500, 0); MAX_VESTING_SCHEDULES as usize]),
+        );
+        System::set_block_number(DURATION);
+
+        let mut weight = Weight::zero();
+        SubtensorModule::swap_vesting_schedules(
+            &hotkey,
+            &old_coldkey,
+            &hotkey,
+            &new_coldkey,
+            netuid,
+            &mut weight,
+        );
+
+        let schedules = VestingSchedules::<Test>::get((netuid, hotkey, new_coldkey));
+        assert_eq!(schedules.len(), MAX_VESTING_SCHEDULES as usize);
+        let total = schedules
+            .iter()
+            .fold(AlphaCurrency::ZERO, |acc, schedule| acc + schedule.total);
+        assert_eq!(
+            total,
+            AlphaCurrency::from(1_000 * (2 * MAX_VESTING_SCHEDULES as u64 - 1))
+        );
+
+        // The merged schedules never lock less than the original ones
+        for now in [DURATION, DURATION + 200, 1_400, 1_500] {
+            let locked = schedules.iter().fold(AlphaCurrency::ZERO, |acc, schedule| {
+                acc + schedule.locked_at(now)
+            });
+            let expected = schedule(1, CLIFF).locked_at(now)
+                * (MAX_VESTING_SCHEDULES as u64 - 1).into()
+                + schedule(500, 0).locked_at(now) * (MAX_VESTING_SCHEDULES as u64).into();
+            assert!(locked >= expected);
+        }
+    });
+}
@@ -6845,6 +6845,127 @@ fn test_reveal_crv3_commits_retry_on_missing_pulse() {
     });
 }
 
+// SKIP_WASM_BUILD=1 RUST_LOG=debug cargo test --package pallet-subtensor --lib -- tests::weights::test_reveal_crv3_commits_from_selected_beacon --exact --show-output
+#[test]
+fn test_reveal_crv3_commits_from_selected_beacon() {
+    new_test_ext(100).execute_with(|| {
+        let netuid = NetUid::from(1);
+        let hotkey: AccountId = U256::from(1);
+        let reveal_round: u64 = 1_000;
+        let chain_hash = [1u8; 32];
+
+        add_network(netuid, 5, 0);
+        register_ok_neuron(netuid, hotkey, U256::from(3), 100_000);
+        SubtensorModule::set_commit_reveal_weights_enabled(netuid, true);
+        assert_ok!(SubtensorModule::set_reveal_period(netuid, 3));
+        SubtensorModule::set_weights_set_rate_limit(netuid, 0);
+        SubtensorModule::set_stake_threshold(0);
+
+        let uid = SubtensorModule::get_uid_for_net_and_hotkey(netuid, &hotkey).unwrap();
+        SubtensorModule::set_validator_permit_for_uid(netuid, uid, true);
+
+        // The beacon must be tracked by the drand pallet
+        assert_err!(
+            SubtensorModule::set_timelocked_weights_beacon(netuid, Some(chain_hash)),
+            Error::<Test>::UnknownDrandBeacon
+        );
+        pallet_drand::BeaconConfigs::<Test>::insert(chain_hash, Default::default());
+        assert_ok!(SubtensorModule::set_timelocked_weights_beacon(
+            netuid,
+            Some(chain_hash)
+        ));
+
+        let payload = WeightsTlockPayload {
+            hotkey: hotkey.encode(),
+            values: vec![10],
+            uids: vec![uid],
+            version_key: SubtensorModule::get_weights_version_key(netuid),
+        };
+        let esk = [2u8; 32];
+        let pk_bytes = hex::decode(
+            "83cf0f2896adee7eb8b5f01fcad3912212c437e0073e911fb90022d3e760183c\
+             8c4b450b6a0a6c3ac6a5776a2d1064510d1fec758c921cc22b0e17e63aaf4bcb\
+             5ed66304de9cf809bd274ca73bab4af5a6e9c76a4bc09e76eae8991ef5ece45a",
+        )
+        .unwrap();
+        let pk =
+            <TinyBLS381 as EngineBLS>::PublicKeyGroup::deserialize_compressed(&*pk_bytes).unwrap();
+        let id_msg = {
+            let mut h = sha2::Sha256::new();
+            h.update(reveal_round.to_be_bytes());
+            h.finalize().to_vec()
+        };
+        let ct = tle::<TinyBLS381, AESGCMStreamCipherProvider, ChaCha20Rng>(
+            pk,
+            esk,
+            &payload.encode(),
+            Identity::new(b"", vec![id_msg]),
+            ChaCha20Rng::seed_from_u64(0),
+        )
+        .unwrap();
+        let mut commit_bytes = Vec::new();
+        ct.serialize_compressed(&mut commit_bytes).unwrap();
+
+        assert_ok!(SubtensorModule::do_commit_timelocked_weights(
+            RuntimeOrigin::signed(hotkey),
+            netuid,
+            commit_bytes.clone().try_into().unwrap(),
+            reveal_round,
+            SubtensorModule::get_commit_reveal_weights_version()
+        ));
+
+        // The beacon cannot change while the commit is pending
+        assert_err!(
+            SubtensorModule::set_timelocked_weights_beacon(netuid, None),
+            Error::<Test>::TimelockedWeightCommitsPending
+        );
+
+        let stored_epoch =
+            TimelockedWeightCommits::<Test>::iter_prefix(NetUidStorageIndex::from(netuid))
+                .next()
+                .map(|(e, _)| e)
+                .expect("commit stored");
+        let first_reveal_epoch = stored_epoch + SubtensorModule::get_reveal_period(netuid);
+        let first_reveal_block =
+            SubtensorModule::get_first_block_of_epoch(netuid, first_reveal_epoch);
+        run_to_block_no_epoch(netuid, first_reveal_block);
+
+        let sig_bytes = hex::decode(
+            "b44679b9a59af2ec876b1a6b1ad52ea9b1615fc3982b19576350f93447cb1125e\
+             342b73a8dd2bacbe47e4b6b63ed5e39",
+        )
+        .unwrap();
+        let pulse = Pulse {
+            round: reveal_round,
+            randomness: vec![0; 32].try_into().unwrap(),
+            signature: sig_bytes.try_into().unwrap(),
+        };
+
+        // A pulse of the default beacon is not used for the reveal
+        pallet_drand::Pulses::<Test>::insert(reveal_round, pulse.clone());
+        step_block(1);
+        assert!(
+            !TimelockedWeightCommits::<Test>::get(NetUidStorageIndex::from(netuid), stored_epoch)
+                .is_empty(),
+            "commit must remain queued without a pulse of the selected beacon"
+        );
+
+        pallet_drand::BeaconPulses::<Test>::insert(chain_hash, reveal_round, pulse);
+        step_block(1);
+
+        let weights = SubtensorModule::get_weights_sparse(netuid.into())
+            .get(uid as usize)
+            .cloned()
+            .unwrap_or_default();
+        assert!(!weights.is_empty(), "weights must be set after pulse");
+        assert!(
+            TimelockedWeightCommits::<Test>::get(NetUidStorageIndex::from(netuid), stored_epoch)
+                .is_empty(),
+            "queue should be empty after successful reveal"
+        );
+    });
+}
+
 #[test]
 fn test_reveal_crv3_commits_legacy_payload_success() {
     new_test_ext(100).execute_with(|| {
@@ -230,7 +230,8 @@ where
                 ..
             }) => {
                 if Self::check_weights_min_stake(who, *netuid) {
-                    if *reveal_round < pallet_drand::LastStoredRound::<T>::get() {
+                    let beacon = Pallet::<T>::get_timelocked_weights_beacon(*netuid);
+                    if *reveal_round < pallet_drand::Pallet::<T>::last_stored_round(beacon) {
                         return Err(CustomTransactionError::InvalidRevealRound.into());
                     }
                     Ok((Default::default(), Some(who.clone()), origin))
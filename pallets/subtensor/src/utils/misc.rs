@@ -412,6 +412,14 @@ impl<T: Config> Pallet<T> {
         RecycleOrBurn::<T>::insert(netuid, recycle_or_burn);
     }
 
+    pub fn get_burned_registration_mode(netuid: NetUid) -> BurnedRegistrationMode {
+        BurnedRegistrationModes::<T>::get(netuid)
+    }
+
+    pub fn set_burned_registration_mode(netuid: NetUid, mode: BurnedRegistrationMode) {
+        BurnedRegistrationModes::<T>::insert(netuid, mode);
+    }
+
     // ========================
     // ========= Sudo =========
     // ========================
@@ -872,6 +880,20 @@ impl<T: Config> Pallet<T> {
         Yuma3On::<T>::get(netuid)
     }
 
+    pub fn set_epoch_debug_enabled(netuid: NetUid, enabled: bool) {
+        EpochDebugOn::<T>::set(netuid, enabled);
+        if !enabled {
+            let mechanisms: u8 = MechanismCountCurrent::<T>::get(netuid).into();
+            for mecid in 0..mechanisms {
+                EpochDebug::<T>::remove(Self::get_mechanism_storage_index(netuid, mecid.into()));
+            }
+        }
+    }
+
+    pub fn get_epoch_debug_enabled(netuid: NetUid) -> bool {
+        EpochDebugOn::<T>::get(netuid)
+    }
+
     pub fn get_subtoken_enabled(netuid: NetUid) -> bool {
         SubtokenEnabled::<T>::get(netuid)
     }
@@ -197,6 +197,11 @@ pub enum Hyperparameter {
     BondsResetEnabled = 22,
     ImmuneNeuronLimit = 23,
     RecycleOrBurn = 24,
+    TimelockedWeightsBeacon = 26,
+    CommitmentHistoryDepth = 27,
+    CommitmentSchema = 28,
+    BurnedRegistrationMode = 29,
+    SubnetPaymaster = 30,
 }
 
 impl<T: Config> Pallet<T> {
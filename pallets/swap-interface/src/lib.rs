@@ -4,7 +4,18 @@ use frame_support::pallet_prelude::*;
 use substrate_fixed::types::U96F32;
 use subtensor_runtime_common::{AlphaCurrency, NetUid, TaoCurrency};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    TypeInfo,
+    MaxEncodedLen,
+)]
 pub enum OrderType {
     Sell,
     Buy,
@@ -37,6 +48,13 @@ pub trait SwapHandler<AccountId> {
     fn dissolve_all_liquidity_providers(netuid: NetUid) -> DispatchResult;
     fn toggle_user_liquidity(netuid: NetUid, enabled: bool);
     fn clear_protocol_liquidity(netuid: NetUid) -> DispatchResult;
+    fn record_price_observation(netuid: NetUid);
+    fn swap_coldkey_positions(old_coldkey: &AccountId, new_coldkey: &AccountId) -> Weight;
+    fn ensure_can_swap_coldkey_positions(
+        old_coldkey: &AccountId,
+        new_coldkey: &AccountId,
+    ) -> DispatchResult;
+    fn swap_coldkey_positions_weight() -> Weight;
 }
 
 #[derive(Debug, PartialEq)]
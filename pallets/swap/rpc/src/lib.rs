@@ -1,6 +1,6 @@
 //! RPC interface for the Swap pallet
 
-use codec::Encode;
+use codec::{Decode, Encode};
 use std::sync::Arc;
 
 use jsonrpsee::{
@@ -10,7 +10,7 @@ use jsonrpsee::{
 };
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
-use sp_runtime::traits::Block as BlockT;
+use sp_runtime::{AccountId32, traits::Block as BlockT};
 use subtensor_runtime_common::{AlphaCurrency, NetUid, TaoCurrency};
 
 pub use pallet_subtensor_swap_runtime_api::SwapRuntimeApi;
@@ -19,6 +19,13 @@ pub use pallet_subtensor_swap_runtime_api::SwapRuntimeApi;
 pub trait SwapRpcApi<BlockHash> {
     #[method(name = "swap_currentAlphaPrice")]
     fn current_alpha_price(&self, netuid: NetUid, at: Option<BlockHash>) -> RpcResult<u64>;
+    #[method(name = "swap_twapAlphaPrice")]
+    fn twap_alpha_price(
+        &self,
+        netuid: NetUid,
+        window_blocks: u64,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<u64>>;
     #[method(name = "swap_simSwapTaoForAlpha")]
     fn sim_swap_tao_for_alpha(
         &self,
@@ -33,6 +40,43 @@ pub trait SwapRpcApi<BlockHash> {
         alpha: AlphaCurrency,
         at: Option<BlockHash>,
     ) -> RpcResult<Vec<u8>>;
+    #[method(name = "swap_simSwapRoute")]
+    fn sim_swap_route(
+        &self,
+        coldkey_account_vec: Vec<u8>,
+        hotkey_account_vec: Vec<u8>,
+        route: Vec<NetUid>,
+        alpha: AlphaCurrency,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<u8>>;
+    #[method(name = "swap_getLimitOrders")]
+    fn get_limit_orders(
+        &self,
+        coldkey_account_vec: Vec<u8>,
+        netuid: NetUid,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<u8>>;
+    #[method(name = "swap_getTicks")]
+    fn get_ticks(
+        &self,
+        netuid: NetUid,
+        price_low: u64,
+        price_high: u64,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<u8>>;
+    #[method(name = "swap_getLiquidityCurve")]
+    fn get_liquidity_curve(
+        &self,
+        netuid: NetUid,
+        ranges_each_side: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<u8>>;
+    #[method(name = "swap_getPositions")]
+    fn get_positions(
+        &self,
+        coldkey_account_vec: Vec<u8>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<u8>>;
 }
 
 /// Error type of this RPC api.
@@ -92,6 +136,21 @@ where
         })
     }
 
+    fn twap_alpha_price(
+        &self,
+        netuid: NetUid,
+        window_blocks: u64,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<u64>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        api.twap_alpha_price(at, netuid, window_blocks)
+            .map_err(|e| {
+                Error::RuntimeError(format!("Unable to get TWAP alpha price: {e:?}")).into()
+            })
+    }
+
     fn sim_swap_tao_for_alpha(
         &self,
         netuid: NetUid,
@@ -127,4 +186,99 @@ where
             .into()),
         }
     }
+
+    fn sim_swap_route(
+        &self,
+        coldkey_account_vec: Vec<u8>,
+        hotkey_account_vec: Vec<u8>,
+        route: Vec<NetUid>,
+        alpha: AlphaCurrency,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<u8>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        let coldkey = AccountId32::decode(&mut &coldkey_account_vec[..])
+            .map_err(|e| Error::RuntimeError(format!("Unable to simulate swap route: {e:?}")))?;
+        let hotkey = AccountId32::decode(&mut &hotkey_account_vec[..])
+            .map_err(|e| Error::RuntimeError(format!("Unable to simulate swap route: {e:?}")))?;
+
+        match api.sim_swap_route(at, coldkey, hotkey, route, alpha) {
+            Ok(Ok(legs)) => Ok(legs.encode()),
+            Ok(Err(e)) => {
+                Err(Error::RuntimeError(format!("Unable to simulate swap route: {e:?}")).into())
+            }
+            Err(e) => {
+                Err(Error::RuntimeError(format!("Unable to simulate swap route: {e:?}")).into())
+            }
+        }
+    }
+
+    fn get_limit_orders(
+        &self,
+        coldkey_account_vec: Vec<u8>,
+        netuid: NetUid,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<u8>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        let coldkey = AccountId32::decode(&mut &coldkey_account_vec[..])
+            .map_err(|e| Error::RuntimeError(format!("Unable to get limit orders: {e:?}")))?;
+
+        match api.get_limit_orders(at, coldkey, netuid) {
+            Ok(result) => Ok(result.encode()),
+            Err(e) => Err(Error::RuntimeError(format!("Unable to get limit orders: {e:?}")).into()),
+        }
+    }
+
+    fn get_ticks(
+        &self,
+        netuid: NetUid,
+        price_low: u64,
+        price_high: u64,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<u8>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        match api.get_ticks(at, netuid, price_low, price_high) {
+            Ok(result) => Ok(result.encode()),
+            Err(e) => Err(Error::RuntimeError(format!("Unable to get ticks: {e:?}")).into()),
+        }
+    }
+
+    fn get_liquidity_curve(
+        &self,
+        netuid: NetUid,
+        ranges_each_side: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<u8>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        match api.get_liquidity_curve(at, netuid, ranges_each_side) {
+            Ok(result) => Ok(result.encode()),
+            Err(e) => {
+                Err(Error::RuntimeError(format!("Unable to get liquidity curve: {e:?}")).into())
+            }
+        }
+    }
+
+    fn get_positions(
+        &self,
+        coldkey_account_vec: Vec<u8>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<u8>> {
+        let api = self.client.runtime_api();
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        let coldkey = AccountId32::decode(&mut &coldkey_account_vec[..])
+            .map_err(|e| Error::RuntimeError(format!("Unable to get positions: {e:?}")))?;
+
+        match api.get_positions(at, coldkey) {
+            Ok(result) => Ok(result.encode()),
+            Err(e) => Err(Error::RuntimeError(format!("Unable to get positions: {e:?}")).into()),
+        }
+    }
 }
@@ -1,8 +1,11 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use frame_support::pallet_prelude::*;
+use sp_runtime::AccountId32;
+use sp_std::vec::Vec;
 use subtensor_macros::freeze_struct;
 use subtensor_runtime_common::{AlphaCurrency, NetUid, TaoCurrency};
+use subtensor_swap_interface::OrderType;
 
 #[freeze_struct("3a4fd213b5de5eb6")]
 #[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
@@ -13,10 +16,107 @@ pub struct SimSwapResult {
     pub alpha_fee: AlphaCurrency,
 }
 
+#[freeze_struct("6c39eeb05fb1b710")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+pub struct LimitOrderInfo<AccountId> {
+    pub order_id: u128,
+    pub hotkey: AccountId,
+    pub netuid: NetUid,
+    pub order_type: OrderType,
+    pub tick: i32,
+    /// Limit price in rao per 10^9 Alpha units
+    pub price: u64,
+    /// Remaining unfilled amount: TAO for buy orders, Alpha for sell orders
+    pub amount: u64,
+}
+
+#[freeze_struct("539a32dba843ab27")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+pub struct SimSwapRouteLeg {
+    pub origin_netuid: NetUid,
+    pub destination_netuid: NetUid,
+    /// Origin Alpha sold, including the fee
+    pub alpha_in: AlphaCurrency,
+    /// TAO received for the origin Alpha and staked into the destination subnet
+    pub tao_amount: TaoCurrency,
+    /// Destination Alpha received
+    pub alpha_out: AlphaCurrency,
+    /// Fee paid on the origin subnet, in origin Alpha
+    pub alpha_fee: AlphaCurrency,
+    /// Fee paid on the destination subnet, in TAO
+    pub tao_fee: TaoCurrency,
+    /// Origin Alpha price after the leg, in rao per Alpha
+    pub origin_price: u64,
+    /// Destination Alpha price after the leg, in rao per Alpha
+    pub destination_price: u64,
+}
+
+#[freeze_struct("adb6dc48a3120514")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+pub struct TickInfo {
+    pub tick: i32,
+    /// Price at the tick in rao per 10^9 Alpha units
+    pub price: u64,
+    /// Liquidity added when the price crosses the tick upwards, removed when crossing downwards
+    pub liquidity_net: i128,
+    /// Total liquidity of the positions that use the tick as a boundary
+    pub liquidity_gross: u64,
+}
+
+#[freeze_struct("1327a0b2aa11a4")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+pub struct LiquidityRange {
+    /// Initialized tick at the lower end of the range (inclusive)
+    pub tick_low: i32,
+    /// Initialized tick at the upper end of the range (exclusive)
+    pub tick_high: i32,
+    /// Price at `tick_low` in rao per 10^9 Alpha units
+    pub price_low: u64,
+    /// Price at `tick_high` in rao per 10^9 Alpha units
+    pub price_high: u64,
+    /// Active liquidity while the price is within the range
+    pub liquidity: u64,
+}
+
+#[freeze_struct("71dee24017be2d79")]
+#[derive(Decode, Encode, PartialEq, Eq, Clone, Debug, TypeInfo)]
+pub struct PositionInfo<AccountId> {
+    pub position_id: u128,
+    pub netuid: NetUid,
+    pub tick_low: i32,
+    pub tick_high: i32,
+    /// Price at `tick_low` in rao per 10^9 Alpha units
+    pub price_low: u64,
+    /// Price at `tick_high` in rao per 10^9 Alpha units
+    pub price_high: u64,
+    pub liquidity: u64,
+    /// TAO withdrawn if the position is removed at the current price
+    pub tao: TaoCurrency,
+    /// Alpha withdrawn if the position is removed at the current price
+    pub alpha: AlphaCurrency,
+    /// Uncollected TAO fees
+    pub fee_tao: TaoCurrency,
+    /// Uncollected Alpha fees
+    pub fee_alpha: AlphaCurrency,
+    /// Operator approved to modify the position
+    pub operator: Option<AccountId>,
+}
+
 sp_api::decl_runtime_apis! {
     pub trait SwapRuntimeApi {
         fn current_alpha_price(netuid: NetUid) -> u64;
+        fn twap_alpha_price(netuid: NetUid, window_blocks: u64) -> Option<u64>;
         fn sim_swap_tao_for_alpha(netuid: NetUid, tao: TaoCurrency) -> SimSwapResult;
         fn sim_swap_alpha_for_tao(netuid: NetUid, alpha: AlphaCurrency) -> SimSwapResult;
+        fn sim_swap_route(
+            coldkey: AccountId32,
+            hotkey: AccountId32,
+            route: Vec<NetUid>,
+            alpha: AlphaCurrency,
+        ) -> Result<Vec<SimSwapRouteLeg>, DispatchError>;
+        fn get_limit_orders(coldkey: AccountId32, netuid: NetUid) -> Vec<LimitOrderInfo<AccountId32>>;
+        fn get_ticks(netuid: NetUid, price_low: u64, price_high: u64) -> Vec<TickInfo>;
+        fn get_liquidity_curve(netuid: NetUid, ranges_each_side: u32) -> Vec<LiquidityRange>;
+        fn get_positions(coldkey: AccountId32) -> Vec<PositionInfo<AccountId32>>;
     }
 }
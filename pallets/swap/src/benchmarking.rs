@@ -8,17 +8,32 @@ use frame_benchmarking::v2::*;
 use frame_support::traits::Get;
 use frame_system::RawOrigin;
 use substrate_fixed::types::{I64F64, U64F64};
-use subtensor_runtime_common::NetUid;
+use subtensor_runtime_common::{BalanceOps, NetUid};
 
 use crate::{
+    OrderType,
+    limit_order::LimitOrderId,
     pallet::{
-        AlphaSqrtPrice, Call, Config, CurrentLiquidity, CurrentTick, EnabledUserLiquidity, Pallet,
-        Positions, SwapV3Initialized,
+        AccountLimitOrders, AlphaSqrtPrice, Call, Config, CurrentLiquidity, CurrentTick,
+        EnabledUserLiquidity, LimitOrderBook, LimitOrders, Pallet, PositionOperators, Positions,
+        SwapV3Initialized,
     },
     position::{Position, PositionId},
     tick::TickIndex,
 };
 
+/// Fills the `order_type` side of the book up to one order below `MaxLimitOrders`, with orders
+/// on ticks below `tick`.
+fn fill_limit_order_book<T: Config>(netuid: NetUid, order_type: OrderType, tick: TickIndex) {
+    LimitOrderBook::<T>::mutate(netuid, order_type, |book| {
+        for i in 0..T::MaxLimitOrders::get().saturating_sub(1) {
+            let filler_tick = tick.saturating_sub(i.saturating_add(1) as i32);
+            let filler_id = LimitOrderId::from(u128::from(u32::MAX).saturating_add(i.into()));
+            book.try_insert(0, (filler_tick, filler_id)).unwrap();
+        }
+    });
+}
+
 #[benchmarks(where T: Config)]
 mod benchmarks {
     use super::*;
@@ -143,5 +158,182 @@ mod benchmarks {
         assert!(EnabledUserLiquidity::<T>::get(netuid));
     }
 
+    #[benchmark]
+    fn place_limit_order() {
+        let netuid = NetUid::from(1);
+
+        if !SwapV3Initialized::<T>::get(netuid) {
+            SwapV3Initialized::<T>::insert(netuid, true);
+            AlphaSqrtPrice::<T>::insert(netuid, U64F64::from_num(1));
+            CurrentTick::<T>::insert(netuid, TickIndex::new(0).unwrap());
+            CurrentLiquidity::<T>::insert(netuid, T::MinimumLiquidity::get());
+        }
+
+        let caller: T::AccountId = whitelisted_caller();
+        let hotkey: T::AccountId = account("hotkey", 0, 0);
+        let tick = TickIndex::new_unchecked(-1000);
+        let amount = T::MinLimitOrderAmount::get();
+        T::BalanceOps::increase_balance(&caller, amount.saturating_mul(2).into());
+
+        // Almost full book and account orders
+        fill_limit_order_book::<T>(netuid, OrderType::Buy, tick);
+        AccountLimitOrders::<T>::mutate(&caller, |orders| {
+            for i in 0..T::MaxLimitOrdersPerAccount::get().saturating_sub(1) {
+                let order_id = LimitOrderId::from(u128::from(u32::MAX).saturating_add(i.into()));
+                orders.try_push((netuid, tick, order_id)).unwrap();
+            }
+        });
+
+        #[extrinsic_call]
+        place_limit_order(
+            RawOrigin::Signed(caller),
+            hotkey,
+            netuid,
+            OrderType::Buy,
+            tick,
+            amount,
+        );
+
+        assert!(LimitOrders::<T>::contains_key((
+            netuid,
+            tick,
+            LimitOrderId::from(1u128)
+        )));
+    }
+
+    #[benchmark]
+    fn cancel_limit_order() {
+        let netuid = NetUid::from(1);
+
+        if !SwapV3Initialized::<T>::get(netuid) {
+            SwapV3Initialized::<T>::insert(netuid, true);
+            AlphaSqrtPrice::<T>::insert(netuid, U64F64::from_num(1));
+            CurrentTick::<T>::insert(netuid, TickIndex::new(0).unwrap());
+            CurrentLiquidity::<T>::insert(netuid, T::MinimumLiquidity::get());
+        }
+
+        let caller: T::AccountId = whitelisted_caller();
+        let hotkey: T::AccountId = account("hotkey", 0, 0);
+        let tick = TickIndex::new_unchecked(-1000);
+
+        // Orders are looked up on the buy side first and removed from an almost full book
+        fill_limit_order_book::<T>(netuid, OrderType::Sell, TickIndex::new_unchecked(2000));
+        let order_id = Pallet::<T>::do_place_limit_order(
+            netuid,
+            &caller,
+            &hotkey,
+            OrderType::Buy,
+            tick,
+            T::MinLimitOrderAmount::get(),
+        )
+        .unwrap();
+        T::BalanceOps::increase_balance(
+            &Pallet::<T>::limit_order_escrow_account_id(),
+            T::MinLimitOrderAmount::get().into(),
+        );
+
+        #[extrinsic_call]
+        cancel_limit_order(RawOrigin::Signed(caller), netuid, order_id);
+
+        assert!(!LimitOrders::<T>::contains_key((netuid, tick, order_id)));
+    }
+
+    #[benchmark]
+    fn fill_limit_order() {
+        let netuid = NetUid::from(1);
+
+        if !SwapV3Initialized::<T>::get(netuid) {
+            SwapV3Initialized::<T>::insert(netuid, true);
+            AlphaSqrtPrice::<T>::insert(netuid, U64F64::from_num(1));
+            CurrentTick::<T>::insert(netuid, TickIndex::new(0).unwrap());
+            CurrentLiquidity::<T>::insert(netuid, T::MinimumLiquidity::get());
+        }
+
+        let caller: T::AccountId = whitelisted_caller();
+        let hotkey: T::AccountId = account("hotkey", 0, 0);
+        let tick = TickIndex::new_unchecked(1000);
+        let order_id = Pallet::<T>::do_place_limit_order(
+            netuid,
+            &caller,
+            &hotkey,
+            OrderType::Sell,
+            tick,
+            T::MinLimitOrderAmount::get(),
+        )
+        .unwrap();
+
+        // A swap moved the price above the order tick
+        let price_tick = TickIndex::new_unchecked(2000);
+        AlphaSqrtPrice::<T>::insert(netuid, price_tick.as_sqrt_price_bounded());
+        CurrentTick::<T>::insert(netuid, price_tick);
+
+        #[block]
+        {
+            Pallet::<T>::fill_triggered_limit_orders(netuid, OrderType::Sell);
+        }
+
+        // The order is closed or the fill pushed the price down to its tick
+        assert!(
+            LimitOrders::<T>::get((netuid, tick, order_id))
+                .is_none_or(|order| !order.is_triggered(Pallet::<T>::current_price_sqrt(netuid)))
+        );
+    }
+
+    #[benchmark]
+    fn transfer_position() {
+        let netuid = NetUid::from(1);
+        let caller: T::AccountId = whitelisted_caller();
+        let new_coldkey: T::AccountId = account("new_coldkey", 0, 0);
+        let operator: T::AccountId = account("operator", 0, 0);
+        let id = PositionId::from(1u128);
+
+        Positions::<T>::insert(
+            (netuid, caller.clone(), id),
+            Position {
+                id,
+                netuid,
+                tick_low: TickIndex::new(-10000).unwrap(),
+                tick_high: TickIndex::new(10000).unwrap(),
+                liquidity: 10000,
+                fees_tao: I64F64::from_num(0),
+                fees_alpha: I64F64::from_num(0),
+                _phantom: PhantomData,
+            },
+        );
+        PositionOperators::<T>::insert(netuid, id, (caller.clone(), operator));
+
+        #[extrinsic_call]
+        transfer_position(RawOrigin::Signed(caller), netuid, id, new_coldkey.clone());
+
+        assert!(Positions::<T>::contains_key((netuid, new_coldkey, id)));
+    }
+
+    #[benchmark]
+    fn set_position_operator() {
+        let netuid = NetUid::from(1);
+        let caller: T::AccountId = whitelisted_caller();
+        let operator: T::AccountId = account("operator", 0, 0);
+        let id = PositionId::from(1u128);
+
+        Positions::<T>::insert(
+            (netuid, caller.clone(), id),
+            Position {
+                id,
+                netuid,
+                tick_low: TickIndex::new(-10000).unwrap(),
+                tick_high: TickIndex::new(10000).unwrap(),
+                liquidity: 10000,
+                fees_tao: I64F64::from_num(0),
+                fees_alpha: I64F64::from_num(0),
+                _phantom: PhantomData,
+            },
+        );
+
+        #[extrinsic_call]
+        set_position_operator(RawOrigin::Signed(caller), netuid, id, Some(operator));
+
+        assert!(PositionOperators::<T>::contains_key(netuid, id));
+    }
+
     impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
 }
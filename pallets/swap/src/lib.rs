@@ -1,8 +1,13 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
 use substrate_fixed::types::U64F64;
 use subtensor_swap_interface::OrderType;
 
+pub mod limit_order;
+pub mod migrations;
+pub mod oracle;
 pub mod pallet;
 pub mod position;
 pub mod tick;
@@ -0,0 +1,96 @@
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use frame_support::pallet_prelude::*;
+use subtensor_macros::freeze_struct;
+use subtensor_runtime_common::NetUid;
+
+use crate::pallet::{Config, LastLimitOrderId};
+use crate::tick::TickIndex;
+use crate::{OrderType, SqrtPrice};
+
+/// LimitOrder designates one resting order that is filled against the pool once the price
+/// crosses its tick.
+///
+/// Buy orders (TAO -> Alpha) rest below the current price and escrow TAO, sell orders
+/// (Alpha -> TAO) rest above the current price and escrow Alpha.
+#[freeze_struct("c63ebd0c14b0a0c5")]
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct LimitOrder<AccountId> {
+    /// Unique ID of the order
+    pub id: LimitOrderId,
+    /// The coldkey that placed the order and receives the proceeds
+    pub owner: AccountId,
+    /// The hotkey that the Alpha stake comes from (sell) or is credited to (buy)
+    pub hotkey: AccountId,
+    /// Network identifier
+    pub netuid: NetUid,
+    /// Direction of the order
+    pub order_type: OrderType,
+    /// Tick at which the order is triggered, also the limit price of the fill
+    pub tick: TickIndex,
+    /// Remaining unfilled input amount: TAO for buy orders, Alpha for sell orders
+    pub amount: u64,
+}
+
+impl<AccountId> LimitOrder<AccountId> {
+    /// Returns true if the order can be (at least partially) filled at the given sqrt price
+    pub fn is_triggered(&self, sqrt_price_curr: SqrtPrice) -> bool {
+        let limit_sqrt_price = self.tick.as_sqrt_price_bounded();
+        match self.order_type {
+            OrderType::Buy => sqrt_price_curr < limit_sqrt_price,
+            OrderType::Sell => sqrt_price_curr > limit_sqrt_price,
+        }
+    }
+
+    /// Returns the TAO value of the remaining amount: the amount itself for buy orders, the
+    /// Alpha amount at the order price for sell orders
+    pub fn tao_value(&self) -> u64 {
+        match self.order_type {
+            OrderType::Buy => self.amount,
+            OrderType::Sell => {
+                let sqrt_price = self.tick.as_sqrt_price_bounded();
+                sqrt_price
+                    .saturating_mul(sqrt_price)
+                    .saturating_mul(SqrtPrice::saturating_from_num(self.amount))
+                    .saturating_to_num()
+            }
+        }
+    }
+}
+
+#[freeze_struct("33c1142fe6ecfcef")]
+#[derive(
+    Clone,
+    Copy,
+    Decode,
+    DecodeWithMemTracking,
+    Default,
+    Encode,
+    Eq,
+    MaxEncodedLen,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+)]
+pub struct LimitOrderId(u128);
+
+impl LimitOrderId {
+    /// Create a new limit order ID
+    pub fn new<T: Config>() -> Self {
+        let new = LastLimitOrderId::<T>::get().saturating_add(1);
+        LastLimitOrderId::<T>::put(new);
+
+        Self(new)
+    }
+}
+
+impl From<u128> for LimitOrderId {
+    fn from(value: u128) -> Self {
+        Self(value)
+    }
+}
+
+impl From<LimitOrderId> for u128 {
+    fn from(value: LimitOrderId) -> Self {
+        value.0
+    }
+}
@@ -0,0 +1,55 @@
+use alloc::string::String;
+use frame_support::{BoundedVec, traits::Get, weights::Weight};
+use sp_std::vec::Vec;
+
+use crate::pallet::{AccountPositions, Config, HasMigrationRun, Pallet, Positions};
+
+/// Fills [`AccountPositions`] with the keys of the existing user positions. Positions above
+/// the `MaxPositionsPerAccount` limit of their coldkey are logged and left out of the index.
+pub fn migrate_account_positions<T: Config>() -> Weight {
+    let migration_name = BoundedVec::truncate_from(b"migrate_account_positions".to_vec());
+    let mut weight = T::DbWeight::get().reads(1);
+
+    if HasMigrationRun::<T>::get(&migration_name) {
+        log::info!(
+            "Migration '{:?}' has already run. Skipping.",
+            String::from_utf8_lossy(&migration_name)
+        );
+        return weight;
+    }
+
+    log::info!(
+        "Running migration '{}'",
+        String::from_utf8_lossy(&migration_name)
+    );
+
+    let protocol_account_id = Pallet::<T>::protocol_account_id();
+    let keys: Vec<_> = Positions::<T>::iter_keys().collect();
+    weight.saturating_accrue(T::DbWeight::get().reads(keys.len() as u64));
+
+    for (netuid, coldkey, position_id) in keys {
+        if coldkey == protocol_account_id {
+            continue;
+        }
+
+        let indexed = AccountPositions::<T>::try_mutate(&coldkey, |positions| {
+            positions.try_push((netuid, position_id))
+        });
+        if indexed.is_err() {
+            log::error!(
+                "migrate_account_positions: too many positions: coldkey={coldkey:?}, netuid={netuid:?}, position_id={position_id:?}"
+            );
+        }
+        weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+    }
+
+    HasMigrationRun::<T>::insert(&migration_name, true);
+    weight.saturating_accrue(T::DbWeight::get().writes(1));
+
+    log::info!(
+        "Migration '{:?}' completed successfully.",
+        String::from_utf8_lossy(&migration_name)
+    );
+
+    weight
+}
@@ -0,0 +1,2 @@
+mod migrate_account_positions;
+pub use migrate_account_positions::*;
@@ -6,7 +6,7 @@ use frame_support::construct_runtime;
 use frame_support::pallet_prelude::*;
 use frame_support::{
     PalletId, parameter_types,
-    traits::{ConstU32, Everything},
+    traits::{ConstU32, Everything, tokens::Preservation},
 };
 use frame_system::{self as system};
 use sp_core::H256;
@@ -79,8 +79,14 @@ parameter_types! {
     pub const SwapProtocolId: PalletId = PalletId(*b"ten/swap");
     pub const MaxFeeRate: u16 = 10000; // 15.26%
     pub const MaxPositions: u32 = 100;
+    pub const MaxPositionsPerAccount: u32 = 256;
     pub const MinimumLiquidity: u64 = 1_000;
     pub const MinimumReserves: NonZeroU64 = NonZeroU64::new(1).unwrap();
+    pub const MaxLimitOrders: u32 = 100;
+    pub const MaxLimitOrdersPerAccount: u32 = 4;
+    pub const MinLimitOrderAmount: u64 = 1_000;
+    pub const MaxPriceObservations: u32 = 100;
+    pub const MinStake: TaoCurrency = TaoCurrency::new(2_000_000);
 }
 
 // Mock implementor of SubnetInfo trait
@@ -180,6 +186,15 @@ impl BalanceOps<AccountId> for MockBalanceOps {
         Ok(tao)
     }
 
+    fn transfer_balance(
+        _from: &AccountId,
+        _to: &AccountId,
+        _tao: TaoCurrency,
+        _preservation: Preservation,
+    ) -> DispatchResult {
+        Ok(())
+    }
+
     fn increase_stake(
         _coldkey: &AccountId,
         _hotkey: &AccountId,
@@ -202,6 +217,28 @@ impl BalanceOps<AccountId> for MockBalanceOps {
     fn decrease_provided_tao_reserve(_netuid: NetUid, _tao: TaoCurrency) {}
     fn increase_provided_alpha_reserve(_netuid: NetUid, _alpha: AlphaCurrency) {}
     fn decrease_provided_alpha_reserve(_netuid: NetUid, _alpha: AlphaCurrency) {}
+
+    fn settle_buy_order_fill(
+        _escrow: &AccountId,
+        _coldkey: &AccountId,
+        _hotkey: &AccountId,
+        _netuid: NetUid,
+        _tao: TaoCurrency,
+        _tao_fee: TaoCurrency,
+        _alpha: AlphaCurrency,
+    ) -> DispatchResult {
+        Ok(())
+    }
+
+    fn settle_sell_order_fill(
+        _coldkey: &AccountId,
+        _netuid: NetUid,
+        _alpha: AlphaCurrency,
+        _alpha_fee: AlphaCurrency,
+        _tao: TaoCurrency,
+    ) -> DispatchResult {
+        Ok(())
+    }
 }
 
 impl crate::pallet::Config for Test {
@@ -211,8 +248,14 @@ impl crate::pallet::Config for Test {
     type ProtocolId = SwapProtocolId;
     type MaxFeeRate = MaxFeeRate;
     type MaxPositions = MaxPositions;
+    type MaxPositionsPerAccount = MaxPositionsPerAccount;
     type MinimumLiquidity = MinimumLiquidity;
     type MinimumReserve = MinimumReserves;
+    type MaxLimitOrders = MaxLimitOrders;
+    type MaxLimitOrdersPerAccount = MaxLimitOrdersPerAccount;
+    type MinLimitOrderAmount = MinLimitOrderAmount;
+    type MinStake = MinStake;
+    type MaxPriceObservations = MaxPriceObservations;
     type WeightInfo = ();
 }
 
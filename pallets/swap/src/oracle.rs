@@ -0,0 +1,33 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::pallet_prelude::*;
+use subtensor_macros::freeze_struct;
+
+/// Observation designates one entry of the per-subnet price oracle ring buffer.
+///
+/// The cumulative tick grows by the tick that was current during each block, so the difference
+/// between two observations divided by the number of blocks between them is the time-weighted
+/// average tick (geometric mean price) over that window.
+#[freeze_struct("e4146cac540ab3ee")]
+#[derive(
+    Clone, Copy, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct Observation {
+    /// Block at which the observation was written
+    pub block_number: u64,
+    /// Sum of the current tick over every block since the first observation
+    pub tick_cumulative: i64,
+}
+
+impl Observation {
+    /// Returns the observation extended to `block_number`, assuming `tick` was current for
+    /// every block since this observation was written.
+    pub fn advance(&self, block_number: u64, tick: i32) -> Self {
+        let elapsed = block_number.saturating_sub(self.block_number);
+        Self {
+            block_number,
+            tick_cumulative: self
+                .tick_cumulative
+                .saturating_add((tick as i64).saturating_mul(elapsed as i64)),
+        }
+    }
+}
@@ -2,10 +2,16 @@ use core::marker::PhantomData;
 use core::ops::Neg;
 
 use frame_support::storage::{TransactionOutcome, transactional};
-use frame_support::{ensure, pallet_prelude::DispatchError, traits::Get};
+use frame_support::{
+    dispatch::DispatchClass, ensure, pallet_prelude::DispatchError, traits::Get,
+    traits::tokens::Preservation, weights::Weight,
+};
+use pallet_subtensor_swap_runtime_api::{
+    LimitOrderInfo, LiquidityRange, PositionInfo, SimSwapRouteLeg, TickInfo,
+};
 use safe_math::*;
 use sp_arithmetic::helpers_128bit;
-use sp_runtime::{DispatchResult, Vec, traits::AccountIdConversion};
+use sp_runtime::{DispatchResult, SaturatedConversion, Vec, traits::AccountIdConversion};
 use substrate_fixed::types::{I64F64, U64F64, U96F32};
 use subtensor_runtime_common::{
     AlphaCurrency, BalanceOps, Currency, NetUid, SubnetInfo, TaoCurrency,
@@ -15,11 +21,16 @@ use subtensor_swap_interface::{SwapHandler, SwapResult};
 use super::pallet::*;
 use crate::{
     OrderType, SqrtPrice,
+    limit_order::{LimitOrder, LimitOrderId},
+    oracle::Observation,
     position::{Position, PositionId},
     tick::{ActiveTickIndexManager, Tick, TickIndex},
+    weights::WeightInfo,
 };
 
 const MAX_SWAP_ITERATIONS: u16 = 1000;
+const MAX_LIMIT_ORDER_FILLS: usize = 8;
+const MAX_LIQUIDITY_CURVE_RANGES: u32 = 1000;
 
 #[derive(Debug, PartialEq)]
 pub struct UpdateLiquidityResult {
@@ -437,6 +448,11 @@ impl<T: Config> Pallet<T> {
         simulate: bool,
     ) -> Result<SwapResult, DispatchError> {
         transactional::with_transaction(|| {
+            // Accumulate the price oracle before this swap moves the price
+            if !simulate {
+                Self::record_price_observation(netuid);
+            }
+
             // Read alpha and tao reserves before transaction
             let tao_reserve = T::SubnetInfo::tao_reserve(netuid.into());
             let alpha_reserve = T::SubnetInfo::alpha_reserve(netuid.into());
@@ -463,6 +479,20 @@ impl<T: Config> Pallet<T> {
                     }
                 }
 
+                // Fill the resting orders on the other side of the book crossed by this swap
+                if result.is_ok() {
+                    let crossed_order_type = match order_type {
+                        OrderType::Buy => OrderType::Sell,
+                        OrderType::Sell => OrderType::Buy,
+                    };
+                    let fills_weight =
+                        Self::fill_triggered_limit_orders(netuid, crossed_order_type);
+                    frame_system::Pallet::<T>::register_extra_weight_unchecked(
+                        fills_weight,
+                        DispatchClass::Normal,
+                    );
+                }
+
                 TransactionOutcome::Commit(result)
             }
         })
@@ -868,6 +898,7 @@ impl<T: Config> Pallet<T> {
             Error::<T>::InvalidLiquidityValue
         );
 
+        Self::index_account_position(coldkey_account_id, netuid, position_id)?;
         Positions::<T>::insert(&(netuid, coldkey_account_id, position.id), position);
 
         Ok((position_id, tao, alpha))
@@ -947,6 +978,8 @@ impl<T: Config> Pallet<T> {
 
         // Remove user position
         Positions::<T>::remove((netuid, coldkey_account_id, position_id));
+        PositionOperators::<T>::remove(netuid, position_id);
+        Self::unindex_account_position(coldkey_account_id, netuid, position_id);
 
         Ok(RemoveLiquidityResult {
             tao: tao.into(),
@@ -1068,6 +1101,8 @@ impl<T: Config> Pallet<T> {
         // Update or, in case if full liquidity is removed, remove the position
         if remove {
             Positions::<T>::remove((netuid, coldkey_account_id, position_id));
+            PositionOperators::<T>::remove(netuid, position_id);
+            Self::unindex_account_position(coldkey_account_id, netuid, position_id);
         } else {
             Positions::<T>::insert(&(netuid, coldkey_account_id, position.id), position.clone());
         }
@@ -1205,6 +1240,40 @@ impl<T: Config> Pallet<T> {
         Positions::<T>::iter_prefix_values((netuid, account_id.clone())).count()
     }
 
+    /// Adds a position to the [`AccountPositions`] index of its coldkey. The protocol account
+    /// is not indexed.
+    fn index_account_position(
+        account_id: &T::AccountId,
+        netuid: NetUid,
+        position_id: PositionId,
+    ) -> Result<(), Error<T>> {
+        if *account_id == Self::protocol_account_id() {
+            return Ok(());
+        }
+
+        AccountPositions::<T>::try_mutate(account_id, |positions| {
+            positions
+                .try_push((netuid, position_id))
+                .map_err(|_| Error::<T>::MaxPositionsExceeded)
+        })
+    }
+
+    /// Removes a position from the [`AccountPositions`] index of its coldkey.
+    fn unindex_account_position(
+        account_id: &T::AccountId,
+        netuid: NetUid,
+        position_id: PositionId,
+    ) {
+        AccountPositions::<T>::mutate_exists(account_id, |maybe_positions| {
+            if let Some(positions) = maybe_positions {
+                positions.retain(|key| *key != (netuid, position_id));
+                if positions.is_empty() {
+                    *maybe_positions = None;
+                }
+            }
+        });
+    }
+
     /// Returns the protocol account ID
     ///
     /// # Returns
@@ -1213,8 +1282,16 @@ impl<T: Config> Pallet<T> {
         T::ProtocolId::get().into_account_truncating()
     }
 
+    /// Returns the account holding the TAO escrowed by resting buy orders
+    pub fn limit_order_escrow_account_id() -> T::AccountId {
+        T::ProtocolId::get().into_sub_account_truncating(b"limit_orders")
+    }
+
     /// Dissolve all LPs and clean state.
     pub fn do_dissolve_all_liquidity_providers(netuid: NetUid) -> DispatchResult {
+        // Limit orders hold user funds in escrow no matter the swap version
+        Self::refund_all_limit_orders(netuid);
+
         if SwapV3Initialized::<T>::get(netuid) {
             // 1) Snapshot only *non‑protocol* positions: (owner, position_id).
             struct CloseItem<A> {
@@ -1385,8 +1462,21 @@ impl<T: Config> Pallet<T> {
             ActiveTickIndexManager::<T>::remove(netuid, ti);
         }
 
+        // Positions that could not be closed are dropped with the pool
+        let leftover_positions: sp_std::vec::Vec<(T::AccountId, PositionId)> =
+            Positions::<T>::iter_key_prefix((netuid,)).collect();
+        for (owner, pos_id) in leftover_positions {
+            Self::unindex_account_position(&owner, netuid, pos_id);
+        }
+
         let _ = Positions::<T>::clear_prefix((netuid,), u32::MAX, None);
+        let _ = PositionOperators::<T>::clear_prefix(netuid, u32::MAX, None);
         let _ = Ticks::<T>::clear_prefix(netuid, u32::MAX, None);
+        let _ = LimitOrders::<T>::clear_prefix((netuid,), u32::MAX, None);
+        let _ = LimitOrderBook::<T>::clear_prefix(netuid, u32::MAX, None);
+        let _ = PriceObservations::<T>::clear_prefix(netuid, u32::MAX, None);
+        PriceObservationHead::<T>::remove(netuid);
+        PriceObservationCount::<T>::remove(netuid);
 
         FeeGlobalTao::<T>::remove(netuid);
         FeeGlobalAlpha::<T>::remove(netuid);
@@ -1407,6 +1497,1029 @@ impl<T: Config> Pallet<T> {
     }
 }
 
+impl<T: Config> Pallet<T> {
+    /// Validates and stores a new resting limit order.
+    ///
+    /// Escrow of the input amount is the responsibility of the caller.
+    ///
+    /// ### Errors:
+    /// - [`Error::LimitOrdersUnsupported`] if the subnet does not use the dynamic mechanism.
+    /// - [`Error::LimitOrderTooSmall`] if the TAO value of the order is below
+    ///   `MinLimitOrderAmount`.
+    /// - [`Error::LimitOrderMarketable`] if the current price already crosses `tick`.
+    /// - [`Error::InsufficientBalance`] if the account can't fund the order.
+    /// - [`Error::LimitOrderBookFull`] if the subnet reached `MaxLimitOrders` on this side.
+    pub fn do_place_limit_order(
+        netuid: NetUid,
+        coldkey_account_id: &T::AccountId,
+        hotkey_account_id: &T::AccountId,
+        order_type: OrderType,
+        tick: TickIndex,
+        amount: u64,
+    ) -> Result<LimitOrderId, Error<T>> {
+        ensure!(
+            T::SubnetInfo::mechanism(netuid) == 1,
+            Error::<T>::LimitOrdersUnsupported
+        );
+
+        let mut order = LimitOrder {
+            id: LimitOrderId::default(),
+            owner: coldkey_account_id.clone(),
+            hotkey: hotkey_account_id.clone(),
+            netuid,
+            order_type,
+            tick,
+            amount,
+        };
+        ensure!(
+            order.tao_value() >= T::MinLimitOrderAmount::get(),
+            Error::<T>::LimitOrderTooSmall
+        );
+
+        Self::maybe_initialize_v3(netuid)?;
+
+        // Orders that can be filled right away belong to add_stake_limit / remove_stake_limit
+        ensure!(
+            !order.is_triggered(Self::current_price_sqrt(netuid)),
+            Error::<T>::LimitOrderMarketable
+        );
+
+        let has_balance = match order_type {
+            OrderType::Buy => {
+                T::BalanceOps::tao_balance(coldkey_account_id) >= TaoCurrency::from(amount)
+            }
+            OrderType::Sell => {
+                T::BalanceOps::alpha_balance(netuid, coldkey_account_id, hotkey_account_id)
+                    >= AlphaCurrency::from(amount)
+            }
+        };
+        ensure!(has_balance, Error::<T>::InsufficientBalance);
+        ensure!(
+            AccountLimitOrders::<T>::decode_len(coldkey_account_id).unwrap_or_default()
+                < T::MaxLimitOrdersPerAccount::get() as usize,
+            Error::<T>::TooManyLimitOrders
+        );
+
+        order.id = LimitOrderId::new::<T>();
+
+        LimitOrderBook::<T>::try_mutate(netuid, order_type, |book| {
+            let index = book.partition_point(|(book_tick, _)| *book_tick <= tick);
+            book.try_insert(index, (tick, order.id))
+                .map_err(|_| Error::<T>::LimitOrderBookFull)
+        })?;
+
+        LimitOrders::<T>::insert((netuid, tick, order.id), order.clone());
+        AccountLimitOrders::<T>::try_mutate(coldkey_account_id, |orders| {
+            orders
+                .try_push((netuid, tick, order.id))
+                .map_err(|_| Error::<T>::TooManyLimitOrders)
+        })?;
+
+        Ok(order.id)
+    }
+
+    /// Looks up a resting limit order by its ID on either side of the book.
+    pub fn find_limit_order(
+        netuid: NetUid,
+        order_id: LimitOrderId,
+    ) -> Option<LimitOrder<T::AccountId>> {
+        [OrderType::Buy, OrderType::Sell]
+            .into_iter()
+            .find_map(|order_type| {
+                LimitOrderBook::<T>::get(netuid, order_type)
+                    .into_iter()
+                    .find(|(_, id)| *id == order_id)
+            })
+            .and_then(|(tick, _)| LimitOrders::<T>::get((netuid, tick, order_id)))
+    }
+
+    /// Removes a limit order from storage and from the book. Does not refund the escrow.
+    pub(super) fn remove_limit_order(order: &LimitOrder<T::AccountId>) {
+        LimitOrders::<T>::remove((order.netuid, order.tick, order.id));
+        LimitOrderBook::<T>::mutate(order.netuid, order.order_type, |book| {
+            book.retain(|(_, id)| *id != order.id);
+        });
+        AccountLimitOrders::<T>::mutate_exists(&order.owner, |maybe_orders| {
+            if let Some(orders) = maybe_orders {
+                orders.retain(|(_, _, id)| *id != order.id);
+                if orders.is_empty() {
+                    *maybe_orders = None;
+                }
+            }
+        });
+    }
+
+    /// Returns the unfilled amount of an order to its owner: TAO from the escrow account to the
+    /// coldkey balance for buy orders, Alpha to the (coldkey, hotkey) stake for sell orders.
+    pub(super) fn refund_limit_order(order: &LimitOrder<T::AccountId>) -> DispatchResult {
+        if order.amount == 0 {
+            return Ok(());
+        }
+
+        match order.order_type {
+            OrderType::Buy => T::BalanceOps::transfer_balance(
+                &Self::limit_order_escrow_account_id(),
+                &order.owner,
+                order.amount.into(),
+                Preservation::Expendable,
+            )?,
+            OrderType::Sell => T::BalanceOps::increase_stake(
+                &order.owner,
+                &order.hotkey,
+                order.netuid,
+                order.amount.into(),
+            )?,
+        }
+
+        Ok(())
+    }
+
+    /// Cancels all resting limit orders on a subnet and refunds them to their owners.
+    ///
+    /// A failed refund doesn't stop the dissolution of the subnet, it is reported with
+    /// [`Event::LimitOrderRefundFailed`] instead.
+    fn refund_all_limit_orders(netuid: NetUid) {
+        let orders = LimitOrders::<T>::iter_prefix_values((netuid,)).collect::<Vec<_>>();
+
+        for order in orders {
+            Self::remove_limit_order(&order);
+            if let Err(error) = Self::refund_limit_order(&order) {
+                log::error!(
+                    "refund_all_limit_orders: refund failed: netuid={netuid:?}, order_id={:?}, err={error:?}",
+                    order.id
+                );
+                Self::deposit_event(Event::LimitOrderRefundFailed {
+                    coldkey: order.owner,
+                    hotkey: order.hotkey,
+                    netuid,
+                    order_id: order.id,
+                    amount: order.amount,
+                    error,
+                });
+            }
+        }
+    }
+
+    /// Fills the resting `order_type` orders whose tick was crossed by the price.
+    ///
+    /// Called by every swap for the side of the book it moves the price into. Orders are filled
+    /// best price first, each against the pool with the order tick as price limit, until the
+    /// next order is not crossed anymore or [`MAX_LIMIT_ORDER_FILLS`] orders were filled. Orders
+    /// left crossed are filled by the next swap in the same direction. A fill that fails leaves
+    /// the order resting until a swap crosses its tick again.
+    ///
+    /// Returns the weight of the fills, which the order owners paid upfront.
+    pub(crate) fn fill_triggered_limit_orders(netuid: NetUid, order_type: OrderType) -> Weight {
+        let mut weight = T::DbWeight::get().reads(1);
+        let book = LimitOrderBook::<T>::get(netuid, order_type);
+
+        // Highest bids and lowest asks first
+        let candidates: Vec<(TickIndex, LimitOrderId)> = match order_type {
+            OrderType::Buy => book.into_iter().rev().collect(),
+            OrderType::Sell => book.into_iter().collect(),
+        };
+
+        let mut fills: usize = 0;
+        for (tick, order_id) in candidates {
+            if fills >= MAX_LIMIT_ORDER_FILLS {
+                break;
+            }
+
+            weight.saturating_accrue(T::DbWeight::get().reads(1));
+            let Some(order) = LimitOrders::<T>::get((netuid, tick, order_id)) else {
+                LimitOrderBook::<T>::mutate(netuid, order_type, |book| {
+                    book.retain(|(_, id)| *id != order_id);
+                });
+                weight.saturating_accrue(T::DbWeight::get().writes(1));
+                continue;
+            };
+
+            // The book is sorted, so if this order is not crossed, none of the next ones are
+            if !order.is_triggered(Self::current_price_sqrt(netuid)) {
+                break;
+            }
+
+            fills = fills.saturating_add(1);
+            weight.saturating_accrue(T::WeightInfo::fill_limit_order());
+
+            if let Err(e) = Self::fill_limit_order(order) {
+                log::debug!(
+                    "fill_triggered_limit_orders: fill failed: netuid={netuid:?}, order_id={order_id:?}, err={e:?}"
+                );
+            }
+        }
+
+        weight
+    }
+
+    /// Swaps the remaining amount of a limit order against the pool and credits the proceeds
+    /// to the owner. Remainders worth less than `MinLimitOrderAmount` are refunded and the order
+    /// is closed.
+    fn fill_limit_order(mut order: LimitOrder<T::AccountId>) -> DispatchResult {
+        let netuid = order.netuid;
+        let limit_sqrt_price = order.tick.as_sqrt_price_bounded();
+
+        let swap_result = transactional::with_transaction(|| {
+            let result: Result<SwapResult, DispatchError> = Self::swap_inner(
+                netuid,
+                order.order_type,
+                order.amount,
+                limit_sqrt_price,
+                false,
+            )
+            .map_err(Into::into)
+            .and_then(|swap_result| {
+                Self::settle_limit_order_fill(&order, &swap_result)?;
+                Ok(swap_result)
+            });
+
+            if result.is_ok() {
+                TransactionOutcome::Commit(result)
+            } else {
+                TransactionOutcome::Rollback(result)
+            }
+        })?;
+
+        let amount_in = swap_result
+            .amount_paid_in
+            .saturating_add(swap_result.fee_paid);
+        if amount_in == 0 {
+            return Ok(());
+        }
+
+        order.amount = order.amount.saturating_sub(amount_in);
+        if order.tao_value() < T::MinLimitOrderAmount::get() {
+            Self::remove_limit_order(&order);
+            Self::refund_limit_order(&order)?;
+            order.amount = 0;
+        } else {
+            LimitOrders::<T>::insert((netuid, order.tick, order.id), order.clone());
+        }
+
+        Self::deposit_event(Event::LimitOrderFilled {
+            coldkey: order.owner,
+            hotkey: order.hotkey,
+            netuid,
+            order_id: order.id,
+            order_type: order.order_type,
+            amount_in,
+            amount_out: swap_result.amount_paid_out,
+            fee: swap_result.fee_paid,
+            remaining: order.amount,
+        });
+
+        Ok(())
+    }
+
+    /// Moves the swapped amounts of a limit order fill between the escrow, the reserves and the
+    /// owner accounts, with the same stake accounting as a swap by the owner.
+    fn settle_limit_order_fill(
+        order: &LimitOrder<T::AccountId>,
+        swap_result: &SwapResult,
+    ) -> DispatchResult {
+        let netuid = order.netuid;
+
+        // Check that reserves are not overused
+        let checked_reserve = match order.order_type {
+            OrderType::Buy => T::SubnetInfo::alpha_reserve(netuid).to_u64(),
+            OrderType::Sell => T::SubnetInfo::tao_reserve(netuid).to_u64(),
+        };
+        ensure!(
+            checked_reserve >= swap_result.amount_paid_out,
+            Error::<T>::InsufficientLiquidity
+        );
+
+        match order.order_type {
+            OrderType::Buy => T::BalanceOps::settle_buy_order_fill(
+                &Self::limit_order_escrow_account_id(),
+                &order.owner,
+                &order.hotkey,
+                netuid,
+                swap_result.amount_paid_in.into(),
+                swap_result.fee_paid.into(),
+                swap_result.amount_paid_out.into(),
+            ),
+            OrderType::Sell => T::BalanceOps::settle_sell_order_fill(
+                &order.owner,
+                netuid,
+                swap_result.amount_paid_in.into(),
+                swap_result.fee_paid.into(),
+                swap_result.amount_paid_out.into(),
+            ),
+        }
+    }
+
+    /// Returns the resting limit orders of a coldkey on a subnet.
+    pub fn get_limit_orders(
+        coldkey_account_id: &T::AccountId,
+        netuid: NetUid,
+    ) -> Vec<LimitOrderInfo<T::AccountId>> {
+        AccountLimitOrders::<T>::get(coldkey_account_id)
+            .into_iter()
+            .filter(|(order_netuid, _, _)| *order_netuid == netuid)
+            .filter_map(|(netuid, tick, order_id)| LimitOrders::<T>::get((netuid, tick, order_id)))
+            .map(|order| {
+                let sqrt_price = order.tick.as_sqrt_price_bounded();
+                LimitOrderInfo {
+                    order_id: order.id.into(),
+                    hotkey: order.hotkey,
+                    netuid,
+                    order_type: order.order_type,
+                    tick: order.tick.get(),
+                    price: sqrt_price
+                        .saturating_mul(sqrt_price)
+                        .saturating_mul(SqrtPrice::saturating_from_num(1_000_000_000))
+                        .saturating_to_num(),
+                    amount: order.amount,
+                }
+            })
+            .collect()
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Writes a price oracle observation for the current block, at most once per block.
+    ///
+    /// Must be called before the price moves in a block, so that the new observation accumulates
+    /// the tick that was current since the previous one.
+    pub fn record_price_observation(netuid: NetUid) {
+        let max_observations = T::MaxPriceObservations::get();
+        if max_observations == 0 {
+            return;
+        }
+
+        let block_number = Self::current_block_number();
+        let head = PriceObservationHead::<T>::get(netuid);
+        let count = PriceObservationCount::<T>::get(netuid);
+
+        let last = if count > 0 {
+            PriceObservations::<T>::get(netuid, head)
+        } else {
+            None
+        };
+
+        let (slot, observation) = match last {
+            Some(last) if last.block_number >= block_number => return,
+            Some(last) => (
+                head.saturating_add(1)
+                    .checked_rem(max_observations)
+                    .unwrap_or_default(),
+                last.advance(block_number, Self::oracle_tick(netuid)),
+            ),
+            None => (
+                0,
+                Observation {
+                    block_number,
+                    tick_cumulative: 0,
+                },
+            ),
+        };
+
+        PriceObservations::<T>::insert(netuid, slot, observation);
+        PriceObservationHead::<T>::insert(netuid, slot);
+        PriceObservationCount::<T>::insert(netuid, count.saturating_add(1).min(max_observations));
+    }
+
+    /// Returns the time-weighted average price (TAO per Alpha) over the last `window_blocks`
+    /// blocks.
+    ///
+    /// The average is taken over ticks, so this is the geometric mean of the price over the
+    /// window, which makes it expensive to move with short-lived price manipulation. Returns
+    /// `None` if `window_blocks` is zero or if the oracle has no observations reaching back
+    /// `window_blocks` blocks.
+    pub fn twap(netuid: NetUid, window_blocks: u64) -> Option<U96F32> {
+        if window_blocks == 0 {
+            return None;
+        }
+
+        let block_number = Self::current_block_number();
+        let target_block = block_number.checked_sub(window_blocks)?;
+
+        let cumulative_now = Self::tick_cumulative_at(netuid, block_number)?;
+        let cumulative_then = Self::tick_cumulative_at(netuid, target_block)?;
+
+        let average_tick = cumulative_now
+            .saturating_sub(cumulative_then)
+            .checked_div_euclid(i64::try_from(window_blocks).ok()?)?;
+        let sqrt_price = TickIndex::new(i32::try_from(average_tick).ok()?)
+            .ok()?
+            .as_sqrt_price_bounded();
+
+        Some(U96F32::saturating_from_num(
+            sqrt_price.saturating_mul(sqrt_price),
+        ))
+    }
+
+    /// Returns the worst case number of storage reads of [`Self::twap`].
+    pub fn twap_max_reads() -> u64 {
+        // Head, count, newest and oldest observations and the current tick (up to 3 reads) for
+        // each end of the window, plus the binary search between the stored observations
+        let search_reads = T::MaxPriceObservations::get()
+            .checked_ilog2()
+            .unwrap_or_default()
+            .saturating_add(1);
+
+        u64::from(search_reads).saturating_add(7).saturating_mul(2)
+    }
+
+    /// Returns the cumulative tick of a subnet at `block_number`, interpolated between the
+    /// stored observations, or `None` if the block is older than the oldest observation.
+    fn tick_cumulative_at(netuid: NetUid, block_number: u64) -> Option<i64> {
+        let head = PriceObservationHead::<T>::get(netuid);
+        let count = PriceObservationCount::<T>::get(netuid);
+        if count == 0 {
+            return None;
+        }
+
+        let newest = PriceObservations::<T>::get(netuid, head)?;
+        if newest.block_number <= block_number {
+            let tick = Self::oracle_tick(netuid);
+            return Some(newest.advance(block_number, tick).tick_cumulative);
+        }
+
+        let oldest_age = count.saturating_sub(1);
+        let oldest = Self::price_observation_by_age(netuid, head, oldest_age)?;
+        if oldest.block_number > block_number {
+            return None;
+        }
+
+        // Binary search for the two observations around `block_number`. Observations get
+        // older as the age grows: `after` is always newer than the block, `before` is not.
+        let (mut age_after, mut after) = (0, newest);
+        let (mut age_before, mut before) = (oldest_age, oldest);
+        while age_before.saturating_sub(age_after) > 1 {
+            let age =
+                age_after.saturating_add(age_before.saturating_sub(age_after).saturating_div(2));
+            let observation = Self::price_observation_by_age(netuid, head, age)?;
+            if observation.block_number > block_number {
+                (age_after, after) = (age, observation);
+            } else {
+                (age_before, before) = (age, observation);
+            }
+        }
+
+        // The tick is constant between two consecutive observations
+        let span = after.block_number.saturating_sub(before.block_number);
+        let tick = after
+            .tick_cumulative
+            .saturating_sub(before.tick_cumulative)
+            .checked_div(i64::try_from(span).ok()?)?;
+
+        Some(
+            before
+                .advance(block_number, i32::try_from(tick).ok()?)
+                .tick_cumulative,
+        )
+    }
+
+    /// Returns the observation written `age` observations before the most recent one.
+    fn price_observation_by_age(netuid: NetUid, head: u32, age: u32) -> Option<Observation> {
+        let slot = head
+            .saturating_add(T::MaxPriceObservations::get())
+            .saturating_sub(age)
+            .checked_rem(T::MaxPriceObservations::get())?;
+
+        PriceObservations::<T>::get(netuid, slot)
+    }
+
+    /// Returns the tick matching the current price of a subnet.
+    fn oracle_tick(netuid: NetUid) -> i32 {
+        let sqrt_price = if SwapV3Initialized::<T>::get(netuid) {
+            AlphaSqrtPrice::<T>::get(netuid)
+        } else {
+            SqrtPrice::saturating_from_num(Self::current_price(netuid))
+                .checked_sqrt(SqrtPrice::saturating_from_num(0.0000000001))
+                .unwrap_or_default()
+        };
+
+        TickIndex::from_sqrt_price_bounded(sqrt_price).get()
+    }
+
+    fn current_block_number() -> u64 {
+        frame_system::Pallet::<T>::block_number().saturated_into()
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Returns the initialized ticks with prices between `price_low` and `price_high` (in rao per
+    /// 10^9 Alpha units), sorted by tick.
+    pub fn get_ticks(netuid: NetUid, price_low: u64, price_high: u64) -> Vec<TickInfo> {
+        let tick_low = Self::tick_at_price_rao(price_low);
+        let tick_high = Self::tick_at_price_rao(price_high);
+
+        let mut ticks = Vec::new();
+        let mut next = ActiveTickIndexManager::<T>::find_closest_higher(netuid, tick_low);
+        while let Some(index) = next {
+            if index > tick_high {
+                break;
+            }
+
+            if let Some(tick) = Ticks::<T>::get(netuid, index) {
+                ticks.push(TickInfo {
+                    tick: index.get(),
+                    price: Self::tick_price_rao(index),
+                    liquidity_net: tick.liquidity_net,
+                    liquidity_gross: tick.liquidity_gross,
+                });
+            }
+
+            next = index
+                .next()
+                .ok()
+                .and_then(|index| ActiveTickIndexManager::<T>::find_closest_higher(netuid, index));
+        }
+
+        ticks
+    }
+
+    /// Returns the active liquidity between consecutive initialized ticks: the range that
+    /// contains the current tick and up to `ranges_each_side` ranges below and above it, sorted
+    /// by tick.
+    pub fn get_liquidity_curve(netuid: NetUid, ranges_each_side: u32) -> Vec<LiquidityRange> {
+        if !SwapV3Initialized::<T>::get(netuid) {
+            return Vec::new();
+        }
+
+        let limit = ranges_each_side.min(MAX_LIQUIDITY_CURVE_RANGES);
+        let current_tick = TickIndex::current_bounded::<T>(netuid);
+        let current_liquidity = i128::from(CurrentLiquidity::<T>::get(netuid));
+
+        let current_low = ActiveTickIndexManager::<T>::find_closest_lower(netuid, current_tick)
+            .unwrap_or(TickIndex::MIN);
+        let current_high = current_tick
+            .next()
+            .ok()
+            .and_then(|index| ActiveTickIndexManager::<T>::find_closest_higher(netuid, index))
+            .unwrap_or(TickIndex::MAX);
+
+        // Walk down from the current range, crossing a tick downwards removes its net liquidity
+        let mut below = Vec::new();
+        let mut liquidity = current_liquidity;
+        let mut tick_high = current_low;
+        for _ in 0..limit {
+            if tick_high <= TickIndex::MIN {
+                break;
+            }
+
+            liquidity = liquidity.saturating_sub(Self::tick_liquidity_net(netuid, tick_high));
+            let tick_low = tick_high
+                .prev()
+                .ok()
+                .and_then(|index| ActiveTickIndexManager::<T>::find_closest_lower(netuid, index))
+                .unwrap_or(TickIndex::MIN);
+            below.push(Self::liquidity_range(tick_low, tick_high, liquidity));
+            tick_high = tick_low;
+        }
+
+        let mut curve: Vec<LiquidityRange> = below.into_iter().rev().collect();
+        curve.push(Self::liquidity_range(
+            current_low,
+            current_high,
+            current_liquidity,
+        ));
+
+        // Walk up from the current range, crossing a tick upwards adds its net liquidity
+        let mut liquidity = current_liquidity;
+        let mut tick_low = current_high;
+        for _ in 0..limit {
+            if tick_low >= TickIndex::MAX {
+                break;
+            }
+
+            liquidity = liquidity.saturating_add(Self::tick_liquidity_net(netuid, tick_low));
+            let tick_high = tick_low
+                .next()
+                .ok()
+                .and_then(|index| ActiveTickIndexManager::<T>::find_closest_higher(netuid, index))
+                .unwrap_or(TickIndex::MAX);
+            curve.push(Self::liquidity_range(tick_low, tick_high, liquidity));
+            tick_low = tick_high;
+        }
+
+        curve
+    }
+
+    /// Returns all liquidity positions of a coldkey with the token amounts they would withdraw at
+    /// the current price and their uncollected fees.
+    pub fn get_positions(coldkey_account_id: &T::AccountId) -> Vec<PositionInfo<T::AccountId>> {
+        SwapV3Initialized::<T>::iter_keys()
+            .flat_map(|netuid| {
+                let sqrt_price = AlphaSqrtPrice::<T>::get(netuid);
+                Positions::<T>::iter_prefix_values((netuid, coldkey_account_id.clone())).map(
+                    move |position| {
+                        let (tao, alpha) =
+                            position.to_token_amounts(sqrt_price).unwrap_or_default();
+                        let (fee_tao, fee_alpha) = position.uncollected_fees();
+
+                        PositionInfo {
+                            position_id: position.id.into(),
+                            netuid,
+                            tick_low: position.tick_low.get(),
+                            tick_high: position.tick_high.get(),
+                            price_low: Self::tick_price_rao(position.tick_low),
+                            price_high: Self::tick_price_rao(position.tick_high),
+                            liquidity: position.liquidity,
+                            tao: tao.into(),
+                            alpha: alpha.into(),
+                            fee_tao: fee_tao.into(),
+                            fee_alpha: fee_alpha.into(),
+                            operator: PositionOperators::<T>::get(netuid, position.id)
+                                .map(|(_, operator)| operator),
+                        }
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn liquidity_range(
+        tick_low: TickIndex,
+        tick_high: TickIndex,
+        liquidity: i128,
+    ) -> LiquidityRange {
+        LiquidityRange {
+            tick_low: tick_low.get(),
+            tick_high: tick_high.get(),
+            price_low: Self::tick_price_rao(tick_low),
+            price_high: Self::tick_price_rao(tick_high),
+            liquidity: u64::try_from(liquidity.max(0)).unwrap_or(u64::MAX),
+        }
+    }
+
+    fn tick_liquidity_net(netuid: NetUid, index: TickIndex) -> i128 {
+        Ticks::<T>::get(netuid, index)
+            .map(|tick| tick.liquidity_net)
+            .unwrap_or_default()
+    }
+
+    /// Price at the tick in rao per 10^9 Alpha units
+    fn tick_price_rao(index: TickIndex) -> u64 {
+        let sqrt_price = index.as_sqrt_price_bounded();
+        sqrt_price
+            .saturating_mul(sqrt_price)
+            .saturating_mul(SqrtPrice::saturating_from_num(1_000_000_000))
+            .saturating_to_num()
+    }
+
+    /// Tick of the price given in rao per 10^9 Alpha units
+    fn tick_at_price_rao(price: u64) -> TickIndex {
+        let price = SqrtPrice::saturating_from_num(price)
+            .safe_div(SqrtPrice::saturating_from_num(1_000_000_000));
+        let epsilon = SqrtPrice::saturating_from_num(0.000000000001);
+        let sqrt_price = price
+            .checked_sqrt(epsilon)
+            .unwrap_or(SqrtPrice::saturating_from_num(0));
+
+        TickIndex::from_sqrt_price_bounded(sqrt_price)
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Returns the owner of the position on whose behalf `who` acts: `who` itself if it owns the
+    /// position, otherwise the owner that approved `who` as operator of the position.
+    pub fn position_owner(
+        netuid: NetUid,
+        who: &T::AccountId,
+        position_id: PositionId,
+    ) -> Result<T::AccountId, Error<T>> {
+        if Positions::<T>::contains_key((netuid, who, position_id)) {
+            return Ok(who.clone());
+        }
+
+        match PositionOperators::<T>::get(netuid, position_id) {
+            Some((owner, operator))
+                if operator == *who
+                    && Positions::<T>::contains_key((netuid, &owner, position_id)) =>
+            {
+                Ok(owner)
+            }
+            _ => Err(Error::<T>::LiquidityNotFound),
+        }
+    }
+
+    /// Moves a position to `new_coldkey_account_id` and revokes its operator approval.
+    ///
+    /// ### Errors:
+    /// - [`Error::LiquidityNotFound`] if the position is not owned by `coldkey_account_id`.
+    /// - [`Error::InvalidPositionRecipient`] if the recipient is the current owner or the
+    ///   protocol account.
+    /// - [`Error::MaxPositionsExceeded`] if the recipient has no free position slots.
+    pub fn do_transfer_position(
+        netuid: NetUid,
+        coldkey_account_id: &T::AccountId,
+        new_coldkey_account_id: &T::AccountId,
+        position_id: PositionId,
+    ) -> Result<(), Error<T>> {
+        let Some(position) = Positions::<T>::get((netuid, coldkey_account_id, position_id)) else {
+            return Err(Error::<T>::LiquidityNotFound);
+        };
+
+        ensure!(
+            coldkey_account_id != new_coldkey_account_id
+                && *new_coldkey_account_id != Self::protocol_account_id(),
+            Error::<T>::InvalidPositionRecipient
+        );
+        ensure!(
+            Self::count_positions(netuid, new_coldkey_account_id) < T::MaxPositions::get() as usize,
+            Error::<T>::MaxPositionsExceeded
+        );
+        Self::index_account_position(new_coldkey_account_id, netuid, position_id)?;
+        Self::unindex_account_position(coldkey_account_id, netuid, position_id);
+
+        Positions::<T>::remove((netuid, coldkey_account_id, position_id));
+        Positions::<T>::insert(&(netuid, new_coldkey_account_id, position_id), position);
+        PositionOperators::<T>::remove(netuid, position_id);
+
+        Ok(())
+    }
+
+    /// Ensures that the positions of `old_coldkey` fit under the `MaxPositionsPerAccount`
+    /// limit of `new_coldkey`, so that [`Self::do_swap_coldkey_positions`] can move all of them.
+    pub fn ensure_can_swap_coldkey_positions(
+        old_coldkey: &T::AccountId,
+        new_coldkey: &T::AccountId,
+    ) -> Result<(), Error<T>> {
+        let positions = AccountPositions::<T>::decode_len(old_coldkey).unwrap_or_default();
+        let new_positions = AccountPositions::<T>::decode_len(new_coldkey).unwrap_or_default();
+        ensure!(
+            positions.saturating_add(new_positions) <= T::MaxPositionsPerAccount::get() as usize,
+            Error::<T>::MaxPositionsExceeded
+        );
+
+        Ok(())
+    }
+
+    /// Moves all positions and resting limit orders of `old_coldkey` on every subnet to
+    /// `new_coldkey`. Operator approvals are kept and follow the positions.
+    ///
+    /// The caller checks [`Self::ensure_can_swap_coldkey_positions`] first. The per subnet
+    /// `MaxPositions` limit is not enforced for the new coldkey. Limit orders that don't fit
+    /// under the `MaxLimitOrdersPerAccount` limit of the new coldkey are cancelled and refunded
+    /// to it.
+    pub fn do_swap_coldkey_positions(
+        old_coldkey: &T::AccountId,
+        new_coldkey: &T::AccountId,
+    ) -> Weight {
+        let mut weight = Self::do_swap_coldkey_limit_orders(old_coldkey, new_coldkey);
+
+        let position_keys = AccountPositions::<T>::take(old_coldkey);
+        weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 2));
+
+        for (netuid, position_id) in position_keys {
+            let Some(position) = Positions::<T>::take((netuid, old_coldkey, position_id)) else {
+                continue;
+            };
+
+            let moved = AccountPositions::<T>::try_mutate(new_coldkey, |positions| {
+                positions.try_push((netuid, position_id))
+            });
+            if moved.is_err() {
+                // Unreachable after `ensure_can_swap_coldkey_positions`, keep the position
+                log::error!(
+                    "swap_coldkey_positions: index full: netuid={netuid:?}, position_id={position_id:?}"
+                );
+            }
+            Positions::<T>::insert(&(netuid, new_coldkey, position_id), position);
+
+            if let Some((_, operator)) = PositionOperators::<T>::get(netuid, position_id) {
+                PositionOperators::<T>::insert(
+                    netuid,
+                    position_id,
+                    (new_coldkey.clone(), operator),
+                );
+            }
+            weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 3));
+
+            Self::deposit_event(Event::PositionTransferred {
+                netuid,
+                position_id,
+                from: old_coldkey.clone(),
+                to: new_coldkey.clone(),
+            });
+        }
+
+        weight
+    }
+
+    /// Moves the resting limit orders of `old_coldkey` to `new_coldkey`. The book only holds
+    /// order keys, so it is unchanged.
+    fn do_swap_coldkey_limit_orders(
+        old_coldkey: &T::AccountId,
+        new_coldkey: &T::AccountId,
+    ) -> Weight {
+        let order_keys = AccountLimitOrders::<T>::take(old_coldkey);
+        let mut weight = T::DbWeight::get().reads_writes(2, 2);
+
+        for (netuid, tick, order_id) in order_keys {
+            let Some(mut order) = LimitOrders::<T>::get((netuid, tick, order_id)) else {
+                continue;
+            };
+            order.owner = new_coldkey.clone();
+
+            let moved = AccountLimitOrders::<T>::try_mutate(new_coldkey, |orders| {
+                orders.try_push((netuid, tick, order_id))
+            });
+            if moved.is_ok() {
+                LimitOrders::<T>::insert((netuid, tick, order_id), order);
+                Self::deposit_event(Event::LimitOrderTransferred {
+                    netuid,
+                    order_id,
+                    from: old_coldkey.clone(),
+                    to: new_coldkey.clone(),
+                });
+            } else {
+                Self::remove_limit_order(&order);
+                match Self::refund_limit_order(&order) {
+                    Ok(()) => Self::deposit_event(Event::LimitOrderCancelled {
+                        coldkey: order.owner,
+                        hotkey: order.hotkey,
+                        netuid,
+                        order_id,
+                        refund: order.amount,
+                    }),
+                    Err(error) => {
+                        log::error!(
+                            "swap_coldkey_limit_orders: refund failed: netuid={netuid:?}, order_id={order_id:?}, err={error:?}"
+                        );
+                        Self::deposit_event(Event::LimitOrderRefundFailed {
+                            coldkey: order.owner,
+                            hotkey: order.hotkey,
+                            netuid,
+                            order_id,
+                            amount: order.amount,
+                            error,
+                        });
+                    }
+                }
+            }
+            // Moving an order is cheaper than cancelling it
+            weight.saturating_accrue(T::WeightInfo::cancel_limit_order());
+        }
+
+        weight
+    }
+
+    /// Returns the worst case weight of moving the positions and limit orders of a coldkey in
+    /// [`Self::do_swap_coldkey_positions`].
+    pub fn swap_coldkey_positions_weight() -> Weight {
+        T::DbWeight::get()
+            .reads_writes(4, 4)
+            .saturating_add(
+                T::WeightInfo::cancel_limit_order()
+                    .saturating_mul(T::MaxLimitOrdersPerAccount::get().into()),
+            )
+            .saturating_add(
+                T::DbWeight::get()
+                    .reads_writes(2, 3)
+                    .saturating_mul(T::MaxPositionsPerAccount::get().into()),
+            )
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Simulates swapping `alpha_amount` of the `(coldkey, hotkey)` stake along `route`, moving
+    /// the full output of each leg `route[i] -> route[i + 1]` into the next one.
+    ///
+    /// Every leg is executed with the same swaps that a stake swap runs (sell on the origin
+    /// subnet, buy on the destination subnet, fees charged on the origin unless it is root) and
+    /// sees the pool state left by the previous legs, so the quote matches the execution of the
+    /// route in the same block. The amount is capped at the stake available on the origin
+    /// subnet and legs moving less than `MinStake` TAO fail, as in the stake swap. All changes
+    /// are rolled back.
+    pub fn sim_swap_route(
+        coldkey: &T::AccountId,
+        hotkey: &T::AccountId,
+        route: &[NetUid],
+        alpha_amount: u64,
+    ) -> Result<Vec<SimSwapRouteLeg>, DispatchError> {
+        let origin_netuid = route.first().ok_or(Error::<T>::InvalidSwapRoute)?;
+        let alpha_available = T::BalanceOps::alpha_balance(*origin_netuid, coldkey, hotkey);
+        let alpha_amount = alpha_amount.min(alpha_available.to_u64());
+
+        transactional::with_transaction(|| {
+            TransactionOutcome::Rollback(Self::swap_route_legs(route, alpha_amount))
+        })
+    }
+
+    fn swap_route_legs(
+        route: &[NetUid],
+        alpha_amount: u64,
+    ) -> Result<Vec<SimSwapRouteLeg>, DispatchError> {
+        ensure!(route.len() >= 2, Error::<T>::InvalidSwapRoute);
+
+        let mut legs = Vec::new();
+        let mut leg_amount = alpha_amount;
+
+        for leg in route.windows(2) {
+            let [origin_netuid, destination_netuid] = *leg else {
+                continue;
+            };
+
+            let drop_fee_origin = origin_netuid.is_root();
+            let sell =
+                Self::swap_route_step(origin_netuid, OrderType::Sell, leg_amount, drop_fee_origin)?;
+
+            // The stake swap doesn't move less than the minimum stake to the next subnet
+            ensure!(
+                TaoCurrency::from(sell.amount_paid_out) > T::MinStake::get(),
+                Error::<T>::InsufficientInputAmount
+            );
+
+            let buy = Self::swap_route_step(
+                destination_netuid,
+                OrderType::Buy,
+                sell.amount_paid_out,
+                !drop_fee_origin,
+            )?;
+            ensure!(buy.amount_paid_out > 0, Error::<T>::InsufficientInputAmount);
+
+            legs.push(SimSwapRouteLeg {
+                origin_netuid,
+                destination_netuid,
+                alpha_in: sell.amount_paid_in.saturating_add(sell.fee_paid).into(),
+                tao_amount: sell.amount_paid_out.into(),
+                alpha_out: buy.amount_paid_out.into(),
+                alpha_fee: sell.fee_paid.into(),
+                tao_fee: buy.fee_paid.into(),
+                origin_price: Self::current_price(origin_netuid)
+                    .saturating_mul(U96F32::saturating_from_num(1_000_000_000))
+                    .saturating_to_num(),
+                destination_price: Self::current_price(destination_netuid)
+                    .saturating_mul(U96F32::saturating_from_num(1_000_000_000))
+                    .saturating_to_num(),
+            });
+
+            leg_amount = buy.amount_paid_out;
+        }
+
+        Ok(legs)
+    }
+
+    /// Executes one side of a route leg and applies the reserve changes the same way the stake
+    /// swap does, so the next legs see the updated reserves.
+    fn swap_route_step(
+        netuid: NetUid,
+        order_type: OrderType,
+        amount: u64,
+        drop_fees: bool,
+    ) -> Result<SwapResult, DispatchError> {
+        ensure!(
+            T::SubnetInfo::exists(netuid),
+            Error::<T>::MechanismDoesNotExist
+        );
+
+        let swap_result = match T::SubnetInfo::mechanism(netuid) {
+            1 => {
+                let price_limit = match order_type {
+                    OrderType::Buy => <Self as SwapHandler<T::AccountId>>::max_price(),
+                    OrderType::Sell => <Self as SwapHandler<T::AccountId>>::min_price(),
+                };
+
+                <Self as SwapHandler<T::AccountId>>::swap(
+                    netuid,
+                    order_type,
+                    amount,
+                    price_limit,
+                    drop_fees,
+                    false,
+                )?
+            }
+            _ => {
+                let delta = amount as i64;
+                SwapResult {
+                    amount_paid_in: amount,
+                    amount_paid_out: amount,
+                    fee_paid: 0,
+                    tao_reserve_delta: match order_type {
+                        OrderType::Buy => delta,
+                        OrderType::Sell => delta.neg(),
+                    },
+                    alpha_reserve_delta: match order_type {
+                        OrderType::Buy => delta.neg(),
+                        OrderType::Sell => delta,
+                    },
+                }
+            }
+        };
+
+        let tao_delta = TaoCurrency::from(swap_result.tao_reserve_delta.unsigned_abs());
+        if swap_result.tao_reserve_delta >= 0 {
+            T::BalanceOps::increase_provided_tao_reserve(netuid, tao_delta);
+        } else {
+            T::BalanceOps::decrease_provided_tao_reserve(netuid, tao_delta);
+        }
+
+        let alpha_delta = AlphaCurrency::from(swap_result.alpha_reserve_delta.unsigned_abs());
+        if swap_result.alpha_reserve_delta >= 0 {
+            T::BalanceOps::increase_provided_alpha_reserve(netuid, alpha_delta);
+        } else {
+            T::BalanceOps::decrease_provided_alpha_reserve(netuid, alpha_delta);
+        }
+
+        Ok(swap_result)
+    }
+}
+
 impl<T: Config> SwapHandler<T::AccountId> for Pallet<T> {
     fn swap(
         netuid: NetUid,
@@ -1506,6 +2619,21 @@ impl<T: Config> SwapHandler<T::AccountId> for Pallet<T> {
     fn clear_protocol_liquidity(netuid: NetUid) -> DispatchResult {
         Self::do_clear_protocol_liquidity(netuid)
     }
+    fn record_price_observation(netuid: NetUid) {
+        Self::record_price_observation(netuid)
+    }
+    fn swap_coldkey_positions(old_coldkey: &T::AccountId, new_coldkey: &T::AccountId) -> Weight {
+        Self::do_swap_coldkey_positions(old_coldkey, new_coldkey)
+    }
+    fn ensure_can_swap_coldkey_positions(
+        old_coldkey: &T::AccountId,
+        new_coldkey: &T::AccountId,
+    ) -> DispatchResult {
+        Self::ensure_can_swap_coldkey_positions(old_coldkey, new_coldkey).map_err(Into::into)
+    }
+    fn swap_coldkey_positions_weight() -> Weight {
+        Self::swap_coldkey_positions_weight()
+    }
 }
 
 #[derive(Debug, PartialEq)]
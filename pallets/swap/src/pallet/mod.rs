@@ -1,7 +1,11 @@
 use core::num::NonZeroU64;
 use core::ops::Neg;
 
-use frame_support::{PalletId, pallet_prelude::*, traits::Get};
+use frame_support::{
+    PalletId,
+    pallet_prelude::*,
+    traits::{Get, tokens::Preservation},
+};
 use frame_system::pallet_prelude::*;
 use substrate_fixed::types::U64F64;
 use subtensor_runtime_common::{
@@ -9,6 +13,10 @@ use subtensor_runtime_common::{
 };
 
 use crate::{
+    OrderType,
+    limit_order::{LimitOrder, LimitOrderId},
+    migrations,
+    oracle::Observation,
     position::{Position, PositionId},
     tick::{LayerLevel, Tick, TickIndex},
     weights::WeightInfo,
@@ -16,6 +24,9 @@ use crate::{
 
 pub use pallet::*;
 
+// Define a maximum length for the migration key
+type MigrationKeyMaxLen = ConstU32<128>;
+
 mod impls;
 #[cfg(test)]
 mod tests;
@@ -55,6 +66,10 @@ mod pallet {
         #[pallet::constant]
         type MaxPositions: Get<u32>;
 
+        /// The maximum number of positions a coldkey can have across all subnets
+        #[pallet::constant]
+        type MaxPositionsPerAccount: Get<u32>;
+
         /// Minimum liquidity that is safe for rounding and integer math.
         #[pallet::constant]
         type MinimumLiquidity: Get<u64>;
@@ -63,6 +78,29 @@ mod pallet {
         #[pallet::constant]
         type MinimumReserve: Get<NonZeroU64>;
 
+        /// The maximum number of resting limit orders per subnet and order side
+        #[pallet::constant]
+        type MaxLimitOrders: Get<u32>;
+
+        /// The maximum number of resting limit orders a coldkey can have across all subnets
+        #[pallet::constant]
+        type MaxLimitOrdersPerAccount: Get<u32>;
+
+        /// Minimum TAO value of a limit order: the TAO input of buy orders, the Alpha input of
+        /// sell orders at the order price. Keeps the bounded books from being filled with dust.
+        #[pallet::constant]
+        type MinLimitOrderAmount: Get<u64>;
+
+        /// The minimum TAO amount that a stake swap moves between subnets. Route quotes fail on
+        /// legs below it, like the stake swap does.
+        #[pallet::constant]
+        type MinStake: Get<TaoCurrency>;
+
+        /// The number of price observations kept per subnet for the TWAP oracle. Bounds the
+        /// longest window (in blocks) that can be queried.
+        #[pallet::constant]
+        type MaxPriceObservations: Get<u32>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -125,6 +163,30 @@ mod pallet {
         OptionQuery,
     >;
 
+    /// Keys of the liquidity positions of a coldkey, across all subnets. The protocol positions
+    /// are not indexed.
+    #[pallet::storage]
+    pub type AccountPositions<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        BoundedVec<(NetUid, PositionId), T::MaxPositionsPerAccount>,
+        ValueQuery,
+    >;
+
+    /// Operators approved to modify a position on behalf of its owner, using subnet ID and
+    /// position ID as keys. The value is the (owner, operator) pair.
+    #[pallet::storage]
+    pub type PositionOperators<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        NetUid,
+        Twox64Concat,
+        PositionId,
+        (T::AccountId, T::AccountId),
+        OptionQuery,
+    >;
+
     /// Position ID counter.
     #[pallet::storage]
     pub type LastPositionId<T> = StorageValue<_, u128, ValueQuery>;
@@ -142,6 +204,64 @@ mod pallet {
         ValueQuery,
     >;
 
+    /// Resting limit orders, using subnet ID, trigger tick and order ID as keys
+    #[pallet::storage]
+    pub type LimitOrders<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Twox64Concat, NetUid>,       // Subnet ID
+            NMapKey<Twox64Concat, TickIndex>,    // Trigger tick
+            NMapKey<Twox64Concat, LimitOrderId>, // Order ID
+        ),
+        LimitOrder<T::AccountId>,
+        OptionQuery,
+    >;
+
+    /// Trigger ticks of resting limit orders per subnet and order side, sorted by tick in
+    /// ascending order. Used to find the orders crossed by a swap without iterating storage.
+    #[pallet::storage]
+    pub type LimitOrderBook<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        NetUid,
+        Twox64Concat,
+        OrderType,
+        BoundedVec<(TickIndex, LimitOrderId), T::MaxLimitOrders>,
+        ValueQuery,
+    >;
+
+    /// Limit order ID counter.
+    #[pallet::storage]
+    pub type LastLimitOrderId<T> = StorageValue<_, u128, ValueQuery>;
+
+    /// Keys of the resting limit orders of a coldkey, across all subnets
+    #[pallet::storage]
+    pub type AccountLimitOrders<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        BoundedVec<(NetUid, TickIndex, LimitOrderId), T::MaxLimitOrdersPerAccount>,
+        ValueQuery,
+    >;
+
+    /// Ring buffer of price oracle observations, using subnet ID and buffer slot as keys
+    #[pallet::storage]
+    pub type PriceObservations<T> =
+        StorageDoubleMap<_, Twox64Concat, NetUid, Twox64Concat, u32, Observation, OptionQuery>;
+
+    /// Buffer slot of the most recent price observation per subnet
+    #[pallet::storage]
+    pub type PriceObservationHead<T> = StorageMap<_, Twox64Concat, NetUid, u32, ValueQuery>;
+
+    /// Number of price observations written per subnet, capped at `MaxPriceObservations`
+    #[pallet::storage]
+    pub type PriceObservationCount<T> = StorageMap<_, Twox64Concat, NetUid, u32, ValueQuery>;
+
+    /// Storage for the migration run status.
+    #[pallet::storage]
+    pub type HasMigrationRun<T: Config> =
+        StorageMap<_, Identity, BoundedVec<u8, MigrationKeyMaxLen>, bool, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -226,6 +346,114 @@ mod pallet {
             /// the upper tick
             tick_high: TickIndex,
         },
+
+        /// Event emitted when a liquidity position is transferred to another coldkey, either
+        /// explicitly or as part of a coldkey swap.
+        PositionTransferred {
+            /// The subnet identifier
+            netuid: NetUid,
+            /// Unique identifier for the liquidity position
+            position_id: PositionId,
+            /// The coldkey account that owned the position
+            from: T::AccountId,
+            /// The coldkey account that owns the position now
+            to: T::AccountId,
+        },
+
+        /// Event emitted when a limit order is moved to another coldkey by a coldkey swap.
+        LimitOrderTransferred {
+            /// The subnet identifier
+            netuid: NetUid,
+            /// Unique identifier for the limit order
+            order_id: LimitOrderId,
+            /// The coldkey account that owned the order
+            from: T::AccountId,
+            /// The coldkey account that owns the order now
+            to: T::AccountId,
+        },
+
+        /// Event emitted when the operator of a liquidity position is approved or revoked.
+        PositionOperatorSet {
+            /// The coldkey account that owns the position
+            coldkey: T::AccountId,
+            /// The subnet identifier
+            netuid: NetUid,
+            /// Unique identifier for the liquidity position
+            position_id: PositionId,
+            /// The approved operator, `None` if the approval is revoked
+            operator: Option<T::AccountId>,
+        },
+
+        /// Event emitted when a limit order is placed on a subnet.
+        LimitOrderPlaced {
+            /// The coldkey account that owns the order
+            coldkey: T::AccountId,
+            /// The hotkey account where Alpha comes from or goes to
+            hotkey: T::AccountId,
+            /// The subnet identifier
+            netuid: NetUid,
+            /// Unique identifier for the limit order
+            order_id: LimitOrderId,
+            /// Direction of the order
+            order_type: OrderType,
+            /// The tick at which the order is triggered
+            tick: TickIndex,
+            /// The amount of TAO (buy) or Alpha (sell) escrowed by the order
+            amount: u64,
+        },
+
+        /// Event emitted when a limit order is (partially) filled by a swap crossing its tick.
+        LimitOrderFilled {
+            /// The coldkey account that owns the order
+            coldkey: T::AccountId,
+            /// The hotkey account where Alpha comes from or goes to
+            hotkey: T::AccountId,
+            /// The subnet identifier
+            netuid: NetUid,
+            /// Unique identifier for the limit order
+            order_id: LimitOrderId,
+            /// Direction of the order
+            order_type: OrderType,
+            /// The amount of TAO (buy) or Alpha (sell) swapped, including fee
+            amount_in: u64,
+            /// The amount of Alpha (buy) or TAO (sell) credited to the owner
+            amount_out: u64,
+            /// The fee paid by the order
+            fee: u64,
+            /// The input amount that remains resting, zero if the order is closed
+            remaining: u64,
+        },
+
+        /// Event emitted when a limit order is cancelled and its escrow is refunded.
+        LimitOrderCancelled {
+            /// The coldkey account that owns the order
+            coldkey: T::AccountId,
+            /// The hotkey account where Alpha is refunded to
+            hotkey: T::AccountId,
+            /// The subnet identifier
+            netuid: NetUid,
+            /// Unique identifier for the limit order
+            order_id: LimitOrderId,
+            /// The refunded amount of TAO (buy) or Alpha (sell)
+            refund: u64,
+        },
+
+        /// Event emitted when the escrow of a limit order closed by a subnet dissolution could
+        /// not be refunded.
+        LimitOrderRefundFailed {
+            /// The coldkey account that owns the order
+            coldkey: T::AccountId,
+            /// The hotkey account where Alpha should have been refunded to
+            hotkey: T::AccountId,
+            /// The subnet identifier
+            netuid: NetUid,
+            /// Unique identifier for the limit order
+            order_id: LimitOrderId,
+            /// The unrefunded amount of TAO (buy) or Alpha (sell)
+            amount: u64,
+            /// The refund error
+            error: DispatchError,
+        },
     }
 
     #[pallet::error]
@@ -252,7 +480,7 @@ mod pallet {
         /// The provided tick range is invalid.
         InvalidTickRange,
 
-        /// Maximum user positions exceeded
+        /// Maximum user positions exceeded, on the subnet or across all subnets
         MaxPositionsExceeded,
 
         /// Too many swap steps
@@ -272,6 +500,38 @@ mod pallet {
 
         /// The subnet does not have subtoken enabled
         SubtokenDisabled,
+
+        /// Limit orders are not supported on this subnet
+        LimitOrdersUnsupported,
+
+        /// The limit order amount is below the minimum
+        LimitOrderTooSmall,
+
+        /// The limit order tick is already crossed by the current price
+        LimitOrderMarketable,
+
+        /// The maximum number of resting limit orders is reached for this subnet
+        LimitOrderBookFull,
+
+        /// Attempted to cancel a limit order that does not exist
+        LimitOrderNotFound,
+
+        /// The coldkey reached `MaxLimitOrdersPerAccount` resting limit orders
+        TooManyLimitOrders,
+
+        /// The position cannot be transferred to the given account
+        InvalidPositionRecipient,
+
+        /// The swap route has less than two subnets
+        InvalidSwapRoute,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            // Index the positions of every coldkey
+            migrations::migrate_account_positions::<T>()
+        }
     }
 
     #[pallet::call]
@@ -473,6 +733,9 @@ mod pallet {
 
         /// Modify a liquidity position.
         ///
+        /// Can be called by the position owner or by the operator approved with
+        /// `set_position_operator`. Balances are always taken from and credited to the owner.
+        ///
         /// Parameters:
         /// - origin: The origin of the transaction
         /// - netuid: Subnet ID
@@ -489,7 +752,7 @@ mod pallet {
             position_id: PositionId,
             liquidity_delta: i64,
         ) -> DispatchResult {
-            let coldkey = ensure_signed(origin)?;
+            let who = ensure_signed(origin)?;
 
             // Ensure that the subnet exists.
             ensure!(
@@ -502,6 +765,9 @@ mod pallet {
                 Error::<T>::SubtokenDisabled
             );
 
+            // Balances are moved on behalf of the owner if called by the operator
+            let coldkey = Self::position_owner(netuid, &who, position_id)?;
+
             // Add or remove liquidity
             let result =
                 Self::do_modify_position(netuid, &coldkey, &hotkey, position_id, liquidity_delta)?;
@@ -584,5 +850,204 @@ mod pallet {
 
             Ok(())
         }
+
+        /// Place a resting limit order on a subnet.
+        ///
+        /// A buy order (TAO -> Alpha) must be placed below the current price and escrows TAO from
+        /// the coldkey balance in the limit order escrow account. A sell order (Alpha -> TAO) must
+        /// be placed above the current price and escrows Alpha staked to the hotkey. The order is
+        /// filled by the swap that moves the price across the order tick, with the tick price as
+        /// the limit price. The weight of the fill is paid upfront.
+        ///
+        /// Parameters:
+        /// - origin: The origin of the transaction
+        /// - hotkey: Hotkey where Alpha comes from (sell) or is credited to (buy)
+        /// - netuid: Subnet ID
+        /// - order_type: Direction of the order
+        /// - tick: Tick at which the order is triggered
+        /// - amount: Amount of TAO (buy) or Alpha (sell) to swap
+        ///
+        /// Emits `Event::LimitOrderPlaced` on success
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::place_limit_order()
+            .saturating_add(<T as pallet::Config>::WeightInfo::fill_limit_order()))]
+        pub fn place_limit_order(
+            origin: OriginFor<T>,
+            hotkey: T::AccountId,
+            netuid: NetUid,
+            order_type: OrderType,
+            tick: TickIndex,
+            amount: u64,
+        ) -> DispatchResult {
+            let coldkey = ensure_signed(origin)?;
+
+            // Ensure that the subnet exists.
+            ensure!(
+                T::SubnetInfo::exists(netuid.into()),
+                Error::<T>::MechanismDoesNotExist
+            );
+
+            ensure!(
+                T::SubnetInfo::is_subtoken_enabled(netuid.into()),
+                Error::<T>::SubtokenDisabled
+            );
+
+            let order_id =
+                Self::do_place_limit_order(netuid, &coldkey, &hotkey, order_type, tick, amount)?;
+
+            // Escrow the input amount or fail transaction if it can't be removed exactly
+            match order_type {
+                OrderType::Buy => {
+                    T::BalanceOps::transfer_balance(
+                        &coldkey,
+                        &Self::limit_order_escrow_account_id(),
+                        TaoCurrency::from(amount),
+                        Preservation::Preserve,
+                    )
+                    .map_err(|_| Error::<T>::InsufficientBalance)?;
+                }
+                OrderType::Sell => {
+                    let alpha = AlphaCurrency::from(amount);
+                    let alpha_provided =
+                        T::BalanceOps::decrease_stake(&coldkey, &hotkey, netuid, alpha)?;
+                    ensure!(alpha_provided == alpha, Error::<T>::InsufficientBalance);
+                }
+            }
+
+            // Emit an event
+            Self::deposit_event(Event::LimitOrderPlaced {
+                coldkey,
+                hotkey,
+                netuid,
+                order_id,
+                order_type,
+                tick,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Cancel a resting limit order and refund the unfilled amount.
+        ///
+        /// Parameters:
+        /// - origin: The origin of the transaction, must be the order owner
+        /// - netuid: Subnet ID
+        /// - order_id: ID of the order to cancel
+        ///
+        /// Emits `Event::LimitOrderCancelled` on success
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::cancel_limit_order())]
+        pub fn cancel_limit_order(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            order_id: LimitOrderId,
+        ) -> DispatchResult {
+            let coldkey = ensure_signed(origin)?;
+
+            let order =
+                Self::find_limit_order(netuid, order_id).ok_or(Error::<T>::LimitOrderNotFound)?;
+            ensure!(order.owner == coldkey, DispatchError::BadOrigin);
+
+            Self::remove_limit_order(&order);
+            Self::refund_limit_order(&order)?;
+
+            // Emit an event
+            Self::deposit_event(Event::LimitOrderCancelled {
+                coldkey,
+                hotkey: order.hotkey,
+                netuid,
+                order_id,
+                refund: order.amount,
+            });
+
+            Ok(())
+        }
+
+        /// Transfer a liquidity position to another coldkey.
+        ///
+        /// The position keeps its liquidity and uncollected fees, which are credited to the new
+        /// owner when the position is modified or removed. Any operator approval is revoked.
+        ///
+        /// Parameters:
+        /// - origin: The origin of the transaction, must be the position owner
+        /// - netuid: Subnet ID
+        /// - position_id: ID of the position to transfer
+        /// - new_coldkey: Coldkey that receives the position
+        ///
+        /// Emits `Event::PositionTransferred` on success
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::transfer_position())]
+        pub fn transfer_position(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            position_id: PositionId,
+            new_coldkey: T::AccountId,
+        ) -> DispatchResult {
+            let coldkey = ensure_signed(origin)?;
+
+            Self::do_transfer_position(netuid, &coldkey, &new_coldkey, position_id)?;
+
+            // Emit an event
+            Self::deposit_event(Event::PositionTransferred {
+                netuid,
+                position_id,
+                from: coldkey,
+                to: new_coldkey,
+            });
+
+            Ok(())
+        }
+
+        /// Approve an operator that may call `modify_position` on a position on behalf of its
+        /// owner, or revoke the approval.
+        ///
+        /// The operator can add liquidity funded by the owner and remove liquidity, but the
+        /// withdrawn amounts and fees are always credited to the owner. A position has at most one
+        /// operator, the approval is revoked when the position is transferred or removed.
+        ///
+        /// Parameters:
+        /// - origin: The origin of the transaction, must be the position owner
+        /// - netuid: Subnet ID
+        /// - position_id: ID of the position
+        /// - operator: Account to approve, `None` to revoke the current approval
+        ///
+        /// Emits `Event::PositionOperatorSet` on success
+        #[pallet::call_index(8)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_position_operator())]
+        pub fn set_position_operator(
+            origin: OriginFor<T>,
+            netuid: NetUid,
+            position_id: PositionId,
+            operator: Option<T::AccountId>,
+        ) -> DispatchResult {
+            let coldkey = ensure_signed(origin)?;
+
+            ensure!(
+                Positions::<T>::contains_key((netuid, &coldkey, position_id)),
+                Error::<T>::LiquidityNotFound
+            );
+
+            match &operator {
+                Some(operator) => {
+                    PositionOperators::<T>::insert(
+                        netuid,
+                        position_id,
+                        (coldkey.clone(), operator.clone()),
+                    );
+                }
+                None => PositionOperators::<T>::remove(netuid, position_id),
+            }
+
+            // Emit an event
+            Self::deposit_event(Event::PositionOperatorSet {
+                coldkey,
+                netuid,
+                position_id,
+                operator,
+            });
+
+            Ok(())
+        }
     }
 }
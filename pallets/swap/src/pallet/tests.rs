@@ -2789,3 +2789,1120 @@ fn test_clear_protocol_liquidity_green_path() {
         assert!(!SwapV3Initialized::<Test>::contains_key(netuid));
     });
 }
+
+#[test]
+fn test_place_and_cancel_limit_order() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let tick = price_to_tick(0.2);
+        let amount = 1_000_000_000;
+
+        assert_ok!(Pallet::<Test>::place_limit_order(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            OK_HOTKEY_ACCOUNT_ID,
+            netuid,
+            OrderType::Buy,
+            tick,
+            amount,
+        ));
+
+        let order_id = LimitOrderId::from(1u128);
+        let order = LimitOrders::<Test>::get((netuid, tick, order_id)).unwrap();
+        assert_eq!(order.owner, OK_COLDKEY_ACCOUNT_ID);
+        assert_eq!(order.hotkey, OK_HOTKEY_ACCOUNT_ID);
+        assert_eq!(order.amount, amount);
+        assert_eq!(
+            LimitOrderBook::<Test>::get(netuid, OrderType::Buy).into_inner(),
+            vec![(tick, order_id)]
+        );
+
+        let orders = Pallet::<Test>::get_limit_orders(&OK_COLDKEY_ACCOUNT_ID, netuid);
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].order_id, 1);
+        assert_eq!(orders[0].tick, tick.get());
+        assert_abs_diff_eq!(orders[0].price, 200_000_000, epsilon = 100_000);
+
+        // Only the owner can cancel
+        assert_noop!(
+            Pallet::<Test>::cancel_limit_order(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID_2),
+                netuid,
+                order_id,
+            ),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(Pallet::<Test>::cancel_limit_order(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            netuid,
+            order_id,
+        ));
+        assert!(!LimitOrders::<Test>::contains_key((netuid, tick, order_id)));
+        assert!(LimitOrderBook::<Test>::get(netuid, OrderType::Buy).is_empty());
+
+        assert_noop!(
+            Pallet::<Test>::cancel_limit_order(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+                netuid,
+                order_id,
+            ),
+            Error::<Test>::LimitOrderNotFound
+        );
+    });
+}
+
+#[test]
+fn test_place_limit_order_rejects_invalid_orders() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+
+        // Current price is 0.25, a buy above it can be filled right away
+        assert_noop!(
+            Pallet::<Test>::place_limit_order(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+                OK_HOTKEY_ACCOUNT_ID,
+                netuid,
+                OrderType::Buy,
+                price_to_tick(0.3),
+                1_000_000_000,
+            ),
+            Error::<Test>::LimitOrderMarketable
+        );
+
+        // Same for a sell below it
+        assert_noop!(
+            Pallet::<Test>::place_limit_order(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+                OK_HOTKEY_ACCOUNT_ID,
+                netuid,
+                OrderType::Sell,
+                price_to_tick(0.2),
+                1_000_000_000,
+            ),
+            Error::<Test>::LimitOrderMarketable
+        );
+
+        assert_noop!(
+            Pallet::<Test>::place_limit_order(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+                OK_HOTKEY_ACCOUNT_ID,
+                netuid,
+                OrderType::Buy,
+                price_to_tick(0.2),
+                MinLimitOrderAmount::get() - 1,
+            ),
+            Error::<Test>::LimitOrderTooSmall
+        );
+
+        // Sell orders are measured in TAO at the order price
+        assert_noop!(
+            Pallet::<Test>::place_limit_order(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+                OK_HOTKEY_ACCOUNT_ID,
+                netuid,
+                OrderType::Sell,
+                price_to_tick(0.3),
+                MinLimitOrderAmount::get(),
+            ),
+            Error::<Test>::LimitOrderTooSmall
+        );
+
+        assert_noop!(
+            Pallet::<Test>::place_limit_order(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+                OK_HOTKEY_ACCOUNT_ID,
+                NetUid::from(0),
+                OrderType::Sell,
+                price_to_tick(0.3),
+                1_000_000_000,
+            ),
+            Error::<Test>::LimitOrdersUnsupported
+        );
+
+        assert_noop!(
+            Pallet::<Test>::place_limit_order(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+                OK_HOTKEY_ACCOUNT_ID,
+                NetUid::from(SUBTOKEN_DISABLED_NETUID),
+                OrderType::Sell,
+                price_to_tick(0.3),
+                1_000_000_000,
+            ),
+            Error::<Test>::SubtokenDisabled
+        );
+    });
+}
+
+#[test]
+fn test_limit_order_book_is_sorted_by_tick() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let ticks = [
+            price_to_tick(0.4),
+            price_to_tick(0.3),
+            price_to_tick(0.5),
+            price_to_tick(0.3),
+        ];
+
+        for tick in ticks {
+            assert_ok!(Pallet::<Test>::place_limit_order(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+                OK_HOTKEY_ACCOUNT_ID,
+                netuid,
+                OrderType::Sell,
+                tick,
+                1_000_000_000,
+            ));
+        }
+
+        // Ascending by tick, orders at the same tick keep their placement order
+        let book = LimitOrderBook::<Test>::get(netuid, OrderType::Sell).into_inner();
+        assert_eq!(
+            book,
+            vec![
+                (ticks[1], LimitOrderId::from(2u128)),
+                (ticks[3], LimitOrderId::from(4u128)),
+                (ticks[0], LimitOrderId::from(1u128)),
+                (ticks[2], LimitOrderId::from(3u128)),
+            ]
+        );
+    });
+}
+
+#[test]
+fn test_limit_order_filled_when_price_crosses() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let tick = price_to_tick(0.24);
+        let amount = 1_000_000_000;
+
+        assert_ok!(Pallet::<Test>::place_limit_order(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            OK_HOTKEY_ACCOUNT_ID,
+            netuid,
+            OrderType::Buy,
+            tick,
+            amount,
+        ));
+        let order_id = LimitOrderId::from(1u128);
+        let min_sqrt_price = SqrtPrice::from_num(0.0001);
+
+        // A small sell doesn't move the price below the order
+        assert_ok!(Pallet::<Test>::do_swap(
+            netuid,
+            OrderType::Sell,
+            10_000_000_000,
+            min_sqrt_price,
+            false,
+            false,
+        ));
+        assert_eq!(
+            LimitOrders::<Test>::get((netuid, tick, order_id))
+                .unwrap()
+                .amount,
+            amount
+        );
+
+        // A large sell crosses the order tick and the order is filled by the same swap
+        assert_ok!(Pallet::<Test>::do_swap(
+            netuid,
+            OrderType::Sell,
+            200_000_000_000,
+            min_sqrt_price,
+            false,
+            false,
+        ));
+        assert!(!LimitOrders::<Test>::contains_key((netuid, tick, order_id)));
+        assert!(AccountLimitOrders::<Test>::get(OK_COLDKEY_ACCOUNT_ID).is_empty());
+        assert!(LimitOrderBook::<Test>::get(netuid, OrderType::Buy).is_empty());
+
+        // The fill never pushes the price above the order limit
+        assert!(Pallet::<Test>::current_price_sqrt(netuid) <= tick.as_sqrt_price_bounded());
+
+        // Simulated swaps never fill orders
+        assert_ok!(Pallet::<Test>::place_limit_order(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            OK_HOTKEY_ACCOUNT_ID,
+            netuid,
+            OrderType::Buy,
+            price_to_tick(0.2),
+            amount,
+        ));
+        assert_ok!(Pallet::<Test>::do_swap(
+            netuid,
+            OrderType::Sell,
+            1_000_000_000_000,
+            min_sqrt_price,
+            false,
+            true,
+        ));
+        assert_eq!(LimitOrderBook::<Test>::get(netuid, OrderType::Buy).len(), 1);
+    });
+}
+
+#[test]
+fn test_limit_orders_per_account_are_capped() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let max_orders = MaxLimitOrdersPerAccount::get();
+
+        for _ in 0..max_orders {
+            assert_ok!(Pallet::<Test>::place_limit_order(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+                OK_HOTKEY_ACCOUNT_ID,
+                netuid,
+                OrderType::Buy,
+                price_to_tick(0.2),
+                1_000_000,
+            ));
+        }
+        assert_eq!(
+            AccountLimitOrders::<Test>::get(OK_COLDKEY_ACCOUNT_ID).len() as u32,
+            max_orders
+        );
+
+        assert_noop!(
+            Pallet::<Test>::place_limit_order(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+                OK_HOTKEY_ACCOUNT_ID,
+                netuid,
+                OrderType::Buy,
+                price_to_tick(0.2),
+                1_000_000,
+            ),
+            Error::<Test>::TooManyLimitOrders
+        );
+
+        // Other coldkeys are not affected
+        assert_ok!(Pallet::<Test>::place_limit_order(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID_2),
+            OK_HOTKEY_ACCOUNT_ID_2,
+            netuid,
+            OrderType::Buy,
+            price_to_tick(0.2),
+            1_000_000,
+        ));
+
+        // Cancelling frees a slot
+        assert_ok!(Pallet::<Test>::cancel_limit_order(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            netuid,
+            LimitOrderId::from(1u128),
+        ));
+        assert_ok!(Pallet::<Test>::place_limit_order(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            OK_HOTKEY_ACCOUNT_ID,
+            netuid,
+            OrderType::Buy,
+            price_to_tick(0.2),
+            1_000_000,
+        ));
+    });
+}
+
+#[test]
+fn test_dissolve_refunds_limit_orders() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+
+        assert_ok!(Pallet::<Test>::place_limit_order(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            OK_HOTKEY_ACCOUNT_ID,
+            netuid,
+            OrderType::Buy,
+            price_to_tick(0.2),
+            1_000_000_000,
+        ));
+        assert_ok!(Pallet::<Test>::place_limit_order(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID_2),
+            OK_HOTKEY_ACCOUNT_ID_2,
+            netuid,
+            OrderType::Sell,
+            price_to_tick(0.3),
+            1_000_000_000,
+        ));
+
+        assert_ok!(Pallet::<Test>::do_dissolve_all_liquidity_providers(netuid));
+
+        assert!(
+            LimitOrders::<Test>::iter_prefix_values((netuid,))
+                .next()
+                .is_none()
+        );
+        assert!(LimitOrderBook::<Test>::get(netuid, OrderType::Buy).is_empty());
+        assert!(LimitOrderBook::<Test>::get(netuid, OrderType::Sell).is_empty());
+        assert!(AccountLimitOrders::<Test>::get(OK_COLDKEY_ACCOUNT_ID).is_empty());
+        assert!(AccountLimitOrders::<Test>::get(OK_COLDKEY_ACCOUNT_ID_2).is_empty());
+    });
+}
+
+#[test]
+fn test_twap_follows_price_changes() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let initial_price = Pallet::<Test>::current_price(netuid).to_num::<f64>();
+
+        System::set_block_number(100);
+        Pallet::<Test>::record_price_observation(netuid);
+
+        // No observations older than the first one
+        assert_eq!(Pallet::<Test>::twap(netuid, 10), None);
+        // An empty window has no average
+        assert_eq!(Pallet::<Test>::twap(netuid, 0), None);
+
+        // The swap records the price that was current since block 100 before moving it
+        System::set_block_number(110);
+        assert_ok!(Pallet::<Test>::do_swap(
+            netuid,
+            OrderType::Sell,
+            200_000_000_000,
+            SqrtPrice::from_num(0.0001),
+            false,
+            false,
+        ));
+        let swapped_price = Pallet::<Test>::current_price(netuid).to_num::<f64>();
+        assert!(swapped_price < initial_price);
+
+        System::set_block_number(120);
+        let twap = |window| {
+            Pallet::<Test>::twap(netuid, window)
+                .unwrap()
+                .to_num::<f64>()
+        };
+        assert_abs_diff_eq!(twap(10), swapped_price, epsilon = swapped_price / 1000.);
+        assert_abs_diff_eq!(
+            twap(20),
+            (initial_price * swapped_price).sqrt(),
+            epsilon = initial_price / 1000.
+        );
+
+        // A window starting between two observations is interpolated
+        System::set_block_number(130);
+        Pallet::<Test>::record_price_observation(netuid);
+        let expected = initial_price.powf(5. / 25.) * swapped_price.powf(20. / 25.);
+        assert_abs_diff_eq!(twap(25), expected, epsilon = expected / 1000.);
+        assert_eq!(Pallet::<Test>::twap(netuid, 31), None);
+    });
+}
+
+#[test]
+fn test_price_observations_ring_buffer() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let max_observations = MaxPriceObservations::get();
+
+        for block in 1..=(max_observations as u64 + 50) {
+            System::set_block_number(block);
+            Pallet::<Test>::record_price_observation(netuid);
+            // Only one observation per block
+            Pallet::<Test>::record_price_observation(netuid);
+        }
+
+        assert_eq!(PriceObservationCount::<Test>::get(netuid), max_observations);
+        assert_eq!(
+            PriceObservations::<Test>::iter_prefix(netuid).count(),
+            max_observations as usize
+        );
+
+        // Oldest observation is at block 51
+        assert!(Pallet::<Test>::twap(netuid, max_observations as u64 - 1).is_some());
+        assert!(Pallet::<Test>::twap(netuid, max_observations as u64).is_none());
+
+        assert_ok!(Pallet::<Test>::do_clear_protocol_liquidity(netuid));
+        assert_eq!(PriceObservationCount::<Test>::get(netuid), 0);
+        assert!(
+            PriceObservations::<Test>::iter_prefix(netuid)
+                .next()
+                .is_none()
+        );
+        assert_eq!(Pallet::<Test>::twap(netuid, 1), None);
+    });
+}
+
+#[test]
+fn test_sim_swap_route() {
+    new_test_ext().execute_with(|| {
+        let netuid1 = NetUid::from(1);
+        let netuid2 = NetUid::from(2);
+        let netuid3 = NetUid::from(3);
+        let alpha_amount = 1_000_000_000;
+
+        assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid1));
+        let sqrt_price_before = AlphaSqrtPrice::<Test>::get(netuid1);
+
+        let legs = Pallet::<Test>::sim_swap_route(
+            &OK_COLDKEY_ACCOUNT_ID,
+            &OK_HOTKEY_ACCOUNT_ID,
+            &[netuid1, netuid2, netuid3],
+            alpha_amount,
+        )
+        .unwrap();
+        assert_eq!(legs.len(), 2);
+
+        let [first, second] = legs.as_slice() else {
+            panic!("two legs expected");
+        };
+        assert_eq!(first.origin_netuid, netuid1);
+        assert_eq!(first.destination_netuid, netuid2);
+        assert_eq!(first.alpha_in, alpha_amount.into());
+        assert!(u64::from(first.alpha_fee) > 0);
+        // Fees are only charged on the origin subnet
+        assert_eq!(u64::from(first.tao_fee), 0);
+        assert_eq!(second.origin_netuid, netuid2);
+        assert_eq!(second.destination_netuid, netuid3);
+        assert_eq!(second.alpha_in, first.alpha_out);
+
+        // 1 Alpha sells for less than 0.25 TAO and buys back less than 1 Alpha at the same price
+        assert!(u64::from(first.tao_amount) < alpha_amount / 4);
+        assert!(u64::from(second.alpha_out) < u64::from(first.alpha_out));
+        assert!(u64::from(first.alpha_out) < alpha_amount);
+
+        // Nothing is persisted
+        assert_eq!(AlphaSqrtPrice::<Test>::get(netuid1), sqrt_price_before);
+        assert!(!SwapV3Initialized::<Test>::get(netuid2));
+        assert!(!SwapV3Initialized::<Test>::get(netuid3));
+
+        // The amount is capped at the available stake
+        let legs = Pallet::<Test>::sim_swap_route(
+            &NOT_SUBNET_OWNER,
+            &NOT_SUBNET_OWNER,
+            &[netuid1, netuid2],
+            alpha_amount * 5,
+        )
+        .unwrap();
+        assert_eq!(legs[0].alpha_in, alpha_amount.into());
+
+        // Legs moving less than the minimum stake fail like the stake swap
+        assert_noop!(
+            Pallet::<Test>::sim_swap_route(
+                &OK_COLDKEY_ACCOUNT_ID,
+                &OK_HOTKEY_ACCOUNT_ID,
+                &[netuid1, netuid2],
+                1_000,
+            ),
+            Error::<Test>::InsufficientInputAmount
+        );
+
+        // Routes through a missing subnet or without a destination cannot be quoted
+        assert_noop!(
+            Pallet::<Test>::sim_swap_route(
+                &OK_COLDKEY_ACCOUNT_ID,
+                &OK_HOTKEY_ACCOUNT_ID,
+                &[netuid1, NON_EXISTENT_NETUID.into()],
+                alpha_amount,
+            ),
+            Error::<Test>::MechanismDoesNotExist
+        );
+        assert_noop!(
+            Pallet::<Test>::sim_swap_route(
+                &OK_COLDKEY_ACCOUNT_ID,
+                &OK_HOTKEY_ACCOUNT_ID,
+                &[netuid1],
+                alpha_amount,
+            ),
+            Error::<Test>::InvalidSwapRoute
+        );
+    });
+}
+
+#[test]
+fn test_transfer_position() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid));
+
+        let (position_id, _, _) = Pallet::<Test>::do_add_liquidity(
+            netuid,
+            &OK_COLDKEY_ACCOUNT_ID,
+            &OK_HOTKEY_ACCOUNT_ID,
+            price_to_tick(0.1),
+            price_to_tick(0.5),
+            2_000_000_000,
+        )
+        .unwrap();
+        let position =
+            Positions::<Test>::get((netuid, OK_COLDKEY_ACCOUNT_ID, position_id)).unwrap();
+
+        // The recipient must be another user account
+        assert_noop!(
+            Pallet::<Test>::transfer_position(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+                netuid,
+                position_id,
+                OK_COLDKEY_ACCOUNT_ID,
+            ),
+            Error::<Test>::InvalidPositionRecipient
+        );
+        assert_noop!(
+            Pallet::<Test>::transfer_position(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+                netuid,
+                position_id,
+                Pallet::<Test>::protocol_account_id(),
+            ),
+            Error::<Test>::InvalidPositionRecipient
+        );
+        // Only the owner can transfer
+        assert_noop!(
+            Pallet::<Test>::transfer_position(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID_2),
+                netuid,
+                position_id,
+                OK_COLDKEY_ACCOUNT_ID_2,
+            ),
+            Error::<Test>::LiquidityNotFound
+        );
+
+        assert_ok!(Pallet::<Test>::set_position_operator(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            netuid,
+            position_id,
+            Some(OK_COLDKEY_ACCOUNT_ID_RICH),
+        ));
+        assert_ok!(Pallet::<Test>::transfer_position(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            netuid,
+            position_id,
+            OK_COLDKEY_ACCOUNT_ID_2,
+        ));
+        System::assert_last_event(
+            Event::PositionTransferred {
+                netuid,
+                position_id,
+                from: OK_COLDKEY_ACCOUNT_ID,
+                to: OK_COLDKEY_ACCOUNT_ID_2,
+            }
+            .into(),
+        );
+
+        // The position moved as is and the operator approval is revoked
+        assert!(!Positions::<Test>::contains_key((
+            netuid,
+            OK_COLDKEY_ACCOUNT_ID,
+            position_id
+        )));
+        assert_eq!(
+            Positions::<Test>::get((netuid, OK_COLDKEY_ACCOUNT_ID_2, position_id)),
+            Some(position)
+        );
+        assert!(!PositionOperators::<Test>::contains_key(
+            netuid,
+            position_id
+        ));
+
+        // The new owner manages the position, the old one does not
+        assert_noop!(
+            Pallet::<Test>::modify_position(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+                OK_HOTKEY_ACCOUNT_ID,
+                netuid,
+                position_id,
+                -1_000_000_000,
+            ),
+            Error::<Test>::LiquidityNotFound
+        );
+        assert_ok!(Pallet::<Test>::remove_liquidity(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID_2),
+            OK_HOTKEY_ACCOUNT_ID_2,
+            netuid,
+            position_id,
+        ));
+    });
+}
+
+#[test]
+fn test_transfer_position_max_positions() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid));
+
+        let add_position = |coldkey, hotkey| {
+            Pallet::<Test>::do_add_liquidity(
+                netuid,
+                &coldkey,
+                &hotkey,
+                price_to_tick(0.1),
+                price_to_tick(0.5),
+                2_000_000,
+            )
+            .unwrap()
+            .0
+        };
+
+        let position_id = add_position(OK_COLDKEY_ACCOUNT_ID, OK_HOTKEY_ACCOUNT_ID);
+        for _ in 0..MaxPositions::get() {
+            add_position(OK_COLDKEY_ACCOUNT_ID_2, OK_HOTKEY_ACCOUNT_ID_2);
+        }
+
+        assert_noop!(
+            Pallet::<Test>::transfer_position(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+                netuid,
+                position_id,
+                OK_COLDKEY_ACCOUNT_ID_2,
+            ),
+            Error::<Test>::MaxPositionsExceeded
+        );
+    });
+}
+
+#[test]
+fn test_position_operator() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let operator = OK_COLDKEY_ACCOUNT_ID_2;
+        assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid));
+
+        let (position_id, _, _) = Pallet::<Test>::do_add_liquidity(
+            netuid,
+            &OK_COLDKEY_ACCOUNT_ID,
+            &OK_HOTKEY_ACCOUNT_ID,
+            price_to_tick(0.1),
+            price_to_tick(0.5),
+            2_000_000_000,
+        )
+        .unwrap();
+
+        // Not approved yet
+        assert_noop!(
+            Pallet::<Test>::modify_position(
+                RuntimeOrigin::signed(operator),
+                OK_HOTKEY_ACCOUNT_ID,
+                netuid,
+                position_id,
+                -100_000_000,
+            ),
+            Error::<Test>::LiquidityNotFound
+        );
+        // Only the owner can approve
+        assert_noop!(
+            Pallet::<Test>::set_position_operator(
+                RuntimeOrigin::signed(operator),
+                netuid,
+                position_id,
+                Some(operator),
+            ),
+            Error::<Test>::LiquidityNotFound
+        );
+
+        assert_ok!(Pallet::<Test>::set_position_operator(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            netuid,
+            position_id,
+            Some(operator),
+        ));
+        System::assert_last_event(
+            Event::PositionOperatorSet {
+                coldkey: OK_COLDKEY_ACCOUNT_ID,
+                netuid,
+                position_id,
+                operator: Some(operator),
+            }
+            .into(),
+        );
+
+        // The operator modifies the position on behalf of the owner
+        assert_ok!(Pallet::<Test>::modify_position(
+            RuntimeOrigin::signed(operator),
+            OK_HOTKEY_ACCOUNT_ID,
+            netuid,
+            position_id,
+            -100_000_000,
+        ));
+        assert!(matches!(
+            System::events().last().map(|record| record.event.clone()),
+            Some(RuntimeEvent::Swap(Event::LiquidityModified { coldkey, .. }))
+                if coldkey == OK_COLDKEY_ACCOUNT_ID
+        ));
+        assert_eq!(
+            Positions::<Test>::get((netuid, OK_COLDKEY_ACCOUNT_ID, position_id))
+                .unwrap()
+                .liquidity,
+            1_900_000_000
+        );
+
+        // Revoked approval
+        assert_ok!(Pallet::<Test>::set_position_operator(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            netuid,
+            position_id,
+            None,
+        ));
+        assert_noop!(
+            Pallet::<Test>::modify_position(
+                RuntimeOrigin::signed(operator),
+                OK_HOTKEY_ACCOUNT_ID,
+                netuid,
+                position_id,
+                -100_000_000,
+            ),
+            Error::<Test>::LiquidityNotFound
+        );
+
+        // Removing the position clears the approval
+        assert_ok!(Pallet::<Test>::set_position_operator(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            netuid,
+            position_id,
+            Some(operator),
+        ));
+        assert_ok!(Pallet::<Test>::remove_liquidity(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            OK_HOTKEY_ACCOUNT_ID,
+            netuid,
+            position_id,
+        ));
+        assert!(!PositionOperators::<Test>::contains_key(
+            netuid,
+            position_id
+        ));
+    });
+}
+
+#[test]
+fn test_swap_coldkey_positions() {
+    new_test_ext().execute_with(|| {
+        let netuid1 = NetUid::from(1);
+        let netuid2 = NetUid::from(2);
+        let new_coldkey = 42;
+
+        let mut position_ids = Vec::new();
+        for netuid in [netuid1, netuid2] {
+            assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid));
+            let (position_id, _, _) = Pallet::<Test>::do_add_liquidity(
+                netuid,
+                &OK_COLDKEY_ACCOUNT_ID,
+                &OK_HOTKEY_ACCOUNT_ID,
+                price_to_tick(0.1),
+                price_to_tick(0.5),
+                2_000_000_000,
+            )
+            .unwrap();
+            position_ids.push((netuid, position_id));
+        }
+        let (_, first_position_id) = position_ids[0];
+        assert_ok!(Pallet::<Test>::set_position_operator(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            netuid1,
+            first_position_id,
+            Some(OK_COLDKEY_ACCOUNT_ID_2),
+        ));
+
+        let weight =
+            Pallet::<Test>::do_swap_coldkey_positions(&OK_COLDKEY_ACCOUNT_ID, &new_coldkey);
+        assert!(!weight.is_zero());
+
+        for (netuid, position_id) in position_ids {
+            assert!(!Positions::<Test>::contains_key((
+                netuid,
+                OK_COLDKEY_ACCOUNT_ID,
+                position_id
+            )));
+            assert!(Positions::<Test>::contains_key((
+                netuid,
+                new_coldkey,
+                position_id
+            )));
+            System::assert_has_event(
+                Event::PositionTransferred {
+                    netuid,
+                    position_id,
+                    from: OK_COLDKEY_ACCOUNT_ID,
+                    to: new_coldkey,
+                }
+                .into(),
+            );
+        }
+
+        // The position index follows the coldkey
+        assert!(!AccountPositions::<Test>::contains_key(
+            OK_COLDKEY_ACCOUNT_ID
+        ));
+        assert_eq!(
+            AccountPositions::<Test>::get(new_coldkey).into_inner(),
+            position_ids
+        );
+
+        // The protocol positions are untouched
+        assert_eq!(
+            Pallet::<Test>::count_positions(netuid1, &Pallet::<Test>::protocol_account_id()),
+            1
+        );
+
+        // The operator approval follows the position
+        assert_eq!(
+            PositionOperators::<Test>::get(netuid1, first_position_id),
+            Some((new_coldkey, OK_COLDKEY_ACCOUNT_ID_2))
+        );
+        assert_eq!(
+            Pallet::<Test>::position_owner(netuid1, &OK_COLDKEY_ACCOUNT_ID_2, first_position_id),
+            Ok(new_coldkey)
+        );
+    });
+}
+
+#[test]
+fn test_swap_coldkey_positions_limit() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let new_coldkey = 42;
+
+        assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid));
+        let (position_id, _, _) = Pallet::<Test>::do_add_liquidity(
+            netuid,
+            &OK_COLDKEY_ACCOUNT_ID,
+            &OK_HOTKEY_ACCOUNT_ID,
+            price_to_tick(0.1),
+            price_to_tick(0.5),
+            2_000_000_000,
+        )
+        .unwrap();
+        assert_eq!(
+            AccountPositions::<Test>::get(OK_COLDKEY_ACCOUNT_ID).into_inner(),
+            vec![(netuid, position_id)]
+        );
+        assert_ok!(Pallet::<Test>::ensure_can_swap_coldkey_positions(
+            &OK_COLDKEY_ACCOUNT_ID,
+            &new_coldkey
+        ));
+
+        // The new coldkey has no free position slots
+        let full = (0..MaxPositionsPerAccount::get())
+            .map(|id| (NetUid::from(2), PositionId::from(u128::from(id))))
+            .collect::<Vec<_>>();
+        AccountPositions::<Test>::insert(new_coldkey, BoundedVec::truncate_from(full));
+        assert_noop!(
+            Pallet::<Test>::ensure_can_swap_coldkey_positions(&OK_COLDKEY_ACCOUNT_ID, &new_coldkey),
+            Error::<Test>::MaxPositionsExceeded
+        );
+
+        // Removing the liquidity drops the position from the index
+        assert_ok!(Pallet::<Test>::do_remove_liquidity(
+            netuid,
+            &OK_COLDKEY_ACCOUNT_ID,
+            position_id
+        ));
+        assert!(!AccountPositions::<Test>::contains_key(
+            OK_COLDKEY_ACCOUNT_ID
+        ));
+    });
+}
+
+#[test]
+fn test_swap_coldkey_limit_orders() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let max_orders = MaxLimitOrdersPerAccount::get();
+
+        // The old coldkey places orders 1 and 2
+        for _ in 0..2 {
+            assert_ok!(Pallet::<Test>::place_limit_order(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+                OK_HOTKEY_ACCOUNT_ID,
+                netuid,
+                OrderType::Buy,
+                price_to_tick(0.2),
+                1_000_000,
+            ));
+        }
+        // The new coldkey has room for only one more order
+        for _ in 1..max_orders {
+            assert_ok!(Pallet::<Test>::place_limit_order(
+                RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID_2),
+                OK_HOTKEY_ACCOUNT_ID_2,
+                netuid,
+                OrderType::Buy,
+                price_to_tick(0.2),
+                1_000_000,
+            ));
+        }
+        let tick = price_to_tick(0.2);
+        let moved_id = LimitOrderId::from(1u128);
+        let cancelled_id = LimitOrderId::from(2u128);
+
+        let weight = Pallet::<Test>::do_swap_coldkey_positions(
+            &OK_COLDKEY_ACCOUNT_ID,
+            &OK_COLDKEY_ACCOUNT_ID_2,
+        );
+        assert!(
+            weight.all_gte(<Test as Config>::WeightInfo::cancel_limit_order().saturating_mul(2))
+        );
+
+        // The first order follows the coldkey
+        assert!(AccountLimitOrders::<Test>::get(OK_COLDKEY_ACCOUNT_ID).is_empty());
+        assert_eq!(
+            AccountLimitOrders::<Test>::get(OK_COLDKEY_ACCOUNT_ID_2).len() as u32,
+            max_orders
+        );
+        assert_eq!(
+            LimitOrders::<Test>::get((netuid, tick, moved_id)).map(|order| order.owner),
+            Some(OK_COLDKEY_ACCOUNT_ID_2)
+        );
+        System::assert_has_event(
+            Event::LimitOrderTransferred {
+                netuid,
+                order_id: moved_id,
+                from: OK_COLDKEY_ACCOUNT_ID,
+                to: OK_COLDKEY_ACCOUNT_ID_2,
+            }
+            .into(),
+        );
+
+        // The order over the cap is cancelled and refunded
+        assert!(!LimitOrders::<Test>::contains_key((
+            netuid,
+            tick,
+            cancelled_id
+        )));
+        System::assert_has_event(
+            Event::LimitOrderCancelled {
+                coldkey: OK_COLDKEY_ACCOUNT_ID_2,
+                hotkey: OK_HOTKEY_ACCOUNT_ID,
+                netuid,
+                order_id: cancelled_id,
+                refund: 1_000_000,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn test_get_ticks_and_liquidity_curve() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid));
+        let protocol_liquidity = CurrentLiquidity::<Test>::get(netuid);
+
+        // One position around the current price 0.25 and one above it
+        let tick_low = price_to_tick(0.2);
+        let tick_mid = price_to_tick(0.3);
+        let tick_high = price_to_tick(0.4);
+        for (low, high, liquidity) in [
+            (tick_low, tick_mid, 2_000_000_000_u64),
+            (tick_mid, tick_high, 1_000_000_000_u64),
+        ] {
+            assert_ok!(Pallet::<Test>::do_add_liquidity(
+                netuid,
+                &OK_COLDKEY_ACCOUNT_ID,
+                &OK_HOTKEY_ACCOUNT_ID,
+                low,
+                high,
+                liquidity,
+            ));
+        }
+
+        let ticks = Pallet::<Test>::get_ticks(netuid, 150_000_000, 450_000_000);
+        assert_eq!(
+            ticks
+                .iter()
+                .map(|tick| (tick.tick, tick.liquidity_net))
+                .collect::<Vec<_>>(),
+            vec![
+                (tick_low.get(), 2_000_000_000),
+                (tick_mid.get(), -1_000_000_000),
+                (tick_high.get(), -1_000_000_000),
+            ]
+        );
+        assert_abs_diff_eq!(ticks[0].price, 200_000_000, epsilon = 100_000);
+        assert_eq!(ticks[1].liquidity_gross, 3_000_000_000);
+
+        // Price range between the ticks
+        assert!(Pallet::<Test>::get_ticks(netuid, 410_000_000, 450_000_000).is_empty());
+
+        let curve = Pallet::<Test>::get_liquidity_curve(netuid, 10);
+        let ranges = curve
+            .iter()
+            .filter(|range| range.tick_low >= tick_low.get() && range.tick_high <= tick_high.get())
+            .map(|range| (range.tick_low, range.tick_high, range.liquidity))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ranges,
+            vec![
+                (
+                    tick_low.get(),
+                    tick_mid.get(),
+                    protocol_liquidity + 2_000_000_000
+                ),
+                (
+                    tick_mid.get(),
+                    tick_high.get(),
+                    protocol_liquidity + 1_000_000_000
+                ),
+            ]
+        );
+        // The curve is contiguous and covers the current tick
+        assert!(
+            curve
+                .windows(2)
+                .all(|pair| pair[0].tick_high == pair[1].tick_low)
+        );
+        let current_tick = CurrentTick::<Test>::get(netuid).get();
+        assert!(
+            curve
+                .iter()
+                .any(|range| range.tick_low <= current_tick && current_tick < range.tick_high)
+        );
+
+        // The number of ranges on each side is limited
+        assert_eq!(Pallet::<Test>::get_liquidity_curve(netuid, 0).len(), 1);
+    });
+}
+
+#[test]
+fn test_get_positions() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        assert_ok!(Pallet::<Test>::maybe_initialize_v3(netuid));
+
+        let (position_id, tao, alpha) = Pallet::<Test>::do_add_liquidity(
+            netuid,
+            &OK_COLDKEY_ACCOUNT_ID,
+            &OK_HOTKEY_ACCOUNT_ID,
+            price_to_tick(0.1),
+            price_to_tick(0.5),
+            2_000_000_000,
+        )
+        .unwrap();
+        assert_ok!(Pallet::<Test>::set_position_operator(
+            RuntimeOrigin::signed(OK_COLDKEY_ACCOUNT_ID),
+            netuid,
+            position_id,
+            Some(OK_COLDKEY_ACCOUNT_ID_2),
+        ));
+
+        let positions = Pallet::<Test>::get_positions(&OK_COLDKEY_ACCOUNT_ID);
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].position_id, u128::from(position_id));
+        assert_eq!(positions[0].netuid, netuid);
+        assert_eq!(positions[0].liquidity, 2_000_000_000);
+        assert_abs_diff_eq!(u64::from(positions[0].tao), tao, epsilon = 1);
+        assert_abs_diff_eq!(u64::from(positions[0].alpha), alpha, epsilon = 1);
+        assert_eq!(positions[0].fee_tao, TaoCurrency::ZERO);
+        assert_eq!(positions[0].operator, Some(OK_COLDKEY_ACCOUNT_ID_2));
+        assert!(Pallet::<Test>::get_positions(&OK_COLDKEY_ACCOUNT_ID_2).is_empty());
+
+        // Swap to accrue fees on the position
+        assert_ok!(Pallet::<Test>::do_swap(
+            netuid,
+            OrderType::Buy,
+            100_000_000,
+            SqrtPrice::from_num(1),
+            false,
+            false,
+        ));
+
+        // The reported amounts match what removing the position returns
+        let position = Pallet::<Test>::get_positions(&OK_COLDKEY_ACCOUNT_ID)
+            .pop()
+            .unwrap();
+        assert!(position.fee_tao > TaoCurrency::ZERO);
+        let removed =
+            Pallet::<Test>::do_remove_liquidity(netuid, &OK_COLDKEY_ACCOUNT_ID, position_id)
+                .unwrap();
+        assert_eq!(position.tao, removed.tao);
+        assert_eq!(position.alpha, removed.alpha);
+        assert_eq!(position.fee_tao, removed.fee_tao);
+        assert_eq!(position.fee_alpha, removed.fee_alpha);
+    });
+}
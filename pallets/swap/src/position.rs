@@ -145,6 +145,13 @@ impl<T: Config> Position<T> {
         )
     }
 
+    /// Fees accrued by the position since they were last collected, without collecting them
+    ///
+    /// returns tuple of (TAO, Alpha)
+    pub fn uncollected_fees(&self) -> (u64, u64) {
+        self.clone().collect_fees()
+    }
+
     /// Get fees in a position's range
     ///
     /// If quote flag is true, Tao is returned, otherwise alpha.
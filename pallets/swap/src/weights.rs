@@ -19,6 +19,11 @@ pub trait WeightInfo {
     fn remove_liquidity() -> Weight;
     fn modify_position() -> Weight;
     fn toggle_user_liquidity() -> Weight;
+    fn place_limit_order() -> Weight;
+    fn cancel_limit_order() -> Weight;
+    fn fill_limit_order() -> Weight;
+    fn transfer_position() -> Weight;
+    fn set_position_operator() -> Weight;
 }
 
 /// Default weights for pallet_subtensor_swap.
@@ -48,7 +53,7 @@ impl<T: frame_system::Config> WeightInfo for DefaultWeight<T> {
     fn modify_position() -> Weight {
         // Conservative weight estimate for modify_position
         Weight::from_parts(50_000_000, 0)
-            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().reads(5))
             .saturating_add(T::DbWeight::get().writes(4))
     }
 
@@ -58,6 +63,50 @@ impl<T: frame_system::Config> WeightInfo for DefaultWeight<T> {
             .saturating_add(T::DbWeight::get().reads(1))
             .saturating_add(T::DbWeight::get().writes(1))
     }
+
+    fn place_limit_order() -> Weight {
+        // From the `place_limit_order` benchmark: almost full book and order count.
+        // Reads: subnet existence, subtoken, mechanism, SwapV3Initialized, AlphaSqrtPrice,
+        // balance or stake (4), AccountLimitOrders, LastLimitOrderId, LimitOrderBook.
+        // Writes: balance or stake (3), AccountLimitOrders, LastLimitOrderId, LimitOrderBook,
+        // LimitOrders.
+        Weight::from_parts(68_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(12))
+            .saturating_add(T::DbWeight::get().writes(7))
+    }
+
+    fn cancel_limit_order() -> Weight {
+        // From the `cancel_limit_order` benchmark: almost full book.
+        // Reads: LimitOrderBook (2), LimitOrders, AccountLimitOrders, refund (5).
+        // Writes: LimitOrders, LimitOrderBook, AccountLimitOrders, refund (4).
+        Weight::from_parts(56_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(9))
+            .saturating_add(T::DbWeight::get().writes(7))
+    }
+
+    fn fill_limit_order() -> Weight {
+        // From the `fill_limit_order` benchmark: one order filled against the pool.
+        // Reads: LimitOrders, price observation (2), swap state (10), reserves (2),
+        // settlement (6). Writes: price observation (2), swap state (6), settlement (6),
+        // LimitOrders, LimitOrderBook, AccountLimitOrders.
+        Weight::from_parts(95_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(21))
+            .saturating_add(T::DbWeight::get().writes(17))
+    }
+
+    fn transfer_position() -> Weight {
+        // Conservative weight estimate for transfer_position
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+
+    fn set_position_operator() -> Weight {
+        // Conservative weight estimate: one read and one write
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
 }
 
 // For backwards compatibility and tests
@@ -82,7 +131,7 @@ impl WeightInfo for () {
 
     fn modify_position() -> Weight {
         Weight::from_parts(50_000_000, 0)
-            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().reads(5))
             .saturating_add(RocksDbWeight::get().writes(4))
     }
 
@@ -91,4 +140,34 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(1))
             .saturating_add(RocksDbWeight::get().writes(1))
     }
+
+    fn place_limit_order() -> Weight {
+        Weight::from_parts(68_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(12))
+            .saturating_add(RocksDbWeight::get().writes(7))
+    }
+
+    fn cancel_limit_order() -> Weight {
+        Weight::from_parts(56_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(9))
+            .saturating_add(RocksDbWeight::get().writes(7))
+    }
+
+    fn fill_limit_order() -> Weight {
+        Weight::from_parts(95_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(21))
+            .saturating_add(RocksDbWeight::get().writes(17))
+    }
+
+    fn transfer_position() -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(3))
+    }
+
+    fn set_position_operator() -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
 }
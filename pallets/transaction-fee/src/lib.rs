@@ -22,6 +22,7 @@ use sp_runtime::{
 
 // Pallets
 use pallet_subtensor::Call as SubtensorCall;
+use pallet_subtensor::SponsoredCall;
 use pallet_transaction_payment::Config as PTPConfig;
 use pallet_transaction_payment::OnChargeTransaction;
 use subtensor_swap_interface::SwapHandler;
@@ -31,7 +32,7 @@ use core::marker::PhantomData;
 use smallvec::smallvec;
 use sp_std::vec::Vec;
 use substrate_fixed::types::U96F32;
-use subtensor_runtime_common::{Balance, Currency, NetUid};
+use subtensor_runtime_common::{Balance, Currency, NetUid, TaoCurrency};
 
 // Tests
 #[cfg(test)]
@@ -72,6 +73,16 @@ pub trait AlphaFeeHandler<T: frame_system::Config> {
         coldkey: &AccountIdOf<T>,
         hotkey: &AccountIdOf<T>,
     ) -> Vec<NetUid>;
+    fn get_fee_alpha_source(
+        who: &AccountIdOf<T>,
+        tao_amount: u64,
+        owner_pays: bool,
+    ) -> Option<(AccountIdOf<T>, AccountIdOf<T>, NetUid)>;
+    fn withdraw_from_fee_alpha_source(
+        who: &AccountIdOf<T>,
+        tao_amount: u64,
+        owner_pays: bool,
+    ) -> bool;
 }
 
 /// Deduct the transaction fee from the Subtensor Pallet TotalIssuance when charging the transaction
@@ -120,6 +131,7 @@ where
 {
     /// This function checks if tao_amount fee can be withdraw in Alpha currency
     /// by converting Alpha to TAO at the current price and ignoring slippage.
+    /// Alpha stake locked by vesting schedules can not pay fees.
     ///
     /// If this function returns true, the transaction will be included in the block
     /// and Alpha will be withdraw from the account, no matter whether transaction
@@ -146,7 +158,7 @@ where
         // and this approach still provides spam protection.
         alpha_vec.iter().any(|(hotkey, netuid)| {
             let alpha_balance = U96F32::saturating_from_num(
-                pallet_subtensor::Pallet::<T>::get_stake_for_hotkey_and_coldkey_on_subnet(
+                pallet_subtensor::Pallet::<T>::get_unlocked_stake_for_hotkey_and_coldkey_on_subnet(
                     hotkey, coldkey, *netuid,
                 ),
             );
@@ -169,7 +181,7 @@ where
         alpha_vec.iter().for_each(|(hotkey, netuid)| {
             // Divide tao_amount evenly among all alpha entries
             let alpha_balance = U96F32::saturating_from_num(
-                pallet_subtensor::Pallet::<T>::get_stake_for_hotkey_and_coldkey_on_subnet(
+                pallet_subtensor::Pallet::<T>::get_unlocked_stake_for_hotkey_and_coldkey_on_subnet(
                     hotkey, coldkey, *netuid,
                 ),
             );
@@ -203,23 +215,79 @@ where
             })
             .collect()
     }
+
+    /// Returns the (coldkey, hotkey, netuid) stake position among the fee alpha sources
+    /// that can pay the whole tao_amount fee of a transaction signed by `who`, including the
+    /// sources of its owner if `owner_pays` is set.
+    fn get_fee_alpha_source(
+        who: &AccountIdOf<T>,
+        tao_amount: u64,
+        owner_pays: bool,
+    ) -> Option<(AccountIdOf<T>, AccountIdOf<T>, NetUid)> {
+        pallet_subtensor::Pallet::<T>::get_fee_in_alpha(who, tao_amount, owner_pays)
+            .map(|(coldkey, hotkey, netuid, _)| (coldkey, hotkey, netuid))
+    }
+
+    /// Charges the tao_amount fee of a transaction signed by `who` from its fee alpha sources,
+    /// selling the alpha on the pool of the subnet. Returns whether the fee was charged.
+    fn withdraw_from_fee_alpha_source(
+        who: &AccountIdOf<T>,
+        tao_amount: u64,
+        owner_pays: bool,
+    ) -> bool {
+        pallet_subtensor::Pallet::<T>::withdraw_fee_in_alpha(who, tao_amount, owner_pays)
+    }
 }
 
-/// Enum that describes either a withdrawn amount of transaction fee in TAO or the
-/// fact that fee was charged in Alpha (without an amount because it is not needed)
+/// Enum that describes either a withdrawn amount of transaction fee in TAO, a withdrawn
+/// amount of transaction fee in TAO paid by the paymaster of a subnet or the fact that
+/// fee was charged in Alpha (without an amount because it is not needed)
 pub enum WithdrawnFee<T: frame_system::Config, F: Balanced<AccountIdOf<T>>> {
     Tao(Credit<AccountIdOf<T>, F>),
+    Sponsored(AccountIdOf<T>, NetUid, Credit<AccountIdOf<T>, F>),
     Alpha,
 }
 
+/// Maps the calls whose transaction fees a subnet paymaster can pay to their subnet. Runtimes
+/// implement it to sponsor calls of other pallets on top of the subtensor ones.
+pub trait SponsoredCalls<T: frame_system::Config> {
+    /// Returns the subnet and the kind of the sponsored call, if the call can be sponsored
+    fn sponsored_call(call: &CallOf<T>) -> Option<(NetUid, SponsoredCall)>;
+}
+
+/// The subtensor calls that subnet paymasters can sponsor
+pub struct SubtensorSponsoredCalls;
+
+impl<T> SponsoredCalls<T> for SubtensorSponsoredCalls
+where
+    T: frame_system::Config + pallet_subtensor::Config,
+    CallOf<T>: IsSubType<pallet_subtensor::Call<T>>,
+{
+    fn sponsored_call(call: &CallOf<T>) -> Option<(NetUid, SponsoredCall)> {
+        match call.is_sub_type() {
+            Some(SubtensorCall::burned_register { netuid, .. }) => {
+                Some((*netuid, SponsoredCall::BurnedRegister))
+            }
+            Some(SubtensorCall::queue_burned_registration { netuid, .. }) => {
+                Some((*netuid, SponsoredCall::QueueBurnedRegistration))
+            }
+            Some(SubtensorCall::serve_axon { netuid, .. })
+            | Some(SubtensorCall::serve_axon_tls { netuid, .. }) => {
+                Some((*netuid, SponsoredCall::ServeAxon))
+            }
+            _ => None,
+        }
+    }
+}
+
 /// Custom OnChargeTransaction implementation based on standard FungibleAdapter from transaction_payment
 /// FRAME pallet
 ///
-pub struct SubtensorTxFeeHandler<F, OU>(PhantomData<(F, OU)>);
+pub struct SubtensorTxFeeHandler<F, OU, SC = SubtensorSponsoredCalls>(PhantomData<(F, OU, SC)>);
 
 /// This implementation contains the list of calls that require paying transaction
 /// fees in Alpha
-impl<F, OU> SubtensorTxFeeHandler<F, OU> {
+impl<F, OU, SC> SubtensorTxFeeHandler<F, OU, SC> {
     /// Returns Vec<(hotkey, netuid)> if the given call should pay fees in Alpha instead of TAO.
     /// The vector represents all subnets where this hotkey has any alpha stake. Fees will be
     /// distributed evenly between subnets in case of multiple subnets.
@@ -232,8 +300,7 @@ impl<F, OU> SubtensorTxFeeHandler<F, OU> {
         let mut alpha_vec: Vec<(AccountIdOf<T>, NetUid)> = Vec::new();
 
         // Otherwise, switch to Alpha for the extrinsics that assume converting Alpha
-        // to TAO
-        // TODO: Populate the list
+        // to TAO. Other extrinsics may still pay in Alpha from the fee alpha sources.
         match call.is_sub_type() {
             Some(SubtensorCall::remove_stake { hotkey, netuid, .. }) => {
                 alpha_vec.push((hotkey.clone(), *netuid))
@@ -278,6 +345,11 @@ impl<F, OU> SubtensorTxFeeHandler<F, OU> {
                 origin_netuid,
                 ..
             }) => alpha_vec.push((hotkey.clone(), *origin_netuid)),
+            Some(SubtensorCall::swap_stake_route { hotkey, route, .. }) => {
+                if let Some(origin_netuid) = route.first() {
+                    alpha_vec.push((hotkey.clone(), *origin_netuid))
+                }
+            }
             Some(SubtensorCall::recycle_alpha {
                 hotkey,
                 amount: _,
@@ -288,19 +360,89 @@ impl<F, OU> SubtensorTxFeeHandler<F, OU> {
                 amount: _,
                 netuid,
             }) => alpha_vec.push((hotkey.clone(), *netuid)),
+            // The first new fee alpha source pays for setting the sources
+            Some(SubtensorCall::set_fee_alpha_sources { sources }) => {
+                if let Some((hotkey, netuid)) = sources.first() {
+                    alpha_vec.push((hotkey.clone(), *netuid))
+                }
+            }
             _ => {}
         }
 
         alpha_vec
     }
+
+    /// Returns whether the owner of the signing hotkey can pay the transaction fee of the given
+    /// call from its fee alpha sources. Only the calls a hotkey signs to operate on its subnets
+    /// are paid, and tips are never paid by the owner.
+    pub fn fee_paid_by_owner<T>(call: &CallOf<T>, tip: u64) -> bool
+    where
+        T: frame_system::Config + pallet_subtensor::Config,
+        CallOf<T>: IsSubType<pallet_subtensor::Call<T>>,
+    {
+        tip == 0
+            && matches!(
+                call.is_sub_type(),
+                Some(SubtensorCall::set_weights { .. })
+                    | Some(SubtensorCall::set_mechanism_weights { .. })
+                    | Some(SubtensorCall::batch_set_weights { .. })
+                    | Some(SubtensorCall::commit_weights { .. })
+                    | Some(SubtensorCall::commit_mechanism_weights { .. })
+                    | Some(SubtensorCall::batch_commit_weights { .. })
+                    | Some(SubtensorCall::reveal_weights { .. })
+                    | Some(SubtensorCall::reveal_mechanism_weights { .. })
+                    | Some(SubtensorCall::batch_reveal_weights { .. })
+                    | Some(SubtensorCall::commit_crv3_mechanism_weights { .. })
+                    | Some(SubtensorCall::commit_timelocked_weights { .. })
+                    | Some(SubtensorCall::commit_timelocked_mechanism_weights { .. })
+                    | Some(SubtensorCall::serve_axon { .. })
+                    | Some(SubtensorCall::serve_axon_tls { .. })
+                    | Some(SubtensorCall::serve_prometheus { .. })
+                    | Some(SubtensorCall::associate_evm_key { .. })
+            )
+    }
+
+    /// Returns (paymaster account, netuid) if the transaction fee of the given call is paid
+    /// by the paymaster of a subnet. Only the calls sponsored by the subnet are paid, within
+    /// the budgets of the paymaster, and tips are never sponsored.
+    pub fn fee_sponsor<T>(
+        who: &AccountIdOf<T>,
+        call: &CallOf<T>,
+        tao_amount: u64,
+        tip: u64,
+    ) -> Option<(AccountIdOf<T>, NetUid)>
+    where
+        T: frame_system::Config + pallet_subtensor::Config,
+        SC: SponsoredCalls<T>,
+    {
+        if tip != 0 {
+            return None;
+        }
+
+        let (netuid, sponsored_call) = SC::sponsored_call(call)?;
+
+        pallet_subtensor::Pallet::<T>::can_sponsor_fee(
+            netuid,
+            sponsored_call,
+            who,
+            TaoCurrency::from(tao_amount),
+        )
+        .then(|| {
+            (
+                pallet_subtensor::Pallet::<T>::paymaster_account(netuid),
+                netuid,
+            )
+        })
+    }
 }
 
-impl<T, F, OU> OnChargeTransaction<T> for SubtensorTxFeeHandler<F, OU>
+impl<T, F, OU, SC> OnChargeTransaction<T> for SubtensorTxFeeHandler<F, OU, SC>
 where
     T: PTPConfig + pallet_subtensor::Config,
     CallOf<T>: IsSubType<pallet_subtensor::Call<T>>,
     F: Balanced<T::AccountId>,
     OU: OnUnbalanced<Credit<T::AccountId, F>> + AlphaFeeHandler<T>,
+    SC: SponsoredCalls<T>,
     <F as Inspect<AccountIdOf<T>>>::Balance: Into<u64>,
 {
     type LiquidityInfo = Option<WithdrawnFee<T, F>>;
@@ -311,12 +453,30 @@ where
         call: &CallOf<T>,
         _dispatch_info: &DispatchInfoOf<CallOf<T>>,
         fee: Self::Balance,
-        _tip: Self::Balance,
+        tip: Self::Balance,
     ) -> Result<Self::LiquidityInfo, TransactionValidityError> {
         if fee.is_zero() {
             return Ok(None);
         }
 
+        // Subnet paymasters pay the fees of the calls they sponsor first
+        let sponsored = Self::fee_sponsor::<T>(who, call, fee.into(), tip.into()).and_then(
+            |(paymaster, netuid)| {
+                F::withdraw(
+                    &paymaster,
+                    fee,
+                    Precision::Exact,
+                    frame_support::traits::tokens::Preservation::Preserve,
+                    frame_support::traits::tokens::Fortitude::Polite,
+                )
+                .ok()
+                .map(|imbalance| WithdrawnFee::Sponsored(paymaster, netuid, imbalance))
+            },
+        );
+        if sponsored.is_some() {
+            return Ok(sponsored);
+        }
+
         // Traditional fees in TAO
         match F::withdraw(
             who,
@@ -327,9 +487,19 @@ where
         ) {
             Ok(imbalance) => Ok(Some(WithdrawnFee::Tao(imbalance))),
             Err(_) => {
+                let fee_u64: u64 = fee.into();
                 let alpha_vec = Self::fees_in_alpha::<T>(who, call);
+                if !alpha_vec.is_empty() && OU::can_withdraw_in_alpha(who, &alpha_vec, fee_u64) {
+                    OU::withdraw_in_alpha(who, &alpha_vec, fee_u64);
+                    return Ok(Some(WithdrawnFee::Alpha));
+                }
+                // Fall back to the fee alpha sources, for any call
+                let owner_pays = Self::fee_paid_by_owner::<T>(call, tip.into());
+                if OU::withdraw_from_fee_alpha_source(who, fee_u64, owner_pays) {
+                    return Ok(Some(WithdrawnFee::Alpha));
+                }
+                // The Alpha price may have changed since the validation, charge what is left
                 if !alpha_vec.is_empty() {
-                    let fee_u64: u64 = fee.into();
                     OU::withdraw_in_alpha(who, &alpha_vec, fee_u64);
                     return Ok(Some(WithdrawnFee::Alpha));
                 }
@@ -343,23 +513,39 @@ where
         call: &CallOf<T>,
         _dispatch_info: &DispatchInfoOf<CallOf<T>>,
         fee: Self::Balance,
-        _tip: Self::Balance,
+        tip: Self::Balance,
     ) -> Result<(), TransactionValidityError> {
         if fee.is_zero() {
             return Ok(());
         }
 
+        // Subnet paymasters pay the fees of the calls they sponsor first
+        let sponsored = Self::fee_sponsor::<T>(who, call, fee.into(), tip.into()).is_some_and(
+            |(paymaster, _)| {
+                matches!(
+                    F::can_withdraw(&paymaster, fee),
+                    WithdrawConsequence::Success
+                )
+            },
+        );
+        if sponsored {
+            return Ok(());
+        }
+
         // Prefer traditional fees in TAO
         match F::can_withdraw(who, fee) {
             WithdrawConsequence::Success => Ok(()),
             _ => {
                 // Fallback to fees in Alpha if possible
+                let fee_u64: u64 = fee.into();
                 let alpha_vec = Self::fees_in_alpha::<T>(who, call);
-                if !alpha_vec.is_empty() {
-                    let fee_u64: u64 = fee.into();
-                    if OU::can_withdraw_in_alpha(who, &alpha_vec, fee_u64) {
-                        return Ok(());
-                    }
+                if !alpha_vec.is_empty() && OU::can_withdraw_in_alpha(who, &alpha_vec, fee_u64) {
+                    return Ok(());
+                }
+                // Then to the fee alpha sources, for any call
+                let owner_pays = Self::fee_paid_by_owner::<T>(call, tip.into());
+                if OU::get_fee_alpha_source(who, fee_u64, owner_pays).is_some() {
+                    return Ok(());
                 }
                 Err(InvalidTransaction::Payment.into())
             }
@@ -375,33 +561,45 @@ where
         already_withdrawn: Self::LiquidityInfo,
     ) -> Result<(), TransactionValidityError> {
         if let Some(withdrawn) = already_withdrawn {
-            // Fee may be paid in TAO or in Alpha. Only refund and update total issuance for
-            // TAO fees because Alpha fees are charged precisely and do not need any adjustments
-            match withdrawn {
-                WithdrawnFee::Tao(paid) => {
-                    // Calculate how much refund we should return
-                    let refund_amount = paid.peek().saturating_sub(corrected_fee);
-                    // refund to the account that paid the fees if it exists. otherwise, don't refund
-                    // anything.
-                    let refund_imbalance = if F::total_balance(who) > F::Balance::zero() {
-                        F::deposit(who, refund_amount, Precision::BestEffort)
-                            .unwrap_or_else(|_| Debt::<T::AccountId, F>::zero())
-                    } else {
-                        Debt::<T::AccountId, F>::zero()
-                    };
-                    // merge the imbalance caused by paying the fees and refunding parts of it again.
-                    let adjusted_paid: Credit<T::AccountId, F> =
-                        paid.offset(refund_imbalance).same().map_err(|_| {
-                            TransactionValidityError::Invalid(InvalidTransaction::Payment)
-                        })?;
-                    // Call someone else to handle the imbalance (fee and tip separately)
-                    let (tip, fee) = adjusted_paid.split(tip);
-                    OU::on_unbalanceds(Some(fee).into_iter().chain(Some(tip)));
+            // Fee may be paid in TAO, by a subnet paymaster or in Alpha. Only refund and update
+            // total issuance for TAO fees because Alpha fees are charged precisely and do not
+            // need any adjustments
+            let (payer, paid) = match withdrawn {
+                WithdrawnFee::Tao(paid) => (who.clone(), paid),
+                WithdrawnFee::Sponsored(paymaster, netuid, paid) => {
+                    // Count the fee actually paid against the budgets of the paymaster
+                    let paid_fee: u64 = paid.peek().min(corrected_fee).into();
+                    pallet_subtensor::Pallet::<T>::record_sponsored_fee(
+                        netuid,
+                        who,
+                        paid_fee.into(),
+                    );
+                    (paymaster, paid)
                 }
                 WithdrawnFee::Alpha => {
                     // Subtensor does not refund Alpha fees, charges are final
+                    return Ok(());
                 }
-            }
+            };
+
+            // Calculate how much refund we should return
+            let refund_amount = paid.peek().saturating_sub(corrected_fee);
+            // refund to the account that paid the fees if it exists. otherwise, don't refund
+            // anything.
+            let refund_imbalance = if F::total_balance(&payer) > F::Balance::zero() {
+                F::deposit(&payer, refund_amount, Precision::BestEffort)
+                    .unwrap_or_else(|_| Debt::<T::AccountId, F>::zero())
+            } else {
+                Debt::<T::AccountId, F>::zero()
+            };
+            // merge the imbalance caused by paying the fees and refunding parts of it again.
+            let adjusted_paid: Credit<T::AccountId, F> = paid
+                .offset(refund_imbalance)
+                .same()
+                .map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
+            // Call someone else to handle the imbalance (fee and tip separately)
+            let (tip, fee) = adjusted_paid.split(tip);
+            OU::on_unbalanceds(Some(fee).into_iter().chain(Some(tip)));
         }
 
         Ok(())
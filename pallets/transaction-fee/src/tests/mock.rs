@@ -216,6 +216,10 @@ parameter_types! {
     pub const InitialKeySwapOnSubnetCost: u64 = 10_000_000;
     pub const HotkeySwapOnSubnetInterval: u64 = 7 * 24 * 60 * 60 / 12; // 7 days
     pub const LeaseDividendsDistributionInterval: u32 = 100; // 100 blocks
+    pub const LeaseProposalVotingPeriod: u32 = 10;
+    pub const MaxLeaseEmissionsShareChange: Percent = Percent::from_percent(10);
+    pub const MinLeaseProposalShare: Percent = Percent::from_percent(5);
+    pub const LeaseProposalCooldown: u32 = 10;
     pub const MaxImmuneUidsPercentage: Percent = Percent::from_percent(80);
     pub const EvmKeyAssociateRateLimit: u64 = 0;
 }
@@ -294,6 +298,10 @@ impl pallet_subtensor::Config for Test {
     type HotkeySwapOnSubnetInterval = HotkeySwapOnSubnetInterval;
     type ProxyInterface = ();
     type LeaseDividendsDistributionInterval = LeaseDividendsDistributionInterval;
+    type LeaseProposalVotingPeriod = LeaseProposalVotingPeriod;
+    type MaxLeaseEmissionsShareChange = MaxLeaseEmissionsShareChange;
+    type MinLeaseProposalShare = MinLeaseProposalShare;
+    type LeaseProposalCooldown = LeaseProposalCooldown;
     type GetCommitments = ();
     type MaxImmuneUidsPercentage = MaxImmuneUidsPercentage;
     type CommitmentsInterface = CommitmentsI;
@@ -322,6 +330,7 @@ parameter_types! {
     pub const MaximumBlockDuration: u64 = 100;
     pub const RefundContributorsLimit: u32 = 5;
     pub const MaxContributors: u32 = 10;
+    pub const MilestoneVotingPeriod: u64 = 10;
 }
 
 impl pallet_crowdloan::Config for Test {
@@ -337,6 +346,7 @@ impl pallet_crowdloan::Config for Test {
     type MaximumBlockDuration = MaximumBlockDuration;
     type RefundContributorsLimit = RefundContributorsLimit;
     type MaxContributors = MaxContributors;
+    type MilestoneVotingPeriod = MilestoneVotingPeriod;
 }
 
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
@@ -400,8 +410,13 @@ parameter_types! {
     pub const SwapProtocolId: PalletId = PalletId(*b"ten/swap");
     pub const SwapMaxFeeRate: u16 = 10000; // 15.26%
     pub const SwapMaxPositions: u32 = 100;
+    pub const SwapMaxPositionsPerAccount: u32 = 256;
     pub const SwapMinimumLiquidity: u64 = 1_000;
     pub const SwapMinimumReserve: NonZeroU64 = NonZeroU64::new(1_000_000).unwrap();
+    pub const SwapMaxLimitOrders: u32 = 100;
+    pub const SwapMaxLimitOrdersPerAccount: u32 = 10;
+    pub const SwapMaxPriceObservations: u32 = 100;
+    pub const SwapMinLimitOrderAmount: u64 = 1_000;
 }
 
 impl pallet_subtensor_swap::Config for Test {
@@ -411,8 +426,14 @@ impl pallet_subtensor_swap::Config for Test {
     type ProtocolId = SwapProtocolId;
     type MaxFeeRate = SwapMaxFeeRate;
     type MaxPositions = SwapMaxPositions;
+    type MaxPositionsPerAccount = SwapMaxPositionsPerAccount;
     type MinimumLiquidity = SwapMinimumLiquidity;
     type MinimumReserve = SwapMinimumReserve;
+    type MaxLimitOrders = SwapMaxLimitOrders;
+    type MaxLimitOrdersPerAccount = SwapMaxLimitOrdersPerAccount;
+    type MinLimitOrderAmount = SwapMinLimitOrderAmount;
+    type MinStake = pallet_subtensor::DefaultMinStake<Test>;
+    type MaxPriceObservations = SwapMaxPriceObservations;
     type WeightInfo = ();
 }
 
@@ -427,6 +448,17 @@ impl PrivilegeCmp<OriginCaller> for OriginPrivilegeCmp {
 pub struct CommitmentsI;
 impl pallet_subtensor::CommitmentsInterface for CommitmentsI {
     fn purge_netuid(_netuid: NetUid) {}
+
+    fn set_commitment_history_depth(_netuid: NetUid, _depth: u32) -> sp_runtime::DispatchResult {
+        Ok(())
+    }
+
+    fn set_commitment_schema(
+        _netuid: NetUid,
+        _schema: Option<pallet_commitments::CommitmentSchema>,
+    ) -> sp_runtime::DispatchResult {
+        Ok(())
+    }
 }
 
 parameter_types! {
@@ -457,6 +489,7 @@ impl pallet_drand::Config for Test {
     type Verifier = pallet_drand::verifier::QuicknetVerifier;
     type UnsignedPriority = ConstU64<{ 1 << 20 }>;
     type HttpFetchTimeout = ConstU64<1_000>;
+    type BeaconUsage = ();
 }
 
 impl frame_system::offchain::SigningTypes for Test {
@@ -1,7 +1,8 @@
 #![allow(clippy::indexing_slicing, clippy::unwrap_used)]
 use crate::TransactionSource;
 use frame_support::assert_ok;
-use frame_support::dispatch::GetDispatchInfo;
+use frame_support::dispatch::{GetDispatchInfo, Pays};
+use pallet_subtensor::staking::vesting::VestingSchedule;
 use pallet_subtensor_swap::AlphaSqrtPrice;
 use sp_runtime::{
     traits::{DispatchTransaction, TransactionExtension, TxBaseImplication},
@@ -1194,3 +1195,442 @@ fn test_recycle_alpha_fees_alpha() {
         assert!(actual_alpha_fee > 0.into());
     });
 }
+
+// cargo test --package subtensor-transaction-fee --lib -- tests::test_set_children_fees_alpha_sources --exact --show-output
+#[test]
+fn test_set_children_fees_alpha_sources() {
+    new_test_ext().execute_with(|| {
+        let stake_amount = TAO;
+        let sn = setup_subnets(2, 2);
+        let netuid0 = sn.subnets[0].netuid;
+        let netuid1 = sn.subnets[1].netuid;
+        setup_stake(netuid0, &sn.coldkey, &sn.hotkeys[0], stake_amount);
+        setup_stake(netuid1, &sn.coldkey, &sn.hotkeys[0], stake_amount);
+
+        // Forse-set signer balance to ED
+        let current_balance = Balances::free_balance(sn.coldkey);
+        let _ = SubtensorModule::remove_balance_from_coldkey_account(
+            &sn.coldkey,
+            current_balance - ExistentialDeposit::get(),
+        );
+
+        // For-set Alpha balance on the first subnet to low
+        let current_stake = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+            &sn.hotkeys[0],
+            &sn.coldkey,
+            netuid0,
+        );
+        SubtensorModule::decrease_stake_for_hotkey_and_coldkey_on_subnet(
+            &sn.hotkeys[0],
+            &sn.coldkey,
+            netuid0,
+            current_stake - AlphaCurrency::from(1),
+        );
+
+        let call = RuntimeCall::SubtensorModule(pallet_subtensor::Call::set_children {
+            hotkey: sn.hotkeys[0],
+            netuid: netuid1,
+            children: vec![(u64::MAX, sn.hotkeys[1])],
+        });
+        let info = call.get_dispatch_info();
+        let ext = pallet_transaction_payment::ChargeTransactionPayment::<Test>::from(0);
+
+        // Set children doesn't pay fees in Alpha without fee alpha sources
+        let result = ext.validate(
+            RuntimeOrigin::signed(sn.coldkey).into(),
+            &call.clone(),
+            &info,
+            0,
+            (),
+            &TxBaseImplication(()),
+            TransactionSource::External,
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            TransactionValidityError::Invalid(InvalidTransaction::Payment)
+        );
+
+        // The first source can't pay the fee, the second one is charged
+        assert_ok!(SubtensorModule::set_fee_alpha_sources(
+            RuntimeOrigin::signed(sn.coldkey),
+            vec![(sn.hotkeys[0], netuid0), (sn.hotkeys[0], netuid1)]
+                .try_into()
+                .unwrap()
+        ));
+        let fee = pallet_transaction_payment::Pallet::<Test>::compute_fee(0, &info, 0);
+        let (coldkey, hotkey, netuid, expected_alpha_fee) =
+            SubtensorModule::get_fee_in_alpha(&sn.coldkey, fee, false).unwrap();
+        assert_eq!(
+            (coldkey, hotkey, netuid),
+            (sn.coldkey, sn.hotkeys[0], netuid1)
+        );
+
+        let balance_before = Balances::free_balance(sn.coldkey);
+        let alpha_before_0 =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid0);
+        let alpha_before_1 =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid1);
+        assert_ok!(ext.dispatch_transaction(
+            RuntimeOrigin::signed(sn.coldkey).into(),
+            call,
+            &info,
+            0,
+            0,
+        ));
+
+        let final_balance = Balances::free_balance(sn.coldkey);
+        let alpha_after_0 =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid0);
+        let alpha_after_1 =
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(&hotkey, &coldkey, netuid1);
+
+        // Extrinsic should pay fees in Alpha from the second source only
+        assert_eq!(balance_before, final_balance);
+        assert_eq!(alpha_before_0, alpha_after_0);
+        assert!(expected_alpha_fee > 0.into());
+        assert!(
+            u64::from(alpha_before_1 - alpha_after_1).abs_diff(u64::from(expected_alpha_fee)) <= 1
+        );
+    });
+}
+
+// cargo test --package subtensor-transaction-fee --lib -- tests::test_set_fee_alpha_sources_fees_alpha --exact --show-output
+#[test]
+fn test_set_fee_alpha_sources_fees_alpha() {
+    new_test_ext().execute_with(|| {
+        let stake_amount = TAO;
+        let sn = setup_subnets(1, 1);
+        let netuid = sn.subnets[0].netuid;
+        setup_stake(netuid, &sn.coldkey, &sn.hotkeys[0], stake_amount);
+
+        // Forse-set signer balance to ED
+        let current_balance = Balances::free_balance(sn.coldkey);
+        let _ = SubtensorModule::remove_balance_from_coldkey_account(
+            &sn.coldkey,
+            current_balance - ExistentialDeposit::get(),
+        );
+
+        // Setting the sources is not free, its fee is paid from the first new source
+        let call = RuntimeCall::SubtensorModule(pallet_subtensor::Call::set_fee_alpha_sources {
+            sources: vec![(sn.hotkeys[0], netuid)].try_into().unwrap(),
+        });
+        let info = call.get_dispatch_info();
+        assert_eq!(info.pays_fee, Pays::Yes);
+        let ext = pallet_transaction_payment::ChargeTransactionPayment::<Test>::from(0);
+
+        let balance_before = Balances::free_balance(sn.coldkey);
+        let alpha_before = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+            &sn.hotkeys[0],
+            &sn.coldkey,
+            netuid,
+        );
+        assert_ok!(ext.dispatch_transaction(
+            RuntimeOrigin::signed(sn.coldkey).into(),
+            call,
+            &info,
+            0,
+            0,
+        ));
+        let alpha_after = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+            &sn.hotkeys[0],
+            &sn.coldkey,
+            netuid,
+        );
+
+        assert_eq!(Balances::free_balance(sn.coldkey), balance_before);
+        assert!(alpha_after < alpha_before);
+        assert_eq!(
+            pallet_subtensor::FeeAlphaSources::<Test>::get(sn.coldkey).into_inner(),
+            vec![(sn.hotkeys[0], netuid)]
+        );
+    });
+}
+
+// cargo test --package subtensor-transaction-fee --lib -- tests::test_vested_stake_does_not_pay_fees --exact --show-output
+#[test]
+fn test_vested_stake_does_not_pay_fees() {
+    new_test_ext().execute_with(|| {
+        let stake_amount = TAO;
+        let sn = setup_subnets(1, 2);
+        let netuid = sn.subnets[0].netuid;
+        setup_stake(netuid, &sn.coldkey, &sn.hotkeys[0], stake_amount);
+
+        // Forse-set signer balance to ED
+        let current_balance = Balances::free_balance(sn.coldkey);
+        let _ = SubtensorModule::remove_balance_from_coldkey_account(
+            &sn.coldkey,
+            current_balance - ExistentialDeposit::get(),
+        );
+
+        // Lock the whole position
+        let stake = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+            &sn.hotkeys[0],
+            &sn.coldkey,
+            netuid,
+        );
+        pallet_subtensor::VestingSchedules::<Test>::insert(
+            (sn.hotkeys[0], sn.coldkey, netuid),
+            vec![VestingSchedule {
+                grantor: sn.hotkeys[1],
+                total: stake,
+                start: 0,
+                cliff: 1_000,
+                duration: 1_000,
+            }],
+        );
+
+        assert_ok!(SubtensorModule::set_fee_alpha_sources(
+            RuntimeOrigin::signed(sn.coldkey),
+            vec![(sn.hotkeys[0], netuid)].try_into().unwrap()
+        ));
+        let call = RuntimeCall::SubtensorModule(pallet_subtensor::Call::set_children {
+            hotkey: sn.hotkeys[0],
+            netuid,
+            children: vec![(u64::MAX, sn.hotkeys[1])],
+        });
+        let info = call.get_dispatch_info();
+        let fee = pallet_transaction_payment::Pallet::<Test>::compute_fee(0, &info, 0);
+        assert!(SubtensorModule::get_fee_in_alpha(&sn.coldkey, fee, false).is_none());
+
+        let ext = pallet_transaction_payment::ChargeTransactionPayment::<Test>::from(0);
+        let result = ext.validate(
+            RuntimeOrigin::signed(sn.coldkey).into(),
+            &call,
+            &info,
+            0,
+            (),
+            &TxBaseImplication(()),
+            TransactionSource::External,
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            TransactionValidityError::Invalid(InvalidTransaction::Payment)
+        );
+    });
+}
+
+// cargo test --package subtensor-transaction-fee --lib -- tests::test_set_weights_fees_alpha_sources_of_owner --exact --show-output
+#[test]
+fn test_set_weights_fees_alpha_sources_of_owner() {
+    new_test_ext().execute_with(|| {
+        let stake_amount = TAO;
+        let sn = setup_subnets(1, 2);
+        let netuid = sn.subnets[0].netuid;
+        setup_stake(netuid, &sn.coldkey, &sn.hotkeys[0], stake_amount);
+        assert_eq!(Balances::free_balance(sn.hotkeys[0]), 0);
+
+        let call = RuntimeCall::SubtensorModule(pallet_subtensor::Call::set_weights {
+            netuid,
+            dests: vec![1],
+            weights: vec![u16::MAX],
+            version_key: 0,
+        });
+        let info = call.get_dispatch_info();
+        let ext = pallet_transaction_payment::ChargeTransactionPayment::<Test>::from(0);
+
+        // The hotkey has no balance and its owner has no fee alpha sources
+        let result = ext.validate(
+            RuntimeOrigin::signed(sn.hotkeys[0]).into(),
+            &call.clone(),
+            &info,
+            0,
+            (),
+            &TxBaseImplication(()),
+            TransactionSource::External,
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            TransactionValidityError::Invalid(InvalidTransaction::Payment)
+        );
+
+        // The owner pays the fees of the hotkey from the stake to it
+        assert_ok!(SubtensorModule::set_fee_alpha_sources(
+            RuntimeOrigin::signed(sn.coldkey),
+            vec![(sn.hotkeys[0], netuid)].try_into().unwrap()
+        ));
+
+        // But not its tips, nor the fees of the calls that don't operate the subnet
+        let tipped_ext = pallet_transaction_payment::ChargeTransactionPayment::<Test>::from(1);
+        let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+        for (ext, call) in [(&tipped_ext, &call), (&ext, &remark)] {
+            let result = ext.validate(
+                RuntimeOrigin::signed(sn.hotkeys[0]).into(),
+                call,
+                &call.get_dispatch_info(),
+                0,
+                (),
+                &TxBaseImplication(()),
+                TransactionSource::External,
+            );
+            assert_eq!(
+                result.unwrap_err(),
+                TransactionValidityError::Invalid(InvalidTransaction::Payment)
+            );
+        }
+
+        let alpha_before = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+            &sn.hotkeys[0],
+            &sn.coldkey,
+            netuid,
+        );
+        assert_ok!(ext.dispatch_transaction(
+            RuntimeOrigin::signed(sn.hotkeys[0]).into(),
+            call,
+            &info,
+            0,
+            0,
+        ));
+        let alpha_after = SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+            &sn.hotkeys[0],
+            &sn.coldkey,
+            netuid,
+        );
+
+        assert_eq!(Balances::free_balance(sn.hotkeys[0]), 0);
+        assert!(alpha_after < alpha_before);
+    });
+}
+
+// cargo test --package subtensor-transaction-fee --lib -- tests::test_burned_register_fees_paymaster --exact --show-output
+#[test]
+fn test_burned_register_fees_paymaster() {
+    new_test_ext().execute_with(|| {
+        let sn = setup_subnets(1, 1);
+        let netuid = sn.subnets[0].netuid;
+        let coldkey = U256::from(30000);
+        let hotkey = U256::from(30001);
+        let paymaster = SubtensorModule::paymaster_account(netuid);
+
+        // The miner only holds the burn
+        let burn = SubtensorModule::get_burn(netuid);
+        SubtensorModule::add_balance_to_coldkey_account(
+            &coldkey,
+            u64::from(burn) + ExistentialDeposit::get(),
+        );
+
+        let call = RuntimeCall::SubtensorModule(pallet_subtensor::Call::burned_register {
+            netuid,
+            hotkey,
+        });
+        let info = call.get_dispatch_info();
+        let ext = pallet_transaction_payment::ChargeTransactionPayment::<Test>::from(0);
+        let fee = pallet_transaction_payment::Pallet::<Test>::compute_fee(0, &info, 0);
+
+        // The subnet owner funds the paymaster, with an account budget lower than the fee
+        SubtensorModule::add_balance_to_coldkey_account(&sn.subnets[0].ck_owner, TAO);
+        assert_ok!(SubtensorModule::fund_paymaster(
+            RuntimeOrigin::signed(sn.subnets[0].ck_owner),
+            netuid,
+            TAO.into()
+        ));
+        assert_ok!(SubtensorModule::set_subnet_paymaster(
+            netuid,
+            vec![SponsoredCall::BurnedRegister],
+            (fee - 1).into(),
+            TAO.into(),
+        ));
+
+        let result = ext.validate(
+            RuntimeOrigin::signed(coldkey).into(),
+            &call.clone(),
+            &info,
+            0,
+            (),
+            &TxBaseImplication(()),
+            TransactionSource::External,
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            TransactionValidityError::Invalid(InvalidTransaction::Payment)
+        );
+
+        // Within the budget, the paymaster pays the fee
+        assert_ok!(SubtensorModule::set_subnet_paymaster(
+            netuid,
+            vec![SponsoredCall::BurnedRegister],
+            fee.into(),
+            TAO.into(),
+        ));
+        let paymaster_balance_before = Balances::free_balance(paymaster);
+        assert_ok!(ext.dispatch_transaction(
+            RuntimeOrigin::signed(coldkey).into(),
+            call,
+            &info,
+            0,
+            0,
+        ));
+
+        assert!(SubtensorModule::is_hotkey_registered_on_network(
+            netuid, &hotkey
+        ));
+        assert_eq!(Balances::free_balance(coldkey), ExistentialDeposit::get());
+        assert_eq!(
+            Balances::free_balance(paymaster),
+            paymaster_balance_before - fee
+        );
+        assert_eq!(
+            PaymasterAccountFees::<Test>::get(netuid, coldkey),
+            fee.into()
+        );
+    });
+}
+
+// cargo test --package subtensor-transaction-fee --lib -- tests::test_subtensor_sponsored_calls --exact --show-output
+#[test]
+fn test_subtensor_sponsored_calls() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(3);
+        let hotkey = U256::from(1);
+        let sponsored_call =
+            <crate::SubtensorSponsoredCalls as crate::SponsoredCalls<Test>>::sponsored_call;
+
+        let serve_axon = RuntimeCall::SubtensorModule(pallet_subtensor::Call::serve_axon {
+            netuid,
+            version: 1,
+            ip: 1676056785,
+            port: 128,
+            ip_type: 4,
+            protocol: 0,
+            placeholder1: 0,
+            placeholder2: 0,
+        });
+        assert_eq!(
+            sponsored_call(&serve_axon),
+            Some((netuid, SponsoredCall::ServeAxon))
+        );
+
+        let serve_axon_tls = RuntimeCall::SubtensorModule(pallet_subtensor::Call::serve_axon_tls {
+            netuid,
+            version: 1,
+            ip: 1676056785,
+            port: 128,
+            ip_type: 4,
+            protocol: 0,
+            placeholder1: 0,
+            placeholder2: 0,
+            certificate: vec![],
+        });
+        assert_eq!(
+            sponsored_call(&serve_axon_tls),
+            Some((netuid, SponsoredCall::ServeAxon))
+        );
+
+        let burned_register =
+            RuntimeCall::SubtensorModule(pallet_subtensor::Call::burned_register {
+                netuid,
+                hotkey,
+            });
+        assert_eq!(
+            sponsored_call(&burned_register),
+            Some((netuid, SponsoredCall::BurnedRegister))
+        );
+
+        // Other calls are never sponsored
+        let add_stake = RuntimeCall::SubtensorModule(pallet_subtensor::Call::add_stake {
+            hotkey,
+            netuid,
+            amount_staked: TAO.into(),
+        });
+        assert_eq!(sponsored_call(&add_stake), None);
+    });
+}
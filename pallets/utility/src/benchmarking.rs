@@ -22,6 +22,7 @@
 use alloc::vec;
 use frame_benchmarking::{benchmarking::add_to_whitelist, v2::*};
 use frame_system::RawOrigin;
+use sp_runtime::traits::Saturating;
 
 use crate::*;
 
@@ -31,6 +32,13 @@ fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
     frame_system::Pallet::<T>::assert_last_event(generic_event.into());
 }
 
+fn fund_scheduled_batch_deposit<T: Config>(who: &T::AccountId) {
+    let deposit = T::ScheduledBatchDepositBase::get().saturating_add(
+        T::ScheduledBatchDepositFactor::get().saturating_mul(MAX_SCHEDULED_CALLS_LEN.into()),
+    );
+    T::Currency::make_free_balance_be(who, T::Currency::minimum_balance().saturating_add(deposit));
+}
+
 #[benchmarks]
 mod benchmark {
     use super::*;
@@ -115,6 +123,41 @@ mod benchmark {
         _(RawOrigin::Signed(caller), main_call, fallback_call);
     }
 
+    #[benchmark]
+    fn schedule_batch(c: Linear<0, 1000>) {
+        let calls = vec![frame_system::Call::remark { remark: vec![] }.into(); c as usize];
+        let caller: T::AccountId = whitelisted_caller();
+        fund_scheduled_batch_deposit::<T>(&caller);
+        let now = frame_system::Pallet::<T>::block_number();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), calls, None, vec![], now, now);
+
+        assert_last_event::<T>(Event::BatchScheduled { id: 0 }.into());
+    }
+
+    #[benchmark]
+    fn cancel_scheduled_batch() {
+        let calls = vec![frame_system::Call::remark { remark: vec![] }.into()];
+        let caller: T::AccountId = whitelisted_caller();
+        fund_scheduled_batch_deposit::<T>(&caller);
+        let now = frame_system::Pallet::<T>::block_number();
+        Pallet::<T>::schedule_batch(
+            RawOrigin::Signed(caller.clone()).into(),
+            calls,
+            None,
+            vec![],
+            now,
+            now,
+        )
+        .expect("the batch is scheduled");
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), 0);
+
+        assert_last_event::<T>(Event::ScheduledBatchCancelled { id: 0 }.into());
+    }
+
     impl_benchmark_test_suite! {
         Pallet,
         tests::new_test_ext(),
@@ -37,7 +37,8 @@
 //!   and are thus hampered with the origin's filters.
 //!
 //! Since proxy filters are respected in all dispatches of this pallet, it should never need to be
-//! filtered by any proxy.
+//! filtered by any proxy, except for `schedule_batch`: the scheduled calls are checked against the
+//! filters when scheduled, but dispatched later from a new origin and not through the proxy.
 //!
 //! ## Interface
 //!
@@ -48,6 +49,15 @@
 //!
 //! #### For pseudonymal dispatch
 //! * `as_derivative` - Dispatch a call from a derivative signed origin.
+//!
+//! #### For scheduled dispatch
+//! * `schedule_batch` - Store a batch of calls to be dispatched from the sender's origin in a
+//!   later block, within a block range and once all its conditions hold.
+//! * `cancel_scheduled_batch` - Remove a scheduled batch and return its deposit.
+//!
+//! The scheduled batches are the only state of this pallet. They are dispatched in `on_idle`
+//! with the `if_else` semantics, the calls being atomically dispatched as with `batch_all`. Each
+//! account has a bounded number of pending batches, within a bounded block range.
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -59,23 +69,85 @@ pub mod weights;
 extern crate alloc;
 
 use alloc::{boxed::Box, vec::Vec};
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
+    BoundedVec,
     dispatch::{
         DispatchClass::{Normal, Operational},
         GetDispatchInfo, PostDispatchInfo, extract_actual_weight,
     },
-    traits::{IsSubType, OriginTrait, UnfilteredDispatchable},
+    traits::{
+        ConstU32, Currency, IsSubType, OriginTrait, ReservableCurrency, UnfilteredDispatchable,
+    },
+    weights::{Weight, WeightMeter},
 };
+use scale_info::TypeInfo;
 use sp_core::TypeId;
 use sp_io::hashing::blake2_256;
-use sp_runtime::traits::{BadOrigin, Dispatchable, TrailingZeroInput};
+use sp_runtime::{
+    RuntimeDebug,
+    traits::{BadOrigin, Dispatchable, TrailingZeroInput},
+};
 pub use weights::WeightInfo;
 
 use subtensor_macros::freeze_struct;
 
 pub use pallet::*;
 
+/// The maximum encoded length of the calls of a scheduled batch.
+pub const MAX_SCHEDULED_CALLS_LEN: u32 = 16 * 1024;
+/// The maximum number of conditions guarding a scheduled batch.
+pub const MAX_BATCH_CONDITIONS: u32 = 4;
+
+type BalanceOf<T> =
+    <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+type ScheduledBatchOf<T> = ScheduledBatch<
+    <T as frame_system::Config>::AccountId,
+    BalanceOf<T>,
+    frame_system::pallet_prelude::BlockNumberFor<T>,
+    <T as Config>::Condition,
+>;
+
+/// A batch of calls waiting to be dispatched.
+#[freeze_struct("7c15bc9ee41a8e3b")]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ScheduledBatch<AccountId, Balance, BlockNumber, Condition> {
+    /// The account the calls are dispatched from.
+    pub owner: AccountId,
+    /// The amount reserved from the owner until the batch is dispatched, cancelled or expired.
+    pub deposit: Balance,
+    /// The encoded `batch_all` call and the optional fallback call.
+    pub calls: BoundedVec<u8, ConstU32<MAX_SCHEDULED_CALLS_LEN>>,
+    /// The maximum weight of the dispatch, fallback included.
+    pub weight: Weight,
+    /// The conditions that must all hold for the batch to be dispatched.
+    pub conditions: BoundedVec<Condition, ConstU32<MAX_BATCH_CONDITIONS>>,
+    /// The first block the batch can be dispatched in.
+    pub not_before: BlockNumber,
+    /// The last block the batch can be dispatched in, it expires afterwards.
+    pub not_after: BlockNumber,
+}
+
+/// Checks the conditions guarding the scheduled batches.
+pub trait ConditionChecker<AccountId, Condition> {
+    /// Returns true if `condition` holds for a batch scheduled by `who`.
+    fn check(who: &AccountId, condition: &Condition) -> bool;
+
+    /// The weight of checking a single condition.
+    fn weight() -> Weight;
+}
+
+impl<AccountId, Condition> ConditionChecker<AccountId, Condition> for () {
+    fn check(_who: &AccountId, _condition: &Condition) -> bool {
+        true
+    }
+
+    fn weight() -> Weight {
+        Weight::zero()
+    }
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -97,6 +169,7 @@ pub mod pallet {
             + GetDispatchInfo
             + From<frame_system::Call<Self>>
             + UnfilteredDispatchable<RuntimeOrigin = Self::RuntimeOrigin>
+            + From<Call<Self>>
             + IsSubType<Call<Self>>
             + IsType<<Self as frame_system::Config>::RuntimeCall>;
 
@@ -107,8 +180,52 @@ pub mod pallet {
 
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
+
+        /// The currency mechanism, used to reserve the deposit of the scheduled batches.
+        type Currency: ReservableCurrency<Self::AccountId>;
+
+        /// A condition guarding the dispatch of a scheduled batch.
+        type Condition: Parameter + Member + MaxEncodedLen;
+
+        /// Checks the conditions of the scheduled batches.
+        type ConditionChecker: ConditionChecker<Self::AccountId, Self::Condition>;
+
+        /// The base amount of currency reserved for scheduling a batch.
+        #[pallet::constant]
+        type ScheduledBatchDepositBase: Get<BalanceOf<Self>>;
+
+        /// The amount of currency reserved per byte of the encoded calls of a scheduled batch.
+        #[pallet::constant]
+        type ScheduledBatchDepositFactor: Get<BalanceOf<Self>>;
+
+        /// The maximum number of pending scheduled batches of an account.
+        #[pallet::constant]
+        type MaxScheduledBatchesPerAccount: Get<u32>;
+
+        /// The maximum number of blocks between the scheduling of a batch and the end of its
+        /// block range.
+        #[pallet::constant]
+        type MaxScheduleHorizon: Get<BlockNumberFor<Self>>;
     }
 
+    /// The pending scheduled batches.
+    #[pallet::storage]
+    pub type ScheduledBatches<T: Config> =
+        StorageMap<_, Twox64Concat, u32, ScheduledBatchOf<T>, OptionQuery>;
+
+    /// The number of pending scheduled batches of an account.
+    #[pallet::storage]
+    pub type ScheduledBatchCount<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+    /// The id of the last scheduled batch visited by `on_idle`, the next block resumes after it.
+    #[pallet::storage]
+    pub type ScheduledBatchCursor<T> = StorageValue<_, u32, OptionQuery>;
+
+    /// The id of the next scheduled batch.
+    #[pallet::storage]
+    pub type NextScheduledBatchId<T> = StorageValue<_, u32, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event {
@@ -129,6 +246,14 @@ pub mod pallet {
         IfElseMainSuccess,
         /// The fallback call was dispatched.
         IfElseFallbackCalled { main_error: DispatchError },
+        /// A batch was scheduled.
+        BatchScheduled { id: u32 },
+        /// A scheduled batch was dispatched.
+        ScheduledBatchDispatched { id: u32, result: DispatchResult },
+        /// A scheduled batch was cancelled by its owner.
+        ScheduledBatchCancelled { id: u32 },
+        /// A scheduled batch expired before being dispatched.
+        ScheduledBatchExpired { id: u32 },
     }
 
     // Align the call size to 1KB. As we are currently compiling the runtime for native/wasm
@@ -164,12 +289,32 @@ pub mod pallet {
                 "Call enum size should be smaller than {CALL_ALIGN} bytes.",
             );
         }
+
+        fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            Self::dispatch_scheduled_batches(now, remaining_weight)
+        }
     }
 
     #[pallet::error]
     pub enum Error<T> {
         /// Too many calls batched.
         TooManyCalls,
+        /// Too many conditions guard the scheduled batch.
+        TooManyConditions,
+        /// The encoded calls of the scheduled batch are too long.
+        ScheduledCallsTooLong,
+        /// The scheduled batch is heavier than a normal extrinsic.
+        ScheduledBatchTooHeavy,
+        /// The block range of the scheduled batch is empty, in the past or too far ahead.
+        InvalidBlockRange,
+        /// The sender has too many batches scheduled.
+        TooManyScheduledBatches,
+        /// The scheduled batch does not exist.
+        ScheduledBatchNotFound,
+        /// The sender is not the owner of the scheduled batch.
+        NotScheduledBatchOwner,
+        /// The calls of the scheduled batch can no longer be decoded.
+        UndecodableScheduledBatch,
     }
 
     #[pallet::call]
@@ -519,54 +664,7 @@ pub mod pallet {
                 return Err(BadOrigin.into());
             }
 
-            let is_root = ensure_root(origin.clone()).is_ok();
-
-            // Track the weights
-            let mut weight = T::WeightInfo::if_else();
-
-            let main_info = main.get_dispatch_info();
-
-            // Execute the main call first
-            let main_result = if is_root {
-                main.dispatch_bypass_filter(origin.clone())
-            } else {
-                main.dispatch(origin.clone())
-            };
-
-            // Add weight of the main call
-            weight = weight.saturating_add(extract_actual_weight(&main_result, &main_info));
-
-            let Err(main_error) = main_result else {
-                // If the main result is Ok, we skip the fallback logic entirely
-                Self::deposit_event(Event::IfElseMainSuccess);
-                return Ok(Some(weight).into());
-            };
-
-            // If the main call failed, execute the fallback call
-            let fallback_info = fallback.get_dispatch_info();
-
-            let fallback_result = if is_root {
-                fallback.dispatch_bypass_filter(origin.clone())
-            } else {
-                fallback.dispatch(origin)
-            };
-
-            // Add weight of the fallback call
-            weight = weight.saturating_add(extract_actual_weight(&fallback_result, &fallback_info));
-
-            let Err(fallback_error) = fallback_result else {
-                // Fallback succeeded.
-                Self::deposit_event(Event::IfElseFallbackCalled {
-                    main_error: main_error.error,
-                });
-                return Ok(Some(weight).into());
-            };
-
-            // Both calls have failed, return fallback error
-            Err(sp_runtime::DispatchErrorWithPostInfo {
-                error: fallback_error.error,
-                post_info: Some(weight).into(),
-            })
+            Self::do_if_else(origin, *main, Some(*fallback))
         }
 
         /// Dispatches a function call with a provided origin.
@@ -597,6 +695,137 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Schedule a batch of calls to be dispatched from the sender's origin in a later block.
+        ///
+        /// The dispatch origin for this call must be _Signed_.
+        ///
+        /// - `calls`: The calls to be dispatched atomically, as with `batch_all`. The number of
+        ///   call must not exceed the constant: `batched_calls_limit` (available in constant
+        ///   metadata).
+        /// - `fallback`: The call to be dispatched if the `calls` fail, as with `if_else`.
+        /// - `conditions`: The conditions that must all hold for the batch to be dispatched.
+        /// - `not_before`: The first block the batch can be dispatched in.
+        /// - `not_after`: The last block the batch can be dispatched in. The batch expires
+        ///   afterwards. Must be at most `MaxScheduleHorizon` blocks ahead.
+        ///
+        /// The pending batches are dispatched in `on_idle` within the weight left in the block,
+        /// each block resuming where the previous one stopped. An account can have at most
+        /// `MaxScheduledBatchesPerAccount` pending batches. The origin filters of the sender
+        /// are checked on the calls when scheduling them, the dispatch only applies the base
+        /// call filter. The weight of the dispatch is charged when scheduling.
+        ///
+        /// A deposit proportional to the encoded size of the calls is reserved until the batch
+        /// is dispatched, cancelled or expired.
+        ///
+        /// ## Complexity
+        /// - O(C) where C is the number of calls to be batched.
+        #[pallet::call_index(8)]
+        #[pallet::weight(
+			T::WeightInfo::schedule_batch(calls.len() as u32)
+				.saturating_add(Pallet::<T>::scheduled_batch_weight(calls, fallback))
+		)]
+        pub fn schedule_batch(
+            origin: OriginFor<T>,
+            calls: Vec<<T as Config>::RuntimeCall>,
+            fallback: Option<Box<<T as Config>::RuntimeCall>>,
+            conditions: Vec<T::Condition>,
+            not_before: BlockNumberFor<T>,
+            not_after: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin.clone())?;
+            ensure!(
+                calls.len() <= Self::batched_calls_limit() as usize,
+                Error::<T>::TooManyCalls
+            );
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                not_before <= not_after
+                    && not_after >= now
+                    && not_after <= now.saturating_add(T::MaxScheduleHorizon::get()),
+                Error::<T>::InvalidBlockRange
+            );
+            let conditions: BoundedVec<_, _> = conditions
+                .try_into()
+                .map_err(|_| Error::<T>::TooManyConditions)?;
+
+            let weight = Self::scheduled_batch_weight(&calls, &fallback);
+            let block_weights = <T as frame_system::Config>::BlockWeights::get();
+            let max_weight = block_weights
+                .get(DispatchClass::Normal)
+                .max_extrinsic
+                .unwrap_or(block_weights.max_block);
+            ensure!(
+                weight.all_lte(max_weight),
+                Error::<T>::ScheduledBatchTooHeavy
+            );
+
+            // The batch is dispatched from a new origin, the filters of this one are checked now.
+            let main: <T as Config>::RuntimeCall = Call::<T>::batch_all {
+                calls: calls.clone(),
+            }
+            .into();
+            ensure!(
+                calls
+                    .iter()
+                    .chain(fallback.as_deref())
+                    .chain(core::iter::once(&main))
+                    .all(|call| origin.filter_call(call.into_ref())),
+                frame_system::Error::<T>::CallFiltered
+            );
+
+            let encoded = (&main, fallback.as_deref()).encode();
+            let deposit = T::ScheduledBatchDepositBase::get().saturating_add(
+                T::ScheduledBatchDepositFactor::get()
+                    .saturating_mul(BalanceOf::<T>::from(encoded.len() as u32)),
+            );
+            let calls =
+                BoundedVec::try_from(encoded).map_err(|_| Error::<T>::ScheduledCallsTooLong)?;
+
+            let count = ScheduledBatchCount::<T>::get(&who);
+            ensure!(
+                count < T::MaxScheduledBatchesPerAccount::get(),
+                Error::<T>::TooManyScheduledBatches
+            );
+            T::Currency::reserve(&who, deposit)?;
+
+            let id = NextScheduledBatchId::<T>::get();
+            ScheduledBatchCount::<T>::insert(&who, count.saturating_add(1));
+            ScheduledBatches::<T>::insert(
+                id,
+                ScheduledBatch {
+                    owner: who,
+                    deposit,
+                    calls,
+                    weight,
+                    conditions,
+                    not_before,
+                    not_after,
+                },
+            );
+            NextScheduledBatchId::<T>::put(id.wrapping_add(1));
+
+            Self::deposit_event(Event::BatchScheduled { id });
+            Ok(())
+        }
+
+        /// Cancel a scheduled batch and return its deposit.
+        ///
+        /// The dispatch origin for this call must be _Signed_ by the owner of the batch.
+        ///
+        /// - `id`: The id of the scheduled batch.
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::cancel_scheduled_batch())]
+        pub fn cancel_scheduled_batch(origin: OriginFor<T>, id: u32) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let batch = ScheduledBatches::<T>::get(id).ok_or(Error::<T>::ScheduledBatchNotFound)?;
+            ensure!(batch.owner == who, Error::<T>::NotScheduledBatchOwner);
+
+            Self::remove_scheduled_batch(id, &batch);
+
+            Self::deposit_event(Event::ScheduledBatchCancelled { id });
+            Ok(())
+        }
     }
 
     impl<T: Config> Pallet<T> {
@@ -627,6 +856,179 @@ pub mod pallet {
 
             (dispatch_weight, dispatch_class, pays)
         }
+
+        /// Dispatch `main` and, if it fails, `fallback`. Without a fallback the error of `main`
+        /// is returned.
+        fn do_if_else(
+            origin: OriginFor<T>,
+            main: <T as Config>::RuntimeCall,
+            fallback: Option<<T as Config>::RuntimeCall>,
+        ) -> DispatchResultWithPostInfo {
+            let is_root = ensure_root(origin.clone()).is_ok();
+
+            // Track the weights
+            let mut weight = T::WeightInfo::if_else();
+
+            let main_info = main.get_dispatch_info();
+
+            // Execute the main call first
+            let main_result = if is_root {
+                main.dispatch_bypass_filter(origin.clone())
+            } else {
+                main.dispatch(origin.clone())
+            };
+
+            // Add weight of the main call
+            weight = weight.saturating_add(extract_actual_weight(&main_result, &main_info));
+
+            let Err(main_error) = main_result else {
+                // If the main result is Ok, we skip the fallback logic entirely
+                Self::deposit_event(Event::IfElseMainSuccess);
+                return Ok(Some(weight).into());
+            };
+
+            let Some(fallback) = fallback else {
+                return Err(sp_runtime::DispatchErrorWithPostInfo {
+                    error: main_error.error,
+                    post_info: Some(weight).into(),
+                });
+            };
+
+            // If the main call failed, execute the fallback call
+            let fallback_info = fallback.get_dispatch_info();
+
+            let fallback_result = if is_root {
+                fallback.dispatch_bypass_filter(origin.clone())
+            } else {
+                fallback.dispatch(origin)
+            };
+
+            // Add weight of the fallback call
+            weight = weight.saturating_add(extract_actual_weight(&fallback_result, &fallback_info));
+
+            let Err(fallback_error) = fallback_result else {
+                // Fallback succeeded.
+                Self::deposit_event(Event::IfElseFallbackCalled {
+                    main_error: main_error.error,
+                });
+                return Ok(Some(weight).into());
+            };
+
+            // Both calls have failed, return fallback error
+            Err(sp_runtime::DispatchErrorWithPostInfo {
+                error: fallback_error.error,
+                post_info: Some(weight).into(),
+            })
+        }
+
+        /// The maximum weight of dispatching a scheduled batch.
+        pub(crate) fn scheduled_batch_weight(
+            calls: &[<T as Config>::RuntimeCall],
+            fallback: &Option<Box<<T as Config>::RuntimeCall>>,
+        ) -> Weight {
+            let (calls_weight, _, _) = Self::weight_and_dispatch_class(calls);
+            let fallback_weight = fallback
+                .as_ref()
+                .map(|call| call.get_dispatch_info().call_weight)
+                .unwrap_or_default();
+            T::WeightInfo::if_else()
+                .saturating_add(T::WeightInfo::batch_all(calls.len() as u32))
+                .saturating_add(calls_weight)
+                .saturating_add(fallback_weight)
+        }
+
+        /// Remove a scheduled batch and return its deposit.
+        fn remove_scheduled_batch(id: u32, batch: &ScheduledBatchOf<T>) {
+            ScheduledBatches::<T>::remove(id);
+            ScheduledBatchCount::<T>::mutate_exists(&batch.owner, |count| {
+                *count = count.map(|c| c.saturating_sub(1)).filter(|c| *c > 0);
+            });
+            T::Currency::unreserve(&batch.owner, batch.deposit);
+        }
+
+        /// Dispatch the scheduled batches whose conditions hold and remove the expired ones,
+        /// within `limit`. The batches are visited in storage order, resuming after the last
+        /// batch visited in the previous block. Returns the weight used.
+        pub(crate) fn dispatch_scheduled_batches(now: BlockNumberFor<T>, limit: Weight) -> Weight {
+            let mut meter = WeightMeter::with_limit(limit);
+            let db_weight = T::DbWeight::get();
+            // Reading and updating the cursor.
+            if meter.try_consume(db_weight.reads_writes(1, 1)).is_err() {
+                return meter.consumed();
+            }
+
+            // Reading, checking and removing a batch.
+            let batch_overhead = db_weight.reads_writes(3, 3).saturating_add(
+                T::ConditionChecker::weight().saturating_mul(MAX_BATCH_CONDITIONS as u64),
+            );
+
+            let mut cursor = ScheduledBatchCursor::<T>::get();
+            let mut batches = match cursor {
+                Some(id) => {
+                    ScheduledBatches::<T>::iter_from(ScheduledBatches::<T>::hashed_key_for(id))
+                }
+                None => ScheduledBatches::<T>::iter(),
+            };
+            loop {
+                if meter.try_consume(batch_overhead).is_err() {
+                    break;
+                }
+                // Start over from the first batch in the next block.
+                let Some((id, batch)) = batches.next() else {
+                    cursor = None;
+                    break;
+                };
+                cursor = Some(id);
+
+                if now > batch.not_after {
+                    Self::remove_scheduled_batch(id, &batch);
+                    Self::deposit_event(Event::ScheduledBatchExpired { id });
+                    continue;
+                }
+                if now < batch.not_before
+                    || !batch
+                        .conditions
+                        .iter()
+                        .all(|condition| T::ConditionChecker::check(&batch.owner, condition))
+                    || !meter.can_consume(batch.weight)
+                {
+                    continue;
+                }
+
+                // The deposit is returned first so that the calls can use it.
+                Self::remove_scheduled_batch(id, &batch);
+                let (result, weight) = Self::dispatch_scheduled_batch(&batch);
+                meter.consume(weight.min(batch.weight));
+
+                Self::deposit_event(Event::ScheduledBatchDispatched { id, result });
+            }
+            ScheduledBatchCursor::<T>::set(cursor);
+
+            meter.consumed()
+        }
+
+        /// Dispatch a scheduled batch from the origin of its owner. Returns the result and the
+        /// weight used.
+        fn dispatch_scheduled_batch(batch: &ScheduledBatchOf<T>) -> (DispatchResult, Weight) {
+            let Ok((main, fallback)) = <(
+                <T as Config>::RuntimeCall,
+                Option<<T as Config>::RuntimeCall>,
+            )>::decode(&mut batch.calls.as_slice()) else {
+                return (
+                    Err(Error::<T>::UndecodableScheduledBatch.into()),
+                    Weight::zero(),
+                );
+            };
+
+            let origin = frame_system::RawOrigin::Signed(batch.owner.clone()).into();
+            match Self::do_if_else(origin, main, fallback) {
+                Ok(post_info) => (Ok(()), post_info.actual_weight.unwrap_or(batch.weight)),
+                Err(err) => (
+                    Err(err.error),
+                    err.post_info.actual_weight.unwrap_or(batch.weight),
+                ),
+            }
+        }
     }
 }
 
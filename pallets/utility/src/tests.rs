@@ -23,17 +23,18 @@
 use super::*;
 
 use crate as utility;
+use codec::DecodeWithMemTracking;
 use frame_support::{
     assert_err_ignore_postinfo, assert_noop, assert_ok, derive_impl,
     dispatch::{DispatchErrorWithPostInfo, Pays},
     parameter_types, storage,
-    traits::{ConstU64, Contains},
+    traits::{ConstU32, ConstU64, Contains, Hooks},
     weights::Weight,
 };
 use pallet_subtensor_collective as pallet_collective;
 use pallet_subtensor_collective::{EnsureProportionAtLeast, Instance1};
 use sp_runtime::{
-    BuildStorage, DispatchError, TokenError,
+    BuildStorage, DispatchError, DispatchResult, TokenError,
     traits::{BadOrigin, BlakeTwo256, Dispatchable, Hash},
 };
 
@@ -240,11 +241,36 @@ impl mock_democracy::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type ExternalMajorityOrigin = EnsureProportionAtLeast<u64, Instance1, 3, 4>;
 }
+
+/// A condition holding while the owner of the batch has at least the given free balance.
+#[derive(
+    Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen,
+)]
+pub struct MinBalance(u64);
+
+pub struct MinBalanceChecker;
+impl ConditionChecker<u64, MinBalance> for MinBalanceChecker {
+    fn check(who: &u64, condition: &MinBalance) -> bool {
+        Balances::free_balance(who) >= condition.0
+    }
+
+    fn weight() -> Weight {
+        Weight::from_parts(1_000, 0)
+    }
+}
+
 impl Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type RuntimeCall = RuntimeCall;
     type PalletsOrigin = OriginCaller;
     type WeightInfo = ();
+    type Currency = Balances;
+    type Condition = MinBalance;
+    type ConditionChecker = MinBalanceChecker;
+    type ScheduledBatchDepositBase = ConstU64<1>;
+    type ScheduledBatchDepositFactor = ConstU64<1>;
+    type MaxScheduledBatchesPerAccount = ConstU32<2>;
+    type MaxScheduleHorizon = ConstU64<100>;
 }
 
 type ExampleCall = example::Call<Test>;
@@ -1195,3 +1221,218 @@ fn if_else_with_nested_if_else_works() {
         System::assert_last_event(utility::Event::IfElseMainSuccess.into());
     });
 }
+
+fn schedule_transfer(
+    who: u64,
+    value: u64,
+    fallback: Option<RuntimeCall>,
+    conditions: Vec<MinBalance>,
+    not_before: u64,
+    not_after: u64,
+) -> DispatchResult {
+    Utility::schedule_batch(
+        RuntimeOrigin::signed(who),
+        vec![call_transfer(2, value)],
+        fallback.map(Box::new),
+        conditions,
+        not_before,
+        not_after,
+    )
+}
+
+#[test]
+fn scheduled_batch_is_dispatched_once_its_conditions_hold() {
+    new_test_ext().execute_with(|| {
+        Balances::force_set_balance(RuntimeOrigin::root(), 1, 100).unwrap();
+        assert_ok!(schedule_transfer(1, 30, None, vec![MinBalance(100)], 3, 10));
+        System::assert_last_event(utility::Event::BatchScheduled { id: 0 }.into());
+
+        let batch = ScheduledBatches::<Test>::get(0).unwrap();
+        assert_eq!(batch.owner, 1);
+        assert_eq!(batch.deposit, 1 + batch.calls.len() as u64);
+        assert_eq!(Balances::reserved_balance(1), batch.deposit);
+        assert_eq!(ScheduledBatchCount::<Test>::get(1), 1);
+
+        // Not in the block range yet.
+        Utility::on_idle(2, Weight::MAX);
+        assert!(ScheduledBatches::<Test>::contains_key(0));
+
+        // The condition doesn't hold.
+        Utility::on_idle(3, Weight::MAX);
+        assert!(ScheduledBatches::<Test>::contains_key(0));
+        assert_eq!(Balances::free_balance(2), 10);
+
+        Balances::force_set_balance(RuntimeOrigin::root(), 1, 100).unwrap();
+        Utility::on_idle(4, Weight::MAX);
+        assert!(!ScheduledBatches::<Test>::contains_key(0));
+        assert!(!ScheduledBatchCount::<Test>::contains_key(1));
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::free_balance(1), 100 + batch.deposit - 30);
+        assert_eq!(Balances::free_balance(2), 40);
+        System::assert_last_event(
+            utility::Event::ScheduledBatchDispatched {
+                id: 0,
+                result: Ok(()),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn scheduled_batch_falls_back_or_expires() {
+    new_test_ext().execute_with(|| {
+        Balances::force_set_balance(RuntimeOrigin::root(), 1, 100).unwrap();
+        assert_ok!(schedule_transfer(
+            1,
+            1_000,
+            Some(call_transfer(2, 5)),
+            vec![],
+            1,
+            1
+        ));
+        assert_ok!(schedule_transfer(1, 5, None, vec![MinBalance(1_000)], 1, 2));
+
+        Utility::on_idle(1, Weight::MAX);
+        assert_eq!(Balances::free_balance(2), 15);
+        assert_eq!(
+            utility_events()
+                .into_iter()
+                .rev()
+                .take(2)
+                .collect::<Vec<_>>(),
+            vec![
+                utility::Event::ScheduledBatchDispatched {
+                    id: 0,
+                    result: Ok(())
+                },
+                utility::Event::IfElseFallbackCalled {
+                    main_error: TokenError::FundsUnavailable.into()
+                },
+            ]
+        );
+        assert!(ScheduledBatches::<Test>::contains_key(1));
+
+        // The second batch expires, its deposit is returned.
+        Utility::on_idle(3, Weight::MAX);
+        assert!(!ScheduledBatches::<Test>::contains_key(1));
+        assert!(!ScheduledBatchCount::<Test>::contains_key(1));
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::free_balance(2), 15);
+        System::assert_last_event(utility::Event::ScheduledBatchExpired { id: 1 }.into());
+    });
+}
+
+#[test]
+fn scheduled_batches_respect_the_weight_limit() {
+    new_test_ext().execute_with(|| {
+        Balances::force_set_balance(RuntimeOrigin::root(), 1, 100).unwrap();
+        let calls = vec![call_foobar(false, Weight::from_parts(1_000_000, 0), None)];
+        for _ in 0..2 {
+            assert_ok!(Utility::schedule_batch(
+                RuntimeOrigin::signed(1),
+                calls.clone(),
+                None,
+                vec![],
+                1,
+                10
+            ));
+        }
+        let weight = ScheduledBatches::<Test>::get(0).unwrap().weight;
+
+        // Only one batch fits.
+        Utility::on_idle(1, weight + Weight::from_parts(10_000, 0));
+        assert_eq!(ScheduledBatches::<Test>::iter().count(), 1);
+        assert_eq!(ScheduledBatchCount::<Test>::get(1), 1);
+
+        Utility::on_idle(2, Weight::MAX);
+        assert_eq!(ScheduledBatches::<Test>::iter().count(), 0);
+        assert_eq!(Balances::reserved_balance(1), 0);
+
+        // All the batches were visited, the next block starts over.
+        assert_eq!(ScheduledBatchCursor::<Test>::get(), None);
+    });
+}
+
+#[test]
+fn schedule_batch_checks_its_arguments() {
+    new_test_ext().execute_with(|| {
+        Balances::force_set_balance(RuntimeOrigin::root(), 1, 100).unwrap();
+        System::set_block_number(5);
+
+        assert_noop!(
+            schedule_transfer(1, 5, None, vec![], 7, 6),
+            Error::<Test>::InvalidBlockRange
+        );
+        assert_noop!(
+            schedule_transfer(1, 5, None, vec![], 1, 4),
+            Error::<Test>::InvalidBlockRange
+        );
+        assert_noop!(
+            schedule_transfer(1, 5, None, vec![], 5, 106),
+            Error::<Test>::InvalidBlockRange
+        );
+        assert_noop!(
+            schedule_transfer(1, 5, None, vec![MinBalance(0); 5], 5, 6),
+            Error::<Test>::TooManyConditions
+        );
+        assert_noop!(
+            Utility::schedule_batch(
+                RuntimeOrigin::signed(1),
+                vec![RuntimeCall::Balances(BalancesCall::transfer_keep_alive {
+                    dest: 2,
+                    value: 5
+                })],
+                None,
+                vec![],
+                5,
+                6
+            ),
+            frame_system::Error::<Test>::CallFiltered
+        );
+        assert_noop!(
+            schedule_transfer(5, 5, None, vec![], 5, 6),
+            pallet_balances::Error::<Test>::InsufficientBalance
+        );
+
+        assert_ok!(schedule_transfer(1, 5, None, vec![], 5, 105));
+        assert_ok!(schedule_transfer(1, 5, None, vec![], 5, 6));
+        assert_noop!(
+            schedule_transfer(1, 5, None, vec![], 5, 6),
+            Error::<Test>::TooManyScheduledBatches
+        );
+
+        // The limit is per account
+        Balances::force_set_balance(RuntimeOrigin::root(), 3, 100).unwrap();
+        assert_ok!(schedule_transfer(3, 5, None, vec![], 5, 6));
+        assert_ok!(Utility::cancel_scheduled_batch(RuntimeOrigin::signed(1), 0));
+        assert_ok!(schedule_transfer(1, 5, None, vec![], 5, 6));
+    });
+}
+
+#[test]
+fn cancel_scheduled_batch_works() {
+    new_test_ext().execute_with(|| {
+        Balances::force_set_balance(RuntimeOrigin::root(), 1, 100).unwrap();
+        assert_ok!(schedule_transfer(1, 5, None, vec![], 2, 3));
+
+        assert_noop!(
+            Utility::cancel_scheduled_batch(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::NotScheduledBatchOwner
+        );
+        assert_noop!(
+            Utility::cancel_scheduled_batch(RuntimeOrigin::signed(1), 1),
+            Error::<Test>::ScheduledBatchNotFound
+        );
+
+        assert_ok!(Utility::cancel_scheduled_batch(RuntimeOrigin::signed(1), 0));
+        System::assert_last_event(utility::Event::ScheduledBatchCancelled { id: 0 }.into());
+        assert!(!ScheduledBatches::<Test>::contains_key(0));
+        assert!(!ScheduledBatchCount::<Test>::contains_key(1));
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::free_balance(1), 100);
+
+        Utility::on_idle(2, Weight::MAX);
+        assert_eq!(Balances::free_balance(2), 10);
+    });
+}
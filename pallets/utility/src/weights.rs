@@ -62,6 +62,8 @@ pub trait WeightInfo {
 	fn force_batch(c: u32, ) -> Weight;
 	fn dispatch_as_fallible() -> Weight;
 	fn if_else() -> Weight;
+	fn schedule_batch(c: u32, ) -> Weight;
+	fn cancel_scheduled_batch() -> Weight;
 }
 
 /// Weights for `pallet_utility` using the Substrate node and recommended hardware.
@@ -150,6 +152,41 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(11_571_000, 7004)
 			.saturating_add(T::DbWeight::get().reads(3_u64))
 	}
+	/// Storage: `Utility::NextScheduledBatchId` (r:1 w:1)
+	/// Proof: `Utility::NextScheduledBatchId` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Utility::ScheduledBatchCount` (r:1 w:1)
+	/// Proof: `Utility::ScheduledBatchCount` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(104), added: 2579, mode: `MaxEncodedLen`)
+	/// Storage: `Utility::ScheduledBatches` (r:0 w:1)
+	/// Proof: `Utility::ScheduledBatches` (`max_values`: None, `max_size`: Some(16732), added: 19207, mode: `MaxEncodedLen`)
+	/// The range of component `c` is `[0, 1000]`.
+	fn schedule_batch(c: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `154`
+		//  Estimated: `3569`
+		// Minimum execution time: 27_310_000 picoseconds.
+		Weight::from_parts(28_442_000, 3569)
+			// Standard Error: 1_904
+			.saturating_add(Weight::from_parts(1_283_511, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Utility::ScheduledBatches` (r:1 w:1)
+	/// Proof: `Utility::ScheduledBatches` (`max_values`: None, `max_size`: Some(16732), added: 19207, mode: `MaxEncodedLen`)
+	/// Storage: `Utility::ScheduledBatchCount` (r:1 w:1)
+	/// Proof: `Utility::ScheduledBatchCount` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(104), added: 2579, mode: `MaxEncodedLen`)
+	fn cancel_scheduled_batch() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `402`
+		//  Estimated: `20197`
+		// Minimum execution time: 24_861_000 picoseconds.
+		Weight::from_parts(25_610_000, 20197)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -237,4 +274,39 @@ impl WeightInfo for () {
 		Weight::from_parts(11_571_000, 7004)
 			.saturating_add(RocksDbWeight::get().reads(3_u64))
 	}
+	/// Storage: `Utility::NextScheduledBatchId` (r:1 w:1)
+	/// Proof: `Utility::NextScheduledBatchId` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Utility::ScheduledBatchCount` (r:1 w:1)
+	/// Proof: `Utility::ScheduledBatchCount` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(104), added: 2579, mode: `MaxEncodedLen`)
+	/// Storage: `Utility::ScheduledBatches` (r:0 w:1)
+	/// Proof: `Utility::ScheduledBatches` (`max_values`: None, `max_size`: Some(16732), added: 19207, mode: `MaxEncodedLen`)
+	/// The range of component `c` is `[0, 1000]`.
+	fn schedule_batch(c: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `154`
+		//  Estimated: `3569`
+		// Minimum execution time: 27_310_000 picoseconds.
+		Weight::from_parts(28_442_000, 3569)
+			// Standard Error: 1_904
+			.saturating_add(Weight::from_parts(1_283_511, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Utility::ScheduledBatches` (r:1 w:1)
+	/// Proof: `Utility::ScheduledBatches` (`max_values`: None, `max_size`: Some(16732), added: 19207, mode: `MaxEncodedLen`)
+	/// Storage: `Utility::ScheduledBatchCount` (r:1 w:1)
+	/// Proof: `Utility::ScheduledBatchCount` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(104), added: 2579, mode: `MaxEncodedLen`)
+	fn cancel_scheduled_batch() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `402`
+		//  Estimated: `20197`
+		// Minimum execution time: 24_861_000 picoseconds.
+		Weight::from_parts(25_610_000, 20197)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
 }
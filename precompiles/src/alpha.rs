@@ -1,7 +1,8 @@
 use core::marker::PhantomData;
 
 use fp_evm::{ExitError, PrecompileFailure};
-use pallet_evm::{BalanceConverter, PrecompileHandle, SubstrateBalance};
+use frame_support::traits::Get;
+use pallet_evm::{BalanceConverter, GasWeightMapping, PrecompileHandle, SubstrateBalance};
 use precompile_utils::EvmResult;
 use sp_core::U256;
 use sp_std::vec::Vec;
@@ -59,6 +60,40 @@ where
         Ok(price_eth)
     }
 
+    #[precompile::public("getTwapAlphaPrice(uint16,uint64)")]
+    #[precompile::view]
+    fn get_twap_alpha_price(
+        handle: &mut impl PrecompileHandle,
+        netuid: u16,
+        window_blocks: u64,
+    ) -> EvmResult<U256> {
+        if window_blocks == 0 {
+            return Err(PrecompileFailure::Error {
+                exit_status: ExitError::Other("window must be at least one block".into()),
+            });
+        }
+
+        // The oracle lookup is a binary search over the stored observations
+        let reads = pallet_subtensor_swap::Pallet::<R>::twap_max_reads();
+        handle.record_cost(<R as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+            <R as frame_system::Config>::DbWeight::get().reads(reads),
+        ))?;
+
+        let price = pallet_subtensor_swap::Pallet::<R>::twap(netuid.into(), window_blocks)
+            .ok_or_else(|| PrecompileFailure::Error {
+                exit_status: ExitError::Other("insufficient price observations".into()),
+            })?;
+        let price: SubstrateBalance = price
+            .saturating_mul(U96F32::saturating_from_num(1_000_000_000))
+            .saturating_to_num::<u64>()
+            .into();
+        let price_eth = <R as pallet_evm::Config>::BalanceConverter::into_evm_balance(price)
+            .map(|amount| amount.into_u256())
+            .ok_or(ExitError::InvalidRange)?;
+
+        Ok(price_eth)
+    }
+
     #[precompile::public("getTaoInPool(uint16)")]
     #[precompile::view]
     fn get_tao_in_pool(_handle: &mut impl PrecompileHandle, netuid: u16) -> EvmResult<u64> {
@@ -13,6 +13,15 @@ interface IAlpha {
     /// @return The moving alpha price in RAO per alpha.
     function getMovingAlphaPrice(uint16 netuid) external view returns (uint256);
 
+    /// @dev Returns the time-weighted average alpha price for a subnet.
+    /// @param netuid The subnet identifier.
+    /// @param windowBlocks The number of past blocks to average the price over, at least one.
+    /// @return The time-weighted average alpha price in RAO per alpha.
+    function getTwapAlphaPrice(
+        uint16 netuid,
+        uint64 windowBlocks
+    ) external view returns (uint256);
+
     /// @dev Returns the amount of TAO in the pool for a subnet.
     /// @param netuid The subnet identifier.
     /// @return The TAO amount in the pool.
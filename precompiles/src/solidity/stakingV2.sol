@@ -165,6 +165,24 @@ interface IStaking {
      */
     function removeProxy(bytes32 delegate) external;
 
+    /**
+     * @dev Delegates staking on a single subnet to a proxy account.
+     *
+     * The proxy can only make staking calls whose netuids are all `netuid`.
+     *
+     * @param delegate The public key (32 bytes) of the delegate.
+     * @param netuid The subnet the proxy is limited to (uint256).
+     */
+    function addSubnetProxy(bytes32 delegate, uint256 netuid) external;
+
+    /**
+     * @dev Removes subnet staking proxy account.
+     *
+     * @param delegate The public key (32 bytes) of the delegate.
+     * @param netuid The subnet the proxy is limited to (uint256).
+     */
+    function removeSubnetProxy(bytes32 delegate, uint256 netuid) external;
+
     /**
      * @dev Returns the validators that have staked alpha under a hotkey.
      *
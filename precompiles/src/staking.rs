@@ -328,6 +328,44 @@ where
         handle.try_dispatch_runtime_call::<R, _>(call, RawOrigin::Signed(account_id))
     }
 
+    #[precompile::public("addSubnetProxy(bytes32,uint256)")]
+    fn add_subnet_proxy(
+        handle: &mut impl PrecompileHandle,
+        delegate: H256,
+        netuid: U256,
+    ) -> EvmResult<()> {
+        let account_id = handle.caller_account_id::<R>();
+        let netuid = try_u16_from_u256(netuid)?;
+        let delegate = R::AccountId::from(delegate.0);
+        let delegate = <R as frame_system::Config>::Lookup::unlookup(delegate);
+        let call = pallet_proxy::Call::<R>::add_proxy {
+            delegate,
+            proxy_type: ProxyType::SubnetStaking(netuid.into()),
+            delay: 0u32.into(),
+        };
+
+        handle.try_dispatch_runtime_call::<R, _>(call, RawOrigin::Signed(account_id))
+    }
+
+    #[precompile::public("removeSubnetProxy(bytes32,uint256)")]
+    fn remove_subnet_proxy(
+        handle: &mut impl PrecompileHandle,
+        delegate: H256,
+        netuid: U256,
+    ) -> EvmResult<()> {
+        let account_id = handle.caller_account_id::<R>();
+        let netuid = try_u16_from_u256(netuid)?;
+        let delegate = R::AccountId::from(delegate.0);
+        let delegate = <R as frame_system::Config>::Lookup::unlookup(delegate);
+        let call = pallet_proxy::Call::<R>::remove_proxy {
+            delegate,
+            proxy_type: ProxyType::SubnetStaking(netuid.into()),
+            delay: 0u32.into(),
+        };
+
+        handle.try_dispatch_runtime_call::<R, _>(call, RawOrigin::Signed(account_id))
+    }
+
     #[precompile::public("addStakeLimit(bytes32,uint256,uint256,bool,uint256)")]
     fn add_stake_limit(
         handle: &mut impl PrecompileHandle,
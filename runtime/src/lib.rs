@@ -31,6 +31,7 @@ use pallet_registry::CanRegisterIdentity;
 use pallet_subtensor::rpc_info::{
     delegate_info::DelegateInfo,
     dynamic_info::DynamicInfo,
+    epoch_simulation::{EpochHyperparamOverrides, SimulatedEpochTerms},
     metagraph::{Metagraph, SelectiveMetagraph},
     neuron_info::{NeuronInfo, NeuronInfoLite},
     show_subnet::SubnetState,
@@ -39,7 +40,9 @@ use pallet_subtensor::rpc_info::{
 };
 use pallet_subtensor_collective as pallet_collective;
 use pallet_subtensor_proxy as pallet_proxy;
-use pallet_subtensor_swap_runtime_api::SimSwapResult;
+use pallet_subtensor_swap_runtime_api::{
+    LimitOrderInfo, LiquidityRange, PositionInfo, SimSwapResult, SimSwapRouteLeg, TickInfo,
+};
 use pallet_subtensor_utility as pallet_utility;
 use runtime_common::prod_or_fast;
 use sp_api::impl_runtime_apis;
@@ -92,7 +95,9 @@ use pallet_transaction_payment::{ConstFeeMultiplier, Multiplier};
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
 pub use sp_runtime::{Perbill, Permill};
-use subtensor_transaction_fee::{SubtensorTxFeeHandler, TransactionFeeHandler};
+use subtensor_transaction_fee::{
+    SponsoredCalls, SubtensorSponsoredCalls, SubtensorTxFeeHandler, TransactionFeeHandler,
+};
 
 use core::marker::PhantomData;
 
@@ -112,6 +117,24 @@ impl pallet_drand::Config for Runtime {
     type Verifier = pallet_drand::verifier::QuicknetVerifier;
     type UnsignedPriority = ConstU64<{ 1 << 20 }>;
     type HttpFetchTimeout = ConstU64<1_000>;
+    type BeaconUsage = DrandBeaconUsage;
+}
+
+/// Subnets refer to additional drand beacons for timelocked weights and commitments.
+pub struct DrandBeaconUsage;
+impl pallet_drand::BeaconUsage for DrandBeaconUsage {
+    fn is_beacon_used(chain_hash: &pallet_drand::types::ChainHash) -> bool {
+        pallet_subtensor::TimelockedWeightsBeacon::<Runtime>::iter_values()
+            .any(|beacon| &beacon == chain_hash)
+            || pallet_commitments::TimelockBeacon::<Runtime>::iter_values()
+                .any(|beacon| &beacon == chain_hash)
+    }
+
+    fn is_beacon_used_weight() -> Weight {
+        // Both maps have at most one entry per subnet
+        <Runtime as frame_system::Config>::DbWeight::get()
+            .reads(u64::from(SubtensorModule::get_max_subnets()).saturating_mul(2))
+    }
 }
 
 impl frame_system::offchain::SigningTypes for Runtime {
@@ -223,7 +246,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     //   `spec_version`, and `authoring_version` are the same between Wasm and native.
     // This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
     //   the compatible custom types.
-    spec_version: 323,
+    spec_version: 348,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
     transaction_version: 1,
@@ -394,11 +417,76 @@ impl pallet_timestamp::Config for Runtime {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    // One storage item of at most 240 bytes besides the calls.
+    pub const ScheduledBatchDepositBase: Balance = deposit(1, 240);
+    pub const ScheduledBatchDepositFactor: Balance = deposit(0, 1);
+    pub const MaxScheduledBatchesPerAccount: u32 = 16;
+    pub const MaxScheduleHorizon: BlockNumber = 7 * DAYS;
+}
+
+/// The window, in blocks, of the average alpha price checked by scheduled batch conditions.
+pub const BATCH_CONDITION_TWAP_WINDOW: u64 = 50;
+
+pub struct BatchConditionChecker;
+impl pallet_utility::ConditionChecker<AccountId, BatchCondition<AccountId>>
+    for BatchConditionChecker
+{
+    fn check(who: &AccountId, condition: &BatchCondition<AccountId>) -> bool {
+        // The time-weighted average price of one alpha, in rao. A price condition never holds
+        // while the subnet lacks the observations to cover the window.
+        let alpha_price = |netuid: &NetUid| -> Option<u64> {
+            use substrate_fixed::types::U96F32;
+
+            pallet_subtensor_swap::Pallet::<Runtime>::twap(*netuid, BATCH_CONDITION_TWAP_WINDOW)
+                .map(|price| {
+                    price
+                        .saturating_mul(U96F32::from_num(1_000_000_000))
+                        .saturating_to_num()
+                })
+        };
+        let stake = |hotkey: &AccountId, netuid: &NetUid| {
+            SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(hotkey, who, *netuid)
+        };
+
+        match condition {
+            BatchCondition::AlphaPriceAtMost { netuid, price } => {
+                alpha_price(netuid).is_some_and(|current| current <= u64::from(*price))
+            }
+            BatchCondition::AlphaPriceAtLeast { netuid, price } => {
+                alpha_price(netuid).is_some_and(|current| current >= u64::from(*price))
+            }
+            BatchCondition::StakeAtLeast {
+                hotkey,
+                netuid,
+                amount,
+            } => stake(hotkey, netuid) >= *amount,
+            BatchCondition::StakeAtMost {
+                hotkey,
+                netuid,
+                amount,
+            } => stake(hotkey, netuid) <= *amount,
+        }
+    }
+
+    fn weight() -> Weight {
+        <Runtime as frame_system::Config>::DbWeight::get()
+            .reads(pallet_subtensor_swap::Pallet::<Runtime>::twap_max_reads().max(4))
+    }
+}
+
 impl pallet_utility::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type RuntimeCall = RuntimeCall;
     type PalletsOrigin = OriginCaller;
     type WeightInfo = pallet_utility::weights::SubstrateWeight<Runtime>;
+    type Currency = Balances;
+    type Condition = BatchCondition<AccountId>;
+    type ConditionChecker = BatchConditionChecker;
+    type ScheduledBatchDepositBase = ScheduledBatchDepositBase;
+    type ScheduledBatchDepositFactor = ScheduledBatchDepositFactor;
+    type MaxScheduledBatchesPerAccount = MaxScheduledBatchesPerAccount;
+    type MaxScheduleHorizon = MaxScheduleHorizon;
 }
 
 parameter_types! {
@@ -479,9 +567,24 @@ parameter_types! {
     pub FeeMultiplier: Multiplier = Multiplier::one();
 }
 
+/// The calls whose transaction fees subnet paymasters can pay, in subtensor and the other pallets
+pub struct RuntimeSponsoredCalls;
+
+impl SponsoredCalls<Runtime> for RuntimeSponsoredCalls {
+    fn sponsored_call(call: &RuntimeCall) -> Option<(NetUid, pallet_subtensor::SponsoredCall)> {
+        match call {
+            RuntimeCall::Commitments(pallet_commitments::Call::set_commitment {
+                netuid, ..
+            }) => Some((*netuid, pallet_subtensor::SponsoredCall::SetCommitment)),
+            _ => <SubtensorSponsoredCalls as SponsoredCalls<Runtime>>::sponsored_call(call),
+        }
+    }
+}
+
 impl pallet_transaction_payment::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
-    type OnChargeTransaction = SubtensorTxFeeHandler<Balances, TransactionFeeHandler<Runtime>>;
+    type OnChargeTransaction =
+        SubtensorTxFeeHandler<Balances, TransactionFeeHandler<Runtime>, RuntimeSponsoredCalls>;
     // Convert dispatch weight to a chargeable fee.
     type WeightToFee = subtensor_transaction_fee::LinearWeightToFee;
     type OperationalFeeMultiplier = OperationalFeeMultiplier;
@@ -519,7 +622,8 @@ impl CanVote<AccountId> for CanVoteToTriumvirate {
 }
 
 use pallet_subtensor::{
-    CollectiveInterface, CommitmentsInterface, MemberManagement, ProxyInterface,
+    CollectiveInterface, CommitmentsInterface, EmissionLedgerEntry, EpochDebugSnapshot,
+    MemberManagement, ProxyInterface,
 };
 pub struct ManageSenateMembers;
 impl MemberManagement<AccountId> for ManageSenateMembers {
@@ -667,28 +771,42 @@ impl pallet_multisig::Config for Runtime {
 parameter_types! {
     // One storage item; key size sizeof(AccountId) = 32, value sizeof(Balance) = 8; 40 total
     pub const ProxyDepositBase: Balance = deposit(1, 40);
-    // Adding 32 bytes + sizeof(ProxyType) = 32 + 1
-    pub const ProxyDepositFactor: Balance = deposit(0, 33);
+    // Adding 32 bytes + sizeof(ProxyType) = 32 + 3
+    pub const ProxyDepositFactor: Balance = deposit(0, 35);
     pub const MaxProxies: u32 = 20; // max num proxies per acct
     pub const MaxPending: u32 = 15 * 5; // max blocks pending ~15min
     // 16 bytes
     pub const AnnouncementDepositBase: Balance =  deposit(1, 16);
     // 68 bytes per announcement
     pub const AnnouncementDepositFactor: Balance = deposit(0, 68);
+    pub const MaxProxyAllowances: u32 = 4; // max num allowances per proxy
 }
 
 impl InstanceFilter<RuntimeCall> for ProxyType {
     fn filter(&self, c: &RuntimeCall) -> bool {
+        // Scheduled batches are dispatched later from the account itself, out of the reach of
+        // the filters and the allowances of the proxy.
+        if matches!(
+            c,
+            RuntimeCall::Utility(pallet_utility::Call::schedule_batch { .. })
+        ) {
+            return false;
+        }
+
         match self {
             ProxyType::Any => true,
             ProxyType::NonTransfer => !matches!(
                 c,
                 RuntimeCall::Balances(..)
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::transfer_stake { .. })
+                    | RuntimeCall::SubtensorModule(
+                        pallet_subtensor::Call::grant_vested_stake { .. }
+                    )
                     | RuntimeCall::SubtensorModule(
                         pallet_subtensor::Call::schedule_swap_coldkey { .. }
                     )
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::swap_coldkey { .. })
+                    | RuntimeCall::Swap(pallet_subtensor_swap::Call::transfer_position { .. })
             ),
             ProxyType::NonFungibile => !matches!(
                 c,
@@ -708,15 +826,27 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
                     )
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::swap_stake { .. })
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::swap_stake_limit { .. })
+                    | RuntimeCall::SubtensorModule(pallet_subtensor::Call::swap_stake_route { .. })
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::move_stake { .. })
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::transfer_stake { .. })
+                    | RuntimeCall::SubtensorModule(
+                        pallet_subtensor::Call::grant_vested_stake { .. }
+                    )
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::burned_register { .. })
+                    | RuntimeCall::SubtensorModule(
+                        pallet_subtensor::Call::queue_burned_registration { .. }
+                    )
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::root_register { .. })
                     | RuntimeCall::SubtensorModule(
                         pallet_subtensor::Call::schedule_swap_coldkey { .. }
                     )
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::swap_coldkey { .. })
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::swap_hotkey { .. })
+                    | RuntimeCall::SubtensorModule(pallet_subtensor::Call::fund_paymaster { .. })
+                    | RuntimeCall::SubtensorModule(
+                        pallet_subtensor::Call::withdraw_paymaster { .. }
+                    )
+                    | RuntimeCall::Swap(pallet_subtensor_swap::Call::transfer_position { .. })
             ),
             ProxyType::Transfer => matches!(
                 c,
@@ -761,6 +891,9 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
                 RuntimeCall::SubtensorModule(pallet_subtensor::Call::dissolve_network { .. })
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::root_register { .. })
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::burned_register { .. })
+                    | RuntimeCall::SubtensorModule(
+                        pallet_subtensor::Call::queue_burned_registration { .. }
+                    )
                     | RuntimeCall::Triumvirate(..)
                     | RuntimeCall::Sudo(..)
             ),
@@ -785,6 +918,7 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
                     )
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::swap_stake { .. })
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::swap_stake_limit { .. })
+                    | RuntimeCall::SubtensorModule(pallet_subtensor::Call::swap_stake_route { .. })
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::move_stake { .. })
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::add_stake_limit { .. })
                     | RuntimeCall::SubtensorModule(
@@ -798,6 +932,9 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
                 c,
                 RuntimeCall::SubtensorModule(pallet_subtensor::Call::burned_register { .. })
                     | RuntimeCall::SubtensorModule(pallet_subtensor::Call::register { .. })
+                    | RuntimeCall::SubtensorModule(
+                        pallet_subtensor::Call::queue_burned_registration { .. }
+                    )
             ),
             ProxyType::RootWeights => false, // deprecated
             ProxyType::ChildKeys => matches!(
@@ -883,6 +1020,18 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
                         pallet_admin_utils::Call::sudo_set_toggle_transfer { .. }
                     )
             ),
+            ProxyType::SubnetStaking(netuid) => {
+                ProxyType::Staking.filter(c) && is_call_on_subnet(c, *netuid)
+            }
+            ProxyType::SubnetChildKeys(netuid) => {
+                ProxyType::ChildKeys.filter(c) && is_call_on_subnet(c, *netuid)
+            }
+            ProxyType::SubnetOwner(netuid) => {
+                ProxyType::Owner.filter(c) && is_call_on_subnet(c, *netuid)
+            }
+            ProxyType::SubnetRegistration(netuid) => {
+                ProxyType::Registration.filter(c) && is_call_on_subnet(c, *netuid)
+            }
         }
     }
     fn is_superset(&self, o: &Self) -> bool {
@@ -896,11 +1045,248 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
             }
             (ProxyType::Governance, ProxyType::Triumvirate | ProxyType::Senate) => true,
             (ProxyType::Transfer, ProxyType::SmallTransfer) => true,
+            (ProxyType::Staking, ProxyType::SubnetStaking(_)) => true,
+            (ProxyType::ChildKeys, ProxyType::SubnetChildKeys(_)) => true,
+            (ProxyType::Owner, ProxyType::SubnetOwner(_)) => true,
+            (ProxyType::Registration, ProxyType::SubnetRegistration(_)) => true,
             _ => false,
         }
     }
 }
 
+/// Returns true if all the netuid arguments of a call are `subnet`. Calls without a netuid
+/// argument act on all the subnets and are never on a single subnet.
+fn is_call_on_subnet(c: &RuntimeCall, subnet: NetUid) -> bool {
+    let netuids = match c {
+        RuntimeCall::SubtensorModule(pallet_subtensor::Call::add_stake { netuid, .. })
+        | RuntimeCall::SubtensorModule(pallet_subtensor::Call::add_stake_limit {
+            netuid, ..
+        })
+        | RuntimeCall::SubtensorModule(pallet_subtensor::Call::remove_stake { netuid, .. })
+        | RuntimeCall::SubtensorModule(pallet_subtensor::Call::remove_stake_limit {
+            netuid, ..
+        })
+        | RuntimeCall::SubtensorModule(pallet_subtensor::Call::remove_stake_full_limit {
+            netuid,
+            ..
+        })
+        | RuntimeCall::SubtensorModule(pallet_subtensor::Call::set_children { netuid, .. })
+        | RuntimeCall::SubtensorModule(pallet_subtensor::Call::set_childkey_take {
+            netuid, ..
+        })
+        | RuntimeCall::SubtensorModule(pallet_subtensor::Call::set_subnet_identity {
+            netuid,
+            ..
+        })
+        | RuntimeCall::SubtensorModule(pallet_subtensor::Call::update_symbol { netuid, .. })
+        | RuntimeCall::SubtensorModule(pallet_subtensor::Call::register { netuid, .. })
+        | RuntimeCall::SubtensorModule(pallet_subtensor::Call::burned_register {
+            netuid, ..
+        })
+        | RuntimeCall::SubtensorModule(pallet_subtensor::Call::queue_burned_registration {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_serving_rate_limit {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_min_difficulty {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_max_difficulty {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_weights_version_key {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_weights_set_rate_limit {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_adjustment_interval {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_adjustment_alpha {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_max_weight_limit {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_immunity_period {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_min_allowed_weights {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_max_allowed_uids {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_kappa { netuid, .. })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_rho { netuid, .. })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_activity_cutoff {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(
+            pallet_admin_utils::Call::sudo_set_network_registration_allowed { netuid, .. },
+        )
+        | RuntimeCall::AdminUtils(
+            pallet_admin_utils::Call::sudo_set_network_pow_registration_allowed { netuid, .. },
+        )
+        | RuntimeCall::AdminUtils(
+            pallet_admin_utils::Call::sudo_set_target_registrations_per_interval { netuid, .. },
+        )
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_min_burn { netuid, .. })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_max_burn { netuid, .. })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_difficulty {
+            netuid, ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_max_allowed_validators {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_bonds_moving_average {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_bonds_penalty {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(
+            pallet_admin_utils::Call::sudo_set_max_registrations_per_block { netuid, .. },
+        )
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_tempo { netuid, .. })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_rao_recycled {
+            netuid, ..
+        })
+        | RuntimeCall::AdminUtils(
+            pallet_admin_utils::Call::sudo_set_commit_reveal_weights_enabled { netuid, .. },
+        )
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_liquid_alpha_enabled {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_alpha_values {
+            netuid, ..
+        })
+        | RuntimeCall::AdminUtils(
+            pallet_admin_utils::Call::sudo_set_commit_reveal_weights_interval { netuid, .. },
+        )
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_toggle_transfer {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_recycle_or_burn {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_subnet_owner_hotkey {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_ema_price_halving_period {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_alpha_sigmoid_steepness {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_yuma3_enabled {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_bonds_reset_enabled {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_sn_owner_hotkey {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_subtoken_enabled {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_owner_immune_neuron_limit {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_mechanism_count {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_mechanism_emission_split {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_trim_to_max_allowed_uids {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_min_allowed_uids {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_epoch_debug_enabled {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_timelocked_weights_beacon {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_commitment_history_depth {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_commitment_schema {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_burned_registration_mode {
+            netuid,
+            ..
+        })
+        | RuntimeCall::AdminUtils(pallet_admin_utils::Call::sudo_set_subnet_paymaster {
+            netuid,
+            ..
+        }) => vec![*netuid],
+        RuntimeCall::SubtensorModule(pallet_subtensor::Call::move_stake {
+            origin_netuid,
+            destination_netuid,
+            ..
+        })
+        | RuntimeCall::SubtensorModule(pallet_subtensor::Call::swap_stake {
+            origin_netuid,
+            destination_netuid,
+            ..
+        })
+        | RuntimeCall::SubtensorModule(pallet_subtensor::Call::swap_stake_limit {
+            origin_netuid,
+            destination_netuid,
+            ..
+        }) => vec![*origin_netuid, *destination_netuid],
+        RuntimeCall::SubtensorModule(pallet_subtensor::Call::swap_stake_route {
+            route, ..
+        }) => route.to_vec(),
+        _ => Vec::new(),
+    };
+
+    !netuids.is_empty() && netuids.iter().all(|netuid| *netuid == subnet)
+}
+
 impl pallet_proxy::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type RuntimeCall = RuntimeCall;
@@ -915,6 +1301,54 @@ impl pallet_proxy::Config for Runtime {
     type AnnouncementDepositBase = AnnouncementDepositBase;
     type AnnouncementDepositFactor = AnnouncementDepositFactor;
     type BlockNumberProvider = System;
+    type AllowanceKind = ProxyAllowanceKind;
+    type AllowanceFunds = ProxyAllowanceFunds;
+    type MaxAllowances = MaxProxyAllowances;
+}
+
+/// The most staking hotkeys of a coldkey whose stake is measured for a proxy alpha allowance.
+pub const MAX_ALLOWANCE_STAKING_HOTKEYS: u64 = 16;
+
+/// Measures the TAO and alpha of the accounts limited by proxy allowances.
+pub struct ProxyAllowanceFunds;
+impl pallet_proxy::AllowanceFunds<AccountId, ProxyAllowanceKind, Balance> for ProxyAllowanceFunds {
+    fn funds(who: &AccountId, kind: &ProxyAllowanceKind) -> Option<Balance> {
+        match kind {
+            ProxyAllowanceKind::Tao => Some(Balances::free_balance(who)),
+            ProxyAllowanceKind::Alpha(netuid) => {
+                let hotkeys = pallet_subtensor::StakingHotkeys::<Runtime>::get(who);
+                if hotkeys.len() as u64 > MAX_ALLOWANCE_STAKING_HOTKEYS {
+                    return None;
+                }
+                Some(hotkeys.iter().fold(0, |funds, hotkey| {
+                    funds.saturating_add(u64::from(
+                        SubtensorModule::get_stake_for_hotkey_and_coldkey_on_subnet(
+                            hotkey, who, *netuid,
+                        ),
+                    ))
+                }))
+            }
+        }
+    }
+
+    fn weight() -> Weight {
+        // StakingHotkeys, then the alpha shares and the share pool of each hotkey
+        Weight::from_parts(2_000_000, 0).saturating_add(
+            <Runtime as frame_system::Config>::DbWeight::get().reads(
+                MAX_ALLOWANCE_STAKING_HOTKEYS
+                    .saturating_mul(3)
+                    .saturating_add(1),
+            ),
+        )
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn benchmark_kind(i: u32) -> ProxyAllowanceKind {
+        match i {
+            0 => ProxyAllowanceKind::Tao,
+            i => ProxyAllowanceKind::Alpha(NetUid::from(i as u16)),
+        }
+    }
 }
 
 pub struct Proxier;
@@ -946,6 +1380,17 @@ impl CommitmentsInterface for CommitmentsI {
     fn purge_netuid(netuid: NetUid) {
         pallet_commitments::Pallet::<Runtime>::purge_netuid(netuid);
     }
+
+    fn set_commitment_history_depth(netuid: NetUid, depth: u32) -> DispatchResult {
+        pallet_commitments::Pallet::<Runtime>::set_commitment_history_depth(netuid, depth)
+    }
+
+    fn set_commitment_schema(
+        netuid: NetUid,
+        schema: Option<pallet_commitments::CommitmentSchema>,
+    ) -> DispatchResult {
+        pallet_commitments::Pallet::<Runtime>::set_commitment_schema(netuid, schema)
+    }
 }
 
 parameter_types! {
@@ -1065,6 +1510,8 @@ parameter_types! {
     pub const MaxCommitFieldsInner: u32 = 3;
     pub const CommitmentInitialDeposit: Balance = 0; // Free
     pub const CommitmentFieldDeposit: Balance = 0; // Free
+    pub const CommitmentBlobBaseDeposit: Balance = deposit(2, 64);
+    pub const CommitmentBlobByteDeposit: Balance = deposit(0, 1);
 }
 
 #[subtensor_macros::freeze_struct("7c76bd954afbb54e")]
@@ -1125,6 +1572,8 @@ impl pallet_commitments::Config for Runtime {
     type InitialDeposit = CommitmentInitialDeposit;
     type FieldDeposit = CommitmentFieldDeposit;
     type TempoInterface = TempoInterface;
+    type BlobBaseDeposit = CommitmentBlobBaseDeposit;
+    type BlobByteDeposit = CommitmentBlobByteDeposit;
 }
 
 pub struct TempoInterface;
@@ -1214,6 +1663,10 @@ parameter_types! {
     pub const SubtensorInitialKeySwapOnSubnetCost: u64 = 1_000_000; // 0.001 TAO
     pub const HotkeySwapOnSubnetInterval : BlockNumber = 5 * 24 * 60 * 60 / 12; // 5 days
     pub const LeaseDividendsDistributionInterval: BlockNumber = 100; // 100 blocks
+    pub const LeaseProposalVotingPeriod: BlockNumber = prod_or_fast!(3 * 24 * 60 * 60 / 12, 10); // 3 days
+    pub const MaxLeaseEmissionsShareChange: Percent = Percent::from_percent(10);
+    pub const MinLeaseProposalShare: Percent = Percent::from_percent(5);
+    pub const LeaseProposalCooldown: BlockNumber = prod_or_fast!(7 * 24 * 60 * 60 / 12, 10); // 7 days
     pub const MaxImmuneUidsPercentage: Percent = Percent::from_percent(80);
     pub const EvmKeyAssociateRateLimit: u64 = EVM_KEY_ASSOCIATE_RATELIMIT;
 }
@@ -1292,6 +1745,10 @@ impl pallet_subtensor::Config for Runtime {
     type HotkeySwapOnSubnetInterval = HotkeySwapOnSubnetInterval;
     type ProxyInterface = Proxier;
     type LeaseDividendsDistributionInterval = LeaseDividendsDistributionInterval;
+    type LeaseProposalVotingPeriod = LeaseProposalVotingPeriod;
+    type MaxLeaseEmissionsShareChange = MaxLeaseEmissionsShareChange;
+    type MinLeaseProposalShare = MinLeaseProposalShare;
+    type LeaseProposalCooldown = LeaseProposalCooldown;
     type GetCommitments = GetCommitmentsStruct;
     type MaxImmuneUidsPercentage = MaxImmuneUidsPercentage;
     type CommitmentsInterface = CommitmentsI;
@@ -1302,9 +1759,15 @@ parameter_types! {
     pub const SwapProtocolId: PalletId = PalletId(*b"ten/swap");
     pub const SwapMaxFeeRate: u16 = 10000; // 15.26%
     pub const SwapMaxPositions: u32 = 100;
+    pub const SwapMaxPositionsPerAccount: u32 = 256;
     pub const SwapMinimumLiquidity: u64 = 1_000;
     pub const SwapMinimumReserve: NonZeroU64 = NonZeroU64::new(1_000_000)
         .expect("1_000_000 fits NonZeroU64");
+    pub const SwapMaxLimitOrders: u32 = 100;
+    pub const SwapMaxLimitOrdersPerAccount: u32 = 10;
+    pub const SwapMaxPriceObservations: u32 = 7200;
+    // 1 TAO, filling a side of a book takes 100 TAO of orders at real prices.
+    pub const SwapMinLimitOrderAmount: u64 = 1_000_000_000;
 }
 
 impl pallet_subtensor_swap::Config for Runtime {
@@ -1314,8 +1777,14 @@ impl pallet_subtensor_swap::Config for Runtime {
     type ProtocolId = SwapProtocolId;
     type MaxFeeRate = SwapMaxFeeRate;
     type MaxPositions = SwapMaxPositions;
+    type MaxPositionsPerAccount = SwapMaxPositionsPerAccount;
     type MinimumLiquidity = SwapMinimumLiquidity;
     type MinimumReserve = SwapMinimumReserve;
+    type MaxLimitOrders = SwapMaxLimitOrders;
+    type MaxLimitOrdersPerAccount = SwapMaxLimitOrdersPerAccount;
+    type MinLimitOrderAmount = SwapMinLimitOrderAmount;
+    type MinStake = pallet_subtensor::DefaultMinStake<Runtime>;
+    type MaxPriceObservations = SwapMaxPriceObservations;
     // TODO: set measured weights when the pallet been benchmarked and the type is generated
     type WeightInfo = pallet_subtensor_swap::weights::DefaultWeight<Runtime>;
 }
@@ -1618,6 +2087,8 @@ parameter_types! {
     pub const MaximumBlockDuration: BlockNumber = prod_or_fast!(432000, 20000);
     pub const RefundContributorsLimit: u32 = 50;
     pub const MaxContributors: u32 = 500;
+    // 3 days (3 * 24 * 60 * 60 / 12)
+    pub const MilestoneVotingPeriod: BlockNumber = prod_or_fast!(21600, 20);
 }
 
 impl pallet_crowdloan::Config for Runtime {
@@ -1633,6 +2104,7 @@ impl pallet_crowdloan::Config for Runtime {
     type MaximumBlockDuration = MaximumBlockDuration;
     type RefundContributorsLimit = RefundContributorsLimit;
     type MaxContributors = MaxContributors;
+    type MilestoneVotingPeriod = MilestoneVotingPeriod;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -2406,6 +2878,10 @@ impl_runtime_apis! {
         fn get_selective_mechagraph(netuid: NetUid, mecid: MechId, metagraph_indexes: Vec<u16>) -> Option<SelectiveMetagraph<AccountId32>> {
             SubtensorModule::get_selective_mechagraph(netuid, mecid, metagraph_indexes)
         }
+
+        fn get_epoch_debug(netuid: NetUid, mecid: MechId) -> Option<EpochDebugSnapshot> {
+            SubtensorModule::get_epoch_debug(netuid, mecid)
+        }
     }
 
     impl subtensor_custom_rpc_runtime_api::StakeInfoRuntimeApi<Block> for Runtime {
@@ -2432,6 +2908,59 @@ impl_runtime_apis! {
         }
     }
 
+    impl subtensor_custom_rpc_runtime_api::FeeInAlphaRuntimeApi<Block> for Runtime {
+        fn query_fee_in_alpha( account: AccountId32, tao_fee: u64 ) -> Option<(AccountId32, AccountId32, NetUid, AlphaCurrency)> {
+            SubtensorModule::get_fee_in_alpha( &account, tao_fee, true )
+        }
+    }
+
+    impl subtensor_custom_rpc_runtime_api::LeaseDividendsRuntimeApi<Block> for Runtime {
+        fn get_claimable_lease_dividends( coldkey_account: AccountId32 ) -> Vec<(u32, TaoCurrency)> {
+            SubtensorModule::get_claimable_lease_dividends_for_account( &coldkey_account )
+        }
+    }
+
+    impl subtensor_custom_rpc_runtime_api::EmissionLedgerRuntimeApi<Block> for Runtime {
+        fn get_emission_ledger( netuid: NetUid, hotkey_account: AccountId32 ) -> Vec<EmissionLedgerEntry> {
+            SubtensorModule::get_emission_ledger( netuid, hotkey_account )
+        }
+
+        fn get_emission_ledgers_for_hotkey( hotkey_account: AccountId32 ) -> Vec<(NetUid, Vec<EmissionLedgerEntry>)> {
+            SubtensorModule::get_emission_ledgers_for_hotkey( hotkey_account )
+        }
+    }
+
+    impl subtensor_custom_rpc_runtime_api::CommitmentHistoryRuntimeApi<Block> for Runtime {
+        fn get_commitment_history( netuid: NetUid, account: AccountId32, limit: u32 ) -> Vec<(u64, Vec<u8>)> {
+            pallet_commitments::Pallet::<Runtime>::get_commitment_history( netuid, &account, limit )
+                .into_iter()
+                .map(|(info, block)| (block.into(), info.encode()))
+                .collect()
+        }
+    }
+
+    impl subtensor_custom_rpc_runtime_api::CommitmentSchemaRuntimeApi<Block> for Runtime {
+        fn get_commitment_schema( netuid: NetUid ) -> Option<pallet_commitments::CommitmentSchema> {
+            pallet_commitments::CommitmentSchemas::<Runtime>::get( netuid )
+        }
+
+        fn decode_commitment( netuid: NetUid, account: AccountId32 ) -> Option<Vec<pallet_commitments::DecodedCommitmentField>> {
+            pallet_commitments::Pallet::<Runtime>::decode_commitment( netuid, &account )
+        }
+    }
+
+    impl subtensor_custom_rpc_runtime_api::CommitmentBlobRuntimeApi<Block> for Runtime {
+        fn get_blob( hash: [u8; 32] ) -> Option<Vec<u8>> {
+            pallet_commitments::Pallet::<Runtime>::get_blob( hash )
+        }
+    }
+
+    impl subtensor_custom_rpc_runtime_api::EpochSimulationRuntimeApi<Block> for Runtime {
+        fn simulate_epoch( netuid: NetUid, mecid: MechId, rao_emission: AlphaCurrency, overrides: EpochHyperparamOverrides, weights: Vec<(u16, Vec<(u16, u16)>)> ) -> Option<Vec<SimulatedEpochTerms<AccountId32>>> {
+            SubtensorModule::simulate_epoch( netuid, mecid, rao_emission, overrides, weights )
+        }
+    }
+
     impl sp_consensus_babe::BabeApi<Block> for Runtime {
         fn configuration() -> BabeConfiguration {
             let config = BabeEpochConfiguration::default();
@@ -2496,6 +3025,16 @@ impl_runtime_apis! {
                 .saturating_to_num()
         }
 
+        fn twap_alpha_price(netuid: NetUid, window_blocks: u64) -> Option<u64> {
+            use substrate_fixed::types::U96F32;
+
+            pallet_subtensor_swap::Pallet::<Runtime>::twap(netuid, window_blocks).map(|price| {
+                price
+                    .saturating_mul(U96F32::from_num(1_000_000_000))
+                    .saturating_to_num()
+            })
+        }
+
         fn sim_swap_tao_for_alpha(netuid: NetUid, tao: TaoCurrency) -> SimSwapResult {
             pallet_subtensor_swap::Pallet::<Runtime>::sim_swap(
                 netuid.into(),
@@ -2539,6 +3078,36 @@ impl_runtime_apis! {
                 },
             )
         }
+
+        fn sim_swap_route(
+            coldkey: AccountId32,
+            hotkey: AccountId32,
+            route: Vec<NetUid>,
+            alpha: AlphaCurrency,
+        ) -> Result<Vec<SimSwapRouteLeg>, sp_runtime::DispatchError> {
+            pallet_subtensor_swap::Pallet::<Runtime>::sim_swap_route(
+                &coldkey,
+                &hotkey,
+                &route,
+                alpha.into(),
+            )
+        }
+
+        fn get_limit_orders(coldkey: AccountId32, netuid: NetUid) -> Vec<LimitOrderInfo<AccountId32>> {
+            pallet_subtensor_swap::Pallet::<Runtime>::get_limit_orders(&coldkey, netuid)
+        }
+
+        fn get_ticks(netuid: NetUid, price_low: u64, price_high: u64) -> Vec<TickInfo> {
+            pallet_subtensor_swap::Pallet::<Runtime>::get_ticks(netuid, price_low, price_high)
+        }
+
+        fn get_liquidity_curve(netuid: NetUid, ranges_each_side: u32) -> Vec<LiquidityRange> {
+            pallet_subtensor_swap::Pallet::<Runtime>::get_liquidity_curve(netuid, ranges_each_side)
+        }
+
+        fn get_positions(coldkey: AccountId32) -> Vec<PositionInfo<AccountId32>> {
+            pallet_subtensor_swap::Pallet::<Runtime>::get_positions(&coldkey)
+        }
     }
 }
 
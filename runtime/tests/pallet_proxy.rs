@@ -8,7 +8,8 @@ use node_subtensor_runtime::{
 };
 use pallet_subtensor_collective as pallet_collective;
 use pallet_subtensor_proxy as pallet_proxy;
-use subtensor_runtime_common::{AccountId, NetUid, ProxyType};
+use pallet_subtensor_utility as pallet_utility;
+use subtensor_runtime_common::{AccountId, NetUid, ProxyAllowanceKind, ProxyType};
 
 const ACCOUNT: [u8; 32] = [1_u8; 32];
 const DELEGATE: [u8; 32] = [2_u8; 32];
@@ -309,3 +310,166 @@ fn test_owner_type_can_set_subnet_identity_and_update_symbol() {
         verify_call_with_proxy_type(&ProxyType::Owner, &call_update_symbol());
     });
 }
+
+#[test]
+fn test_subnet_proxy_types_are_limited_to_their_subnet() {
+    new_test_ext().execute_with(|| {
+        let netuid = NetUid::from(1);
+        let other_netuid = NetUid::from(2);
+        let hotkey = AccountId::from(DELEGATE);
+
+        let add_stake_on = |netuid: NetUid| {
+            RuntimeCall::SubtensorModule(pallet_subtensor::Call::add_stake {
+                hotkey: hotkey.clone(),
+                netuid,
+                amount_staked: 100.into(),
+            })
+        };
+        let move_stake = RuntimeCall::SubtensorModule(pallet_subtensor::Call::move_stake {
+            origin_hotkey: hotkey.clone(),
+            destination_hotkey: hotkey.clone(),
+            origin_netuid: netuid,
+            destination_netuid: other_netuid,
+            alpha_amount: 100.into(),
+        });
+        let unstake_all = RuntimeCall::SubtensorModule(pallet_subtensor::Call::unstake_all {
+            hotkey: hotkey.clone(),
+        });
+
+        let staking = ProxyType::SubnetStaking(netuid);
+        assert!(staking.filter(&add_stake_on(netuid)));
+        assert!(!staking.filter(&add_stake_on(other_netuid)));
+        assert!(!staking.filter(&move_stake));
+        assert!(!staking.filter(&unstake_all));
+        assert!(!staking.filter(&call_transfer()));
+
+        let owner = ProxyType::SubnetOwner(netuid);
+        assert!(owner.filter(&call_owner_util()));
+        assert!(owner.filter(&call_update_symbol()));
+        assert!(!owner.filter(&call_sn_owner_hotkey()));
+        assert!(!ProxyType::SubnetOwner(other_netuid).filter(&call_owner_util()));
+
+        assert!(ProxyType::Staking.is_superset(&staking));
+        assert!(ProxyType::Owner.is_superset(&owner));
+        assert!(!staking.is_superset(&ProxyType::Staking));
+        assert!(!staking.is_superset(&ProxyType::SubnetStaking(other_netuid)));
+
+        assert_ok!(Proxy::add_proxy(
+            RuntimeOrigin::signed(AccountId::from(ACCOUNT)),
+            AccountId::from(DELEGATE).into(),
+            staking,
+            0
+        ));
+        verify_call_with_proxy_type(&staking, &add_stake_on(netuid));
+        verify_call_with_proxy_type(&staking, &add_stake_on(other_netuid));
+    });
+}
+
+#[test]
+fn test_proxies_scheduling_batches() {
+    new_test_ext().execute_with(|| {
+        let schedule_batch = RuntimeCall::Utility(pallet_utility::Call::schedule_batch {
+            calls: vec![call_transfer()],
+            fallback: Some(Box::new(RuntimeCall::Utility(
+                pallet_utility::Call::batch_all {
+                    calls: vec![call_remark(), call_transfer()],
+                },
+            ))),
+            conditions: vec![],
+            not_before: 1,
+            not_after: 10,
+        });
+
+        // No proxy can schedule batches, even with full permissions
+        assert!(!ProxyType::Any.filter(&schedule_batch));
+        assert!(!ProxyType::NonTransfer.filter(&schedule_batch));
+        assert!(!ProxyType::NonFungibile.filter(&schedule_batch));
+        assert!(!ProxyType::NonCritical.filter(&schedule_batch));
+
+        assert_ok!(Proxy::add_proxy(
+            RuntimeOrigin::signed(AccountId::from(ACCOUNT)),
+            AccountId::from(DELEGATE).into(),
+            ProxyType::Any,
+            0
+        ));
+        let filtered = pallet_proxy::Event::ProxyExecuted {
+            result: Err(SystemError::CallFiltered.into()),
+        };
+        assert_ok!(Proxy::proxy(
+            RuntimeOrigin::signed(AccountId::from(DELEGATE)),
+            AccountId::from(ACCOUNT).into(),
+            None,
+            Box::new(schedule_batch.clone()),
+        ));
+        System::assert_last_event(filtered.clone().into());
+
+        // Nor nest them in other calls
+        assert_ok!(Proxy::proxy(
+            RuntimeOrigin::signed(AccountId::from(DELEGATE)),
+            AccountId::from(ACCOUNT).into(),
+            None,
+            Box::new(RuntimeCall::Utility(pallet_utility::Call::batch_all {
+                calls: vec![call_remark(), schedule_batch],
+            })),
+        ));
+        System::assert_last_event(filtered.into());
+        assert_eq!(
+            pallet_utility::ScheduledBatchCount::<Runtime>::get(AccountId::from(ACCOUNT)),
+            0
+        );
+    });
+}
+
+#[test]
+fn test_proxy_allowances_measure_funds() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Proxy::add_proxy(
+            RuntimeOrigin::signed(AccountId::from(ACCOUNT)),
+            AccountId::from(DELEGATE).into(),
+            ProxyType::Any,
+            0
+        ));
+        assert_ok!(Proxy::set_allowance(
+            RuntimeOrigin::signed(AccountId::from(ACCOUNT)),
+            AccountId::from(DELEGATE).into(),
+            ProxyAllowanceKind::Tao,
+            150,
+            100
+        ));
+
+        // The TAO leaving the free balance is charged, whatever the call moving it
+        let batch = RuntimeCall::Utility(pallet_utility::Call::batch {
+            calls: vec![call_remark(), call_transfer()],
+        });
+        assert_ok!(Proxy::proxy(
+            RuntimeOrigin::signed(AccountId::from(DELEGATE)),
+            AccountId::from(ACCOUNT).into(),
+            None,
+            Box::new(batch),
+        ));
+        System::assert_last_event(pallet_proxy::Event::ProxyExecuted { result: Ok(()) }.into());
+        let allowances = pallet_proxy::Allowances::<Runtime>::get(
+            AccountId::from(ACCOUNT),
+            AccountId::from(DELEGATE),
+        );
+        assert_eq!(allowances.first().unwrap().spent, 100);
+
+        let balance = pallet_balances::Pallet::<Runtime>::free_balance(AccountId::from(ACCOUNT));
+        assert_ok!(Proxy::proxy(
+            RuntimeOrigin::signed(AccountId::from(DELEGATE)),
+            AccountId::from(ACCOUNT).into(),
+            None,
+            Box::new(call_transfer()),
+        ));
+        System::assert_last_event(
+            pallet_proxy::Event::ProxyExecuted {
+                result: Err(pallet_proxy::Error::<Runtime>::AllowanceExceeded.into()),
+            }
+            .into(),
+        );
+        assert_eq!(
+            pallet_balances::Pallet::<Runtime>::free_balance(AccountId::from(ACCOUNT)),
+            balance
+        );
+    });
+}